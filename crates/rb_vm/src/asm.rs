@@ -0,0 +1,298 @@
+//! Text assembler/disassembler for TLV chips.
+//!
+//! One instruction per line, `#`/`;` comments and blank lines ignored.
+//! Mnemonics are the opcode names; operand syntax depends on the opcode:
+//!
+//! ```text
+//! ConstI64 42          # i64, 8-byte BE payload
+//! ConstBytes 0xdeadbeef  or  ConstBytes "utf-8 text"
+//! PushInput 0          # u16 input index
+//! CmpI64 EQ            # EQ NE LT LE GT GE (or raw 0-5)
+//! JsonGetKey "amount"  # utf-8 key (quotes optional)
+//! ConstDec 12.34       # fixed-point decimal; scale = fraction digits
+//! AddDec 2 HALF_EVEN   # result scale + rounding (HALF_EVEN DOWN UP or 0-2)
+//! CmpDec GE            # same operators as CmpI64
+//! EmitRc               # payload-less opcodes take no operand
+//! ```
+//!
+//! `disassemble` produces exactly this format, so asm ∘ dis is the identity
+//! on any decodable chip.
+
+use crate::opcode::Opcode;
+use crate::tlv::DecodeError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AsmError {
+    #[error("line {0}: unknown mnemonic {1:?}")]
+    UnknownMnemonic(usize, String),
+    #[error("line {0}: {1}")]
+    BadOperand(usize, String),
+    #[error("line {0}: payload exceeds u16 length")]
+    PayloadTooLong(usize),
+}
+
+pub(crate) fn mnemonic(op: Opcode) -> &'static str {
+    use Opcode::*;
+    match op {
+        ConstI64 => "ConstI64",
+        ConstBytes => "ConstBytes",
+        JsonNormalize => "JsonNormalize",
+        JsonValidate => "JsonValidate",
+        AddI64 => "AddI64",
+        SubI64 => "SubI64",
+        MulI64 => "MulI64",
+        CmpI64 => "CmpI64",
+        AssertTrue => "AssertTrue",
+        HashBlake3 => "HashBlake3",
+        CasPut => "CasPut",
+        CasGet => "CasGet",
+        SetRcBody => "SetRcBody",
+        AttachProof => "AttachProof",
+        SignDefault => "SignDefault",
+        EmitRc => "EmitRc",
+        Drop => "Drop",
+        PushInput => "PushInput",
+        JsonGetKey => "JsonGetKey",
+        VerifyEd25519 => "VerifyEd25519",
+        MapNew => "MapNew",
+        MapInsert => "MapInsert",
+        ArrayNew => "ArrayNew",
+        ArrayPush => "ArrayPush",
+        GhostAssert => "GhostAssert",
+        ExecChip => "ExecChip",
+        RandDeterministic => "RandDeterministic",
+        ConstDec => "ConstDec",
+        AddDec => "AddDec",
+        CmpDec => "CmpDec",
+    }
+}
+
+fn opcode_for(name: &str) -> Option<Opcode> {
+    // Every opcode is representable as a u8 in 0x01..=0x1E; scan the range
+    // so the table stays in one place (the mnemonic function above).
+    (0x01..=0x1Eu8)
+        .filter_map(|b| Opcode::try_from(b).ok())
+        .find(|op| mnemonic(*op) == name)
+}
+
+pub(crate) const CMP_OPS: &[&str] = &["EQ", "NE", "LT", "LE", "GT", "GE"];
+
+pub(crate) const DEC_MODES: &[&str] = &["HALF_EVEN", "DOWN", "UP"];
+
+/// Parse a decimal literal like `12.34` or `-0.05` into (mantissa, scale).
+fn parse_dec(line_no: usize, operand: &str) -> Result<(i64, u8), AsmError> {
+    let bad = |msg: String| AsmError::BadOperand(line_no, msg);
+    let (int_part, frac_part) = match operand.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (operand, ""),
+    };
+    let scale = frac_part.len();
+    if scale > crate::exec::MAX_DEC_SCALE as usize {
+        return Err(bad(format!("scale {scale} exceeds max 18")));
+    }
+    let digits = format!("{int_part}{frac_part}");
+    let m: i64 = digits
+        .parse()
+        .map_err(|_| bad(format!("expected decimal literal, got {operand:?}")))?;
+    Ok((m, scale as u8))
+}
+
+fn cmp_code(operand: &str) -> Option<u8> {
+    CMP_OPS
+        .iter()
+        .position(|c| c.eq_ignore_ascii_case(operand))
+        .map(|p| p as u8)
+        .or_else(|| operand.parse().ok())
+        .filter(|c| *c <= 5)
+}
+
+/// Parse an operand into payload bytes: `0x…` hex, a quoted string, or a
+/// bare word (taken as utf-8 — convenient for JSON keys).
+fn parse_bytes(line_no: usize, operand: &str) -> Result<Vec<u8>, AsmError> {
+    if let Some(hexstr) = operand.strip_prefix("0x") {
+        return hex::decode(hexstr)
+            .map_err(|e| AsmError::BadOperand(line_no, format!("bad hex: {e}")));
+    }
+    let s = operand
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(operand);
+    Ok(s.as_bytes().to_vec())
+}
+
+/// Assemble chip text into the TLV wire format.
+pub fn assemble(text: &str) -> Result<Vec<u8>, AsmError> {
+    let mut out = Vec::new();
+    for (idx, raw_line) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw_line
+            .split(['#', ';'])
+            .next()
+            .unwrap_or("")
+            .trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (name, operand) = match line.split_once(char::is_whitespace) {
+            Some((n, rest)) => (n, rest.trim()),
+            None => (line, ""),
+        };
+        let op = opcode_for(name)
+            .ok_or_else(|| AsmError::UnknownMnemonic(line_no, name.to_string()))?;
+
+        let payload: Vec<u8> = match op {
+            Opcode::ConstI64 => {
+                let v: i64 = operand.parse().map_err(|_| {
+                    AsmError::BadOperand(line_no, format!("expected i64, got {operand:?}"))
+                })?;
+                v.to_be_bytes().to_vec()
+            }
+            Opcode::PushInput => {
+                let v: u16 = operand.parse().map_err(|_| {
+                    AsmError::BadOperand(line_no, format!("expected u16 index, got {operand:?}"))
+                })?;
+                v.to_be_bytes().to_vec()
+            }
+            Opcode::CmpI64 | Opcode::CmpDec => {
+                let code = cmp_code(operand).ok_or_else(|| {
+                    AsmError::BadOperand(
+                        line_no,
+                        format!("expected EQ/NE/LT/LE/GT/GE or 0-5, got {operand:?}"),
+                    )
+                })?;
+                vec![code]
+            }
+            Opcode::ConstDec => {
+                let (m, scale) = parse_dec(line_no, operand)?;
+                let mut p = m.to_be_bytes().to_vec();
+                p.push(scale);
+                p
+            }
+            Opcode::AddDec => {
+                let (scale_str, mode_str) = operand
+                    .split_once(char::is_whitespace)
+                    .map(|(s, m)| (s, m.trim()))
+                    .unwrap_or((operand, "HALF_EVEN"));
+                let scale: u8 = scale_str
+                    .parse()
+                    .ok()
+                    .filter(|s| *s <= crate::exec::MAX_DEC_SCALE)
+                    .ok_or_else(|| {
+                        AsmError::BadOperand(
+                            line_no,
+                            format!("expected scale 0-18, got {scale_str:?}"),
+                        )
+                    })?;
+                let mode = DEC_MODES
+                    .iter()
+                    .position(|m| m.eq_ignore_ascii_case(mode_str))
+                    .map(|p| p as u8)
+                    .or_else(|| mode_str.parse().ok())
+                    .filter(|m| *m <= 2)
+                    .ok_or_else(|| {
+                        AsmError::BadOperand(
+                            line_no,
+                            format!("expected HALF_EVEN/DOWN/UP or 0-2, got {mode_str:?}"),
+                        )
+                    })?;
+                vec![scale, mode]
+            }
+            Opcode::ConstBytes | Opcode::JsonGetKey | Opcode::MapInsert
+            | Opcode::RandDeterministic => parse_bytes(line_no, operand)?,
+            _ => {
+                if !operand.is_empty() {
+                    return Err(AsmError::BadOperand(
+                        line_no,
+                        format!("{name} takes no operand, got {operand:?}"),
+                    ));
+                }
+                Vec::new()
+            }
+        };
+
+        if payload.len() > u16::MAX as usize {
+            return Err(AsmError::PayloadTooLong(line_no));
+        }
+        out.push(op as u8);
+        out.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        out.extend_from_slice(&payload);
+    }
+    Ok(out)
+}
+
+/// Disassemble a TLV chip back into assembler text. Operand decoding
+/// lives in [`crate::disasm`], shared with the streaming formatter.
+pub fn disassemble(chip: &[u8]) -> Result<String, DecodeError> {
+    crate::disasm::format(chip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlv;
+
+    #[test]
+    fn assemble_basic_chip() {
+        let text = r#"
+            # add two constants and assert the sum
+            ConstI64 40
+            ConstI64 2
+            AddI64
+            ConstI64 42
+            CmpI64 EQ
+            AssertTrue
+            EmitRc
+        "#;
+        let chip = assemble(text).unwrap();
+        let code = tlv::decode_stream(&chip).unwrap();
+        assert_eq!(code.len(), 7);
+        assert_eq!(code[0].op, Opcode::ConstI64);
+        assert_eq!(code[4].payload, &[0u8]); // EQ
+    }
+
+    #[test]
+    fn roundtrips_through_disassembler() {
+        let text = "ConstBytes \"hello\"\nHashBlake3\nCasPut\nJsonGetKey \"amount\"\n";
+        let chip = assemble(text).unwrap();
+        let dis = disassemble(&chip).unwrap();
+        let chip2 = assemble(&dis).unwrap();
+        assert_eq!(chip, chip2);
+    }
+
+    #[test]
+    fn decimal_mnemonics_roundtrip() {
+        let text = "ConstDec 12.34\nConstDec -0.05\nAddDec 2 HALF_EVEN\nCmpDec GE\n";
+        let chip = assemble(text).unwrap();
+        let code = tlv::decode_stream(&chip).unwrap();
+        assert_eq!(code[0].payload, [1234i64.to_be_bytes().as_slice(), &[2]].concat());
+        assert_eq!(code[1].payload, [(-5i64).to_be_bytes().as_slice(), &[2]].concat());
+        assert_eq!(code[2].payload, &[2, 0]);
+        assert_eq!(disassemble(&chip).unwrap(), text);
+    }
+
+    #[test]
+    fn rejects_unknown_mnemonic_and_bad_operand() {
+        assert!(matches!(
+            assemble("Nope"),
+            Err(AsmError::UnknownMnemonic(1, _))
+        ));
+        assert!(matches!(
+            assemble("ConstI64 forty"),
+            Err(AsmError::BadOperand(1, _))
+        ));
+        assert!(matches!(
+            assemble("EmitRc 7"),
+            Err(AsmError::BadOperand(1, _))
+        ));
+    }
+
+    #[test]
+    fn every_opcode_has_a_unique_mnemonic() {
+        let mut seen = std::collections::HashSet::new();
+        for b in 0x01..=0x1Eu8 {
+            let op = Opcode::try_from(b).unwrap();
+            assert!(seen.insert(mnemonic(op)), "duplicate mnemonic for {op:?}");
+            assert_eq!(opcode_for(mnemonic(op)), Some(op));
+        }
+    }
+}
@@ -6,6 +6,12 @@ pub trait CanonProvider {
     fn canon(&self, v: Value) -> Value;
 }
 
+impl CanonProvider for &dyn CanonProvider {
+    fn canon(&self, v: Value) -> Value {
+        (**self).canon(v)
+    }
+}
+
 /// Implementação ingênua para desenvolvimento: ordena chaves recursivamente.
 pub struct NaiveCanon;
 impl CanonProvider for NaiveCanon {
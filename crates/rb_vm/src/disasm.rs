@@ -0,0 +1,169 @@
+//! Streaming disassembler: formatted lines without buffering the chip.
+//!
+//! [`crate::asm::disassemble`] materializes every instruction before
+//! formatting. [`Disasm`] walks the TLV stream lazily instead — one
+//! formatted line per `next()` — so an inspector can render arbitrarily
+//! large chips incrementally and stop at the first decode error.
+//! [`format`] is the collect-everything convenience over it; on a
+//! decodable chip its output is what `asm::assemble` accepts back.
+
+use crate::asm::{mnemonic, CMP_OPS, DEC_MODES};
+use crate::opcode::Opcode;
+use crate::tlv::{DecodeError, Instr};
+
+/// Format one decoded instruction as an assembler line (no newline).
+/// Operands decode per opcode — i64 constants, input indices, comparison
+/// operators, decimals, utf-8 keys — with raw hex as the fallback for
+/// payloads that don't match their expected shape.
+pub fn format_instr(ins: &Instr) -> String {
+    let mut out = String::from(mnemonic(ins.op));
+    match ins.op {
+        Opcode::ConstI64 if ins.payload.len() == 8 => {
+            let v = i64::from_be_bytes(ins.payload.try_into().unwrap());
+            out.push_str(&format!(" {v}"));
+        }
+        Opcode::PushInput if ins.payload.len() == 2 => {
+            let v = u16::from_be_bytes([ins.payload[0], ins.payload[1]]);
+            out.push_str(&format!(" {v}"));
+        }
+        Opcode::CmpI64 | Opcode::CmpDec if ins.payload.len() == 1 => {
+            match CMP_OPS.get(ins.payload[0] as usize) {
+                Some(name) => out.push_str(&format!(" {name}")),
+                None => out.push_str(&format!(" {}", ins.payload[0])),
+            }
+        }
+        Opcode::ConstDec if ins.payload.len() == 9 => {
+            let m = i64::from_be_bytes(ins.payload[..8].try_into().unwrap());
+            out.push_str(&format!(" {}", crate::exec::dec_to_string(m, ins.payload[8])));
+        }
+        Opcode::AddDec if ins.payload.len() == 2 => {
+            let mode = DEC_MODES
+                .get(ins.payload[1] as usize)
+                .map(|m| m.to_string())
+                .unwrap_or_else(|| ins.payload[1].to_string());
+            out.push_str(&format!(" {} {mode}", ins.payload[0]));
+        }
+        Opcode::JsonGetKey | Opcode::MapInsert if std::str::from_utf8(ins.payload).is_ok() => {
+            out.push_str(&format!(" {:?}", std::str::from_utf8(ins.payload).unwrap()));
+        }
+        _ if !ins.payload.is_empty() => {
+            out.push_str(&format!(" 0x{}", hex::encode(ins.payload)));
+        }
+        _ => {}
+    }
+    out
+}
+
+/// Lazy disassembly over a TLV byte stream: each `next()` decodes and
+/// formats exactly one instruction. The first decode error is yielded
+/// once and ends the stream — everything before it is still valid text.
+pub struct Disasm<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    done: bool,
+}
+
+impl<'a> Disasm<'a> {
+    pub fn new(chip: &'a [u8]) -> Self {
+        Self {
+            buf: chip,
+            pos: 0,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Disasm<'_> {
+    type Item = Result<String, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.pos >= self.buf.len() {
+            return None;
+        }
+        if self.pos + 3 > self.buf.len() {
+            self.done = true;
+            return Some(Err(DecodeError::Truncated));
+        }
+        let op_u8 = self.buf[self.pos];
+        let len = u16::from_be_bytes([self.buf[self.pos + 1], self.buf[self.pos + 2]]) as usize;
+        let start = self.pos + 3;
+        let end = start + len;
+        if end > self.buf.len() {
+            self.done = true;
+            return Some(Err(DecodeError::Truncated));
+        }
+        let op = match Opcode::try_from(op_u8) {
+            Ok(op) => op,
+            Err(()) => {
+                self.done = true;
+                return Some(Err(DecodeError::UnknownOpcode(op_u8)));
+            }
+        };
+        let payload = &self.buf[start..end];
+        self.pos = end;
+        Some(Ok(format_instr(&Instr { op, payload })))
+    }
+}
+
+/// Disassemble a whole chip into assembler text, one instruction per
+/// line. Errors mid-stream discard the partial text; use [`Disasm`]
+/// directly to keep the lines decoded before the failure.
+pub fn format(chip: &[u8]) -> Result<String, DecodeError> {
+    let mut out = String::new();
+    for line in Disasm::new(chip) {
+        out.push_str(&line?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    #[test]
+    fn streaming_matches_bulk_disassembly() {
+        // One of every operand shape the formatter decodes
+        let text = "ConstI64 -7\nPushInput 3\nCmpI64 GE\nConstDec 12.34\n\
+                    AddDec 2 HALF_EVEN\nJsonGetKey \"amount\"\nConstBytes 0xdeadbeef\n\
+                    AssertTrue\nEmitRc\n";
+        let chip = assemble(text).unwrap();
+        assert_eq!(format(&chip).unwrap(), text);
+        let lines: Vec<String> = Disasm::new(&chip).map(|l| l.unwrap()).collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "ConstI64 -7");
+        assert_eq!(lines.join("\n") + "\n", crate::asm::disassemble(&chip).unwrap());
+    }
+
+    #[test]
+    fn every_opcode_formats() {
+        // Payload-less encoding of each opcode still yields its mnemonic
+        for b in 0x01..=0x1Eu8 {
+            let op = Opcode::try_from(b).unwrap();
+            let line = format_instr(&Instr { op, payload: &[] });
+            assert!(line.starts_with(char::is_alphabetic), "op {op:?}: {line}");
+        }
+        // A malformed payload falls back to raw hex instead of panicking
+        let line = format_instr(&Instr {
+            op: Opcode::ConstI64,
+            payload: &[0xAB],
+        });
+        assert_eq!(line, "ConstI64 0xab");
+    }
+
+    #[test]
+    fn first_error_ends_the_stream() {
+        let mut chip = assemble("ConstI64 1\n").unwrap();
+        chip.push(0xFF); // not an opcode
+        chip.extend_from_slice(&[0x00, 0x00]);
+        let mut it = Disasm::new(&chip);
+        assert_eq!(it.next().unwrap().unwrap(), "ConstI64 1");
+        assert!(matches!(it.next(), Some(Err(DecodeError::UnknownOpcode(0xFF)))));
+        assert!(it.next().is_none());
+
+        let mut it = Disasm::new(&[0x01, 0x00]); // header cut short
+        assert!(matches!(it.next(), Some(Err(DecodeError::Truncated))));
+        assert!(it.next().is_none());
+    }
+}
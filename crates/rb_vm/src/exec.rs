@@ -8,6 +8,9 @@ use serde_json::json;
 
 pub type Fuel = u64;
 
+/// Maximum chip-composition nesting (ExecChip inside ExecChip inside …).
+const MAX_CHIP_DEPTH: usize = 4;
+
 #[derive(Debug, thiserror::Error)]
 pub enum ExecError {
     #[error("fuel exhausted")]
@@ -20,6 +23,62 @@ pub enum ExecError {
     InvalidPayload(Opcode),
     #[error("deny: {0}")]
     Deny(String),
+    #[error("chip recursion depth exceeded (max {MAX_CHIP_DEPTH})")]
+    DepthExceeded,
+}
+
+/// Maximum decimal scale (digits after the point) for fixed-point values.
+pub const MAX_DEC_SCALE: u8 = 18;
+
+fn pow10(exp: u8) -> i128 {
+    10i128.pow(exp as u32)
+}
+
+/// Rescale a decimal mantissa from one scale to another. Scaling up is
+/// exact (or denies on overflow); scaling down rounds per `mode`:
+/// 0 = half-even (banker's), 1 = down (toward zero), 2 = up (away from zero).
+fn dec_rescale(m: i64, from: u8, to: u8, mode: u8) -> Result<i64, ExecError> {
+    if to >= from {
+        let scaled = (m as i128)
+            .checked_mul(pow10(to - from))
+            .filter(|v| i64::try_from(*v).is_ok())
+            .ok_or(ExecError::Deny("dec_overflow".into()))?;
+        return Ok(scaled as i64);
+    }
+    let factor = pow10(from - to);
+    let q = m as i128 / factor;
+    let r = m as i128 % factor;
+    let rounded = match mode {
+        1 => q, // toward zero
+        2 if r != 0 => q + r.signum(),
+        2 => q,
+        _ => {
+            // half-even
+            let twice = r.abs() * 2;
+            if twice > factor || (twice == factor && q % 2 != 0) {
+                q + r.signum()
+            } else {
+                q
+            }
+        }
+    };
+    Ok(rounded as i64)
+}
+
+/// Deterministic decimal rendering: "-12.34", "0.050", "7" (scale 0).
+pub(crate) fn dec_to_string(m: i64, scale: u8) -> String {
+    if scale == 0 {
+        return m.to_string();
+    }
+    let sign = if m < 0 { "-" } else { "" };
+    let abs = (m as i128).unsigned_abs();
+    let factor = pow10(scale) as u128;
+    format!(
+        "{sign}{}.{:0width$}",
+        abs / factor,
+        abs % factor,
+        width = scale as usize
+    )
 }
 
 pub trait CasProvider {
@@ -27,6 +86,17 @@ pub trait CasProvider {
     fn get(&self, cid: &Cid) -> Option<Vec<u8>>;
 }
 
+// Child VMs borrow the parent's CAS through a trait object, so ExecChip
+// nesting doesn't grow a new Vm type per depth level.
+impl CasProvider for &mut dyn CasProvider {
+    fn put(&mut self, bytes: &[u8]) -> Cid {
+        (**self).put(bytes)
+    }
+    fn get(&self, cid: &Cid) -> Option<Vec<u8>> {
+        (**self).get(cid)
+    }
+}
+
 pub trait SignProvider {
     fn sign_jws(&self, payload_nrf_bytes: &[u8]) -> Vec<u8>;
     fn kid(&self) -> String;
@@ -37,6 +107,10 @@ pub struct VmConfig {
     pub fuel_limit: Fuel,
     pub ghost: bool,
     pub trace: bool,
+    /// Checked integer semantics: Add/Sub/Mul deny with "int_overflow"
+    /// instead of saturating. Recorded in the RC payload either way, so
+    /// verifiers know which semantics produced a result.
+    pub checked_arith: bool,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -60,6 +134,8 @@ pub struct Vm<'a, C: CasProvider, S: SignProvider, K: CanonProvider> {
     rc_body: serde_json::Value,
     proofs: Vec<Cid>,
     trace: Vec<TraceStep>,
+    /// ExecChip nesting level; 0 for the top-level VM.
+    depth: usize,
 }
 
 #[derive(Debug)]
@@ -84,6 +160,7 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
             rc_body: json!({}),
             proofs: Vec::new(),
             trace: Vec::new(),
+            depth: 0,
         }
     }
 
@@ -106,6 +183,20 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
         self.stack.push(v);
     }
 
+    /// Deterministic projection of a stack value into JSON, used by the
+    /// map/array construction opcodes. Bytes become `0x`-hex strings.
+    fn value_to_json(v: Value) -> serde_json::Value {
+        match v {
+            Value::I64(n) => json!(n),
+            Value::Dec { m, scale } => json!(dec_to_string(m, scale)),
+            Value::Bool(b) => json!(b),
+            Value::Bytes(b) => json!(format!("0x{}", hex::encode(b))),
+            Value::Cid(c) => json!(c.0),
+            Value::Json(j) => j,
+            Value::Unit => serde_json::Value::Null,
+        }
+    }
+
     pub fn run(&mut self, code: &[Instr<'_>]) -> Result<VmOutcome, ExecError> {
         use Value::*;
         for ins in code {
@@ -147,10 +238,19 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                         I64(v) => v,
                         _ => return Err(ExecError::TypeMismatch(ins.op)),
                     };
-                    let r = match ins.op {
-                        Opcode::AddI64 => a.saturating_add(b),
-                        Opcode::SubI64 => a.saturating_sub(b),
-                        _ => a.saturating_mul(b),
+                    let r = if self.cfg.checked_arith {
+                        match ins.op {
+                            Opcode::AddI64 => a.checked_add(b),
+                            Opcode::SubI64 => a.checked_sub(b),
+                            _ => a.checked_mul(b),
+                        }
+                        .ok_or(ExecError::Deny("int_overflow".into()))?
+                    } else {
+                        match ins.op {
+                            Opcode::AddI64 => a.saturating_add(b),
+                            Opcode::SubI64 => a.saturating_sub(b),
+                            _ => a.saturating_mul(b),
+                        }
                     };
                     self.push(I64(r));
                 }
@@ -239,6 +339,67 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                         .ok_or(ExecError::Deny("json_key_missing_or_not_i64".into()))?;
                     self.push(Value::I64(n));
                 }
+                Opcode::VerifyEd25519 => {
+                    // Signature verification is far more expensive than a basic op;
+                    // price it at 100 fuel units total (1 base + 99 here).
+                    self.charge(99)?;
+                    let sig = match self.pop()? {
+                        Bytes(b) => b,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::VerifyEd25519)),
+                    };
+                    let payload = match self.pop()? {
+                        Bytes(b) => b,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::VerifyEd25519)),
+                    };
+                    let pubkey = match self.pop()? {
+                        Bytes(b) => b,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::VerifyEd25519)),
+                    };
+                    let pk: [u8; 32] = pubkey
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| ExecError::Deny("ed25519_bad_pubkey_len".into()))?;
+                    let sig_arr: [u8; 64] = sig
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| ExecError::Deny("ed25519_bad_signature_len".into()))?;
+                    let ok = match ed25519_dalek::VerifyingKey::from_bytes(&pk) {
+                        Ok(vk) => {
+                            use ed25519_dalek::Verifier;
+                            vk.verify(&payload, &ed25519_dalek::Signature::from_bytes(&sig_arr))
+                                .is_ok()
+                        }
+                        Err(_) => false,
+                    };
+                    self.push(Bool(ok));
+                }
+                Opcode::MapNew => {
+                    self.push(Json(json!({})));
+                }
+                Opcode::MapInsert => {
+                    let key = std::str::from_utf8(ins.payload)
+                        .map_err(|_| ExecError::InvalidPayload(Opcode::MapInsert))?
+                        .to_string();
+                    let val = self.pop()?;
+                    let mut map = match self.pop()? {
+                        Json(serde_json::Value::Object(m)) => m,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::MapInsert)),
+                    };
+                    map.insert(key, Self::value_to_json(val));
+                    self.push(Json(serde_json::Value::Object(map)));
+                }
+                Opcode::ArrayNew => {
+                    self.push(Json(json!([])));
+                }
+                Opcode::ArrayPush => {
+                    let val = self.pop()?;
+                    let mut arr = match self.pop()? {
+                        Json(serde_json::Value::Array(a)) => a,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::ArrayPush)),
+                    };
+                    arr.push(Self::value_to_json(val));
+                    self.push(Json(serde_json::Value::Array(arr)));
+                }
                 Opcode::HashBlake3 => {
                     let bytes = match self.pop()? {
                         Value::Bytes(b) => b,
@@ -264,6 +425,140 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                 Opcode::SignDefault => {
                     // no-op here; signing is done in EmitRc using provider
                 }
+                Opcode::GhostAssert => {
+                    // Chips that must only run as dry-runs guard themselves with this
+                    if !self.cfg.ghost {
+                        return Err(ExecError::Deny("ghost mode required".into()));
+                    }
+                }
+                Opcode::ExecChip => {
+                    let chip_cid = match self.pop()? {
+                        Cid(c) => c,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::ExecChip)),
+                    };
+                    if self.depth >= MAX_CHIP_DEPTH {
+                        return Err(ExecError::DepthExceeded);
+                    }
+                    let chip_bytes = self
+                        .cas
+                        .get(&chip_cid)
+                        .ok_or(ExecError::InvalidPayload(Opcode::ExecChip))?;
+                    let child_code = crate::tlv::decode_stream(&chip_bytes)
+                        .map_err(|_| ExecError::InvalidPayload(Opcode::ExecChip))?;
+                    // Child runs on an isolated stack with the parent's remaining
+                    // fuel as its sub-budget; its usage is charged back to us.
+                    let child_cfg = VmConfig {
+                        fuel_limit: self.cfg.fuel_limit.saturating_sub(self.fuel_used),
+                        ghost: self.cfg.ghost,
+                        trace: false,
+                        checked_arith: self.cfg.checked_arith,
+                    };
+                    let cas: &mut dyn CasProvider = &mut self.cas;
+                    let canon: &dyn CanonProvider = &self.canon;
+                    let mut child = Vm::new(child_cfg, cas, self.signer, canon, self.inputs.clone());
+                    child.depth = self.depth + 1;
+                    let outcome = child.run(&child_code)?;
+                    self.charge(outcome.fuel_used)?;
+                    self.steps += outcome.steps;
+                    match outcome.rc_cid {
+                        Some(c) => self.push(Value::Cid(c)),
+                        None => self.push(Unit),
+                    }
+                }
+                Opcode::ConstDec => {
+                    if ins.payload.len() != 9 {
+                        return Err(ExecError::InvalidPayload(Opcode::ConstDec));
+                    }
+                    let m = i64::from_be_bytes(ins.payload[..8].try_into().unwrap());
+                    let scale = ins.payload[8];
+                    if scale > MAX_DEC_SCALE {
+                        return Err(ExecError::InvalidPayload(Opcode::ConstDec));
+                    }
+                    self.push(Dec { m, scale });
+                }
+                Opcode::AddDec => {
+                    if ins.payload.len() != 2 {
+                        return Err(ExecError::InvalidPayload(Opcode::AddDec));
+                    }
+                    let scale = ins.payload[0];
+                    let mode = ins.payload[1];
+                    if scale > MAX_DEC_SCALE || mode > 2 {
+                        return Err(ExecError::InvalidPayload(Opcode::AddDec));
+                    }
+                    let (bm, bs) = match self.pop()? {
+                        Dec { m, scale } => (m, scale),
+                        _ => return Err(ExecError::TypeMismatch(Opcode::AddDec)),
+                    };
+                    let (am, as_) = match self.pop()? {
+                        Dec { m, scale } => (m, scale),
+                        _ => return Err(ExecError::TypeMismatch(Opcode::AddDec)),
+                    };
+                    // Align both operands to the result scale, then add
+                    // exactly; money denies on overflow, never saturates.
+                    let a = dec_rescale(am, as_, scale, mode)?;
+                    let b = dec_rescale(bm, bs, scale, mode)?;
+                    let sum = (a as i128)
+                        .checked_add(b as i128)
+                        .filter(|v| i64::try_from(*v).is_ok())
+                        .ok_or(ExecError::Deny("dec_overflow".into()))?;
+                    self.push(Dec {
+                        m: sum as i64,
+                        scale,
+                    });
+                }
+                Opcode::CmpDec => {
+                    if ins.payload.len() != 1 || ins.payload[0] > 5 {
+                        return Err(ExecError::InvalidPayload(Opcode::CmpDec));
+                    }
+                    let (bm, bs) = match self.pop()? {
+                        Dec { m, scale } => (m, scale),
+                        _ => return Err(ExecError::TypeMismatch(Opcode::CmpDec)),
+                    };
+                    let (am, as_) = match self.pop()? {
+                        Dec { m, scale } => (m, scale),
+                        _ => return Err(ExecError::TypeMismatch(Opcode::CmpDec)),
+                    };
+                    // Exact comparison: widen to i128 at the common scale,
+                    // no rounding involved.
+                    let common = as_.max(bs);
+                    let a = am as i128 * pow10(common - as_);
+                    let b = bm as i128 * pow10(common - bs);
+                    let ok = match ins.payload[0] {
+                        0 => a == b,
+                        1 => a != b,
+                        2 => a < b,
+                        3 => a <= b,
+                        4 => a > b,
+                        _ => a >= b,
+                    };
+                    self.push(Bool(ok));
+                }
+                Opcode::RandDeterministic => {
+                    // Reproducible "randomness": keyed BLAKE3 over the chip's
+                    // nonce payload, keyed by the concatenated input CIDs. The
+                    // same chip on the same inputs always derives the same
+                    // bytes — no ambient entropy enters the VM. Priced at 10
+                    // fuel units total (1 base + 9 here) for the XOF work.
+                    self.charge(9)?;
+                    let n = match self.pop()? {
+                        I64(v) => v,
+                        _ => return Err(ExecError::TypeMismatch(Opcode::RandDeterministic)),
+                    };
+                    if !(1..=1024).contains(&n) {
+                        return Err(ExecError::Deny("rand_len_out_of_range".into()));
+                    }
+                    let mut key_hasher = blake3::Hasher::new();
+                    for cid in &self.inputs {
+                        key_hasher.update(cid.0.as_bytes());
+                    }
+                    let key = key_hasher.finalize();
+                    let mut out = vec![0u8; n as usize];
+                    blake3::Hasher::new_keyed(key.as_bytes())
+                        .update(ins.payload)
+                        .finalize_xof()
+                        .fill(&mut out);
+                    self.push(Bytes(out));
+                }
                 Opcode::EmitRc => {
                     if self.cfg.trace {
                         self.trace.push(TraceStep {
@@ -283,13 +578,25 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                         proofs: self.proofs.clone(),
                         steps: self.steps,
                         fuel_used: self.fuel_used,
+                        arith: if self.cfg.checked_arith {
+                            "checked".into()
+                        } else {
+                            "saturating".into()
+                        },
                         policy_id: "default:v1".into(),
                         decision: json!({"status":"ok"}),
                         body: self.rc_body.clone(),
                     };
                     let bytes = serde_json::to_vec(&payload).unwrap(); // TODO: canon NRF
                     let _sig = self.signer.sign_jws(&bytes); // MVP: assinatura crua
-                    let cid = self.cas.put(&bytes);
+                    // Ghost RCs never touch the CAS: hash locally so persistent
+                    // providers (FsCas etc.) see no write, but the CID stays
+                    // identical to what a real run would produce.
+                    let cid = if self.cfg.ghost {
+                        crate::types::Cid(format!("b3:{}", blake3::hash(&bytes).to_hex()))
+                    } else {
+                        self.cas.put(&bytes)
+                    };
                     return Ok(VmOutcome {
                         rc_cid: Some(cid),
                         steps: self.steps,
@@ -6,13 +6,22 @@
 //! - TLV bytecode format
 //! - Minimal opcode set aligned with Fractal lower layer canon
 
+pub mod asm;
 pub mod canon;
+pub mod disasm;
 pub mod exec;
+pub mod lint;
 pub mod opcode;
 pub mod providers;
+pub mod stdchips;
 pub mod tlv;
 pub mod types;
 
+pub use asm::{assemble, disassemble, AsmError};
+pub use disasm::Disasm;
 pub use exec::{CasProvider, ExecError, Fuel, SignProvider, TraceStep, Vm, VmConfig, VmOutcome};
+pub use lint::{lint_chip, Diagnostic, LintReport, Severity};
 pub use opcode::Opcode;
+pub use stdchips::StdChip;
+pub use tlv::DecodedChip;
 pub use types::{Cid, RcPayload, Value};
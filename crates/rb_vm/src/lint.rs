@@ -0,0 +1,244 @@
+//! Static analysis for TLV chips.
+//!
+//! Runs before execution to catch structural problems without spending fuel:
+//! stack balance, unreachable code, missing EmitRc, payload size violations,
+//! and estimated worst-case fuel.
+
+use crate::opcode::Opcode;
+use crate::tlv;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single structured diagnostic about a chip.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable machine-readable code, e.g. "stack_underflow".
+    pub code: String,
+    /// Instruction index in the decoded stream, if applicable.
+    pub instr_index: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct LintReport {
+    pub diagnostics: Vec<Diagnostic>,
+    /// Sum of per-opcode fuel prices (mirrors the VM's charging).
+    pub worst_case_fuel: u64,
+    pub instr_count: usize,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+/// Fuel price of an opcode. Must mirror the charges in `exec::Vm::run`.
+pub fn fuel_cost(op: Opcode) -> u64 {
+    match op {
+        Opcode::VerifyEd25519 => 100,
+        Opcode::RandDeterministic => 10,
+        _ => 1,
+    }
+}
+
+/// Stack effect (pops, pushes) of an opcode.
+fn stack_effect(op: Opcode) -> (usize, usize) {
+    use Opcode::*;
+    match op {
+        ConstI64 | ConstBytes | ConstDec | PushInput | MapNew | ArrayNew => (0, 1),
+        JsonNormalize | JsonValidate | JsonGetKey | HashBlake3 | CasPut | CasGet | ExecChip
+        | RandDeterministic => (1, 1),
+        AddI64 | SubI64 | MulI64 | CmpI64 | AddDec | CmpDec | MapInsert | ArrayPush => (2, 1),
+        AssertTrue | SetRcBody | AttachProof | Drop => (1, 0),
+        SignDefault | EmitRc | GhostAssert => (0, 0),
+        VerifyEd25519 => (3, 1),
+    }
+}
+
+/// Expected payload size: Some(n) = exactly n bytes, None = variable.
+fn expected_payload_len(op: Opcode) -> Option<usize> {
+    use Opcode::*;
+    match op {
+        ConstI64 => Some(8),
+        ConstDec => Some(9),
+        PushInput | AddDec => Some(2),
+        CmpI64 | CmpDec => Some(1),
+        ConstBytes | JsonGetKey | MapInsert | RandDeterministic => None,
+        _ => Some(0),
+    }
+}
+
+fn diag(severity: Severity, code: &str, instr_index: Option<usize>, message: String) -> Diagnostic {
+    Diagnostic {
+        severity,
+        code: code.into(),
+        instr_index,
+        message,
+    }
+}
+
+/// Statically analyze a raw TLV chip. Never executes anything.
+pub fn lint_chip(chip: &[u8]) -> LintReport {
+    let mut diagnostics = Vec::new();
+
+    let code = match tlv::decode_stream(chip) {
+        Ok(c) => c,
+        Err(e) => {
+            diagnostics.push(diag(
+                Severity::Error,
+                "tlv_decode",
+                None,
+                format!("TLV decode failed: {e}"),
+            ));
+            return LintReport {
+                diagnostics,
+                worst_case_fuel: 0,
+                instr_count: 0,
+            };
+        }
+    };
+
+    let mut depth: usize = 0;
+    let mut worst_case_fuel: u64 = 0;
+    let mut emit_rc_at: Option<usize> = None;
+
+    for (i, ins) in code.iter().enumerate() {
+        worst_case_fuel += fuel_cost(ins.op);
+
+        // Unreachable code: everything after the first EmitRc never runs.
+        if let Some(emit_idx) = emit_rc_at {
+            diagnostics.push(diag(
+                Severity::Warning,
+                "unreachable_code",
+                Some(i),
+                format!("{:?} is unreachable (EmitRc at instruction {emit_idx})", ins.op),
+            ));
+            continue;
+        }
+
+        // Payload size rules
+        match expected_payload_len(ins.op) {
+            Some(0) if !ins.payload.is_empty() => {
+                diagnostics.push(diag(
+                    Severity::Warning,
+                    "unused_payload",
+                    Some(i),
+                    format!("{:?} takes no payload but has {} bytes", ins.op, ins.payload.len()),
+                ));
+            }
+            Some(n) if n > 0 && ins.payload.len() != n => {
+                diagnostics.push(diag(
+                    Severity::Error,
+                    "payload_size",
+                    Some(i),
+                    format!(
+                        "{:?} expects a {n}-byte payload, got {}",
+                        ins.op,
+                        ins.payload.len()
+                    ),
+                ));
+            }
+            _ => {}
+        }
+
+        // Key-carrying opcodes need a valid non-empty UTF-8 key
+        if matches!(ins.op, Opcode::JsonGetKey | Opcode::MapInsert) {
+            match std::str::from_utf8(ins.payload) {
+                Ok("") => diagnostics.push(diag(
+                    Severity::Error,
+                    "empty_key",
+                    Some(i),
+                    format!("{:?} key payload is empty", ins.op),
+                )),
+                Ok(_) => {}
+                Err(_) => diagnostics.push(diag(
+                    Severity::Error,
+                    "invalid_key_utf8",
+                    Some(i),
+                    format!("{:?} key payload is not valid UTF-8", ins.op),
+                )),
+            }
+        }
+
+        // CmpI64/CmpDec operator byte must be a known comparison
+        if matches!(ins.op, Opcode::CmpI64 | Opcode::CmpDec)
+            && ins.payload.len() == 1
+            && ins.payload[0] > 5
+        {
+            diagnostics.push(diag(
+                Severity::Error,
+                "invalid_cmp_op",
+                Some(i),
+                format!("{:?} operator {} is out of range (0..=5)", ins.op, ins.payload[0]),
+            ));
+        }
+
+        // Decimal payloads: scale capped at 18, rounding mode 0..=2
+        let scale_byte = match ins.op {
+            Opcode::ConstDec if ins.payload.len() == 9 => Some(ins.payload[8]),
+            Opcode::AddDec if ins.payload.len() == 2 => Some(ins.payload[0]),
+            _ => None,
+        };
+        if let Some(scale) = scale_byte {
+            if scale > crate::exec::MAX_DEC_SCALE {
+                diagnostics.push(diag(
+                    Severity::Error,
+                    "invalid_dec_scale",
+                    Some(i),
+                    format!("{:?} scale {scale} exceeds max 18", ins.op),
+                ));
+            }
+        }
+        if ins.op == Opcode::AddDec && ins.payload.len() == 2 && ins.payload[1] > 2 {
+            diagnostics.push(diag(
+                Severity::Error,
+                "invalid_rounding_mode",
+                Some(i),
+                format!("AddDec rounding mode {} is out of range (0..=2)", ins.payload[1]),
+            ));
+        }
+
+        // Stack balance
+        let (pops, pushes) = stack_effect(ins.op);
+        if pops > depth {
+            diagnostics.push(diag(
+                Severity::Error,
+                "stack_underflow",
+                Some(i),
+                format!("{:?} pops {pops} value(s) but stack depth is {depth}", ins.op),
+            ));
+            depth = 0;
+        } else {
+            depth -= pops;
+        }
+        depth += pushes;
+
+        if ins.op == Opcode::EmitRc {
+            emit_rc_at = Some(i);
+        }
+    }
+
+    if emit_rc_at.is_none() {
+        diagnostics.push(diag(
+            Severity::Warning,
+            "missing_emit_rc",
+            None,
+            "chip never emits an RC (no EmitRc instruction)".into(),
+        ));
+    }
+
+    LintReport {
+        diagnostics,
+        worst_case_fuel,
+        instr_count: code.len(),
+    }
+}
@@ -20,6 +20,17 @@ pub enum Opcode {
     Drop = 0x11,
     PushInput = 0x12,  // payload: u16 index
     JsonGetKey = 0x13, // payload: utf-8 key
+    VerifyEd25519 = 0x14,
+    MapNew = 0x15,
+    MapInsert = 0x16, // payload: utf-8 key
+    ArrayNew = 0x17,
+    ArrayPush = 0x18,
+    GhostAssert = 0x19, // deny unless the VM runs in ghost mode
+    ExecChip = 0x1A,    // pop chip CID, run it in a child VM, push its RC CID
+    RandDeterministic = 0x1B, // payload: nonce; pop byte count, push keyed-BLAKE3 bytes
+    ConstDec = 0x1C, // payload: 8-byte BE mantissa + 1 byte scale
+    AddDec = 0x1D,   // payload: 1 byte result scale + 1 byte rounding mode
+    CmpDec = 0x1E,   // payload: 1 byte operator (same codes as CmpI64)
 }
 
 impl TryFrom<u8> for Opcode {
@@ -46,6 +57,17 @@ impl TryFrom<u8> for Opcode {
             0x11 => Drop,
             0x12 => PushInput,
             0x13 => JsonGetKey,
+            0x14 => VerifyEd25519,
+            0x15 => MapNew,
+            0x16 => MapInsert,
+            0x17 => ArrayNew,
+            0x18 => ArrayPush,
+            0x19 => GhostAssert,
+            0x1A => ExecChip,
+            0x1B => RandDeterministic,
+            0x1C => ConstDec,
+            0x1D => AddDec,
+            0x1E => CmpDec,
             _ => return Err(()),
         })
     }
@@ -0,0 +1,275 @@
+//! Standard policy chips shipped with the crate.
+//!
+//! A curated set of pre-built TLV chips for the validations everyone ends
+//! up writing — age gate, amount limit, field presence, signature check —
+//! so users compose known-good bytecode instead of hand-rolling it. The
+//! sources are embedded and assembled once per process; bytecode CIDs are
+//! content hashes of the assembled TLV, so they are stable across builds
+//! and pinned by the golden test below. The gate lists this set via
+//! `GET /v1/chips/standard`.
+
+use std::sync::OnceLock;
+
+/// One standard chip: embedded assembly source plus its metadata.
+/// Bytecode and CID are derived lazily via [`StdChip::bytecode`].
+pub struct StdChip {
+    /// Stable identifier, e.g. `"age-gate"`.
+    pub name: &'static str,
+    /// What the chip checks and how it denies.
+    pub description: &'static str,
+    /// What each VM input slot must contain, in order.
+    pub inputs: &'static [&'static str],
+    source: &'static str,
+}
+
+static CHIPS: &[StdChip] = &[
+    StdChip {
+        name: "age-gate",
+        description: "Denies unless the JSON input carries an integer `age` of at least 18.",
+        inputs: &["JSON object with an integer `age` field"],
+        source: r#"
+            PushInput 0
+            CasGet
+            JsonNormalize
+            JsonGetKey "age"
+            ConstI64 18
+            CmpI64 GE
+            AssertTrue
+            EmitRc
+        "#,
+    },
+    StdChip {
+        name: "amount-limit",
+        description: "Denies unless the JSON input carries an integer `amount` of at most \
+                      1000000 (minor units).",
+        inputs: &["JSON object with an integer `amount` field"],
+        source: r#"
+            PushInput 0
+            CasGet
+            JsonNormalize
+            JsonGetKey "amount"
+            ConstI64 1000000
+            CmpI64 LE
+            AssertTrue
+            EmitRc
+        "#,
+    },
+    StdChip {
+        name: "field-presence",
+        description: "Denies unless the JSON input carries an integer `id` field.",
+        inputs: &["JSON object with an integer `id` field"],
+        source: r#"
+            PushInput 0
+            CasGet
+            JsonNormalize
+            JsonGetKey "id"
+            Drop
+            EmitRc
+        "#,
+    },
+    StdChip {
+        name: "signature-check",
+        description: "Denies unless input 2 is a valid Ed25519 signature by input 0 over \
+                      input 1. Inputs are raw CAS blobs, not JSON.",
+        inputs: &[
+            "raw 32-byte Ed25519 public key",
+            "raw payload bytes",
+            "raw 64-byte signature",
+        ],
+        source: r#"
+            PushInput 0
+            CasGet
+            PushInput 1
+            CasGet
+            PushInput 2
+            CasGet
+            VerifyEd25519
+            AssertTrue
+            EmitRc
+        "#,
+    },
+];
+
+/// Assembled bytecode and CID per chip, in [`CHIPS`] order. The sources
+/// are covered by tests, so the expect can only fire on a broken build.
+fn assembled() -> &'static [(Vec<u8>, String)] {
+    static ASSEMBLED: OnceLock<Vec<(Vec<u8>, String)>> = OnceLock::new();
+    ASSEMBLED.get_or_init(|| {
+        CHIPS
+            .iter()
+            .map(|chip| {
+                let code = crate::asm::assemble(chip.source)
+                    .unwrap_or_else(|e| panic!("standard chip '{}': {e}", chip.name));
+                let cid = format!("b3:{}", blake3::hash(&code).to_hex());
+                (code, cid)
+            })
+            .collect()
+    })
+}
+
+impl StdChip {
+    // Name lookup, not address identity: `&self` coming out of `all()` or
+    // `by_name()` is not guaranteed to point into the same allocation this
+    // iteration sees, but names are unique by construction.
+    fn index(&self) -> usize {
+        CHIPS
+            .iter()
+            .position(|c| c.name == self.name)
+            .expect("StdChip values only exist inside CHIPS")
+    }
+
+    /// Assembler source of the chip.
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+
+    /// Assembled TLV bytecode, ready for the VM or `/v1/execute/rb`.
+    pub fn bytecode(&self) -> &'static [u8] {
+        &assembled()[self.index()].0
+    }
+
+    /// Content CID (`b3:` BLAKE3) of the assembled bytecode.
+    pub fn bytecode_cid(&self) -> &'static str {
+        &assembled()[self.index()].1
+    }
+}
+
+/// The full standard set, in a stable listing order.
+pub fn all() -> &'static [StdChip] {
+    CHIPS
+}
+
+/// Look up one standard chip by name.
+pub fn by_name(name: &str) -> Option<&'static StdChip> {
+    CHIPS.iter().find(|c| c.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::{CasProvider, SignProvider};
+    use crate::{Cid, ExecError, Vm, VmConfig};
+    use std::collections::HashMap;
+
+    struct MemCas {
+        store: HashMap<String, Vec<u8>>,
+    }
+
+    impl CasProvider for MemCas {
+        fn put(&mut self, bytes: &[u8]) -> Cid {
+            let cid = Cid(format!("b3:{}", blake3::hash(bytes).to_hex()));
+            self.store.insert(cid.0.clone(), bytes.to_vec());
+            cid
+        }
+        fn get(&self, cid: &Cid) -> Option<Vec<u8>> {
+            self.store.get(&cid.0).cloned()
+        }
+    }
+
+    struct FixedSigner;
+
+    impl SignProvider for FixedSigner {
+        fn sign_jws(&self, payload: &[u8]) -> Vec<u8> {
+            use ed25519_dalek::Signer;
+            ed25519_dalek::SigningKey::from_bytes(&[7u8; 32])
+                .sign(payload)
+                .to_bytes()
+                .to_vec()
+        }
+        fn kid(&self) -> String {
+            "did:dev#k1".into()
+        }
+    }
+
+    /// Run a standard chip over raw input blobs; Ok(cid) on allow,
+    /// Err on deny.
+    fn run_chip(name: &str, inputs: &[&[u8]]) -> Result<Option<Cid>, ExecError> {
+        let chip = by_name(name).expect("chip exists");
+        let mut cas = MemCas { store: HashMap::new() };
+        let input_cids: Vec<Cid> = inputs.iter().map(|b| cas.put(b)).collect();
+        let cfg = VmConfig {
+            fuel_limit: 10_000,
+            ghost: true,
+            trace: false,
+            checked_arith: false,
+        };
+        let code = crate::tlv::decode_stream(chip.bytecode()).unwrap();
+        let signer = FixedSigner;
+        let mut vm = Vm::new(cfg, cas, &signer, crate::canon::NaiveCanon, input_cids);
+        vm.run(&code).map(|o| o.rc_cid)
+    }
+
+    #[test]
+    fn every_chip_assembles_and_lints_clean() {
+        for chip in all() {
+            let report = crate::lint_chip(chip.bytecode());
+            assert!(!report.has_errors(), "{}: {report:?}", chip.name);
+            assert!(chip.bytecode_cid().starts_with("b3:"), "{}", chip.name);
+        }
+    }
+
+    #[test]
+    fn by_name_finds_the_full_set() {
+        assert_eq!(all().len(), 4);
+        for name in ["age-gate", "amount-limit", "field-presence", "signature-check"] {
+            assert!(by_name(name).is_some(), "{name}");
+        }
+        assert!(by_name("nope").is_none());
+    }
+
+    #[test]
+    fn bytecode_cids_are_golden() {
+        // These are the published CIDs users pin against; changing a chip's
+        // source is a breaking change and must land as a new chip name.
+        let expected = [
+            ("age-gate", "b3:b9be1d2d84e868d74d520f64bc728ecf86321a871662a30e8bd5df56b27b9e3a"),
+            ("amount-limit", "b3:264892f22b5fbe6a54039079e8220ba1f9b5366976b2d9a236f2551ce1755ce3"),
+            ("field-presence", "b3:49df3893b7a7309a19382e4a21433a9985489da600c93a0accb0ffbece3d85b5"),
+            ("signature-check", "b3:ef5e96acf3205ab3905003c7eee60567fa1ad5cbcc4e46999f03f5c74e0bd402"),
+        ];
+        for (name, cid) in expected {
+            assert_eq!(by_name(name).unwrap().bytecode_cid(), cid, "{name}");
+        }
+    }
+
+    #[test]
+    fn age_gate_allows_18_and_denies_under() {
+        assert!(run_chip("age-gate", &[br#"{"age":21}"#]).unwrap().is_some());
+        let err = run_chip("age-gate", &[br#"{"age":16}"#]).unwrap_err();
+        assert!(matches!(err, ExecError::Deny(_)), "got: {err}");
+    }
+
+    #[test]
+    fn amount_limit_denies_over_the_cap() {
+        assert!(run_chip("amount-limit", &[br#"{"amount":999999}"#])
+            .unwrap()
+            .is_some());
+        let err = run_chip("amount-limit", &[br#"{"amount":1000001}"#]).unwrap_err();
+        assert!(matches!(err, ExecError::Deny(_)), "got: {err}");
+    }
+
+    #[test]
+    fn field_presence_denies_missing_id() {
+        assert!(run_chip("field-presence", &[br#"{"id":7}"#]).unwrap().is_some());
+        let err = run_chip("field-presence", &[br#"{"name":"x"}"#]).unwrap_err();
+        assert!(matches!(err, ExecError::Deny(_)), "got: {err}");
+    }
+
+    #[test]
+    fn signature_check_verifies_raw_ed25519() {
+        use ed25519_dalek::Signer;
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let payload = b"attested bytes";
+        let sig = key.sign(payload).to_bytes();
+        let pk = key.verifying_key().to_bytes();
+
+        assert!(run_chip("signature-check", &[&pk, payload, &sig])
+            .unwrap()
+            .is_some());
+
+        let mut bad = sig;
+        bad[0] ^= 1;
+        let err = run_chip("signature-check", &[&pk, payload, &bad]).unwrap_err();
+        assert!(matches!(err, ExecError::Deny(_)), "got: {err}");
+    }
+}
@@ -14,6 +14,42 @@ pub enum DecodeError {
     UnknownOpcode(u8),
 }
 
+/// Owned form of a decoded TLV stream.
+///
+/// `Instr` borrows its payload from the input buffer, which makes it
+/// unsuitable for caching across requests. `DecodedChip` owns the payload
+/// bytes and hands out borrowed `Instr` views on demand.
+#[derive(Debug, Clone)]
+pub struct DecodedChip {
+    instrs: Vec<(Opcode, Vec<u8>)>,
+}
+
+impl DecodedChip {
+    pub fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let instrs = decode_stream(buf)?
+            .into_iter()
+            .map(|i| (i.op, i.payload.to_vec()))
+            .collect();
+        Ok(Self { instrs })
+    }
+
+    pub fn len(&self) -> usize {
+        self.instrs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instrs.is_empty()
+    }
+
+    /// Borrowed instruction views, suitable for `Vm::run`.
+    pub fn instrs(&self) -> Vec<Instr<'_>> {
+        self.instrs
+            .iter()
+            .map(|(op, payload)| Instr { op: *op, payload })
+            .collect()
+    }
+}
+
 pub fn decode_stream(buf: &[u8]) -> Result<Vec<Instr<'_>>, DecodeError> {
     let mut i = 0;
     let mut out = Vec::new();
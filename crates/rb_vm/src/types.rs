@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     I64(i64),
+    /// Fixed-point decimal: mantissa scaled by 10^-scale (12.34 = m:1234, scale:2).
+    Dec { m: i64, scale: u8 },
     Bytes(Vec<u8>),
     Json(serde_json::Value),
     Cid(Cid),
@@ -22,6 +24,8 @@ pub struct RcPayload {
     pub proofs: Vec<Cid>,
     pub steps: u64,
     pub fuel_used: u64,
+    /// Integer semantics the run used: "saturating" or "checked".
+    pub arith: String,
     pub policy_id: String,
     pub decision: serde_json::Value,
     pub body: serde_json::Value,
@@ -138,6 +138,27 @@ fn tlv_emit_rc() -> Vec<u8> {
 fn tlv_drop() -> Vec<u8> {
     tlv_instr(0x11, &[])
 }
+fn tlv_verify_ed25519() -> Vec<u8> {
+    tlv_instr(0x14, &[])
+}
+fn tlv_map_new() -> Vec<u8> {
+    tlv_instr(0x15, &[])
+}
+fn tlv_map_insert(key: &str) -> Vec<u8> {
+    tlv_instr(0x16, key.as_bytes())
+}
+fn tlv_array_new() -> Vec<u8> {
+    tlv_instr(0x17, &[])
+}
+fn tlv_array_push() -> Vec<u8> {
+    tlv_instr(0x18, &[])
+}
+fn tlv_ghost_assert() -> Vec<u8> {
+    tlv_instr(0x19, &[])
+}
+fn tlv_exec_chip() -> Vec<u8> {
+    tlv_instr(0x1A, &[])
+}
 
 fn build_chip(instrs: &[Vec<u8>]) -> Vec<u8> {
     instrs.iter().flat_map(|i| i.iter().copied()).collect()
@@ -154,6 +175,7 @@ fn run_chip(chip: &[u8], inputs_json: &[&str]) -> Result<VmOutcome, ExecError> {
         fuel_limit: 50_000,
         ghost: false,
         trace: false,
+        checked_arith: false,
     };
     let mut vm = Vm::new(cfg, cas, &signer, canon, input_cids);
     vm.run(&code)
@@ -174,6 +196,7 @@ fn run_chip_with_fuel(
         fuel_limit: fuel,
         ghost: false,
         trace: false,
+        checked_arith: false,
     };
     let mut vm = Vm::new(cfg, cas, &signer, canon, input_cids);
     vm.run(&code)
@@ -190,6 +213,7 @@ fn run_chip_ghost(chip: &[u8], inputs_json: &[&str]) -> Result<VmOutcome, ExecEr
         fuel_limit: 50_000,
         ghost: true,
         trace: false,
+        checked_arith: false,
     };
     let mut vm = Vm::new(cfg, cas, &signer, canon, input_cids);
     vm.run(&code)
@@ -290,7 +314,7 @@ fn law2_tlv_decode_rejects_truncated() {
 
 #[test]
 fn law2_tlv_roundtrip_all_opcodes() {
-    for op_byte in 0x01..=0x13u8 {
+    for op_byte in 0x01..=0x1Bu8 {
         let payload = vec![0u8; 8];
         let encoded = tlv_instr(op_byte, &payload);
         let decoded = tlv::decode_stream(&encoded).expect("decode");
@@ -300,6 +324,28 @@ fn law2_tlv_roundtrip_all_opcodes() {
     }
 }
 
+#[test]
+fn law2_decoded_chip_matches_borrowed_decode() {
+    // The owned DecodedChip must reproduce decode_stream exactly
+    let mut chip = tlv_instr(0x01, &7i64.to_be_bytes());
+    chip.extend(tlv_instr(0x01, &35i64.to_be_bytes()));
+    chip.extend(tlv_instr(0x05, &[]));
+
+    let borrowed = tlv::decode_stream(&chip).expect("decode_stream");
+    let owned = tlv::DecodedChip::decode(&chip).expect("DecodedChip");
+    assert_eq!(owned.len(), borrowed.len());
+    for (a, b) in owned.instrs().iter().zip(borrowed.iter()) {
+        assert_eq!(a.op as u8, b.op as u8);
+        assert_eq!(a.payload, b.payload);
+    }
+}
+
+#[test]
+fn law2_decoded_chip_rejects_invalid_stream() {
+    assert!(tlv::DecodedChip::decode(&[0xFF, 0x00, 0x00]).is_err());
+    assert!(tlv::DecodedChip::decode(&[0x01, 0x00]).is_err());
+}
+
 // ═══════════════════════════════════════════════════════════════════
 // LAW 3: No-IO by construction — VM only touches CAS + Sign
 // ═══════════════════════════════════════════════════════════════════
@@ -438,6 +484,7 @@ fn law5_invalid_payload_const_i64() {
         fuel_limit: 50_000,
         ghost: false,
         trace: false,
+        checked_arith: false,
     };
     let mut vm = Vm::new(cfg, cas, &signer, canon, vec![]);
     let result = vm.run(&code);
@@ -475,6 +522,63 @@ fn law6_ghost_deterministic() {
     assert_eq!(cid1, cid2, "Law 6: ghost mode must be deterministic");
 }
 
+#[test]
+fn law6_ghost_assert_requires_ghost_mode() {
+    let chip = build_chip(&[tlv_ghost_assert(), tlv_emit_rc()]);
+    assert!(
+        run_chip_ghost(&chip, &[]).is_ok(),
+        "Law 6: GhostAssert passes in ghost mode"
+    );
+    match run_chip(&chip, &[]) {
+        Err(ExecError::Deny(reason)) => assert!(reason.contains("ghost")),
+        other => panic!("Law 6: GhostAssert must deny outside ghost mode, got {other:?}"),
+    }
+}
+
+#[test]
+fn law6_ghost_rc_never_persisted() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// MemCas wrapper that counts writes, so we can prove EmitRc skipped put().
+    struct CountingCas {
+        inner: MemCas,
+        puts: Arc<AtomicUsize>,
+    }
+    impl CasProvider for CountingCas {
+        fn put(&mut self, bytes: &[u8]) -> Cid {
+            self.puts.fetch_add(1, Ordering::Relaxed);
+            self.inner.put(bytes)
+        }
+        fn get(&self, cid: &Cid) -> Option<Vec<u8>> {
+            self.inner.get(cid)
+        }
+    }
+
+    let chip = build_chip(&[tlv_emit_rc()]);
+    let code = tlv::decode_stream(&chip).expect("decode");
+    let puts = Arc::new(AtomicUsize::new(0));
+    let cas = CountingCas {
+        inner: MemCas::new(),
+        puts: puts.clone(),
+    };
+    let signer = FixedSigner::new();
+    let cfg = VmConfig {
+        fuel_limit: 50_000,
+        ghost: true,
+        trace: false,
+        checked_arith: false,
+    };
+    let mut vm = Vm::new(cfg, cas, &signer, NaiveCanon, vec![]);
+    let outcome = vm.run(&code).expect("ghost run");
+    assert!(outcome.rc_cid.is_some(), "Law 6: ghost run still emits RC");
+    assert_eq!(
+        puts.load(Ordering::Relaxed),
+        0,
+        "Law 6: ghost RC must never reach the CAS"
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════
 // LAW 7: Intact CID chain — CasPut → CasGet roundtrip
 // ═══════════════════════════════════════════════════════════════════
@@ -564,6 +668,86 @@ fn law9_kid_is_did() {
     assert!(kid.starts_with("did:"), "Law 9: kid must be a DID");
 }
 
+#[test]
+fn law9_verify_ed25519_valid_signature() {
+    use ed25519_dalek::Signer;
+    let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let payload = b"delegated-authorization";
+    let sig = key.sign(payload);
+
+    // Stack order: pubkey, payload, signature → VerifyEd25519 → Bool(true)
+    let chip = build_chip(&[
+        tlv_const_bytes(key.verifying_key().as_bytes()),
+        tlv_const_bytes(payload),
+        tlv_const_bytes(&sig.to_bytes()),
+        tlv_verify_ed25519(),
+        tlv_assert_true(),
+    ]);
+    let result = run_chip(&chip, &[]);
+    assert!(result.is_ok(), "Law 9: valid signature must verify");
+}
+
+#[test]
+fn law9_verify_ed25519_invalid_signature_pushes_false() {
+    use ed25519_dalek::Signer;
+    let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let sig = key.sign(b"original");
+
+    // Signature over different bytes → Bool(false) → AssertTrue denies
+    let chip = build_chip(&[
+        tlv_const_bytes(key.verifying_key().as_bytes()),
+        tlv_const_bytes(b"tampered"),
+        tlv_const_bytes(&sig.to_bytes()),
+        tlv_verify_ed25519(),
+        tlv_assert_true(),
+    ]);
+    let result = run_chip(&chip, &[]);
+    assert!(
+        matches!(result, Err(ExecError::Deny(_))),
+        "Law 9: tampered payload must not verify"
+    );
+}
+
+#[test]
+fn law9_verify_ed25519_bad_pubkey_len_denies() {
+    let chip = build_chip(&[
+        tlv_const_bytes(&[0u8; 31]),
+        tlv_const_bytes(b"payload"),
+        tlv_const_bytes(&[0u8; 64]),
+        tlv_verify_ed25519(),
+    ]);
+    let result = run_chip(&chip, &[]);
+    assert!(
+        matches!(result, Err(ExecError::Deny(_))),
+        "Law 9: malformed pubkey must deny"
+    );
+}
+
+#[test]
+fn law4_verify_ed25519_fuel_priced() {
+    use ed25519_dalek::Signer;
+    let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let payload = b"fuel";
+    let sig = key.sign(payload);
+
+    let chip = build_chip(&[
+        tlv_const_bytes(key.verifying_key().as_bytes()),
+        tlv_const_bytes(payload),
+        tlv_const_bytes(&sig.to_bytes()),
+        tlv_verify_ed25519(),
+    ]);
+    let outcome = run_chip(&chip, &[]).unwrap();
+    // 3 ConstBytes (1 each) + VerifyEd25519 (100)
+    assert_eq!(outcome.fuel_used, 103, "Law 4: VerifyEd25519 costs 100");
+
+    // Not enough fuel for the verification itself
+    let result = run_chip_with_fuel(&chip, &[], 10);
+    assert!(
+        matches!(result, Err(ExecError::FuelExhausted)),
+        "Law 4: verification must respect fuel limit"
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════
 // LAW 10: Mandatory narrative on critical denies
 // ═══════════════════════════════════════════════════════════════════
@@ -613,6 +797,77 @@ fn law10_deny_missing_key() {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════
+// Structured rc_body construction (MapNew/MapInsert/ArrayNew/ArrayPush)
+// ═══════════════════════════════════════════════════════════════════
+
+fn map_builder_chip() -> Vec<u8> {
+    // rc_body = {"decision":"allow","score":42,"tags":[1,2]}
+    build_chip(&[
+        tlv_map_new(),
+        tlv_const_bytes(b"allow"),
+        tlv_map_insert("decision"),
+        tlv_const_i64(42),
+        tlv_map_insert("score"),
+        tlv_array_new(),
+        tlv_const_i64(1),
+        tlv_array_push(),
+        tlv_const_i64(2),
+        tlv_array_push(),
+        tlv_map_insert("tags"),
+        tlv_set_rc_body(),
+        tlv_sign_default(),
+        tlv_emit_rc(),
+    ])
+}
+
+#[test]
+fn map_built_rc_body_emits_rc() {
+    let result = run_chip(&map_builder_chip(), &[]).unwrap();
+    assert!(
+        result.rc_cid.is_some(),
+        "structured rc_body must emit an RC"
+    );
+}
+
+#[test]
+fn map_built_rc_body_is_deterministic() {
+    let chip = map_builder_chip();
+    let first = run_chip(&chip, &[]).unwrap().rc_cid.unwrap();
+    for _ in 0..5 {
+        let cid = run_chip(&chip, &[]).unwrap().rc_cid.unwrap();
+        assert_eq!(first, cid, "constructed rc_body must be deterministic");
+    }
+}
+
+#[test]
+fn map_insert_on_non_map_is_type_mismatch() {
+    let chip = build_chip(&[
+        tlv_const_i64(1),
+        tlv_const_i64(2),
+        tlv_map_insert("k"),
+    ]);
+    let result = run_chip(&chip, &[]);
+    assert!(
+        matches!(result, Err(ExecError::TypeMismatch(_))),
+        "MapInsert on a non-map must be a type mismatch"
+    );
+}
+
+#[test]
+fn array_push_on_non_array_is_type_mismatch() {
+    let chip = build_chip(&[
+        tlv_map_new(),
+        tlv_const_i64(1),
+        tlv_array_push(),
+    ]);
+    let result = run_chip(&chip, &[]);
+    assert!(
+        matches!(result, Err(ExecError::TypeMismatch(_))),
+        "ArrayPush on a non-array must be a type mismatch"
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════
 // Golden CID: deny_age chip with age=25 (allow path)
 // ═══════════════════════════════════════════════════════════════════
@@ -630,3 +885,309 @@ fn golden_deny_age_allow_cid_stable() {
         assert_eq!(c.0, golden, "Golden CID drift detected!");
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════
+// LAW 11: Chip composition — ExecChip runs audited chips by CID
+// ═══════════════════════════════════════════════════════════════════
+
+#[test]
+fn law11_exec_chip_pushes_child_rc_cid() {
+    // Child: a minimal chip that just emits an RC.
+    let child = build_chip(&[tlv_emit_rc()]);
+    // Parent: put the child bytes in CAS (pushes its CID), run it, then
+    // emit our own RC with the child's RC CID dropped off the stack.
+    let parent = build_chip(&[
+        tlv_const_bytes(&child),
+        tlv_cas_put(),
+        tlv_exec_chip(),
+        tlv_drop(),
+        tlv_emit_rc(),
+    ]);
+    let outcome = run_chip(&parent, &[]).expect("composition must run");
+    assert!(outcome.rc_cid.is_some(), "Law 11: parent still emits RC");
+}
+
+#[test]
+fn law11_exec_chip_requires_cid_operand() {
+    let chip = build_chip(&[tlv_const_i64(1), tlv_exec_chip()]);
+    match run_chip(&chip, &[]) {
+        Err(ExecError::TypeMismatch(_)) => {}
+        other => panic!("Law 11: non-CID operand must be a type error, got {other:?}"),
+    }
+}
+
+#[test]
+fn law11_exec_chip_child_deny_propagates() {
+    // Child chip denies (AssertTrue on a false comparison)
+    let child = build_chip(&[
+        tlv_const_i64(1),
+        tlv_const_i64(2),
+        tlv_cmp_i64(0), // EQ → false
+        tlv_assert_true(),
+        tlv_emit_rc(),
+    ]);
+    let parent = build_chip(&[tlv_const_bytes(&child), tlv_cas_put(), tlv_exec_chip()]);
+    match run_chip(&parent, &[]) {
+        Err(ExecError::Deny(_)) => {}
+        other => panic!("Law 11: child deny must propagate, got {other:?}"),
+    }
+}
+
+#[test]
+fn law11_exec_chip_depth_limited() {
+    // Nest chips well past the depth limit: chip N runs chip N-1.
+    let mut chip = build_chip(&[tlv_emit_rc()]);
+    for _ in 0..8 {
+        chip = build_chip(&[
+            tlv_const_bytes(&chip),
+            tlv_cas_put(),
+            tlv_exec_chip(),
+            tlv_drop(),
+            tlv_emit_rc(),
+        ]);
+    }
+    match run_chip(&chip, &[]) {
+        Err(ExecError::DepthExceeded) => {}
+        other => panic!("Law 11: deep nesting must hit the depth limit, got {other:?}"),
+    }
+}
+
+#[test]
+fn law11_exec_chip_charges_child_fuel_to_parent() {
+    let child = build_chip(&[tlv_const_i64(1), tlv_drop(), tlv_emit_rc()]);
+    let parent = build_chip(&[
+        tlv_const_bytes(&child),
+        tlv_cas_put(),
+        tlv_exec_chip(),
+        tlv_drop(),
+        tlv_emit_rc(),
+    ]);
+    let solo = run_chip(&child, &[]).unwrap().fuel_used;
+    let combined = run_chip(&parent, &[]).unwrap().fuel_used;
+    assert!(
+        combined > solo,
+        "Law 11: parent fuel must include the child's usage"
+    );
+}
+
+// ── Law 1 addendum: deterministic pseudo-randomness ──────────────
+
+fn tlv_rand_deterministic(nonce: &[u8]) -> Vec<u8> {
+    tlv_instr(0x1B, nonce)
+}
+
+fn rand_chip(nonce: &[u8]) -> Vec<u8> {
+    build_chip(&[
+        tlv_map_new(),
+        tlv_const_i64(32),
+        tlv_rand_deterministic(nonce),
+        tlv_map_insert("rand"),
+        tlv_set_rc_body(),
+        tlv_emit_rc(),
+    ])
+}
+
+#[test]
+fn rand_deterministic_same_inputs_same_bytes() {
+    let chip = rand_chip(b"tiebreak-v1");
+    let input = r#"{"seed":"material"}"#;
+    let cid1 = run_chip(&chip, &[input]).unwrap().rc_cid.unwrap();
+    let cid2 = run_chip(&chip, &[input]).unwrap().rc_cid.unwrap();
+    assert_eq!(cid1, cid2, "Law 1: derived bytes must be reproducible");
+}
+
+#[test]
+fn rand_deterministic_varies_with_nonce_and_inputs() {
+    let input = r#"{"seed":"material"}"#;
+    let base = run_chip(&rand_chip(b"nonce-a"), &[input]).unwrap().rc_cid.unwrap();
+    let other_nonce = run_chip(&rand_chip(b"nonce-b"), &[input]).unwrap().rc_cid.unwrap();
+    let other_input = run_chip(&rand_chip(b"nonce-a"), &[r#"{"seed":"different"}"#])
+        .unwrap()
+        .rc_cid
+        .unwrap();
+    assert_ne!(base, other_nonce, "nonce must enter the derivation");
+    assert_ne!(base, other_input, "input CIDs must key the derivation");
+}
+
+#[test]
+fn rand_deterministic_rejects_bad_length_and_prices_fuel() {
+    // Requested byte count must be 1..=1024
+    let chip = build_chip(&[tlv_const_i64(0), tlv_rand_deterministic(b"n"), tlv_emit_rc()]);
+    match run_chip(&chip, &[]) {
+        Err(ExecError::Deny(reason)) => assert_eq!(reason, "rand_len_out_of_range"),
+        other => panic!("zero-length derivation must deny, got {other:?}"),
+    }
+
+    // 10 fuel units for the derivation, mirrored by the linter
+    let chip = build_chip(&[tlv_const_i64(32), tlv_rand_deterministic(b"n"), tlv_drop()]);
+    let outcome = run_chip(&chip, &[]).unwrap();
+    assert_eq!(outcome.fuel_used, 12); // ConstI64 + RandDeterministic(10) + Drop
+    assert_eq!(rb_vm::lint_chip(&chip).worst_case_fuel, 12);
+}
+
+// ── Law 4 addendum: overflow semantics ───────────────────────────
+
+fn overflow_chip() -> Vec<u8> {
+    build_chip(&[
+        tlv_const_i64(i64::MAX),
+        tlv_const_i64(1),
+        tlv_add_i64(),
+        tlv_drop(),
+        tlv_emit_rc(),
+    ])
+}
+
+fn run_chip_checked(chip: &[u8], inputs_json: &[&str]) -> Result<VmOutcome, ExecError> {
+    let code = tlv::decode_stream(chip).expect("decode");
+    let mut cas = MemCas::new();
+    let signer = FixedSigner::new();
+    let canon = NaiveCanon;
+
+    let input_cids: Vec<Cid> = inputs_json.iter().map(|j| cas.put(j.as_bytes())).collect();
+    let cfg = VmConfig {
+        fuel_limit: 50_000,
+        ghost: false,
+        trace: false,
+        checked_arith: true,
+    };
+    let mut vm = Vm::new(cfg, cas, &signer, canon, input_cids);
+    vm.run(&code)
+}
+
+#[test]
+fn saturating_arith_is_the_default() {
+    // i64::MAX + 1 saturates and the chip completes
+    let outcome = run_chip(&overflow_chip(), &[]).unwrap();
+    assert!(outcome.rc_cid.is_some());
+}
+
+#[test]
+fn checked_arith_denies_on_overflow() {
+    match run_chip_checked(&overflow_chip(), &[]) {
+        Err(ExecError::Deny(reason)) => assert_eq!(reason, "int_overflow"),
+        other => panic!("checked overflow must deny, got {other:?}"),
+    }
+}
+
+#[test]
+fn rc_payload_records_arith_semantics() {
+    // Verifiers must be able to tell which semantics produced a result
+    let chip = build_chip(&[tlv_const_i64(1), tlv_const_i64(2), tlv_add_i64(), tlv_drop(), tlv_emit_rc()]);
+    let code = tlv::decode_stream(&chip).expect("decode");
+    let signer = FixedSigner::new();
+
+    for (checked, expected) in [(false, "saturating"), (true, "checked")] {
+        let mut cas = MemCas::new();
+        let cfg = VmConfig {
+            fuel_limit: 50_000,
+            ghost: false,
+            trace: false,
+            checked_arith: checked,
+        };
+        let cas_ref: &mut dyn rb_vm::exec::CasProvider = &mut cas;
+        let mut vm = Vm::new(cfg, cas_ref, &signer, NaiveCanon, vec![]);
+        let rc_cid = vm.run(&code).unwrap().rc_cid.unwrap();
+        drop(vm);
+        let payload: serde_json::Value =
+            serde_json::from_slice(&cas.get(&rc_cid).unwrap()).unwrap();
+        assert_eq!(payload["arith"], expected, "checked={checked}");
+    }
+}
+
+// ── Law 8 addendum: fixed-point decimals ─────────────────────────
+
+fn tlv_const_dec(m: i64, scale: u8) -> Vec<u8> {
+    let mut p = m.to_be_bytes().to_vec();
+    p.push(scale);
+    tlv_instr(0x1C, &p)
+}
+fn tlv_add_dec(scale: u8, mode: u8) -> Vec<u8> {
+    tlv_instr(0x1D, &[scale, mode])
+}
+fn tlv_cmp_dec(op: u8) -> Vec<u8> {
+    tlv_instr(0x1E, &[op])
+}
+
+/// Chip asserting `a + b == expected`, all fixed-point at the given scale.
+fn dec_sum_chip(a: (i64, u8), b: (i64, u8), scale: u8, mode: u8, expected: (i64, u8)) -> Vec<u8> {
+    build_chip(&[
+        tlv_const_dec(a.0, a.1),
+        tlv_const_dec(b.0, b.1),
+        tlv_add_dec(scale, mode),
+        tlv_const_dec(expected.0, expected.1),
+        tlv_cmp_dec(0), // EQ
+        tlv_assert_true(),
+        tlv_emit_rc(),
+    ])
+}
+
+#[test]
+fn dec_addition_is_exact_at_scale() {
+    // 19.99 + 0.01 == 20.00 — no float drift
+    let chip = dec_sum_chip((1999, 2), (1, 2), 2, 0, (2000, 2));
+    assert!(run_chip(&chip, &[]).unwrap().rc_cid.is_some());
+}
+
+#[test]
+fn dec_rounding_modes_are_deterministic() {
+    // 0.125 rescaled to 2 digits: half-even → 0.12, down → 0.12, up → 0.13
+    for (mode, expected) in [(0u8, 12i64), (1, 12), (2, 13)] {
+        let chip = dec_sum_chip((125, 3), (0, 2), 2, mode, (expected, 2));
+        assert!(
+            run_chip(&chip, &[]).unwrap().rc_cid.is_some(),
+            "mode {mode} must round 0.125 to 0.{expected}"
+        );
+    }
+    // 0.135 half-even rounds to the even neighbour 0.14
+    let chip = dec_sum_chip((135, 3), (0, 2), 2, 0, (14, 2));
+    assert!(run_chip(&chip, &[]).unwrap().rc_cid.is_some());
+}
+
+#[test]
+fn dec_comparison_aligns_scales_exactly() {
+    // 1.5 == 1.50 across scales
+    let chip = build_chip(&[
+        tlv_const_dec(15, 1),
+        tlv_const_dec(150, 2),
+        tlv_cmp_dec(0), // EQ
+        tlv_assert_true(),
+        tlv_emit_rc(),
+    ]);
+    assert!(run_chip(&chip, &[]).unwrap().rc_cid.is_some());
+}
+
+#[test]
+fn dec_overflow_denies() {
+    let chip = dec_sum_chip((i64::MAX, 0), (1, 0), 0, 0, (0, 0));
+    match run_chip(&chip, &[]) {
+        Err(ExecError::Deny(reason)) => assert_eq!(reason, "dec_overflow"),
+        other => panic!("decimal overflow must deny, got {other:?}"),
+    }
+}
+
+#[test]
+fn dec_renders_canonically_in_rc_body() {
+    // MapInsert projects decimals as fixed-scale strings, never floats
+    let chip = build_chip(&[
+        tlv_map_new(),
+        tlv_const_dec(1250, 2),
+        tlv_map_insert("total"),
+        tlv_set_rc_body(),
+        tlv_emit_rc(),
+    ]);
+    let code = tlv::decode_stream(&chip).expect("decode");
+    let signer = FixedSigner::new();
+    let mut cas = MemCas::new();
+    let cfg = VmConfig {
+        fuel_limit: 50_000,
+        ghost: false,
+        trace: false,
+        checked_arith: false,
+    };
+    let cas_ref: &mut dyn rb_vm::exec::CasProvider = &mut cas;
+    let mut vm = Vm::new(cfg, cas_ref, &signer, NaiveCanon, vec![]);
+    let rc_cid = vm.run(&code).unwrap().rc_cid.unwrap();
+    drop(vm);
+    let payload: serde_json::Value = serde_json::from_slice(&cas.get(&rc_cid).unwrap()).unwrap();
+    assert_eq!(payload["body"]["total"], "12.50");
+}
@@ -0,0 +1,118 @@
+//! Static analyzer tests: diagnostics must catch structural chip problems
+//! without ever executing the chip.
+
+use rb_vm::{lint_chip, Severity};
+
+fn tlv_instr(op: u8, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len() as u16;
+    let mut out = vec![op];
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+fn build_chip(instrs: &[Vec<u8>]) -> Vec<u8> {
+    instrs.iter().flat_map(|i| i.iter().copied()).collect()
+}
+
+fn codes(report: &rb_vm::LintReport) -> Vec<&str> {
+    report.diagnostics.iter().map(|d| d.code.as_str()).collect()
+}
+
+#[test]
+fn clean_chip_has_no_errors() {
+    // ConstI64(1), ConstI64(2), AddI64, Drop, EmitRc
+    let chip = build_chip(&[
+        tlv_instr(0x01, &1i64.to_be_bytes()),
+        tlv_instr(0x01, &2i64.to_be_bytes()),
+        tlv_instr(0x05, &[]),
+        tlv_instr(0x11, &[]),
+        tlv_instr(0x10, &[]),
+    ]);
+    let report = lint_chip(&chip);
+    assert!(!report.has_errors(), "diags: {:?}", report.diagnostics);
+    assert_eq!(report.instr_count, 5);
+    assert_eq!(report.worst_case_fuel, 5);
+}
+
+#[test]
+fn stack_underflow_detected() {
+    // AddI64 on an empty stack
+    let chip = tlv_instr(0x05, &[]);
+    let report = lint_chip(&chip);
+    assert!(report.has_errors());
+    assert!(codes(&report).contains(&"stack_underflow"));
+}
+
+#[test]
+fn unreachable_after_emit_rc() {
+    let chip = build_chip(&[
+        tlv_instr(0x10, &[]),                   // EmitRc
+        tlv_instr(0x01, &1i64.to_be_bytes()),   // unreachable
+    ]);
+    let report = lint_chip(&chip);
+    assert!(codes(&report).contains(&"unreachable_code"));
+}
+
+#[test]
+fn missing_emit_rc_is_warning() {
+    let chip = build_chip(&[
+        tlv_instr(0x01, &1i64.to_be_bytes()),
+        tlv_instr(0x11, &[]),
+    ]);
+    let report = lint_chip(&chip);
+    assert!(!report.has_errors(), "missing EmitRc is only a warning");
+    assert!(codes(&report).contains(&"missing_emit_rc"));
+}
+
+#[test]
+fn payload_size_violation_detected() {
+    // ConstI64 with a 4-byte payload
+    let chip = tlv_instr(0x01, &[0, 0, 0, 1]);
+    let report = lint_chip(&chip);
+    assert!(report.has_errors());
+    assert!(codes(&report).contains(&"payload_size"));
+}
+
+#[test]
+fn invalid_cmp_operator_detected() {
+    let chip = build_chip(&[
+        tlv_instr(0x01, &1i64.to_be_bytes()),
+        tlv_instr(0x01, &2i64.to_be_bytes()),
+        tlv_instr(0x08, &[9]), // operator out of range
+    ]);
+    let report = lint_chip(&chip);
+    assert!(codes(&report).contains(&"invalid_cmp_op"));
+}
+
+#[test]
+fn empty_json_key_detected() {
+    let chip = build_chip(&[
+        tlv_instr(0x15, &[]), // MapNew
+        tlv_instr(0x01, &1i64.to_be_bytes()),
+        tlv_instr(0x16, &[]), // MapInsert with empty key
+    ]);
+    let report = lint_chip(&chip);
+    assert!(codes(&report).contains(&"empty_key"));
+}
+
+#[test]
+fn worst_case_fuel_prices_verify_ed25519() {
+    let chip = build_chip(&[
+        tlv_instr(0x02, &[0u8; 32]),
+        tlv_instr(0x02, b"payload"),
+        tlv_instr(0x02, &[0u8; 64]),
+        tlv_instr(0x14, &[]), // VerifyEd25519
+    ]);
+    let report = lint_chip(&chip);
+    assert_eq!(report.worst_case_fuel, 103);
+}
+
+#[test]
+fn undecodable_chip_is_single_error() {
+    let report = lint_chip(&[0xFF, 0x00, 0x00]);
+    assert!(report.has_errors());
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].code, "tlv_decode");
+    assert_eq!(report.diagnostics[0].severity, Severity::Error);
+}
@@ -35,31 +35,30 @@ fn ensure_dir(p: &Path) -> io::Result<()> {
     Ok(())
 }
 
+fn ledger_path(cid: &str) -> PathBuf {
+    let prefix = &cid[cid.len().saturating_sub(2)..];
+    repo_root().join(LEDGER_DIR).join(prefix).join(cid)
+}
+
+fn store_blob(bytes: &[u8]) -> io::Result<String> {
+    let cid = cidv1_raw_sha256_base32(bytes);
+    let dst = ledger_path(&cid);
+    ensure_dir(dst.parent().expect("sharded path"))?;
+    fs::write(&dst, bytes)?;
+    Ok(cid)
+}
+
 fn cmd_put(path: &Path) -> io::Result<()> {
     let mut f = fs::File::open(path)?;
     let mut buf = Vec::new();
     f.read_to_end(&mut buf)?;
-    let cid = cidv1_raw_sha256_base32(&buf);
-
-    // store under ledger/<prefix>/<cid>
-    let root = repo_root();
-    let ledger = root.join(LEDGER_DIR);
-    ensure_dir(&ledger)?;
-    let prefix = &cid[cid.len().saturating_sub(2)..];
-    let shard = ledger.join(prefix);
-    ensure_dir(&shard)?;
-    let dst = shard.join(&cid);
-    fs::write(&dst, &buf)?;
-
+    let cid = store_blob(&buf)?;
     println!("{cid}");
     Ok(())
 }
 
 fn cmd_get(cid: &str, out: Option<&Path>) -> io::Result<()> {
-    let root = repo_root();
-    let ledger = root.join(LEDGER_DIR);
-    let prefix = &cid[cid.len().saturating_sub(2)..];
-    let path = ledger.join(prefix).join(cid);
+    let path = ledger_path(cid);
     let bytes = fs::read(&path)?;
     if let Some(outp) = out {
         fs::write(outp, &bytes)?;
@@ -93,13 +92,38 @@ fn validate_required(obj: &serde_json::Value, required: &[&str]) -> io::Result<(
     Ok(())
 }
 
-fn cmd_attest(target_cid: &str, claim: &str, signer: &str) -> io::Result<()> {
+/// Turn evidence arguments into ledger CIDs: a file path is stored first
+/// (like `ubl put`), a CID must already exist in the ledger. The result is
+/// sorted and deduplicated so the attestation body is canonical.
+fn resolve_evidence(evidence: &[String]) -> io::Result<Vec<String>> {
+    let mut cids = Vec::new();
+    for arg in evidence {
+        let path = Path::new(arg);
+        if path.is_file() {
+            cids.push(store_blob(&fs::read(path)?)?);
+        } else {
+            if !ledger_path(arg).exists() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("evidence not found in ledger: {arg}"),
+                ));
+            }
+            cids.push(arg.clone());
+        }
+    }
+    cids.sort();
+    cids.dedup();
+    Ok(cids)
+}
+
+fn cmd_attest(target_cid: &str, claim: &str, signer: &str, evidence: &[String]) -> io::Result<()> {
     let now = Utc::now().to_rfc3339();
+    let evidence_cids = resolve_evidence(evidence)?;
     let att = json!({
         "type": "attestation",
         "target_cid": target_cid,
         "claim": claim,
-        "evidence": [],
+        "evidence": evidence_cids,
         "signer": signer,
         "created_at": now,
         "signature": "base64:TODO"
@@ -230,6 +254,16 @@ fn cmd_story(target: &str) -> io::Result<()> {
             let claim = at.get("claim").and_then(|v| v.as_str()).unwrap_or("?");
             let signer = at.get("signer").and_then(|v| v.as_str()).unwrap_or("?");
             println!("- [{created}] {claim} (by {signer})");
+            for ev in at
+                .get("evidence")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(cid) = ev.as_str() {
+                    println!("  evidence: {cid}");
+                }
+            }
         }
         println!();
     }
@@ -319,7 +353,7 @@ fn help() {
     println!("USAGE:");
     println!("  ubl put <file>               # store blob and print CID");
     println!("  ubl get <cid> [out]          # fetch blob by CID");
-    println!("  ubl attest <cid> <claim> <signer>");
+    println!("  ubl attest <cid> <claim> <signer> [evidence...]  # evidence = file or ledger CID");
     println!("  ubl event <kind> <cid> [title]   # kind=release|supersede|deprecate|yank");
     println!("  ubl story <cid>              # timeline");
     println!("  ubl verify <cid|receipt.json> # verify CID or receipt file");
@@ -341,7 +375,8 @@ fn main() -> io::Result<()> {
             let cid = args.next().expect("cid");
             let claim = args.next().expect("claim");
             let signer = args.next().expect("signer");
-            cmd_attest(&cid, &claim, &signer)?
+            let evidence: Vec<String> = args.collect();
+            cmd_attest(&cid, &claim, &signer, &evidence)?
         }
         Some("event") => {
             let kind = args.next().expect("kind");
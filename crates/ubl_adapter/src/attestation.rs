@@ -0,0 +1,75 @@
+//! Adapter attestations — auditable provenance for every adapter call.
+//!
+//! An adapter execution is external IO; once its result is pinned, the
+//! host mints a signed `ubl/attestation` receipt over the call so the
+//! chain records what was fetched, under which policy, and for which
+//! execution. This module builds the canonical attestation body; signing
+//! stays host-side (`ubl_runtime::attest_adapter`) because keys never
+//! enter the Wasm-safe boundary.
+
+use crate::types::{AdapterPolicy, AdapterResponse};
+use serde_json::json;
+
+/// Build the attestation body for a completed adapter call.
+///
+/// Records the adapter kind, the frozen-params CID, the pinned response
+/// CID and status, and the CID of the policy that admitted the call.
+/// A denied call never reaches this point — it fails before any IO — so
+/// the recorded decision is always `allow`; the policy CID lets auditors
+/// recover the exact constraints that were in force.
+pub fn attestation_body(resp: &AdapterResponse, policy: &AdapterPolicy) -> serde_json::Value {
+    let policy_bytes = serde_json::to_vec(policy).unwrap_or_default();
+    json!({
+        "type": "ubl/attestation",
+        "action": "adapter",
+        "kind": resp.kind,
+        "params_cid": resp.params_cid,
+        "response_cid": resp.pinned.cid,
+        "status": resp.pinned.status,
+        "policy_decision": "allow",
+        "policy_cid": crate::cid::cid_b3(&policy_bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PinnedBlob;
+    use std::collections::BTreeMap;
+
+    fn response() -> AdapterResponse {
+        AdapterResponse {
+            kind: "http".into(),
+            params_cid: "b3:abc".into(),
+            pinned: PinnedBlob::from_bytes(b"{\"ok\":true}", 200, BTreeMap::new()),
+            attempts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn body_records_call_provenance() {
+        let body = attestation_body(&response(), &AdapterPolicy::default());
+        assert_eq!(body["type"], "ubl/attestation");
+        assert_eq!(body["action"], "adapter");
+        assert_eq!(body["kind"], "http");
+        assert_eq!(body["params_cid"], "b3:abc");
+        assert_eq!(body["response_cid"], response().pinned.cid);
+        assert_eq!(body["status"], 200);
+        assert_eq!(body["policy_decision"], "allow");
+        assert!(body["policy_cid"].as_str().unwrap().starts_with("b3:"));
+    }
+
+    #[test]
+    fn policy_cid_tracks_policy_changes() {
+        let resp = response();
+        let open = attestation_body(&resp, &AdapterPolicy::default());
+        let pinned = attestation_body(
+            &resp,
+            &AdapterPolicy {
+                allowed_urls: vec!["https://api.example.com/*".into()],
+                ..Default::default()
+            },
+        );
+        assert_ne!(open["policy_cid"], pinned["policy_cid"]);
+    }
+}
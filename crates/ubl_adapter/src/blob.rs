@@ -0,0 +1,139 @@
+//! Blob Adapter — freezes ops-controlled file/object reads by CID.
+//!
+//! Some pipeline inputs live as files or objects managed by operations
+//! (mounted reference data, exported datasets). The adapter reads them at
+//! the IO boundary and returns a `PinnedBlob`, so the runtime only ever
+//! consumes the CID — the host persists the pinned bytes to the ledger.
+//!
+//! Policy enforcement:
+//! - Mount allowlist: reads go through configured mount aliases only,
+//!   never arbitrary paths. An empty allowlist denies everything.
+//! - Path hygiene: relative paths only, no traversal.
+//! - Max response size.
+//!
+//! Mount aliases resolve to real roots only at the IO boundary
+//! (env `UBL_BLOB_MOUNT_<ALIAS>`); an S3-backed mount plugs in the same
+//! way on hosts built with the ledger's `s3` feature.
+
+use crate::error::{AdapterError, Result};
+use crate::types::{AdapterPolicy, BlobParams};
+#[cfg(feature = "blob")]
+use crate::types::{AdapterResponse, PinnedBlob};
+
+/// Verify that the blob read is allowed by the adapter policy.
+pub fn check_policy(params: &BlobParams, policy: &AdapterPolicy) -> Result<()> {
+    // Mounts are explicit: no allowlist means no filesystem access
+    if !policy.allowed_blob_mounts.contains(&params.mount) {
+        return Err(AdapterError::PolicyDeny {
+            adapter: format!("blob: mount '{}' not in allowlist", params.mount),
+        });
+    }
+
+    // Relative, traversal-free paths only
+    let path = std::path::Path::new(&params.path);
+    let clean = path.components().all(|c| {
+        matches!(c, std::path::Component::Normal(_) | std::path::Component::CurDir)
+    });
+    if params.path.is_empty() || !clean {
+        return Err(AdapterError::PolicyDeny {
+            adapter: format!("blob: path '{}' must be relative without traversal", params.path),
+        });
+    }
+
+    Ok(())
+}
+
+/// Read a blob from a configured mount and pin it by CID.
+///
+/// This is the IO boundary — it runs OUTSIDE the deterministic runtime.
+/// The mount alias resolves via `UBL_BLOB_MOUNT_<ALIAS>` (uppercased) to
+/// the mount's root directory.
+#[cfg(feature = "blob")]
+pub fn execute(params: &BlobParams, policy: &AdapterPolicy) -> Result<AdapterResponse> {
+    check_policy(params, policy)?;
+
+    let env_key = format!(
+        "UBL_BLOB_MOUNT_{}",
+        params.mount.to_uppercase().replace('-', "_")
+    );
+    let root = std::env::var(&env_key).map_err(|_| {
+        AdapterError::General(format!("blob: mount '{}' not configured ({env_key})", params.mount))
+    })?;
+
+    let full = std::path::Path::new(&root).join(&params.path);
+    let bytes = std::fs::read(&full)
+        .map_err(|e| AdapterError::General(format!("blob: read '{}': {e}", params.path)))?;
+
+    if policy.max_response_bytes > 0 && bytes.len() > policy.max_response_bytes {
+        return Err(AdapterError::General(format!(
+            "blob: too large: {} bytes (max {})",
+            bytes.len(),
+            policy.max_response_bytes
+        )));
+    }
+
+    Ok(AdapterResponse {
+        kind: "blob".into(),
+        params_cid: params.params_cid(),
+        pinned: PinnedBlob::from_bytes(&bytes, 0, std::collections::BTreeMap::new()),
+        attempts: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(mount: &str, path: &str) -> BlobParams {
+        BlobParams {
+            mount: mount.into(),
+            path: path.into(),
+        }
+    }
+
+    fn policy(mounts: &[&str]) -> AdapterPolicy {
+        AdapterPolicy {
+            allowed_blob_mounts: mounts.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn empty_allowlist_denies_everything() {
+        assert!(check_policy(&params("refdata", "rates.json"), &AdapterPolicy::default()).is_err());
+    }
+
+    #[test]
+    fn mount_allowlist_is_enforced() {
+        let policy = policy(&["refdata"]);
+        assert!(check_policy(&params("refdata", "rates.json"), &policy).is_ok());
+        assert!(check_policy(&params("secrets", "keys.pem"), &policy).is_err());
+    }
+
+    #[test]
+    fn traversal_and_absolute_paths_are_rejected() {
+        let policy = policy(&["refdata"]);
+        assert!(check_policy(&params("refdata", "../etc/passwd"), &policy).is_err());
+        assert!(check_policy(&params("refdata", "/etc/passwd"), &policy).is_err());
+        assert!(check_policy(&params("refdata", "a/../../b"), &policy).is_err());
+        assert!(check_policy(&params("refdata", ""), &policy).is_err());
+        assert!(check_policy(&params("refdata", "sub/dir/file.json"), &policy).is_ok());
+    }
+
+    #[cfg(feature = "blob")]
+    #[test]
+    fn execute_pins_mounted_file_by_cid() {
+        let dir = std::env::temp_dir().join(format!("ubl-blob-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("rates.json"), br#"{"usd":1.0}"#).unwrap();
+        std::env::set_var("UBL_BLOB_MOUNT_REFDATA", &dir);
+
+        let resp = execute(&params("refdata", "rates.json"), &policy(&["refdata"])).unwrap();
+        assert_eq!(resp.kind, "blob");
+        assert!(resp.pinned.verify());
+        assert_eq!(resp.pinned.data, r#"{"usd":1.0}"#);
+
+        // Unconfigured mounts fail closed even when allowlisted
+        assert!(execute(&params("other", "rates.json"), &policy(&["other"])).is_err());
+    }
+}
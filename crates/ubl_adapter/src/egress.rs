@@ -0,0 +1,148 @@
+//! Egress guard — keeps adapter traffic off internal networks.
+//!
+//! URL allowlists alone can be bypassed with DNS tricks (a public name
+//! resolving to 169.254.169.254) or open redirects. The guard resolves
+//! the target host BEFORE any request leaves the adapter and blocks
+//! private, loopback, link-local (cloud metadata), CGNAT and unique-local
+//! ranges. Redirect hops are re-validated in `http::execute`.
+
+use crate::error::{AdapterError, Result};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+/// Is this address off-limits for egress?
+pub fn ip_is_blocked(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4_blocked(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(mapped) => v4_blocked(&mapped),
+            None => v6_blocked(v6),
+        },
+    }
+}
+
+fn v4_blocked(ip: &Ipv4Addr) -> bool {
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local() // includes 169.254.169.254 (cloud metadata)
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        // CGNAT 100.64.0.0/10
+        || (ip.octets()[0] == 100 && ip.octets()[1] & 0b1100_0000 == 64)
+}
+
+fn v6_blocked(ip: &Ipv6Addr) -> bool {
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.segments()[0] & 0xffc0 == 0xfe80 // link-local fe80::/10
+        || ip.segments()[0] & 0xfe00 == 0xfc00 // unique-local fc00::/7
+}
+
+/// Split an URL authority into host and optional port.
+/// Handles bracketed IPv6 literals (`[::1]:8443`).
+pub(crate) fn split_host(host: &str) -> &str {
+    if let Some(rest) = host.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    }
+}
+
+/// Check a URL host (optionally with port) against the guard and return
+/// the vetted addresses.
+///
+/// Literal IPs are checked directly; names are resolved and EVERY
+/// returned address must pass — a single A record pointing inside is
+/// enough to block the call. Callers making the actual connection must
+/// pin these addresses into their HTTP client: a second, independent
+/// lookup at connect time would let a rebinding DNS server answer the
+/// guard with a public IP and the client with an internal one. The port
+/// in the returned addrs is a placeholder; the URL port wins.
+pub fn vetted_addrs(host: &str) -> Result<Vec<SocketAddr>> {
+    let name = split_host(host);
+
+    if let Ok(ip) = name.parse::<IpAddr>() {
+        if ip_is_blocked(&ip) {
+            return Err(AdapterError::PolicyDeny {
+                adapter: format!("http: egress to blocked address {ip}"),
+            });
+        }
+        return Ok(vec![SocketAddr::new(ip, 0)]);
+    }
+
+    let addrs: Vec<SocketAddr> = (name, 443u16)
+        .to_socket_addrs()
+        .map_err(|e| AdapterError::Http(format!("egress: resolve '{name}': {e}")))?
+        .collect();
+    for addr in &addrs {
+        if ip_is_blocked(&addr.ip()) {
+            return Err(AdapterError::PolicyDeny {
+                adapter: format!("http: host '{name}' resolves to blocked address {}", addr.ip()),
+            });
+        }
+    }
+    Ok(addrs)
+}
+
+/// Pass/fail form of [`vetted_addrs`], for callers that only gate (e.g.
+/// redirect re-validation) and never open the connection themselves.
+pub fn check_host(host: &str) -> Result<()> {
+    vetted_addrs(host).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocked(s: &str) -> bool {
+        ip_is_blocked(&s.parse().unwrap())
+    }
+
+    #[test]
+    fn private_and_special_ranges_are_blocked() {
+        for ip in [
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "127.0.0.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fe80::1",
+            "fc00::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(blocked(ip), "{ip} should be blocked");
+        }
+    }
+
+    #[test]
+    fn public_addresses_pass() {
+        for ip in ["1.1.1.1", "8.8.8.8", "2606:4700::1111", "::ffff:1.1.1.1"] {
+            assert!(!blocked(ip), "{ip} should pass");
+        }
+    }
+
+    #[test]
+    fn literal_hosts_checked_with_and_without_port() {
+        assert!(check_host("169.254.169.254").is_err());
+        assert!(check_host("192.168.0.1:8080").is_err());
+        assert!(check_host("[::1]:8443").is_err());
+        assert!(check_host("1.1.1.1:443").is_ok());
+    }
+
+    #[test]
+    fn names_resolving_inside_are_blocked() {
+        assert!(check_host("localhost").is_err());
+    }
+
+    #[test]
+    fn vetted_addrs_returns_the_checked_addresses() {
+        // Literal hosts vet to themselves (placeholder port; URL port wins)
+        let addrs = vetted_addrs("1.1.1.1:443").unwrap();
+        assert_eq!(addrs, vec!["1.1.1.1:0".parse().unwrap()]);
+        assert!(vetted_addrs("localhost").is_err());
+    }
+}
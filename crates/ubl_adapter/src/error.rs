@@ -8,6 +8,9 @@ pub enum AdapterError {
     #[error("http: {0}")]
     Http(String),
 
+    #[error("sql: {0}")]
+    Sql(String),
+
     #[error("policy: adapter '{adapter}' not allowed by policy")]
     PolicyDeny { adapter: String },
 
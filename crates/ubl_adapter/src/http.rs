@@ -8,34 +8,44 @@
 //! - URL allowlist (glob matching)
 //! - Max response size
 //! - Timeout
+//! - Retry with backoff and a per-host circuit breaker (half-open probing)
 
 use crate::error::{AdapterError, Result};
-use crate::types::{AdapterPolicy, AdapterResponse, HttpParams};
+use crate::types::{AdapterPolicy, AdapterResponse, HttpParams, RetryPolicy};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
 #[cfg(any(feature = "http", test))]
 use crate::types::PinnedBlob;
 #[cfg(any(feature = "http", test))]
 use std::collections::BTreeMap;
 
+/// Does a URL match the allowlist? Empty allowlist = allow all.
+/// Shared by the pre-flight check and redirect re-validation.
+pub fn url_allowed(url: &str, allowed_urls: &[String]) -> bool {
+    if allowed_urls.is_empty() {
+        return true;
+    }
+    allowed_urls.iter().any(|pattern| {
+        if pattern == "*" {
+            return true;
+        }
+        // Simple glob: "https://api.example.com/*" matches any path
+        if let Some(prefix) = pattern.strip_suffix('*') {
+            url.starts_with(prefix)
+        } else {
+            url == pattern
+        }
+    })
+}
+
 /// Verify that the HTTP request is allowed by the adapter policy.
 pub fn check_policy(params: &HttpParams, policy: &AdapterPolicy) -> Result<()> {
     // URL allowlist
-    if !policy.allowed_urls.is_empty() {
-        let allowed = policy.allowed_urls.iter().any(|pattern| {
-            if pattern == "*" {
-                return true;
-            }
-            // Simple glob: "https://api.example.com/*" matches any path
-            if let Some(prefix) = pattern.strip_suffix('*') {
-                params.url.starts_with(prefix)
-            } else {
-                params.url == *pattern
-            }
+    if !url_allowed(&params.url, &policy.allowed_urls) {
+        return Err(AdapterError::PolicyDeny {
+            adapter: format!("http: URL '{}' not in allowlist", params.url),
         });
-        if !allowed {
-            return Err(AdapterError::PolicyDeny {
-                adapter: format!("http: URL '{}' not in allowlist", params.url),
-            });
-        }
     }
 
     // Timeout cap
@@ -49,23 +59,102 @@ pub fn check_policy(params: &HttpParams, policy: &AdapterPolicy) -> Result<()> {
     Ok(())
 }
 
+/// Extract the host (including port) from a URL, for breaker bookkeeping.
+pub fn host_of(url: &str) -> String {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    rest.split(['/', '?', '#'])
+        .next()
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+/// Ceiling on a single backoff sleep. Anything longer is an effective
+/// hang, not a retry strategy.
+const MAX_BACKOFF_MS: u64 = 60_000;
+
+/// Exponential backoff for the given 1-based attempt, with the exponent
+/// and the resulting delay both capped: `max_attempts` is a deserialized
+/// `u32`, so an unclamped `backoff_ms << (attempt - 1)` overflows the
+/// shift at attempt 65 and inflates into years long before that.
+pub fn backoff_delay_ms(retry: &RetryPolicy, attempt: u32) -> u64 {
+    retry
+        .backoff_ms
+        .saturating_mul(1u64 << attempt.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF_MS)
+}
+
+/// Should a response status trigger another attempt?
+/// Transport errors (no status) always retry; statuses only when listed.
+pub fn should_retry(status: Option<u16>, retry: &RetryPolicy) -> bool {
+    match status {
+        None => true,
+        Some(s) => retry.retry_on_statuses.contains(&s),
+    }
+}
+
+/// Per-host circuit state: consecutive failures and when the circuit
+/// last opened. Shared across calls — flakiness is a host property.
+struct Breaker {
+    failures: u32,
+    opened_at: Option<Instant>,
+}
+
+fn breakers() -> &'static Mutex<HashMap<String, Breaker>> {
+    static BREAKERS: OnceLock<Mutex<HashMap<String, Breaker>>> = OnceLock::new();
+    BREAKERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Gate a call on the host's circuit. An open circuit rejects until the
+/// cooldown elapses, then admits a single half-open probe whose outcome
+/// closes or reopens it.
+pub fn breaker_allow(host: &str, policy: &AdapterPolicy) -> Result<()> {
+    if policy.breaker_failure_threshold == 0 {
+        return Ok(());
+    }
+    let mut map = breakers().lock().unwrap();
+    if let Some(state) = map.get_mut(host) {
+        if let Some(opened) = state.opened_at {
+            if opened.elapsed().as_millis() < u128::from(policy.breaker_cooldown_ms) {
+                return Err(AdapterError::Http(format!(
+                    "circuit open for host '{host}'"
+                )));
+            }
+            // Half-open: admit one probe; failures stay at threshold so a
+            // failed probe reopens immediately.
+            state.opened_at = None;
+        }
+    }
+    Ok(())
+}
+
+/// Record a call outcome against the host's circuit.
+pub fn breaker_record(host: &str, ok: bool, policy: &AdapterPolicy) {
+    if policy.breaker_failure_threshold == 0 {
+        return;
+    }
+    let mut map = breakers().lock().unwrap();
+    let state = map.entry(host.to_string()).or_insert(Breaker {
+        failures: 0,
+        opened_at: None,
+    });
+    if ok {
+        state.failures = 0;
+        state.opened_at = None;
+    } else {
+        state.failures += 1;
+        if state.failures >= policy.breaker_failure_threshold {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
+
 /// Execute an HTTP request and pin the response by CID.
 ///
 /// This is the IO boundary — it runs OUTSIDE the deterministic runtime.
 /// The returned `AdapterResponse` contains a `PinnedBlob` whose CID
 /// can be verified independently.
 #[cfg(feature = "http")]
-pub async fn execute(
-    params: &HttpParams,
-    policy: &AdapterPolicy,
-) -> Result<AdapterResponse> {
-    check_policy(params, policy)?;
-
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_millis(params.timeout_ms))
-        .build()
-        .map_err(|e| AdapterError::Http(e.to_string()))?;
-
+fn build_request(client: &reqwest::Client, params: &HttpParams) -> Result<reqwest::RequestBuilder> {
     let mut req = match params.method.to_uppercase().as_str() {
         "GET" => client.get(&params.url),
         "POST" => client.post(&params.url),
@@ -88,43 +177,135 @@ pub async fn execute(
         req = req.body(body.clone());
     }
 
-    let resp = req
-        .send()
-        .await
+    Ok(req)
+}
+
+#[cfg(feature = "http")]
+pub async fn execute(
+    params: &HttpParams,
+    policy: &AdapterPolicy,
+) -> Result<AdapterResponse> {
+    check_policy(params, policy)?;
+
+    let host = host_of(&params.url);
+    // Egress guard: resolve the host and refuse internal addresses before
+    // any bytes leave the adapter
+    let vetted = crate::egress::vetted_addrs(&host)?;
+    breaker_allow(&host, policy)?;
+
+    // Redirects are disabled unless the policy opts in; each admitted hop
+    // is re-validated against the allowlist and the egress guard
+    let redirect = if policy.max_redirect_hops == 0 {
+        reqwest::redirect::Policy::none()
+    } else {
+        let allowed_urls = policy.allowed_urls.clone();
+        let max_hops = policy.max_redirect_hops as usize;
+        reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() > max_hops {
+                return attempt.error("too many redirect hops");
+            }
+            if !url_allowed(attempt.url().as_str(), &allowed_urls) {
+                return attempt.error("redirect target not in allowlist");
+            }
+            match attempt.url().host_str() {
+                Some(host) if crate::egress::check_host(host).is_ok() => attempt.follow(),
+                _ => attempt.error("redirect target blocked by egress guard"),
+            }
+        })
+    };
+
+    // Pin the vetted addresses into the client so it cannot re-resolve
+    // the name: without this a rebinding DNS server could answer the
+    // guard with a public IP and the connect-time lookup with an
+    // internal one
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(params.timeout_ms))
+        .redirect(redirect)
+        .resolve_to_addrs(crate::egress::split_host(&host), &vetted)
+        .build()
         .map_err(|e| AdapterError::Http(e.to_string()))?;
 
-    let status = resp.status().as_u16();
+    let total = policy.retry.max_attempts.max(1);
+    let mut attempts = Vec::new();
+
+    for attempt in 1..=total {
+        let resp = match build_request(&client, params)?.send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                attempts.push(crate::types::AttemptMeta {
+                    attempt,
+                    status: None,
+                    error: Some(e.to_string()),
+                });
+                if attempt < total {
+                    tokio::time::sleep(std::time::Duration::from_millis(backoff_delay_ms(
+                        &policy.retry,
+                        attempt,
+                    )))
+                    .await;
+                    continue;
+                }
+                breaker_record(&host, false, policy);
+                return Err(AdapterError::Http(format!(
+                    "{e} (after {attempt} attempts)"
+                )));
+            }
+        };
+
+        let status = resp.status().as_u16();
+        attempts.push(crate::types::AttemptMeta {
+            attempt,
+            status: Some(status),
+            error: None,
+        });
 
-    // Capture response headers (subset for audit)
-    let mut resp_headers = BTreeMap::new();
-    for (k, v) in resp.headers() {
-        if let Ok(val) = v.to_str() {
-            resp_headers.insert(k.to_string(), val.to_string());
+        if should_retry(Some(status), &policy.retry) && attempt < total {
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_delay_ms(
+                &policy.retry,
+                attempt,
+            )))
+            .await;
+            continue;
         }
-    }
 
-    let body_bytes = resp
-        .bytes()
-        .await
-        .map_err(|e| AdapterError::Http(e.to_string()))?;
+        // A retryable status on the last attempt still pins (provenance
+        // beats prettiness) but counts as a failure for the breaker.
+        breaker_record(&host, !should_retry(Some(status), &policy.retry), policy);
 
-    // Enforce max response size
-    if policy.max_response_bytes > 0 && body_bytes.len() > policy.max_response_bytes {
-        return Err(AdapterError::Http(format!(
-            "response too large: {} bytes (max {})",
-            body_bytes.len(),
-            policy.max_response_bytes
-        )));
-    }
+        // Capture response headers (subset for audit)
+        let mut resp_headers = BTreeMap::new();
+        for (k, v) in resp.headers() {
+            if let Ok(val) = v.to_str() {
+                resp_headers.insert(k.to_string(), val.to_string());
+            }
+        }
 
-    let pinned = PinnedBlob::from_bytes(&body_bytes, status, resp_headers);
-    let params_cid = params.params_cid();
+        let body_bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| AdapterError::Http(e.to_string()))?;
 
-    Ok(AdapterResponse {
-        kind: "http".into(),
-        params_cid,
-        pinned,
-    })
+        // Enforce max response size
+        if policy.max_response_bytes > 0 && body_bytes.len() > policy.max_response_bytes {
+            return Err(AdapterError::Http(format!(
+                "response too large: {} bytes (max {})",
+                body_bytes.len(),
+                policy.max_response_bytes
+            )));
+        }
+
+        let pinned = PinnedBlob::from_bytes(&body_bytes, status, resp_headers);
+        let params_cid = params.params_cid();
+
+        return Ok(AdapterResponse {
+            kind: "http".into(),
+            params_cid,
+            pinned,
+            attempts,
+        });
+    }
+
+    unreachable!("retry loop always returns on the last attempt")
 }
 
 /// Verify that a previously pinned response still matches its CID.
@@ -202,6 +383,88 @@ mod tests {
         assert!(check_policy(&p, &policy).is_ok());
     }
 
+    #[test]
+    fn host_extraction_includes_port_and_drops_path() {
+        assert_eq!(host_of("https://API.Example.com/v1/data?x=1"), "api.example.com");
+        assert_eq!(host_of("http://localhost:8080/x"), "localhost:8080");
+        assert_eq!(host_of("no-scheme.example.com"), "no-scheme.example.com");
+    }
+
+    #[test]
+    fn backoff_doubles_then_caps() {
+        let retry = RetryPolicy {
+            backoff_ms: 500,
+            ..Default::default()
+        };
+        assert_eq!(backoff_delay_ms(&retry, 1), 500);
+        assert_eq!(backoff_delay_ms(&retry, 2), 1_000);
+        assert_eq!(backoff_delay_ms(&retry, 7), 32_000);
+        // Delay cap kicks in well before the exponent cap
+        assert_eq!(backoff_delay_ms(&retry, 8), MAX_BACKOFF_MS);
+        // Deserialized max_attempts is unclamped; attempt 65+ must not
+        // overflow the shift, and huge bases must not overflow the mul
+        assert_eq!(backoff_delay_ms(&retry, 65), MAX_BACKOFF_MS);
+        assert_eq!(backoff_delay_ms(&retry, u32::MAX), MAX_BACKOFF_MS);
+        let huge = RetryPolicy {
+            backoff_ms: u64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(backoff_delay_ms(&huge, 50), MAX_BACKOFF_MS);
+    }
+
+    #[test]
+    fn retry_classification() {
+        let retry = RetryPolicy {
+            retry_on_statuses: vec![502, 503],
+            ..Default::default()
+        };
+        assert!(should_retry(None, &retry));
+        assert!(should_retry(Some(503), &retry));
+        assert!(!should_retry(Some(500), &retry));
+        assert!(!should_retry(Some(200), &retry));
+        // No listed statuses: only transport errors retry
+        assert!(!should_retry(Some(503), &RetryPolicy::default()));
+    }
+
+    #[test]
+    fn breaker_opens_after_threshold_and_probes_half_open() {
+        let policy = AdapterPolicy {
+            breaker_failure_threshold: 2,
+            breaker_cooldown_ms: 60_000,
+            ..Default::default()
+        };
+        let host = "breaker-test-open.example.com";
+        assert!(breaker_allow(host, &policy).is_ok());
+        breaker_record(host, false, &policy);
+        assert!(breaker_allow(host, &policy).is_ok());
+        breaker_record(host, false, &policy);
+        assert!(breaker_allow(host, &policy).is_err());
+
+        // Zero cooldown: the next check admits a half-open probe, and a
+        // failed probe reopens immediately
+        let probing = AdapterPolicy {
+            breaker_cooldown_ms: 0,
+            ..policy.clone()
+        };
+        assert!(breaker_allow(host, &probing).is_ok());
+        breaker_record(host, false, &probing);
+        assert!(breaker_allow(host, &policy).is_err());
+
+        // A successful probe closes the circuit
+        assert!(breaker_allow(host, &probing).is_ok());
+        breaker_record(host, true, &probing);
+        assert!(breaker_allow(host, &policy).is_ok());
+    }
+
+    #[test]
+    fn breaker_disabled_when_threshold_zero() {
+        let policy = AdapterPolicy::default();
+        let host = "breaker-test-disabled.example.com";
+        breaker_record(host, false, &policy);
+        breaker_record(host, false, &policy);
+        assert!(breaker_allow(host, &policy).is_ok());
+    }
+
     #[test]
     fn verify_pinned_ok() {
         let pinned = PinnedBlob::from_bytes(b"test data", 200, BTreeMap::new());
@@ -209,6 +472,7 @@ mod tests {
             kind: "http".into(),
             params_cid: "b3:test".into(),
             pinned,
+            attempts: Vec::new(),
         };
         assert!(verify_pinned(&resp).is_ok());
     }
@@ -221,6 +485,7 @@ mod tests {
             kind: "http".into(),
             params_cid: "b3:test".into(),
             pinned,
+            attempts: Vec::new(),
         };
         assert!(verify_pinned(&resp).is_err());
     }
@@ -21,10 +21,17 @@
 //! The runtime only ever sees CIDs. The actual IO happens outside the
 //! deterministic boundary, and the response is pinned by its content hash.
 
+pub mod attestation;
+pub mod blob;
 pub mod cid;
+pub mod egress;
 pub mod error;
 pub mod http;
+pub mod sql;
 pub mod types;
 
 pub use error::AdapterError;
-pub use types::{AdapterRequest, AdapterResponse, HttpParams, PinnedBlob};
+pub use types::{
+    AdapterRequest, AdapterResponse, AttemptMeta, BlobParams, HttpParams, PinnedBlob, RetryPolicy,
+    SqlParams,
+};
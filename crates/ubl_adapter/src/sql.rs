@@ -0,0 +1,264 @@
+//! SQL Adapter — freezes read-only query results by CID.
+//!
+//! The adapter takes a frozen `SqlParams` (connection alias + parameterized
+//! query), executes it outside the deterministic boundary, canonicalizes
+//! the result rows, and returns a `PinnedBlob` so the runtime only ever
+//! consumes CID-pinned datasets.
+//!
+//! Policy enforcement:
+//! - Read-only: the query must be a single SELECT (or WITH … SELECT)
+//! - Alias allowlist (exact match)
+//! - Query allowlist (exact or prefix glob, like URL patterns)
+//! - Timeout
+//!
+//! Connection aliases resolve to real connections only at the IO boundary
+//! (env `UBL_SQL_DB_<ALIAS>`), so credentials never enter params or CIDs.
+
+use crate::error::{AdapterError, Result};
+use crate::types::{AdapterPolicy, PinnedBlob, SqlParams};
+#[cfg(feature = "sql")]
+use crate::types::AdapterResponse;
+use std::collections::BTreeMap;
+
+/// Verify that the SQL query is allowed by the adapter policy.
+pub fn check_policy(params: &SqlParams, policy: &AdapterPolicy) -> Result<()> {
+    // Read-only: single SELECT statement (WITH allows CTEs)
+    let normalized = params.query.trim().to_lowercase();
+    if !(normalized.starts_with("select") || normalized.starts_with("with")) {
+        return Err(AdapterError::PolicyDeny {
+            adapter: "sql: only read-only SELECT queries are allowed".into(),
+        });
+    }
+    if normalized.trim_end_matches(';').contains(';') {
+        return Err(AdapterError::PolicyDeny {
+            adapter: "sql: multiple statements are not allowed".into(),
+        });
+    }
+
+    // Alias allowlist
+    if !policy.allowed_sql_aliases.is_empty()
+        && !policy.allowed_sql_aliases.contains(&params.alias)
+    {
+        return Err(AdapterError::PolicyDeny {
+            adapter: format!("sql: alias '{}' not in allowlist", params.alias),
+        });
+    }
+
+    // Query allowlist (same glob rules as URL patterns)
+    if !policy.allowed_sql_queries.is_empty() {
+        let allowed = policy.allowed_sql_queries.iter().any(|pattern| {
+            if pattern == "*" {
+                return true;
+            }
+            if let Some(prefix) = pattern.strip_suffix('*') {
+                params.query.starts_with(prefix)
+            } else {
+                params.query == *pattern
+            }
+        });
+        if !allowed {
+            return Err(AdapterError::PolicyDeny {
+                adapter: "sql: query not in allowlist".into(),
+            });
+        }
+    }
+
+    // Timeout cap
+    if policy.max_timeout_ms > 0 && params.timeout_ms > policy.max_timeout_ms {
+        return Err(AdapterError::Timeout {
+            adapter: "sql".into(),
+            timeout_ms: policy.max_timeout_ms,
+        });
+    }
+
+    Ok(())
+}
+
+/// Canonicalize result rows and pin them by CID. Column order is
+/// normalized (sorted keys via BTreeMap), so the same result set always
+/// pins to the same CID regardless of SELECT column order.
+pub fn pin_rows(rows: &[BTreeMap<String, serde_json::Value>]) -> Result<PinnedBlob> {
+    let bytes = serde_json::to_vec(rows)?;
+    Ok(PinnedBlob::from_bytes(&bytes, 0, BTreeMap::new()))
+}
+
+/// Execute a read-only SQL query and pin the canonicalized rows by CID.
+///
+/// This is the IO boundary — it runs OUTSIDE the deterministic runtime.
+/// The alias resolves via `UBL_SQL_DB_<ALIAS>` (uppercased) to a SQLite
+/// database path, opened read-only.
+#[cfg(feature = "sql")]
+pub fn execute(params: &SqlParams, policy: &AdapterPolicy) -> Result<AdapterResponse> {
+    check_policy(params, policy)?;
+
+    let env_key = format!("UBL_SQL_DB_{}", params.alias.to_uppercase().replace('-', "_"));
+    let path = std::env::var(&env_key)
+        .map_err(|_| AdapterError::Sql(format!("alias '{}' not configured ({env_key})", params.alias)))?;
+
+    let conn = rusqlite::Connection::open_with_flags(
+        &path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )
+    .map_err(|e| AdapterError::Sql(e.to_string()))?;
+    conn.busy_timeout(std::time::Duration::from_millis(params.timeout_ms))
+        .map_err(|e| AdapterError::Sql(e.to_string()))?;
+
+    let mut stmt = conn
+        .prepare(&params.query)
+        .map_err(|e| AdapterError::Sql(e.to_string()))?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+    let args: Vec<rusqlite::types::Value> = params
+        .args
+        .iter()
+        .map(json_to_sql)
+        .collect::<Result<_>>()?;
+
+    let mut rows_out: Vec<BTreeMap<String, serde_json::Value>> = Vec::new();
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(args))
+        .map_err(|e| AdapterError::Sql(e.to_string()))?;
+    while let Some(row) = rows.next().map_err(|e| AdapterError::Sql(e.to_string()))? {
+        let mut out = BTreeMap::new();
+        for (i, col) in columns.iter().enumerate() {
+            let val = match row.get_ref(i).map_err(|e| AdapterError::Sql(e.to_string()))? {
+                rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+                rusqlite::types::ValueRef::Integer(n) => serde_json::Value::from(n),
+                rusqlite::types::ValueRef::Real(f) => serde_json::Value::from(f),
+                rusqlite::types::ValueRef::Text(t) => {
+                    serde_json::Value::from(String::from_utf8_lossy(t).to_string())
+                }
+                rusqlite::types::ValueRef::Blob(b) => {
+                    serde_json::Value::from(format!("0x{}", hex::encode(b)))
+                }
+            };
+            out.insert(col.clone(), val);
+        }
+        rows_out.push(out);
+    }
+
+    let pinned = pin_rows(&rows_out)?;
+    if policy.max_response_bytes > 0 && pinned.data.len() > policy.max_response_bytes {
+        return Err(AdapterError::Sql(format!(
+            "result set too large: {} bytes (max {})",
+            pinned.data.len(),
+            policy.max_response_bytes
+        )));
+    }
+
+    Ok(AdapterResponse {
+        kind: "sql".into(),
+        params_cid: params.params_cid(),
+        pinned,
+        attempts: Vec::new(),
+    })
+}
+
+#[cfg(feature = "sql")]
+fn json_to_sql(v: &serde_json::Value) -> Result<rusqlite::types::Value> {
+    use rusqlite::types::Value as Sv;
+    Ok(match v {
+        serde_json::Value::Null => Sv::Null,
+        serde_json::Value::Bool(b) => Sv::Integer(*b as i64),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Sv::Integer(i)
+            } else if let Some(f) = n.as_f64() {
+                Sv::Real(f)
+            } else {
+                return Err(AdapterError::Sql(format!("unsupported number: {n}")));
+            }
+        }
+        serde_json::Value::String(s) => Sv::Text(s.clone()),
+        other => {
+            return Err(AdapterError::Sql(format!(
+                "unsupported argument type: {other}"
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(query: &str) -> SqlParams {
+        SqlParams {
+            alias: "refdata".into(),
+            query: query.into(),
+            args: vec![],
+            timeout_ms: 5000,
+        }
+    }
+
+    #[test]
+    fn policy_rejects_writes() {
+        let policy = AdapterPolicy::default();
+        assert!(check_policy(&params("SELECT * FROM rates"), &policy).is_ok());
+        assert!(check_policy(&params("WITH r AS (SELECT 1) SELECT * FROM r"), &policy).is_ok());
+        assert!(check_policy(&params("UPDATE rates SET x = 1"), &policy).is_err());
+        assert!(check_policy(&params("DELETE FROM rates"), &policy).is_err());
+        assert!(check_policy(&params("DROP TABLE rates"), &policy).is_err());
+    }
+
+    #[test]
+    fn policy_rejects_multiple_statements() {
+        let policy = AdapterPolicy::default();
+        assert!(check_policy(&params("SELECT 1; DROP TABLE rates"), &policy).is_err());
+        // A trailing semicolon alone is fine
+        assert!(check_policy(&params("SELECT 1;"), &policy).is_ok());
+    }
+
+    #[test]
+    fn policy_alias_allowlist() {
+        let policy = AdapterPolicy {
+            allowed_sql_aliases: vec!["refdata".into()],
+            ..Default::default()
+        };
+        assert!(check_policy(&params("SELECT 1"), &policy).is_ok());
+        let mut p = params("SELECT 1");
+        p.alias = "prod-writes".into();
+        assert!(check_policy(&p, &policy).is_err());
+    }
+
+    #[test]
+    fn policy_query_allowlist_glob() {
+        let policy = AdapterPolicy {
+            allowed_sql_queries: vec!["SELECT * FROM rates*".into()],
+            ..Default::default()
+        };
+        assert!(check_policy(&params("SELECT * FROM rates WHERE day = ?1"), &policy).is_ok());
+        assert!(check_policy(&params("SELECT * FROM users"), &policy).is_err());
+    }
+
+    #[test]
+    fn policy_timeout_cap() {
+        let policy = AdapterPolicy {
+            max_timeout_ms: 3000,
+            ..Default::default()
+        };
+        let mut p = params("SELECT 1");
+        p.timeout_ms = 5000;
+        assert!(check_policy(&p, &policy).is_err());
+    }
+
+    #[test]
+    fn pinned_rows_are_deterministic_and_verify() {
+        let rows = vec![BTreeMap::from([
+            ("rate".to_string(), serde_json::json!(42)),
+            ("day".to_string(), serde_json::json!("2026-09-01")),
+        ])];
+        let a = pin_rows(&rows).unwrap();
+        let b = pin_rows(&rows).unwrap();
+        assert_eq!(a.cid, b.cid);
+        assert!(a.verify());
+    }
+
+    #[test]
+    fn sql_params_cid_changes_with_args() {
+        let p1 = params("SELECT * FROM rates WHERE day = ?1");
+        let mut p2 = p1.clone();
+        p2.args = vec![serde_json::json!("2026-09-01")];
+        assert_ne!(p1.params_cid(), p2.params_cid());
+    }
+}
@@ -27,6 +27,30 @@ fn default_timeout() -> u64 {
     10_000
 }
 
+/// A frozen SQL query — read-only reference data, content-addressed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlParams {
+    /// Connection alias resolved outside the deterministic boundary
+    /// (never a raw connection string — credentials stay out of CIDs).
+    pub alias: String,
+    /// Parameterized query text (placeholders, never interpolation).
+    pub query: String,
+    /// Positional arguments for the placeholders.
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+    /// Timeout in milliseconds
+    #[serde(default = "default_timeout")]
+    pub timeout_ms: u64,
+}
+
+impl SqlParams {
+    /// Compute the CID of the frozen query parameters.
+    pub fn params_cid(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        crate::cid::cid_b3(&bytes)
+    }
+}
+
 impl HttpParams {
     /// Compute the CID of the frozen request parameters.
     pub fn params_cid(&self) -> String {
@@ -35,6 +59,23 @@ impl HttpParams {
     }
 }
 
+/// A frozen blob read — ops-controlled mounts only, content-addressed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobParams {
+    /// Mount alias (resolved at the IO boundary, never a raw path root).
+    pub mount: String,
+    /// Path relative to the mount root.
+    pub path: String,
+}
+
+impl BlobParams {
+    /// Compute the CID of the frozen read parameters.
+    pub fn params_cid(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        crate::cid::cid_b3(&bytes)
+    }
+}
+
 /// A content-addressed blob (response body pinned by CID).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PinnedBlob {
@@ -83,6 +124,51 @@ pub struct AdapterRequest {
     pub policy: AdapterPolicy,
 }
 
+/// Retry behaviour for flaky upstreams (HTTP adapter).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts including the first. 0 or 1 = no retries.
+    #[serde(default = "default_attempts")]
+    pub max_attempts: u32,
+    /// Base backoff between attempts in ms, doubled each retry.
+    #[serde(default = "default_backoff")]
+    pub backoff_ms: u64,
+    /// Response statuses worth retrying. Empty = retry transport errors only.
+    #[serde(default)]
+    pub retry_on_statuses: Vec<u16>,
+}
+
+fn default_attempts() -> u32 {
+    1
+}
+fn default_backoff() -> u64 {
+    250
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_attempts(),
+            backoff_ms: default_backoff(),
+            retry_on_statuses: Vec::new(),
+        }
+    }
+}
+
+/// Audit record of a single upstream attempt. Carried on the
+/// `AdapterResponse` envelope only — never part of the pinned body CID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttemptMeta {
+    /// 1-based attempt number.
+    pub attempt: u32,
+    /// Response status, if the attempt got one.
+    #[serde(default)]
+    pub status: Option<u16>,
+    /// Transport error, if the attempt never got a response.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
 /// Policy constraints on adapter execution.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AdapterPolicy {
@@ -95,6 +181,29 @@ pub struct AdapterPolicy {
     /// Max timeout in ms. 0 = use adapter default.
     #[serde(default)]
     pub max_timeout_ms: u64,
+    /// Allowed SQL connection aliases. Empty = allow all.
+    #[serde(default)]
+    pub allowed_sql_aliases: Vec<String>,
+    /// Allowed SQL query patterns (exact or prefix glob). Empty = allow all.
+    #[serde(default)]
+    pub allowed_sql_queries: Vec<String>,
+    /// Allowed blob mount aliases. Empty = deny all — filesystem reads
+    /// are never implicitly allowed.
+    #[serde(default)]
+    pub allowed_blob_mounts: Vec<String>,
+    /// Retry behaviour for the HTTP adapter.
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Consecutive failures per host before the circuit opens. 0 = disabled.
+    #[serde(default)]
+    pub breaker_failure_threshold: u32,
+    /// How long an open circuit waits before a half-open probe, in ms.
+    #[serde(default)]
+    pub breaker_cooldown_ms: u64,
+    /// Max redirect hops, each re-validated against the allowlist and
+    /// egress guard. 0 = redirects disabled (default).
+    #[serde(default)]
+    pub max_redirect_hops: u32,
 }
 
 /// Generic adapter response.
@@ -106,6 +215,10 @@ pub struct AdapterResponse {
     pub params_cid: String,
     /// The pinned response blob
     pub pinned: PinnedBlob,
+    /// Per-attempt audit trail (retries, transport errors). Envelope
+    /// metadata only — the pinned body CID is unaffected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<AttemptMeta>,
 }
 
 #[cfg(test)]
@@ -6,6 +6,8 @@ use std::io::{Cursor, Read};
 use unicode_normalization::UnicodeNormalization;
 
 pub const MAGIC: [u8; 4] = [0x6e, 0x72, 0x66, 0x31];
+/// JSON marker key for typed bytes: `{"$bytes": "<base64>"}` maps to Bytes.
+pub const BYTES_MARKER_KEY: &str = "$bytes";
 const TAG_NULL: u8 = 0x00;
 const TAG_FALSE: u8 = 0x01;
 const TAG_TRUE: u8 = 0x02;
@@ -210,6 +212,17 @@ pub fn json_to_nrf(value: &Value) -> Result<NrfValue> {
             Ok(NrfValue::Array(items))
         }
         Value::Object(map) => {
+            // Typed bytes marker: {"$bytes": "<base64>"} becomes native Bytes,
+            // so binary payloads don't carry the base64 inflation into NRF.
+            if map.len() == 1 {
+                if let Some(Value::String(b64)) = map.get(BYTES_MARKER_KEY) {
+                    use base64::Engine;
+                    let bytes = base64::engine::general_purpose::STANDARD
+                        .decode(b64)
+                        .context("InvalidBase64 in $bytes")?;
+                    return Ok(NrfValue::Bytes(bytes));
+                }
+            }
             let mut bt = BTreeMap::new();
             for (k, v) in map {
                 if k.chars().any(|c| c == '\u{feff}') {
@@ -436,6 +449,42 @@ mod tests {
         assert_eq!(nrf, NrfValue::String(nfc_str.to_string()));
     }
 
+    #[test]
+    fn json_to_nrf_bytes_marker_becomes_bytes() {
+        let j = json!({"$bytes": "AP/erQ=="});
+        assert_eq!(
+            json_to_nrf(&j).unwrap(),
+            NrfValue::Bytes(vec![0x00, 0xff, 0xde, 0xad])
+        );
+    }
+
+    #[test]
+    fn json_to_nrf_bytes_marker_rejects_bad_base64() {
+        let j = json!({"$bytes": "!!!not-base64!!!"});
+        let err = json_to_nrf(&j);
+        assert!(err.is_err(), "invalid base64 in $bytes must be rejected");
+    }
+
+    #[test]
+    fn json_to_nrf_marker_with_extra_keys_stays_map() {
+        let j = json!({"$bytes": "AA==", "other": 1});
+        let nrf = json_to_nrf(&j).unwrap();
+        assert!(matches!(nrf, NrfValue::Map(_)), "only the lone marker is bytes");
+    }
+
+    #[test]
+    fn bytes_marker_encodes_smaller_than_base64_string() {
+        let payload = vec![0xabu8; 300];
+        use base64::Engine;
+        let b64 = base64::engine::general_purpose::STANDARD.encode(&payload);
+        let as_marker = encode_to_vec(&json_to_nrf(&json!({"$bytes": b64})).unwrap()).unwrap();
+        let as_string = encode_to_vec(&json_to_nrf(&json!(b64)).unwrap()).unwrap();
+        assert!(
+            as_marker.len() < as_string.len(),
+            "native bytes must undo the base64 inflation"
+        );
+    }
+
     #[test]
     fn json_to_nrf_null_array_nested() {
         let j = json!({"a": [null, true, 99, "x"]});
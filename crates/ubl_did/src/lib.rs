@@ -22,6 +22,26 @@ pub fn runtime_did_document() -> serde_json::Value {
     })
 }
 
+/// Verification method entry for a tenant-scoped signing key.
+/// The controller is the DID part of the kid (before the `#` fragment).
+pub fn tenant_verification_method(kid: &str, pubkey: &[u8; 32]) -> serde_json::Value {
+    let multibase = format!("z{}", bs58::encode(pubkey).into_string());
+    let controller = kid.split('#').next().unwrap_or(kid);
+    json!({
+        "id": kid,
+        "type": "Ed25519VerificationKey2020",
+        "controller": controller,
+        "publicKeyMultibase": multibase,
+    })
+}
+
+/// Decode a `publicKeyMultibase` entry (base58-btc, `z` prefix) into raw
+/// Ed25519 key bytes.
+pub fn decode_public_key_multibase(multibase: &str) -> Option<[u8; 32]> {
+    let b58 = multibase.strip_prefix('z')?;
+    bs58::decode(b58).into_vec().ok()?.try_into().ok()
+}
+
 pub fn resolve_did_or_cid(id: &str, base_url: &str) -> serde_json::Value {
     if let Some(cid) = id.strip_prefix("did:cid:") {
         let url = format!("{base_url}/cid/{cid}");
@@ -5,8 +5,433 @@ use tokio::fs;
 
 const STORE_DIR: &str = "store";
 const RECEIPT_DIR: &str = "index/receipt";
+const JOURNAL_PATH: &str = "store/journal.log";
+
+/// Monotonic suffix for temp files so concurrent writers never collide.
+static TMP_CTR: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Crash-safe write: write to a temp file in the target directory, then
+/// rename into place. Rename is atomic on POSIX, so readers never observe
+/// a half-written blob.
+async fn atomic_write(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    write_fault()?;
+    fs::create_dir_all(path.parent().unwrap()).await?;
+    let n = TMP_CTR.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let tmp = path.with_extension(format!("tmp.{}.{n}", std::process::id()));
+    fs::write(&tmp, bytes).await?;
+    fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
+/// Append one line to the write-ahead journal. Tab-separated plain text,
+/// append-only; a single append is the atomicity unit for both plain
+/// writes and batches.
+async fn journal_append_line(line: String) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+    if let Some(parent) = std::path::Path::new(JOURNAL_PATH).parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(JOURNAL_PATH)
+        .await?;
+    file.write_all(format!("{line}\n").as_bytes()).await?;
+    Ok(())
+}
+
+/// Append a `(cid, path, len)` entry to the write-ahead journal.
+async fn journal_append(cid: &str, path: &std::path::Path, len: usize) -> Result<()> {
+    journal_append_line(format!("{cid}\t{}\t{len}", path.display())).await
+}
+
+/// Journaled atomic write: the blob lands via temp-file + rename, then the
+/// journal records what should exist so `recover()` can verify it later.
+/// The journal length is the *stored* length, which may be smaller than
+/// the canonical bytes when compression is on.
+async fn put_journaled(cid: &str, path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    let encoded = encode_for_store(bytes);
+    let stored = encoded.as_deref().unwrap_or(bytes);
+    atomic_write(path, stored).await?;
+    journal_append(cid, path, stored.len()).await
+}
+
+/// Deterministic staging location for a batch member, next to its final
+/// path so the publishing rename stays within one filesystem.
+fn staged_variant(path: &std::path::Path) -> PathBuf {
+    path.with_extension("staged")
+}
+
+// ── Transactional write batches ─────────────────────────────────────
+
+/// A group of writes that commits all-or-nothing. Every blob is first
+/// staged next to its final path, then a *single* journal append
+/// publishes the whole batch, then the staged files are renamed into
+/// place. The journal append is the commit point: a crash before it
+/// leaves only staged files no reader looks at, and a crash after it is
+/// rolled forward by [`recover()`], which completes any rename the crash
+/// interrupted. Index lines ride in the same commit so listings can
+/// never lead the blobs they point at.
+#[derive(Default)]
+pub struct WriteBatch {
+    items: Vec<(String, PathBuf, Vec<u8>)>,
+    index: Vec<(String, Vec<u8>)>,
+}
+
+impl WriteBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.index.is_empty()
+    }
+
+    /// Queue detached receipt-body bytes under a string CID for `tenant`.
+    pub fn put_body(&mut self, tenant: &str, cid: &str, bytes: Vec<u8>) {
+        self.items
+            .push((cid.to_string(), tenant_body_path(tenant, cid), bytes));
+    }
+
+    /// Queue a line for the tenant's receipt index, appended only after
+    /// every blob in the batch has landed.
+    pub fn index_line(&mut self, tenant: &str, line: Vec<u8>) {
+        self.index.push((tenant.to_string(), line));
+    }
+
+    /// Commit the batch: stage, publish, rename. Nothing becomes
+    /// readable unless the single journal append succeeds.
+    pub async fn commit(mut self) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        write_fault()?;
+        // Encode before staging so the journal records stored lengths
+        for (_, _, bytes) in &mut self.items {
+            if let Some(z) = encode_for_store(bytes) {
+                *bytes = z;
+            }
+        }
+        for (_, path, bytes) in &self.items {
+            fs::create_dir_all(path.parent().unwrap()).await?;
+            fs::write(staged_variant(path), bytes).await?;
+        }
+        let mut line = String::from("BATCH");
+        for (cid, path, bytes) in &self.items {
+            use std::fmt::Write;
+            let _ = write!(line, "\t{cid}\t{}\t{}", path.display(), bytes.len());
+        }
+        journal_append_line(line).await?;
+        // Past the commit point: recover() redoes these if we crash now
+        for (_, path, _) in &self.items {
+            fs::rename(staged_variant(path), path).await?;
+        }
+        for (tenant, line) in &self.index {
+            tenant_index_append(tenant, line).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that `bytes` hash to the CID they are stored under.
+/// Supports "b3:<hex>" string CIDs (blake3) and CIDv1 sha2-256 multihash.
+/// Unknown hash codes are treated as unverifiable, not corrupt.
+fn content_matches_cid(cid_str: &str, bytes: &[u8]) -> bool {
+    if let Some(hex_digest) = cid_str.strip_prefix("b3:") {
+        return hex::encode(blake3::hash(bytes).as_bytes()) == hex_digest;
+    }
+    if let Ok(c) = Cid::try_from(cid_str) {
+        // 0x12 = sha2-256 multihash code
+        if c.hash().code() == 0x12 {
+            use sha2::Digest;
+            return sha2::Sha256::digest(bytes).as_slice() == c.hash().digest();
+        }
+    }
+    true
+}
+
+// ── Verify-on-read ──────────────────────────────────────────────────
+
+/// A blob's bytes no longer hash to the CID it is stored under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityError {
+    pub cid: String,
+    pub path: PathBuf,
+}
+
+impl std::fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "integrity violation: {} does not hash to {}",
+            self.path.display(),
+            self.cid
+        )
+    }
+}
+
+impl std::error::Error for IntegrityError {}
+
+/// Total reads that failed CID verification (for the /metrics exporter).
+static CORRUPTED_READS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+pub fn corrupted_read_count() -> u64 {
+    CORRUPTED_READS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Repair hook: given a CID, refetch the blob from a replica.
+/// Registered once at startup; used by verified reads on mismatch.
+type RepairFn = dyn Fn(&str) -> Option<Vec<u8>> + Send + Sync;
+static REPAIR_HOOK: std::sync::OnceLock<Box<RepairFn>> = std::sync::OnceLock::new();
+
+pub fn set_repair_hook(hook: impl Fn(&str) -> Option<Vec<u8>> + Send + Sync + 'static) {
+    let _ = REPAIR_HOOK.set(Box::new(hook));
+}
+
+fn note_corrupted_read() {
+    CORRUPTED_READS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn repair_fetch(cid: &str) -> Option<Vec<u8>> {
+    REPAIR_HOOK.get().and_then(|hook| hook(cid))
+}
+
+/// Fault hook for chaos tests: asked before every write whether the
+/// "ledger" injection point should fail. Registered once at startup
+/// (the gate wires it to `ubl_runtime::faults`); absent in production.
+type FaultFn = dyn Fn(&str) -> bool + Send + Sync;
+static FAULT_HOOK: std::sync::OnceLock<Box<FaultFn>> = std::sync::OnceLock::new();
+
+pub fn set_fault_hook(hook: impl Fn(&str) -> bool + Send + Sync + 'static) {
+    let _ = FAULT_HOOK.set(Box::new(hook));
+}
+
+fn write_fault() -> Result<()> {
+    if FAULT_HOOK.get().map(|h| h("ledger")).unwrap_or(false) {
+        anyhow::bail!("fault injection: ledger write failed");
+    }
+    Ok(())
+}
+
+/// Whether plain getters should also verify (env `UBL_LEDGER_VERIFY_READS=1`).
+pub fn verify_reads_enabled() -> bool {
+    std::env::var("UBL_LEDGER_VERIFY_READS")
+        .map(|v| v == "1")
+        .unwrap_or(false)
+}
+
+// ── Storage compression (zstd, opt-in) ──────────────────────────────
+
+/// Zstd frame magic. The stored form self-identifies, so reads need no
+/// side-channel marker and flipping the flag never strands old blobs.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Whether new blobs are stored zstd-compressed (env `UBL_LEDGER_COMPRESS=1`).
+/// Compression is a storage encoding only: CIDs, journal entries and
+/// verification all speak canonical bytes, and every read path decodes
+/// back to them before serving or hashing.
+pub fn compression_enabled() -> bool {
+    std::env::var("UBL_LEDGER_COMPRESS")
+        .map(|v| v == "1")
+        .unwrap_or(false)
+}
+
+/// Compress canonical bytes for storage. `None` means store as-is:
+/// either compression is off, or the frame would not beat the original
+/// (small or already-dense blobs).
+fn encode_for_store(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !compression_enabled() {
+        return None;
+    }
+    match zstd::bulk::compress(bytes, zstd::DEFAULT_COMPRESSION_LEVEL) {
+        Ok(z) if z.len() < bytes.len() => Some(z),
+        _ => None,
+    }
+}
+
+/// Decode stored bytes back to canonical form. Uncompressed blobs pass
+/// through untouched; bytes that carry the magic but fail to decode are
+/// served raw and left to CID verification to judge.
+fn decode_stored(bytes: Vec<u8>) -> Vec<u8> {
+    if !bytes.starts_with(&ZSTD_MAGIC) {
+        return bytes;
+    }
+    zstd::stream::decode_all(bytes.as_slice()).unwrap_or(bytes)
+}
+
+/// Core of verify-on-read: check the bytes at `path` against `cid`.
+/// On mismatch, try the repair hook; a successful refetch is re-written
+/// atomically and served. Otherwise the corrupt blob is quarantined and a
+/// typed `IntegrityError` is returned.
+async fn read_verified(
+    cid: &str,
+    path: &std::path::Path,
+) -> Result<Option<Vec<u8>>, IntegrityError> {
+    let Ok(bytes) = fs::read(path).await else {
+        return Ok(None);
+    };
+    let bytes = decode_stored(bytes);
+    if content_matches_cid(cid, &bytes) {
+        return Ok(Some(bytes));
+    }
+    note_corrupted_read();
+    if let Some(fresh) = repair_fetch(cid) {
+        if content_matches_cid(cid, &fresh) {
+            let _ = atomic_write(path, &fresh).await;
+            return Ok(Some(fresh));
+        }
+    }
+    let _ = fs::rename(path, path.with_extension("quarantine")).await;
+    Err(IntegrityError {
+        cid: cid.to_string(),
+        path: path.to_path_buf(),
+    })
+}
+
+pub async fn get_raw_verified(cid: &Cid) -> Result<Option<Vec<u8>>, IntegrityError> {
+    match read_verified(&cid.to_string(), &cid_path(cid, "nrf")).await {
+        Ok(None) => read_verified(&cid.to_string(), &legacy_cid_path(cid, "nrf")).await,
+        other => other,
+    }
+}
+
+pub async fn tenant_get_raw_verified(
+    tenant: &str,
+    cid: &Cid,
+) -> Result<Option<Vec<u8>>, IntegrityError> {
+    match read_verified(&cid.to_string(), &tenant_cid_path(tenant, cid, "nrf")).await {
+        Ok(None) => {
+            read_verified(&cid.to_string(), &legacy_tenant_cid_path(tenant, cid, "nrf")).await
+        }
+        other => other,
+    }
+}
+
+pub async fn tenant_get_body_verified(
+    tenant: &str,
+    cid: &str,
+) -> Result<Option<Vec<u8>>, IntegrityError> {
+    match read_verified(cid, &tenant_body_path(tenant, cid)).await {
+        Ok(None) => read_verified(cid, &legacy_tenant_body_path(tenant, cid)).await,
+        other => other,
+    }
+}
+
+/// Outcome of a `recover()` scan over the journal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryReport {
+    pub scanned: usize,
+    pub verified: usize,
+    /// Blobs whose contents no longer hash to their CID (moved aside).
+    pub quarantined: usize,
+    /// Journal entries whose blob is gone (e.g. crash before rename).
+    pub missing: usize,
+}
+
+/// Startup recovery: walk the journal and verify every recorded blob still
+/// hashes to its CID. Corrupt blobs are renamed to `<path>.quarantine` so
+/// they stop serving reads but remain available for forensics.
+pub async fn recover() -> Result<RecoveryReport> {
+    let mut report = RecoveryReport::default();
+    let Ok(journal) = fs::read_to_string(JOURNAL_PATH).await else {
+        return Ok(report); // no journal yet — nothing to recover
+    };
+    for line in journal.lines() {
+        // A published batch is one journal line holding every member;
+        // roll each of them forward individually.
+        let mut fields = match line.strip_prefix("BATCH\t") {
+            Some(rest) => rest.split('\t'),
+            None => line.split('\t'),
+        };
+        while let (Some(cid), Some(path)) = (fields.next(), fields.next()) {
+            let _len = fields.next();
+            report.scanned += 1;
+            recover_entry(cid, PathBuf::from(path), &mut report).await;
+        }
+    }
+    Ok(report)
+}
+
+/// Verify one journaled blob, rolling forward interrupted work: a blob
+/// re-sharded by `migrate_store()` is followed to its digest shard, and
+/// a published batch member whose rename never happened is completed
+/// from its staged file.
+async fn recover_entry(cid: &str, mut path: PathBuf, report: &mut RecoveryReport) {
+    if !fs::try_exists(&path).await.unwrap_or(false) {
+        let staged = staged_variant(&path);
+        if fs::try_exists(&staged).await.unwrap_or(false) {
+            if fs::rename(&staged, &path).await.is_err() {
+                report.missing += 1;
+                return;
+            }
+        } else if let Some(moved) = resharded_path(cid, &path) {
+            if fs::try_exists(&moved).await.unwrap_or(false) {
+                path = moved;
+            }
+        }
+    }
+    match fs::read(&path).await {
+        Err(_) => report.missing += 1,
+        Ok(bytes) => {
+            if content_matches_cid(cid, &decode_stored(bytes)) {
+                report.verified += 1;
+            } else {
+                let quarantine = path.with_extension("quarantine");
+                let _ = fs::rename(&path, &quarantine).await;
+                report.quarantined += 1;
+            }
+        }
+    }
+}
+
+/// Two-level shard directories for a CID, derived from its *digest bytes*
+/// rather than its string prefix. String slicing (`&s[2..6]`) looked
+/// uniform but wasn't: every CIDv1 base32 string starts "bafk…", so all
+/// blobs piled into one or two directories, and "b3:<hex>" strings
+/// sharded on "3:" + one hex char. Digest bytes are uniformly random by
+/// construction, so 256×256 shards fill evenly regardless of CID flavor.
+fn shard_dirs(cid_str: &str) -> (String, String) {
+    let digest_prefix = |d: &[u8]| match d {
+        [a, b, ..] => Some((format!("{a:02x}"), format!("{b:02x}"))),
+        _ => None,
+    };
+    if let Some(hex_digest) = cid_str.strip_prefix("b3:") {
+        if let Ok(bytes) = hex::decode(hex_digest.get(..4).unwrap_or_default()) {
+            if let Some(dirs) = digest_prefix(&bytes) {
+                return dirs;
+            }
+        }
+    }
+    if let Ok(c) = Cid::try_from(cid_str) {
+        if let Some(dirs) = digest_prefix(c.hash().digest()) {
+            return dirs;
+        }
+    }
+    // Opaque identifier: hash the string itself so the shard is still
+    // deterministic and balanced.
+    digest_prefix(blake3::hash(cid_str.as_bytes()).as_bytes()).unwrap()
+}
+
+/// Where a journaled blob lives after `migrate_store()` re-sharded it:
+/// same container, same filename, digest-derived shard dirs.
+fn resharded_path(cid: &str, path: &std::path::Path) -> Option<PathBuf> {
+    let container = path.parent()?.parent()?.parent()?;
+    let (p1, p2) = shard_dirs(cid);
+    Some(container.join(p1).join(p2).join(path.file_name()?))
+}
 
 fn cid_path(cid: &Cid, ext: &str) -> PathBuf {
+    let s = cid.to_string();
+    let (p1, p2) = shard_dirs(&s);
+    PathBuf::from(STORE_DIR)
+        .join(p1)
+        .join(p2)
+        .join(format!("{s}.{ext}"))
+}
+
+/// Pre-migration layout: shards sliced from the CID *string*. Kept only
+/// as a read fallback until `migrate_store()` has run everywhere.
+fn legacy_cid_path(cid: &Cid, ext: &str) -> PathBuf {
     let s = cid.to_string();
     let (p1, p2) = (&s[2..4], &s[4..6]);
     PathBuf::from(STORE_DIR)
@@ -20,6 +445,16 @@ fn receipt_path(cid: &Cid) -> PathBuf {
 }
 
 fn tenant_cid_path(tenant: &str, cid: &Cid, ext: &str) -> PathBuf {
+    let s = cid.to_string();
+    let (p1, p2) = shard_dirs(&s);
+    PathBuf::from(STORE_DIR)
+        .join(tenant)
+        .join(p1)
+        .join(p2)
+        .join(format!("{s}.{ext}"))
+}
+
+fn legacy_tenant_cid_path(tenant: &str, cid: &Cid, ext: &str) -> PathBuf {
     let s = cid.to_string();
     let (p1, p2) = (&s[2..4], &s[4..6]);
     PathBuf::from(STORE_DIR)
@@ -36,25 +471,35 @@ fn tenant_receipt_path(tenant: &str, cid: &Cid) -> PathBuf {
 }
 
 pub async fn put(cid: &Cid, bytes: &[u8]) -> Result<()> {
-    let path = cid_path(cid, "nrf");
-    fs::create_dir_all(path.parent().unwrap()).await?;
-    fs::write(path, bytes).await?;
-    Ok(())
+    put_journaled(&cid.to_string(), &cid_path(cid, "nrf"), bytes).await
 }
 
 pub async fn exists(cid: &Cid) -> bool {
-    fs::try_exists(cid_path(cid, "nrf")).await.unwrap_or(false)
+    if fs::try_exists(cid_path(cid, "nrf")).await.unwrap_or(false) {
+        return true;
+    }
+    fs::try_exists(legacy_cid_path(cid, "nrf"))
+        .await
+        .unwrap_or(false)
 }
 
 pub async fn get_raw(cid: &Cid) -> Option<Vec<u8>> {
-    fs::read(cid_path(cid, "nrf")).await.ok()
+    if verify_reads_enabled() {
+        return get_raw_verified(cid).await.ok().flatten();
+    }
+    if let Ok(bytes) = fs::read(cid_path(cid, "nrf")).await {
+        return Some(decode_stored(bytes));
+    }
+    fs::read(legacy_cid_path(cid, "nrf"))
+        .await
+        .ok()
+        .map(decode_stored)
 }
 
 pub async fn put_receipt(cid: &Cid, bytes: &[u8]) -> Result<()> {
-    let path = receipt_path(cid);
-    fs::create_dir_all(path.parent().unwrap()).await?;
-    fs::write(path, bytes).await?;
-    Ok(())
+    // Receipts are JSON envelopes, not raw content: their bytes don't hash
+    // to the CID they index, so they get atomic writes but no journal entry.
+    atomic_write(&receipt_path(cid), bytes).await
 }
 
 pub async fn get_receipt(cid: &Cid) -> Option<Vec<u8>> {
@@ -64,33 +509,538 @@ pub async fn get_receipt(cid: &Cid) -> Option<Vec<u8>> {
 // ── Tenant-scoped operations ────────────────────────────────────────
 
 pub async fn tenant_put(tenant: &str, cid: &Cid, bytes: &[u8]) -> Result<()> {
-    let path = tenant_cid_path(tenant, cid, "nrf");
-    fs::create_dir_all(path.parent().unwrap()).await?;
-    fs::write(path, bytes).await?;
-    Ok(())
+    put_journaled(&cid.to_string(), &tenant_cid_path(tenant, cid, "nrf"), bytes).await
 }
 
 pub async fn tenant_exists(tenant: &str, cid: &Cid) -> bool {
-    fs::try_exists(tenant_cid_path(tenant, cid, "nrf"))
-        .await
-        .unwrap_or(false)
+    for path in [
+        tenant_cid_path(tenant, cid, "nrf"),
+        legacy_tenant_cid_path(tenant, cid, "nrf"),
+    ] {
+        if fs::try_exists(&path).await.unwrap_or(false) {
+            return true;
+        }
+        if let Some(archive) = archived_variant(&path) {
+            if fs::try_exists(archive).await.unwrap_or(false) {
+                return true;
+            }
+        }
+    }
+    false
 }
 
 pub async fn tenant_get_raw(tenant: &str, cid: &Cid) -> Option<Vec<u8>> {
-    fs::read(tenant_cid_path(tenant, cid, "nrf")).await.ok()
+    let s = cid.to_string();
+    if let Some(bytes) = read_tiered(&s, &tenant_cid_path(tenant, cid, "nrf")).await {
+        return Some(bytes);
+    }
+    read_tiered(&s, &legacy_tenant_cid_path(tenant, cid, "nrf")).await
+}
+
+/// A stored blob ready to serve: an open file when the on-disk form *is*
+/// the canonical bytes, or a decoded buffer when the blob was stored
+/// compressed (a zstd frame cannot be range-served raw).
+pub enum RawBlob {
+    /// Canonical bytes on disk: stream straight from the file.
+    Stream(fs::File, u64),
+    /// Stored compressed: the whole blob, decoded to canonical bytes.
+    Buffered(Vec<u8>),
+}
+
+/// Open the raw blob for streaming reads (tenant path first, then legacy).
+/// Compressed blobs come back buffered so callers always see canonical
+/// bytes. Streaming skips verify-on-read; callers that need verification
+/// should use the whole-blob getters.
+pub async fn tenant_open_raw(tenant: &str, cid: &Cid) -> Option<RawBlob> {
+    let hot = tenant_cid_path(tenant, cid, "nrf");
+    let legacy = legacy_tenant_cid_path(tenant, cid, "nrf");
+    let mut candidates = vec![hot.clone(), legacy.clone()];
+    candidates.extend(archived_variant(&hot));
+    candidates.extend(archived_variant(&legacy));
+    candidates.push(cid_path(cid, "nrf"));
+    candidates.push(legacy_cid_path(cid, "nrf"));
+    for path in candidates {
+        if let Ok(mut file) = fs::File::open(&path).await {
+            if let Ok(meta) = file.metadata().await {
+                use tokio::io::{AsyncReadExt, AsyncSeekExt};
+                let mut magic = [0u8; 4];
+                if file.read_exact(&mut magic).await.is_ok() && magic == ZSTD_MAGIC {
+                    if let Ok(bytes) = fs::read(&path).await {
+                        return Some(RawBlob::Buffered(decode_stored(bytes)));
+                    }
+                    continue;
+                }
+                if file.seek(std::io::SeekFrom::Start(0)).await.is_err() {
+                    continue;
+                }
+                return Some(RawBlob::Stream(file, meta.len()));
+            }
+        }
+    }
+    None
 }
 
 pub async fn tenant_put_receipt(tenant: &str, cid: &Cid, bytes: &[u8]) -> Result<()> {
-    let path = tenant_receipt_path(tenant, cid);
-    fs::create_dir_all(path.parent().unwrap()).await?;
-    fs::write(path, bytes).await?;
-    Ok(())
+    atomic_write(&tenant_receipt_path(tenant, cid), bytes).await
 }
 
 pub async fn tenant_get_receipt(tenant: &str, cid: &Cid) -> Option<Vec<u8>> {
     fs::read(tenant_receipt_path(tenant, cid)).await.ok()
 }
 
+// ── Per-tenant receipt index (append-only) ──────────────────────────
+
+fn tenant_index_path(tenant: &str) -> PathBuf {
+    PathBuf::from(RECEIPT_DIR).join(tenant).join("receipts.idx")
+}
+
+/// Append one JSON line to the tenant's receipt index. The index is
+/// append-only plain text, one entry per line, so listings can be served
+/// without materializing the whole receipt registry in memory.
+pub async fn tenant_index_append(tenant: &str, line: &[u8]) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+    let path = tenant_index_path(tenant);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await?;
+    file.write_all(line).await?;
+    file.write_all(b"\n").await?;
+    Ok(())
+}
+
+/// Read every index line for a tenant, oldest first. No index yet = empty.
+pub async fn tenant_index_lines(tenant: &str) -> Vec<String> {
+    match fs::read_to_string(tenant_index_path(tenant)).await {
+        Ok(text) => text.lines().map(str::to_string).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Open the tenant's receipt index for streaming reads, so exports can
+/// process one line at a time instead of loading the whole file. No index
+/// yet = `None`.
+pub async fn tenant_index_open(tenant: &str) -> Option<fs::File> {
+    fs::File::open(tenant_index_path(tenant)).await.ok()
+}
+
+// ── Detached receipt bodies (string CIDs, e.g. "b3:…") ─────────────
+
+fn tenant_body_path(tenant: &str, cid: &str) -> PathBuf {
+    let safe = cid.replace(':', "_");
+    let (p1, p2) = shard_dirs(cid);
+    PathBuf::from(STORE_DIR)
+        .join(tenant)
+        .join("bodies")
+        .join(p1)
+        .join(p2)
+        .join(format!("{safe}.json"))
+}
+
+fn legacy_tenant_body_path(tenant: &str, cid: &str) -> PathBuf {
+    let safe = cid.replace(':', "_");
+    let (p1, p2) = if safe.len() >= 4 {
+        (&safe[..2], &safe[2..4])
+    } else {
+        ("xx", "xx")
+    };
+    PathBuf::from(STORE_DIR)
+        .join(tenant)
+        .join("bodies")
+        .join(p1)
+        .join(p2)
+        .join(format!("{safe}.json"))
+}
+
+/// Store canonical receipt-body bytes under a string CID (body-by-reference).
+pub async fn tenant_put_body(tenant: &str, cid: &str, bytes: &[u8]) -> Result<()> {
+    put_journaled(cid, &tenant_body_path(tenant, cid), bytes).await
+}
+
+/// Fetch detached receipt-body bytes by string CID.
+pub async fn tenant_get_body(tenant: &str, cid: &str) -> Option<Vec<u8>> {
+    if let Some(bytes) = read_tiered(cid, &tenant_body_path(tenant, cid)).await {
+        return Some(bytes);
+    }
+    read_tiered(cid, &legacy_tenant_body_path(tenant, cid)).await
+}
+
+// ── Synchronous blob lookup (runtime codec hook) ────────────────────
+
+/// Blocking raw-blob lookup by CID string, for callers that cannot await
+/// (e.g. the runtime's `bytes.from_cid` codec). Probes the legacy global
+/// path first, then every tenant directory; bytes are always verified
+/// against the CID before being served, so a corrupt blob reads as a miss.
+pub fn find_raw_blocking(cid_str: &str) -> Option<Vec<u8>> {
+    let cid = Cid::try_from(cid_str).ok()?;
+    let mut candidates = vec![cid_path(&cid, "nrf"), legacy_cid_path(&cid, "nrf")];
+    if let Ok(entries) = std::fs::read_dir(STORE_DIR) {
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                if let Some(tenant) = entry.file_name().to_str() {
+                    for hot in [
+                        tenant_cid_path(tenant, &cid, "nrf"),
+                        legacy_tenant_cid_path(tenant, &cid, "nrf"),
+                    ] {
+                        let archive = archived_variant(&hot);
+                        candidates.push(hot);
+                        candidates.extend(archive);
+                    }
+                }
+            }
+        }
+    }
+    for path in candidates {
+        if let Ok(bytes) = std::fs::read(&path) {
+            let bytes = decode_stored(bytes);
+            if content_matches_cid(cid_str, &bytes) {
+                return Some(bytes);
+            }
+            note_corrupted_read();
+        }
+    }
+    None
+}
+
+// ── Retention classes and archival tiering ──────────────────────────
+
+/// Storage tier a blob belongs to, by age under the tenant's policy.
+/// Hot and warm blobs stay in place; archive blobs are physically moved
+/// to the archive root (typically a cheaper mount, or S3 when the `s3`
+/// feature is wired in) and served transparently via read-through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionClass {
+    Hot,
+    Warm,
+    Archive,
+}
+
+impl RetentionClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionClass::Hot => "hot",
+            RetentionClass::Warm => "warm",
+            RetentionClass::Archive => "archive",
+        }
+    }
+}
+
+/// Per-tenant retention thresholds in seconds of blob age. `None`
+/// disables the transition; the default policy keeps everything hot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub warm_after_secs: Option<u64>,
+    pub archive_after_secs: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn classify(&self, age_secs: u64) -> RetentionClass {
+        if let Some(archive) = self.archive_after_secs {
+            if age_secs >= archive {
+                return RetentionClass::Archive;
+            }
+        }
+        if let Some(warm) = self.warm_after_secs {
+            if age_secs >= warm {
+                return RetentionClass::Warm;
+            }
+        }
+        RetentionClass::Hot
+    }
+}
+
+fn tenant_retention_path(tenant: &str) -> PathBuf {
+    PathBuf::from(STORE_DIR).join(tenant).join("retention.conf")
+}
+
+/// Persist the tenant's retention policy (plain `key=secs` lines, same
+/// register as the journal).
+pub async fn tenant_set_retention(tenant: &str, policy: RetentionPolicy) -> Result<()> {
+    let mut text = String::new();
+    if let Some(w) = policy.warm_after_secs {
+        text.push_str(&format!("warm={w}\n"));
+    }
+    if let Some(a) = policy.archive_after_secs {
+        text.push_str(&format!("archive={a}\n"));
+    }
+    atomic_write(&tenant_retention_path(tenant), text.as_bytes()).await
+}
+
+/// Read the tenant's retention policy; no file means everything stays hot.
+pub async fn tenant_retention(tenant: &str) -> RetentionPolicy {
+    let mut policy = RetentionPolicy::default();
+    let Ok(text) = fs::read_to_string(tenant_retention_path(tenant)).await else {
+        return policy;
+    };
+    for line in text.lines() {
+        if let Some(secs) = line.strip_prefix("warm=").and_then(|v| v.parse().ok()) {
+            policy.warm_after_secs = Some(secs);
+        }
+        if let Some(secs) = line.strip_prefix("archive=").and_then(|v| v.parse().ok()) {
+            policy.archive_after_secs = Some(secs);
+        }
+    }
+    policy
+}
+
+/// Rebase a hot-tier path under the archive root:
+/// `store/<tenant>/…` → `store/archive/<tenant>/…`.
+fn archived_variant(path: &std::path::Path) -> Option<PathBuf> {
+    let rel = path.strip_prefix(STORE_DIR).ok()?;
+    Some(PathBuf::from(STORE_DIR).join("archive").join(rel))
+}
+
+/// Read-through across tiers: hot path first, then the archive variant.
+/// Honors verify-on-read on both tiers.
+async fn read_tiered(cid: &str, path: &std::path::Path) -> Option<Vec<u8>> {
+    if verify_reads_enabled() {
+        if let Ok(Some(bytes)) = read_verified(cid, path).await {
+            return Some(bytes);
+        }
+    } else if let Ok(bytes) = fs::read(path).await {
+        return Some(decode_stored(bytes));
+    }
+    let archive = archived_variant(path)?;
+    if verify_reads_enabled() {
+        read_verified(cid, &archive).await.ok().flatten()
+    } else {
+        fs::read(archive).await.ok().map(decode_stored)
+    }
+}
+
+/// Outcome of one archival sweep over a tenant's blobs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveReport {
+    pub scanned: usize,
+    pub archived: usize,
+}
+
+/// Move every blob past the tenant's archive threshold to the archive
+/// tier. Tombstones never move (they must keep answering redacted reads);
+/// warm blobs stay in place — warm is an accounting class, not a second
+/// copy. Age is taken from the filesystem mtime, which rename preserves.
+pub async fn tenant_archive_sweep(tenant: &str) -> Result<ArchiveReport> {
+    let policy = tenant_retention(tenant).await;
+    let mut report = ArchiveReport::default();
+    if policy.archive_after_secs.is_none() {
+        return Ok(report);
+    }
+    let mut stack = vec![PathBuf::from(STORE_DIR).join(tenant)];
+    while let Some(dir) = stack.pop() {
+        let Ok(mut entries) = fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false) {
+                if path.file_name().and_then(|n| n.to_str()) != Some("tombstones") {
+                    stack.push(path);
+                }
+                continue;
+            }
+            let is_blob = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e == "nrf" || e == "json")
+                .unwrap_or(false);
+            if !is_blob {
+                continue;
+            }
+            report.scanned += 1;
+            let age_secs = entry
+                .metadata()
+                .await
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .and_then(|m| m.elapsed().ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            if policy.classify(age_secs) != RetentionClass::Archive {
+                continue;
+            }
+            let Some(dest) = archived_variant(&path) else {
+                continue;
+            };
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            if fs::rename(&path, &dest).await.is_ok() {
+                report.archived += 1;
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Sweep every tenant directory under the store root. The archive root
+/// itself and the two-character shard dirs of the legacy global store
+/// are not tenants.
+pub async fn archive_sweep_all() -> Vec<(String, ArchiveReport)> {
+    let mut out = Vec::new();
+    let Ok(mut entries) = fs::read_dir(STORE_DIR).await else {
+        return out;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if !entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false) {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if name == "archive" || name.len() == 2 {
+            continue;
+        }
+        if let Ok(report) = tenant_archive_sweep(&name).await {
+            out.push((name, report));
+        }
+    }
+    out
+}
+
+// ── Redaction tombstones (GDPR) ─────────────────────────────────────
+
+fn tenant_tombstone_path(tenant: &str, cid: &str) -> PathBuf {
+    let safe = cid.replace([':', '/'], "_");
+    PathBuf::from(STORE_DIR)
+        .join(tenant)
+        .join("tombstones")
+        .join(format!("{safe}.json"))
+}
+
+/// Remove every stored form of `cid` (tenant raw, legacy raw, detached body).
+/// Returns true if anything was actually deleted.
+pub async fn tenant_redact(tenant: &str, cid_str: &str) -> bool {
+    let mut removed = false;
+    if let Ok(cid) = Cid::try_from(cid_str) {
+        for path in [
+            tenant_cid_path(tenant, &cid, "nrf"),
+            legacy_tenant_cid_path(tenant, &cid, "nrf"),
+            cid_path(&cid, "nrf"),
+            legacy_cid_path(&cid, "nrf"),
+        ] {
+            removed |= fs::remove_file(path).await.is_ok();
+        }
+    }
+    removed |= fs::remove_file(tenant_body_path(tenant, cid_str))
+        .await
+        .is_ok();
+    removed |= fs::remove_file(legacy_tenant_body_path(tenant, cid_str))
+        .await
+        .is_ok();
+    removed
+}
+
+/// Persist a redaction tombstone for `cid` (survives the deleted blob).
+pub async fn tenant_put_tombstone(tenant: &str, cid: &str, bytes: &[u8]) -> Result<()> {
+    atomic_write(&tenant_tombstone_path(tenant, cid), bytes).await
+}
+
+/// Fetch the redaction tombstone for `cid`, if the blob was redacted.
+pub async fn tenant_get_tombstone(tenant: &str, cid: &str) -> Option<Vec<u8>> {
+    fs::read(tenant_tombstone_path(tenant, cid)).await.ok()
+}
+
+// ── Store migration (legacy string-prefix shards → digest shards) ───
+
+/// Outcome of a `migrate_store()` pass.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MigrateReport {
+    pub scanned: usize,
+    /// Blobs relocated from a legacy shard to their digest shard.
+    pub moved: usize,
+    /// Blobs already at their digest-sharded path.
+    pub already_placed: usize,
+    /// Blobs whose bytes no longer hash to their CID (left in place).
+    pub corrupt: usize,
+    /// Files that are not recognizable sharded blobs.
+    pub skipped: usize,
+}
+
+/// One blob found by the migration walk: its CID (recovered from the
+/// filename) and the directory the shard levels hang off of.
+fn migration_target(path: &std::path::Path) -> Option<(String, PathBuf)> {
+    let name = path.file_name()?.to_str()?;
+    let cid = match path.extension()?.to_str()? {
+        "nrf" => name.strip_suffix(".nrf")?.to_string(),
+        // Detached bodies store "b3:<hex>" as "b3_<hex>.json"
+        "json" => name.strip_suffix(".json")?.replacen('_', ":", 1),
+        _ => return None,
+    };
+    let p2 = path.parent()?;
+    let p1 = p2.parent()?;
+    // Both layouts use two single-level shard dirs of exactly two chars;
+    // anything else (tombstones, index files) is not a sharded blob.
+    if p2.file_name()?.to_str()?.len() != 2 || p1.file_name()?.to_str()?.len() != 2 {
+        return None;
+    }
+    Some((cid, p1.parent()?.to_path_buf()))
+}
+
+/// Relocate every blob in the store from the legacy string-prefix shard
+/// layout to the digest-based one, verifying each blob's bytes against
+/// its CID during the move. Corrupt blobs stay where they are (and are
+/// counted) so forensics can find them; reads already treat them as
+/// misses. Synchronous by design — this is an operator tool
+/// (`ublx admin migrate-store`), not a request path.
+pub fn migrate_store() -> Result<MigrateReport> {
+    let mut report = MigrateReport::default();
+    let mut stack = vec![PathBuf::from(STORE_DIR)];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                // Tombstones are keyed by name, not sharded; leave them be.
+                if path.file_name().and_then(|n| n.to_str()) != Some("tombstones") {
+                    stack.push(path);
+                }
+                continue;
+            }
+            let Some((cid, container)) = migration_target(&path) else {
+                report.skipped += 1;
+                continue;
+            };
+            report.scanned += 1;
+            let (s1, s2) = shard_dirs(&cid);
+            let dest = container
+                .join(s1)
+                .join(s2)
+                .join(path.file_name().unwrap());
+            if dest == path {
+                report.already_placed += 1;
+                continue;
+            }
+            // Concurrent sweeps can move a blob between listing and read;
+            // a vanished file is not worth aborting the whole pass for.
+            let Ok(bytes) = std::fs::read(&path) else {
+                report.skipped += 1;
+                continue;
+            };
+            if !content_matches_cid(&cid, &decode_stored(bytes)) {
+                report.corrupt += 1;
+                continue;
+            }
+            std::fs::create_dir_all(dest.parent().unwrap())?;
+            std::fs::rename(&path, &dest)?;
+            report.moved += 1;
+            // Prune the emptied legacy shard dirs (fails silently if not empty)
+            if let Some(old_p2) = path.parent() {
+                let _ = std::fs::remove_dir(old_p2);
+                if let Some(old_p1) = old_p2.parent() {
+                    let _ = std::fs::remove_dir(old_p1);
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
 // ── S3 backend (feature-gated) ──────────────────────────────────────
 
 #[cfg(feature = "s3")]
@@ -167,6 +1117,33 @@ pub mod s3 {
             Some(out.body.collect().await.ok()?.into_bytes().to_vec())
         }
 
+        /// Get bytes by CID, verifying they still hash to it. On mismatch the
+        /// corrupted read is counted, the repair hook gets a chance to refetch
+        /// from a replica (a matching refetch is re-put and served), and
+        /// otherwise a typed `IntegrityError` is returned.
+        pub async fn get_verified(
+            &self,
+            cid: &str,
+        ) -> Result<Option<Vec<u8>>, crate::IntegrityError> {
+            let Some(bytes) = self.get(cid).await else {
+                return Ok(None);
+            };
+            if crate::content_matches_cid(cid, &bytes) {
+                return Ok(Some(bytes));
+            }
+            crate::note_corrupted_read();
+            if let Some(fresh) = crate::repair_fetch(cid) {
+                if crate::content_matches_cid(cid, &fresh) {
+                    let _ = self.put(cid, &fresh).await;
+                    return Ok(Some(fresh));
+                }
+            }
+            Err(crate::IntegrityError {
+                cid: cid.to_string(),
+                path: std::path::PathBuf::from(self.s3_key(cid)),
+            })
+        }
+
         /// Head check: returns (exists, content_length) without downloading body.
         pub async fn head(&self, cid: &str) -> Result<(bool, u64)> {
             match self
@@ -223,6 +1200,48 @@ pub mod s3 {
             Some(out.body.collect().await.ok()?.into_bytes().to_vec())
         }
 
+        /// Archive-tier put: stores under an `archive/` keyspace (so
+        /// lifecycle rules can target it) with infrequent-access storage.
+        pub async fn archive_put(&self, cid: &str, bytes: &[u8]) -> Result<()> {
+            use aws_sdk_s3::types::{ServerSideEncryption, StorageClass};
+
+            let md5 = base64_encode(&md5_hash(bytes));
+            let key = format!("{}archive/{}", self.prefix, cid.replace(':', "_"));
+            self.client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(&key)
+                .body(bytes.to_vec().into())
+                .content_md5(&md5)
+                .content_type("application/x-nrf")
+                .server_side_encryption(ServerSideEncryption::Aes256)
+                .storage_class(StorageClass::StandardIa)
+                .metadata("ubl-cid", cid)
+                .send()
+                .await
+                .context("S3 archive_put failed")?;
+            Ok(())
+        }
+
+        /// Archive-tier get (read-through for archived blobs).
+        pub async fn archive_get(&self, cid: &str) -> Option<Vec<u8>> {
+            let key = format!("{}archive/{}", self.prefix, cid.replace(':', "_"));
+            let out = self
+                .client
+                .get_object()
+                .bucket(&self.bucket)
+                .key(&key)
+                .send()
+                .await
+                .ok()?;
+            Some(out.body.collect().await.ok()?.into_bytes().to_vec())
+        }
+
+        /// Lifecycle for the archive tier: expire archived objects after `days`.
+        pub async fn set_archive_lifecycle(&self, days: i32) -> Result<()> {
+            self.set_lifecycle_expiry("archive/", days).await
+        }
+
         /// Configure lifecycle rule: expire objects with given prefix after `days`.
         pub async fn set_lifecycle_expiry(&self, rule_prefix: &str, days: i32) -> Result<()> {
             use aws_sdk_s3::types::{
@@ -323,3 +1342,443 @@ pub mod s3 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn b3_cid(bytes: &[u8]) -> String {
+        format!("b3:{}", hex::encode(blake3::hash(bytes).as_bytes()))
+    }
+
+    #[tokio::test]
+    async fn atomic_body_write_roundtrips() {
+        let bytes = br#"{"law":"atomic"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-atomic", &cid, bytes).await.unwrap();
+        assert_eq!(tenant_get_body("t-atomic", &cid).await.unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn recover_quarantines_corrupt_blobs() {
+        let bytes = br#"{"law":"recovery"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-recover", &cid, bytes).await.unwrap();
+
+        // Simulate a torn write: blob bytes no longer hash to the CID
+        let path = tenant_body_path("t-recover", &cid);
+        fs::write(&path, b"corrupted").await.unwrap();
+
+        let report = recover().await.unwrap();
+        assert!(report.scanned >= 1);
+        assert!(report.quarantined >= 1, "corrupt blob must be quarantined");
+        assert!(
+            !fs::try_exists(&path).await.unwrap(),
+            "quarantined blob must stop serving reads"
+        );
+        assert!(tenant_get_body("t-recover", &cid).await.is_none());
+        assert!(
+            fs::try_exists(path.with_extension("quarantine"))
+                .await
+                .unwrap(),
+            "blob kept for forensics"
+        );
+    }
+
+    /// Replica stand-in for the repair hook. The hook is a process-wide
+    /// OnceLock, so every test shares this one registration.
+    static REPLICA: std::sync::Mutex<Option<(String, Vec<u8>)>> = std::sync::Mutex::new(None);
+
+    fn install_test_hook() {
+        set_repair_hook(|cid| {
+            let replica = REPLICA.lock().unwrap();
+            match replica.as_ref() {
+                Some((c, bytes)) if c == cid => Some(bytes.clone()),
+                _ => None,
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn verified_read_rejects_corrupted_blob() {
+        install_test_hook();
+        let bytes = br#"{"law":"verify"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-verify", &cid, bytes).await.unwrap();
+
+        let path = tenant_body_path("t-verify", &cid);
+        fs::write(&path, b"bitrot").await.unwrap();
+
+        let before = corrupted_read_count();
+        let err = tenant_get_body_verified("t-verify", &cid)
+            .await
+            .expect_err("mismatched bytes must be a typed IntegrityError");
+        assert_eq!(err.cid, cid);
+        assert!(corrupted_read_count() > before);
+        assert!(
+            !fs::try_exists(&path).await.unwrap(),
+            "corrupt blob must be quarantined"
+        );
+        // Absent blobs are a clean miss, not an integrity failure
+        assert!(tenant_get_body_verified("t-verify", &cid)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn repair_hook_refetches_and_heals_in_place() {
+        install_test_hook();
+        let bytes = br#"{"law":"repair"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-repair", &cid, bytes).await.unwrap();
+        *REPLICA.lock().unwrap() = Some((cid.clone(), bytes.to_vec()));
+
+        let path = tenant_body_path("t-repair", &cid);
+        fs::write(&path, b"bitrot").await.unwrap();
+
+        let served = tenant_get_body_verified("t-repair", &cid)
+            .await
+            .expect("repair hook should heal the read")
+            .unwrap();
+        assert_eq!(served, bytes);
+        assert_eq!(
+            fs::read(&path).await.unwrap(),
+            bytes,
+            "healed bytes must be re-written in place"
+        );
+    }
+
+    #[tokio::test]
+    async fn receipt_index_appends_in_order() {
+        tenant_index_append("t-index", br#"{"cid":"b3:one"}"#)
+            .await
+            .unwrap();
+        tenant_index_append("t-index", br#"{"cid":"b3:two"}"#)
+            .await
+            .unwrap();
+        let lines = tenant_index_lines("t-index").await;
+        assert!(lines.len() >= 2);
+        let one = lines.iter().rposition(|l| l.contains("b3:one")).unwrap();
+        let two = lines.iter().rposition(|l| l.contains("b3:two")).unwrap();
+        assert!(one < two, "index must preserve append order");
+        assert!(tenant_index_lines("t-index-absent").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_raw_blocking_probes_tenant_paths() {
+        use sha2::Digest;
+        let bytes = b"blob for sync lookup";
+        let mh = cid::multihash::Multihash::<64>::wrap(0x12, &sha2::Sha256::digest(bytes))
+            .unwrap();
+        let cid = Cid::new_v1(0x55, mh);
+        tenant_put("t-sync", &cid, bytes).await.unwrap();
+        assert_eq!(find_raw_blocking(&cid.to_string()).unwrap(), bytes);
+
+        // Absent blobs are a clean miss
+        let other = Cid::new_v1(
+            0x55,
+            cid::multihash::Multihash::<64>::wrap(0x12, &sha2::Sha256::digest(b"absent")).unwrap(),
+        );
+        assert!(find_raw_blocking(&other.to_string()).is_none());
+    }
+
+    #[test]
+    fn retention_classifies_by_age() {
+        let policy = RetentionPolicy {
+            warm_after_secs: Some(100),
+            archive_after_secs: Some(1000),
+        };
+        assert_eq!(policy.classify(0), RetentionClass::Hot);
+        assert_eq!(policy.classify(100), RetentionClass::Warm);
+        assert_eq!(policy.classify(1000), RetentionClass::Archive);
+        // Default policy keeps everything hot forever
+        assert_eq!(RetentionPolicy::default().classify(u64::MAX), RetentionClass::Hot);
+    }
+
+    #[tokio::test]
+    async fn retention_policy_roundtrips() {
+        let policy = RetentionPolicy {
+            warm_after_secs: Some(86_400),
+            archive_after_secs: Some(604_800),
+        };
+        tenant_set_retention("t-retention", policy).await.unwrap();
+        assert_eq!(tenant_retention("t-retention").await, policy);
+        assert_eq!(
+            tenant_retention("t-retention-absent").await,
+            RetentionPolicy::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn archive_sweep_moves_blobs_and_reads_fall_through() {
+        let bytes = br#"{"law":"archive"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-archive", &cid, bytes).await.unwrap();
+
+        // Threshold 0: everything is immediately archive-class
+        tenant_set_retention(
+            "t-archive",
+            RetentionPolicy {
+                warm_after_secs: None,
+                archive_after_secs: Some(0),
+            },
+        )
+        .await
+        .unwrap();
+        let report = tenant_archive_sweep("t-archive").await.unwrap();
+        assert!(report.archived >= 1, "blob must migrate: {report:?}");
+
+        let hot = tenant_body_path("t-archive", &cid);
+        assert!(!fs::try_exists(&hot).await.unwrap(), "hot copy must be gone");
+        assert!(
+            fs::try_exists(archived_variant(&hot).unwrap()).await.unwrap(),
+            "blob must live under the archive root"
+        );
+        // Transparent read-through: callers never see the migration
+        assert_eq!(tenant_get_body("t-archive", &cid).await.unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn sweep_without_archive_threshold_is_a_noop() {
+        let bytes = br#"{"law":"stay-hot"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-hot", &cid, bytes).await.unwrap();
+        let report = tenant_archive_sweep("t-hot").await.unwrap();
+        assert_eq!(report.archived, 0);
+        assert!(fs::try_exists(tenant_body_path("t-hot", &cid)).await.unwrap());
+    }
+
+    #[test]
+    fn cid_verification_covers_both_formats() {
+        let bytes = b"hello ledger";
+        assert!(content_matches_cid(&b3_cid(bytes), bytes));
+        assert!(!content_matches_cid(&b3_cid(bytes), b"other"));
+        // Unknown formats are unverifiable, not corrupt
+        assert!(content_matches_cid("not-a-cid", bytes));
+    }
+
+    #[test]
+    fn shards_come_from_digest_bytes_not_string_prefixes() {
+        // b3: CIDs shard on the first two digest bytes
+        let cid = b3_cid(b"shard me");
+        let hex_digest = cid.strip_prefix("b3:").unwrap();
+        assert_eq!(
+            shard_dirs(&cid),
+            (hex_digest[..2].to_string(), hex_digest[2..4].to_string())
+        );
+
+        // CIDv1 base32 strings all start "bafk…", which is exactly why
+        // string slicing broke: the digest bytes must drive the shard.
+        let cidv1 = "bafkreigibhh2eucyeberwvkqx56braqzvokd2d45jrg24d5iqcsoumjmrq";
+        let (p1, p2) = shard_dirs(cidv1);
+        assert_ne!((p1.as_str(), p2.as_str()), ("fk", "re"), "not the string slice");
+        let c = Cid::try_from(cidv1).unwrap();
+        let d = c.hash().digest();
+        assert_eq!((p1, p2), (format!("{:02x}", d[0]), format!("{:02x}", d[1])));
+
+        // Opaque identifiers still land somewhere deterministic
+        assert_eq!(shard_dirs("weird"), shard_dirs("weird"));
+    }
+
+    #[tokio::test]
+    async fn legacy_sharded_blobs_stay_readable() {
+        let bytes = br#"{"law":"legacy"}"#;
+        let cid = b3_cid(bytes);
+        let legacy = legacy_tenant_body_path("t-legacy", &cid);
+        fs::create_dir_all(legacy.parent().unwrap()).await.unwrap();
+        fs::write(&legacy, bytes).await.unwrap();
+
+        assert_ne!(legacy, tenant_body_path("t-legacy", &cid));
+        assert_eq!(tenant_get_body("t-legacy", &cid).await.unwrap(), bytes);
+        assert_eq!(
+            tenant_get_body_verified("t-legacy", &cid).await.unwrap().unwrap(),
+            bytes
+        );
+    }
+
+    #[tokio::test]
+    async fn write_batch_commits_blobs_and_index_together() {
+        let a = br#"{"law":"batch-a"}"#;
+        let b = br#"{"law":"batch-b"}"#;
+        let (cid_a, cid_b) = (b3_cid(a), b3_cid(b));
+
+        let mut batch = WriteBatch::new();
+        batch.put_body("t-batch", &cid_a, a.to_vec());
+        batch.put_body("t-batch", &cid_b, b.to_vec());
+        batch.index_line("t-batch", format!(r#"{{"cid":"{cid_a}"}}"#).into_bytes());
+        batch.commit().await.unwrap();
+
+        assert_eq!(tenant_get_body("t-batch", &cid_a).await.unwrap(), a);
+        assert_eq!(tenant_get_body("t-batch", &cid_b).await.unwrap(), b);
+        assert!(tenant_index_lines("t-batch")
+            .await
+            .iter()
+            .any(|l| l.contains(&cid_a)));
+        // Staging leftovers are gone after a clean commit
+        assert!(
+            !fs::try_exists(staged_variant(&tenant_body_path("t-batch", &cid_a)))
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn recover_rolls_a_published_batch_forward() {
+        // Simulate a crash *after* the commit point (journal append) but
+        // *before* the renames: staged files exist, final paths don't.
+        // Unique bytes per run: the store (and journal) outlive the test
+        // process, and a previous run already promoted constant content.
+        let nonce = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let a = format!(r#"{{"law":"crash-a","nonce":{nonce}}}"#).into_bytes();
+        let b = format!(r#"{{"law":"crash-b","nonce":{nonce}}}"#).into_bytes();
+        let (a, b) = (a.as_slice(), b.as_slice());
+        let (cid_a, cid_b) = (b3_cid(a), b3_cid(b));
+        let (path_a, path_b) = (
+            tenant_body_path("t-crash", &cid_a),
+            tenant_body_path("t-crash", &cid_b),
+        );
+        for (path, bytes) in [(&path_a, a), (&path_b, b)] {
+            fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+            fs::write(staged_variant(path), bytes).await.unwrap();
+        }
+        journal_append_line(format!(
+            "BATCH\t{cid_a}\t{}\t{}\t{cid_b}\t{}\t{}",
+            path_a.display(),
+            a.len(),
+            path_b.display(),
+            b.len()
+        ))
+        .await
+        .unwrap();
+
+        // Published but unrenamed: readers can't see the batch yet
+        assert!(tenant_get_body("t-crash", &cid_a).await.is_none());
+
+        let report = recover().await.unwrap();
+        assert!(report.verified >= 2, "both members roll forward: {report:?}");
+        assert_eq!(tenant_get_body("t-crash", &cid_a).await.unwrap(), a);
+        assert_eq!(tenant_get_body("t-crash", &cid_b).await.unwrap(), b);
+    }
+
+    #[tokio::test]
+    async fn unpublished_batch_leaves_nothing_readable() {
+        // Crash *before* the journal append: only staged files exist and
+        // neither reads nor recovery promote them.
+        let bytes = br#"{"law":"unpublished"}"#;
+        let cid = b3_cid(bytes);
+        let path = tenant_body_path("t-unpublished", &cid);
+        fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+        fs::write(staged_variant(&path), bytes).await.unwrap();
+
+        recover().await.unwrap();
+        assert!(tenant_get_body("t-unpublished", &cid).await.is_none());
+        assert!(!fs::try_exists(&path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn migrate_store_relocates_and_verifies() {
+        let bytes = br#"{"law":"migrate"}"#;
+        let cid = b3_cid(bytes);
+        let legacy = legacy_tenant_body_path("t-migrate", &cid);
+        fs::create_dir_all(legacy.parent().unwrap()).await.unwrap();
+        fs::write(&legacy, bytes).await.unwrap();
+
+        // A blob that no longer hashes to its CID must not be relocated
+        let bad_cid = b3_cid(b"something else entirely");
+        let bad = legacy_tenant_body_path("t-migrate", &bad_cid);
+        fs::create_dir_all(bad.parent().unwrap()).await.unwrap();
+        fs::write(&bad, b"tampered").await.unwrap();
+
+        let report = tokio::task::spawn_blocking(migrate_store)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(report.moved >= 1, "legacy blob must move: {report:?}");
+        assert!(report.corrupt >= 1, "tampered blob must be counted");
+
+        let dest = tenant_body_path("t-migrate", &cid);
+        assert!(fs::try_exists(&dest).await.unwrap(), "blob at digest shard");
+        assert!(!fs::try_exists(&legacy).await.unwrap(), "legacy copy gone");
+        assert!(fs::try_exists(&bad).await.unwrap(), "corrupt blob left for forensics");
+        assert_eq!(tenant_get_body("t-migrate", &cid).await.unwrap(), bytes);
+    }
+
+    #[test]
+    fn decode_passes_raw_and_pseudo_zstd_bytes_through() {
+        assert_eq!(decode_stored(b"plain bytes".to_vec()), b"plain bytes");
+        // Bytes that merely start with the frame magic decode as themselves
+        let fake = [&ZSTD_MAGIC[..], b"not a real frame"].concat();
+        assert_eq!(decode_stored(fake.clone()), fake);
+        let real = zstd::bulk::compress(b"round trip", 0).unwrap();
+        assert_eq!(decode_stored(real), b"round trip");
+    }
+
+    #[tokio::test]
+    async fn compressed_blobs_decode_transparently_on_read() {
+        // Reads don't consult the env flag: the frame magic alone decides,
+        // so blobs written under compression outlive the setting.
+        let bytes = br#"{"law":"squeeze","trace":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"}"#;
+        let cid = b3_cid(bytes);
+        let path = tenant_body_path("t-zstd", &cid);
+        fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+        let stored = zstd::bulk::compress(bytes, 0).unwrap();
+        assert!(stored.len() < bytes.len(), "fixture must actually compress");
+        fs::write(&path, &stored).await.unwrap();
+
+        assert_eq!(tenant_get_body("t-zstd", &cid).await.unwrap(), bytes);
+        // Verified reads hash the *decoded* bytes: compression is an
+        // encoding, not bitrot
+        assert_eq!(
+            tenant_get_body_verified("t-zstd", &cid).await.unwrap().unwrap(),
+            bytes
+        );
+        assert!(fs::try_exists(&path).await.unwrap(), "no quarantine");
+    }
+
+    #[tokio::test]
+    async fn recover_treats_compressed_blobs_as_intact() {
+        let bytes = br#"{"law":"squeeze-recover","pad":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"}"#;
+        let cid = b3_cid(bytes);
+        tenant_put_body("t-zstd-recover", &cid, bytes).await.unwrap();
+
+        // Same canonical bytes, compressed stored form
+        let path = tenant_body_path("t-zstd-recover", &cid);
+        fs::write(&path, zstd::bulk::compress(bytes, 0).unwrap())
+            .await
+            .unwrap();
+
+        recover().await.unwrap();
+        assert!(
+            fs::try_exists(&path).await.unwrap(),
+            "compressed form is an encoding, not corruption"
+        );
+        assert_eq!(tenant_get_body("t-zstd-recover", &cid).await.unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn open_raw_buffers_compressed_blobs() {
+        use sha2::Digest;
+        let bytes = br#"{"law":"squeeze-stream","pad":"cccccccccccccccccccccccccccccccc"}"#;
+        let mh = cid::multihash::Multihash::<64>::wrap(0x12, &sha2::Sha256::digest(bytes)).unwrap();
+        let cid = Cid::new_v1(0x55, mh);
+        tenant_put("t-zstd-open", &cid, bytes).await.unwrap();
+
+        match tenant_open_raw("t-zstd-open", &cid).await.unwrap() {
+            RawBlob::Stream(_, len) => assert_eq!(len, bytes.len() as u64),
+            RawBlob::Buffered(_) => panic!("canonical bytes on disk should stream"),
+        }
+
+        let path = tenant_cid_path("t-zstd-open", &cid, "nrf");
+        fs::write(&path, zstd::bulk::compress(bytes, 0).unwrap())
+            .await
+            .unwrap();
+        match tenant_open_raw("t-zstd-open", &cid).await.unwrap() {
+            RawBlob::Buffered(b) => assert_eq!(b, bytes),
+            RawBlob::Stream(..) => panic!("compressed blobs must be decoded, not streamed"),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+//! Property-based test harness for the workspace.
+//!
+//! This crate carries no library code — it exists so the proptest suites
+//! under `tests/` have their own compilation unit and dependency set.
@@ -0,0 +1,100 @@
+//! Property-based determinism checks for the runtime engine and canonical form.
+//!
+//! Three invariants the rest of the system leans on:
+//!  1. `execute()` is a pure function of (manifest, vars) — same inputs,
+//!     same CID, every time.
+//!  2. Canonicalization is idempotent: canon(canon(x)) == canon(x).
+//!  3. The CID is collision-sensitive: flipping any single byte of the
+//!     canonical form yields a different CID.
+
+use base64::Engine;
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use ubl_runtime::{canon, cid, execute, ExecuteConfig, Manifest};
+
+/// Arbitrary JSON values, bounded in depth and width so canon stays cheap.
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        "[a-zA-Z0-9 _.:-]{0,24}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 24, 6, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..6).prop_map(Value::from),
+            prop::collection::btree_map("[a-z_]{1,8}", inner, 0..6)
+                .prop_map(|m| Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+/// A minimal NRF-valid manifest: decode base64 input, pass it through.
+fn manifest_for(pipeline: &str) -> Manifest {
+    serde_json::from_value(json!({
+        "pipeline": pipeline,
+        "in_grammar": {
+            "inputs": {"raw_b64": ""},
+            "mappings": [{"from": "raw_b64", "codec": "base64.decode", "to": "raw.bytes"}],
+            "output_from": "raw.bytes"
+        },
+        "out_grammar": {
+            "inputs": {"content": ""},
+            "mappings": [],
+            "output_from": "content"
+        },
+        "policy": {"allow": true}
+    }))
+    .expect("manifest template must deserialize")
+}
+
+proptest! {
+    /// execute() is deterministic: two independent runs over the same
+    /// manifest + vars produce byte-identical CIDs and outputs.
+    #[test]
+    fn execute_cid_is_stable(
+        pipeline in "[a-z][a-z0-9_]{0,15}",
+        payload in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        let manifest = manifest_for(&pipeline);
+        let raw_b64 = base64::engine::general_purpose::STANDARD.encode(&payload);
+        let vars: BTreeMap<String, Value> =
+            BTreeMap::from([("raw_b64".to_string(), json!(raw_b64))]);
+        let cfg = ExecuteConfig { version: "0.1.0".into(), codecs: Default::default() };
+
+        let a = execute(&manifest, &vars, &cfg).unwrap();
+        let b = execute(&manifest, &vars, &cfg).unwrap();
+        prop_assert_eq!(&a.cid, &b.cid);
+        prop_assert_eq!(a.artifacts.output, b.artifacts.output);
+        prop_assert_eq!(a.dimension_stack, b.dimension_stack);
+    }
+
+    /// Canonicalization is idempotent: re-canonicalizing the parsed
+    /// canonical form reproduces the exact same bytes.
+    #[test]
+    fn canon_is_idempotent(v in arb_json()) {
+        let once = canon::canonical_bytes(&v).unwrap();
+        let reparsed: Value = serde_json::from_slice(&once).unwrap();
+        let twice = canon::canonical_bytes(&reparsed).unwrap();
+        prop_assert_eq!(once, twice);
+    }
+
+    /// Canonical bytes fully determine the CID, and any single-byte
+    /// mutation of them diverges.
+    #[test]
+    fn cid_diverges_on_single_byte_mutation(
+        v in arb_json(),
+        idx in any::<prop::sample::Index>(),
+        flip in 1u8..=255,
+    ) {
+        let bytes = canon::canonical_bytes(&v).unwrap();
+        prop_assume!(!bytes.is_empty());
+        let original = cid::cid_b3(&bytes);
+
+        let mut mutated = bytes.clone();
+        let i = idx.index(mutated.len());
+        mutated[i] ^= flip; // flip != 0 guarantees the byte changes
+        prop_assert_ne!(original, cid::cid_b3(&mutated));
+    }
+}
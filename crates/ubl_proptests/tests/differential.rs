@@ -0,0 +1,66 @@
+//! Differential tests between near-duplicate implementations.
+//!
+//! The backlog asked for a harness pitting `ubl_runtime::engine` against a
+//! second `ubl_tdln::engine`; that crate is not part of this tree. What we
+//! do have are duplicated primitives that risk exactly the same silent
+//! divergence: `cid_b3` exists in both `ubl_runtime::cid` and
+//! `ubl_adapter::cid`, and JSON canonicalization exists as both
+//! `ubl_runtime::canon` and the NRF provider `ubl_runtime::nrf_canon::Nrf1Canon`.
+//! This suite locks them together; once one is re-exported from the other
+//! (tracked as the consolidation follow-up), the paired assertions collapse
+//! into trivial identities and can be dropped.
+
+use proptest::prelude::*;
+use rb_vm::canon::CanonProvider;
+use serde_json::Value;
+use ubl_runtime::nrf_canon::Nrf1Canon;
+
+/// Arbitrary NRF-safe JSON: i64 numbers only, BOM-free ASCII-ish strings.
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        "[a-zA-Z0-9 _.:-]{0,24}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 24, 6, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..6).prop_map(Value::from),
+            prop::collection::btree_map("[a-z_]{1,8}", inner, 0..6)
+                .prop_map(|m| Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+proptest! {
+    /// The two cid_b3 implementations must hash identically — receipts
+    /// minted by the runtime are verified by adapter-side clients.
+    #[test]
+    fn cid_b3_implementations_agree(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+        prop_assert_eq!(
+            ubl_runtime::cid::cid_b3(&bytes),
+            ubl_adapter::cid::cid_b3(&bytes)
+        );
+    }
+
+    /// Over the NRF-safe domain, runtime canonical bytes and the rb_vm
+    /// NRF-1.1 provider must agree on the canonical form.
+    #[test]
+    fn canon_and_nrf1_agree(v in arb_json()) {
+        let canon_bytes = ubl_runtime::canon::canonical_bytes(&v).unwrap();
+        let nrf_bytes = serde_json::to_vec(&Nrf1Canon.canon(v)).unwrap();
+        prop_assert_eq!(canon_bytes, nrf_bytes);
+    }
+
+    /// Downstream consequence of the above: the CID of the canonical form
+    /// is the same no matter which canonicalizer produced it.
+    #[test]
+    fn canonical_cids_agree(v in arb_json()) {
+        let canon_bytes = ubl_runtime::canon::canonical_bytes(&v).unwrap();
+        let nrf_bytes = serde_json::to_vec(&Nrf1Canon.canon(v)).unwrap();
+        prop_assert_eq!(
+            ubl_runtime::cid::cid_b3(&canon_bytes),
+            ubl_adapter::cid::cid_b3(&nrf_bytes)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Canonicalization throughput: single-pass writer vs the old two-pass
+//! normalize-then-serialize.
+//!
+//! Run with `cargo bench -p ubl_runtime --bench canon`. The two-pass
+//! reference lives here (it was deleted from `canon.rs`); byte equality
+//! is asserted before timing.
+
+use serde_json::{json, Value};
+use unicode_normalization::UnicodeNormalization;
+
+const ITERS: usize = 20_000;
+
+/// The pre-rewrite implementation: build a normalized copy of the whole
+/// tree, then serialize it.
+fn two_pass(v: &Value) -> Vec<u8> {
+    fn normalize(v: &Value) -> Value {
+        match v {
+            Value::String(s) => Value::String(s.nfc().collect::<String>()),
+            Value::Array(arr) => Value::Array(arr.iter().map(normalize).collect()),
+            Value::Object(obj) => {
+                let mut out = serde_json::Map::new();
+                let mut keys: Vec<_> = obj.keys().cloned().collect();
+                keys.sort();
+                for k in keys {
+                    if let Some(v) = obj.get(&k) {
+                        if *v != Value::Null {
+                            out.insert(k, normalize(v));
+                        }
+                    }
+                }
+                Value::Object(out)
+            }
+            other => other.clone(),
+        }
+    }
+    serde_json::to_string(&normalize(v)).unwrap().into_bytes()
+}
+
+/// A WF-receipt-shaped body: the kind of value canonicalized five-plus
+/// times per execute.
+fn sample_body() -> Value {
+    json!({
+        "type": "ubl/wf",
+        "rho_cid": format!("b3:{}", "a".repeat(64)),
+        "outputs_cid": format!("b3:{}", "b".repeat(64)),
+        "decision": "ALLOW",
+        "dimension_stack": ["parse", "policy", "render"],
+        "policy_trace": (0..8).map(|i| json!({
+            "rule": format!("rule_{i}"),
+            "matched": i % 2 == 0,
+            "detail": null,
+        })).collect::<Vec<_>>(),
+        "witness": {"vm": "ubl-runtime@0.1.0", "note": "benchmark body"},
+    })
+}
+
+fn main() {
+    let body = sample_body();
+
+    let old = two_pass(&body);
+    let new = ubl_runtime::canon::canonical_bytes(&body).unwrap();
+    assert_eq!(old, new, "single-pass output must be byte-identical");
+
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        std::hint::black_box(two_pass(&body));
+    }
+    let two_pass_elapsed = started.elapsed();
+
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        std::hint::black_box(ubl_runtime::canon::canonical_bytes(&body).unwrap());
+    }
+    let single_elapsed = started.elapsed();
+
+    let mut buf = Vec::new();
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        ubl_runtime::canon::canonical_bytes_into(&body, &mut buf).unwrap();
+        std::hint::black_box(&buf);
+    }
+    let reused_elapsed = started.elapsed();
+
+    let rate = |d: std::time::Duration| ITERS as f64 / d.as_secs_f64();
+    println!("two-pass:       {:>10.0} bodies/s ({two_pass_elapsed:?})", rate(two_pass_elapsed));
+    println!("single-pass:    {:>10.0} bodies/s ({single_elapsed:?})", rate(single_elapsed));
+    println!("reused buffer:  {:>10.0} bodies/s ({reused_elapsed:?})", rate(reused_elapsed));
+    println!(
+        "speedup: {:.2}x (reused {:.2}x)",
+        rate(single_elapsed) / rate(two_pass_elapsed),
+        rate(reused_elapsed) / rate(two_pass_elapsed),
+    );
+
+    // Representative payload sizes: a small WA intention, a policy-heavy
+    // WF body, and a bulk array — throughput in MiB/s for each
+    println!();
+    for (label, body, iters) in [
+        ("small (~0.2 KiB)", json!({"type": "ubl/wa", "inputs_raw_cid": format!("b3:{}", "c".repeat(64)), "intention": {"op": "execute", "pipeline": "@bench/sweep/1.0.0"}}), 50_000usize),
+        ("medium (~12 KiB)", json!({"type": "ubl/wf", "decision": "ALLOW", "policy_trace": (0..100).map(|i| json!({"rule": format!("rule_{i}"), "matched": i % 2 == 0, "detail": format!("evaluated clause {i} against dimension stack")})).collect::<Vec<_>>()}), 2_000),
+        ("large (~120 KiB)", json!({"type": "ubl/wf", "decision": "ALLOW", "items": (0..1_000).map(|i| json!({"sku": format!("sku-{i:06}"), "qty": i % 7, "price": format!("{}.{:02}", i, i % 100)})).collect::<Vec<_>>()}), 200),
+    ] {
+        let bytes = ubl_runtime::canon::canonical_bytes(&body).unwrap();
+        let started = std::time::Instant::now();
+        for _ in 0..iters {
+            std::hint::black_box(ubl_runtime::canon::canonical_bytes(&body).unwrap());
+        }
+        let elapsed = started.elapsed();
+        let mb_per_s = (bytes.len() * iters) as f64 / 1_048_576.0 / elapsed.as_secs_f64();
+        println!(
+            "{label:>17}: {:>8.0} bodies/s  {mb_per_s:>6.1} MiB/s  ({} bytes)",
+            iters as f64 / elapsed.as_secs_f64(),
+            bytes.len(),
+        );
+    }
+}
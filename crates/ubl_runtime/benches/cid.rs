@@ -0,0 +1,25 @@
+//! BLAKE3 CID throughput across representative blob sizes.
+//!
+//! Run with `cargo bench -p ubl_runtime --bench cid`. See `make bench`
+//! for comparing against main.
+
+fn main() {
+    for (label, size, iters) in [
+        ("1 KiB", 1 << 10, 200_000usize),
+        ("64 KiB", 1 << 16, 10_000),
+        ("1 MiB", 1 << 20, 500),
+    ] {
+        let blob: Vec<u8> = (0..size).map(|i| i as u8).collect();
+        // Warm-up keeps first-touch page faults out of the timing
+        std::hint::black_box(ubl_runtime::cid::cid_b3(&blob));
+
+        let started = std::time::Instant::now();
+        for _ in 0..iters {
+            std::hint::black_box(ubl_runtime::cid::cid_b3(&blob));
+        }
+        let elapsed = started.elapsed();
+        let mb_per_s = (size * iters) as f64 / 1_048_576.0 / elapsed.as_secs_f64();
+        let per_s = iters as f64 / elapsed.as_secs_f64();
+        println!("cid_b3 {label:>7}: {per_s:>10.0} cids/s  {mb_per_s:>8.0} MiB/s  ({elapsed:?})");
+    }
+}
@@ -0,0 +1,66 @@
+//! End-to-end `run_with_receipts` throughput: bind → mappings → policy →
+//! canon → sign for the four-receipt chain, plus the per-phase breakdown
+//! the result's `timings` already carry.
+//!
+//! Run with `cargo bench -p ubl_runtime --bench execute`. See
+//! `make bench` for comparing against main.
+
+use serde_json::json;
+use std::collections::BTreeMap;
+use ubl_runtime::{ExecuteConfig, Grammar, Manifest, Policy};
+
+const ITERS: usize = 2_000;
+
+fn manifest() -> Manifest {
+    Manifest {
+        pipeline: "bench".into(),
+        in_grammar: Grammar {
+            inputs: BTreeMap::from([("raw_b64".into(), json!(""))]),
+            mappings: vec![ubl_runtime::engine::Mapping {
+                from: "raw_b64".into(),
+                codec: "base64.decode".into(),
+                to: "raw.bytes".into(),
+            }],
+            output_from: "raw.bytes".into(),
+        },
+        out_grammar: Grammar {
+            inputs: BTreeMap::from([("content".into(), json!(""))]),
+            mappings: vec![],
+            output_from: "content".into(),
+        },
+        policy: Policy { allow: true, rules: vec![] },
+    }
+}
+
+fn main() {
+    let manifest = manifest();
+    let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8gYmVuY2htYXJr"))]);
+    let cfg = ExecuteConfig {
+        version: "0.1.0".into(),
+        codecs: Default::default(),
+    };
+
+    // Warm-up also seeds the lazy signing pool
+    let warm = ubl_runtime::run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+
+    let mut timings = warm.timings;
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        let run = ubl_runtime::run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+        timings.merge(&std::hint::black_box(run).timings);
+    }
+    let elapsed = started.elapsed();
+
+    let per_s = ITERS as f64 / elapsed.as_secs_f64();
+    println!("run_with_receipts: {per_s:>8.0} runs/s  ({elapsed:?})");
+    let total = ITERS as u64 + 1;
+    for (phase, micros) in [
+        ("bind", timings.bind_micros),
+        ("mappings", timings.mappings_micros),
+        ("policy", timings.policy_micros),
+        ("canon", timings.canon_micros),
+        ("sign", timings.sign_micros),
+    ] {
+        println!("  {phase:>9}: {:>7.1} µs/run", micros as f64 / total as f64);
+    }
+}
@@ -0,0 +1,80 @@
+//! RB-VM chip throughput through the runtime bridge (`execute_rb`),
+//! which is how chips actually run in production: TLV decode (cached),
+//! fuel metering, ghost-mode execution.
+//!
+//! Run with `cargo bench -p ubl_runtime --bench rb`. See `make bench`
+//! for comparing against main.
+
+use serde_json::json;
+use ubl_runtime::{execute_rb, ExecuteRbReq};
+
+const ITERS: usize = 50_000;
+
+fn bench_chip(label: &str, asm: &str, inputs: Vec<serde_json::Value>) {
+    let chip = rb_vm::assemble(asm).expect("chip assembles");
+    let req = ExecuteRbReq {
+        chip,
+        inputs,
+        ghost: Some(true),
+        fuel: None,
+        checked_arith: None,
+    };
+    // Warm-up fills the decode cache; steady state is what we measure
+    let warm = execute_rb(&req).expect("chip runs");
+
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        std::hint::black_box(execute_rb(&req).expect("chip runs"));
+    }
+    let elapsed = started.elapsed();
+    let per_s = ITERS as f64 / elapsed.as_secs_f64();
+    println!(
+        "{label:>12}: {per_s:>9.0} runs/s  {} steps, {} fuel  ({elapsed:?})",
+        warm.steps, warm.fuel_used
+    );
+}
+
+fn main() {
+    // Policy-style arithmetic guard: the most common chip shape
+    bench_chip(
+        "arith-guard",
+        r#"
+            ConstI64 40
+            ConstI64 2
+            AddI64
+            ConstI64 42
+            CmpI64 EQ
+            AssertTrue
+        "#,
+        vec![],
+    );
+
+    // JSON payload work: parse + canon-normalize an inline document
+    let payload = serde_json::to_vec(&json!({"b": 1, "a": [1, 2, 3], "note": "benchmark"})).unwrap();
+    bench_chip(
+        "json-canon",
+        &format!("ConstBytes 0x{}\nJsonNormalize\nDrop\n", hex::encode(&payload)),
+        vec![],
+    );
+
+    // Content addressing: hash raw bytes the way CasPut does
+    bench_chip(
+        "blake3-hash",
+        &format!("ConstBytes 0x{}\nHashBlake3\nDrop\n", hex::encode(&payload)),
+        vec![],
+    );
+
+    // Fixed-point money math (decimal opcodes)
+    bench_chip(
+        "dec-sum",
+        r#"
+            ConstDec 12.34
+            ConstDec 0.66
+            AddDec 2 HALF_EVEN
+            ConstDec 13.00
+            CmpDec EQ
+            AssertTrue
+        "#,
+        vec![],
+    );
+}
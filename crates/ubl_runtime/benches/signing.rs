@@ -0,0 +1,65 @@
+//! Throughput comparison: inline vs pooled receipt signing.
+//!
+//! Run with `cargo bench -p ubl_runtime --bench signing`. Before timing
+//! anything the bench asserts the pooled proofs are bit-identical to
+//! inline signing — the pool must never change outputs, only latency.
+//!
+//! Each iteration signs one execute's worth of bodies (WA, transition,
+//! policy, WF), which is the batch shape `run_with_receipts` submits.
+//! The speedup tracks available cores: on a single-core box the pool can
+//! only add handoff overhead, so read the number next to the printed
+//! parallelism.
+
+use ubl_runtime::jws::sign_detached;
+use ubl_runtime::KeyRing;
+
+const BATCH: usize = 4;
+const ITERS: usize = 2_000;
+
+fn payloads() -> Vec<Vec<u8>> {
+    (0..BATCH)
+        .map(|i| {
+            format!("{{\"type\":\"ubl/bench\",\"seq\":{i},\"pad\":\"{}\"}}", "x".repeat(512))
+                .into_bytes()
+        })
+        .collect()
+}
+
+fn main() {
+    let keys = KeyRing::dev();
+    let bodies = payloads();
+
+    // Correctness first: identical outputs, or the numbers are meaningless
+    let inline: Vec<_> = bodies
+        .iter()
+        .map(|p| sign_detached(p, &keys.active, &keys.active_kid))
+        .collect();
+    let pooled = keys.sign_batch(bodies.clone());
+    assert_eq!(inline, pooled, "pooled signatures must match inline signing");
+
+    // Warm the pool so thread spawn doesn't land inside the measurement
+    let _ = keys.sign_batch(bodies.clone());
+
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        for p in &bodies {
+            std::hint::black_box(sign_detached(p, &keys.active, &keys.active_kid));
+        }
+    }
+    let inline_elapsed = started.elapsed();
+
+    let started = std::time::Instant::now();
+    for _ in 0..ITERS {
+        std::hint::black_box(keys.sign_batch(bodies.clone()));
+    }
+    let pooled_elapsed = started.elapsed();
+
+    let sigs = (BATCH * ITERS) as f64;
+    let inline_rate = sigs / inline_elapsed.as_secs_f64();
+    let pooled_rate = sigs / pooled_elapsed.as_secs_f64();
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    println!("cores:   {cores}");
+    println!("inline:  {inline_rate:>10.0} sigs/s ({inline_elapsed:?} total)");
+    println!("pooled:  {pooled_rate:>10.0} sigs/s ({pooled_elapsed:?} total)");
+    println!("speedup: {:.2}x", pooled_rate / inline_rate);
+}
@@ -2,20 +2,229 @@ use crate::error::{Result, RuntimeError};
 use serde_json::Value;
 use std::collections::BTreeMap;
 
+/// Structural limits on vars, so canonicalization cost stays bounded.
+/// Defaults are generous for real payloads but stop adversarial nesting.
+#[derive(Debug, Clone)]
+pub struct VarsLimits {
+    pub max_depth: usize,
+    pub max_keys: usize,
+    pub max_string_len: usize,
+    pub max_canonical_bytes: usize,
+}
+
+impl Default for VarsLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 32,
+            max_keys: 1024,
+            max_string_len: 64 * 1024,
+            max_canonical_bytes: 1024 * 1024,
+        }
+    }
+}
+
+impl VarsLimits {
+    /// Override defaults via `UBL_VARS_MAX_DEPTH`, `UBL_VARS_MAX_KEYS`,
+    /// `UBL_VARS_MAX_STRING_LEN`, `UBL_VARS_MAX_CANONICAL_BYTES`.
+    pub fn from_env() -> Self {
+        fn env(name: &str, default: usize) -> usize {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+        let d = Self::default();
+        Self {
+            max_depth: env("UBL_VARS_MAX_DEPTH", d.max_depth),
+            max_keys: env("UBL_VARS_MAX_KEYS", d.max_keys),
+            max_string_len: env("UBL_VARS_MAX_STRING_LEN", d.max_string_len),
+            max_canonical_bytes: env("UBL_VARS_MAX_CANONICAL_BYTES", d.max_canonical_bytes),
+        }
+    }
+}
+
+/// Enforce structural limits on vars, with the offending path in the error.
+pub fn enforce_vars_limits(vars: &BTreeMap<String, Value>, limits: &VarsLimits) -> Result<()> {
+    fn walk(
+        v: &Value,
+        path: &str,
+        depth: usize,
+        keys: &mut usize,
+        limits: &VarsLimits,
+    ) -> Result<()> {
+        if depth > limits.max_depth {
+            return Err(RuntimeError::VarsLimit(format!(
+                "depth {depth} at {path} exceeds max_depth {}",
+                limits.max_depth
+            )));
+        }
+        match v {
+            Value::String(s) if s.len() > limits.max_string_len => {
+                Err(RuntimeError::VarsLimit(format!(
+                    "string of {} bytes at {path} exceeds max_string_len {}",
+                    s.len(),
+                    limits.max_string_len
+                )))
+            }
+            Value::Object(m) => {
+                *keys += m.len();
+                if *keys > limits.max_keys {
+                    return Err(RuntimeError::VarsLimit(format!(
+                        "{keys} keys (counted up to {path}) exceed max_keys {}",
+                        limits.max_keys
+                    )));
+                }
+                for (k, val) in m {
+                    walk(val, &format!("{path}.{k}"), depth + 1, keys, limits)?;
+                }
+                Ok(())
+            }
+            Value::Array(a) => {
+                for (i, val) in a.iter().enumerate() {
+                    walk(val, &format!("{path}[{i}]"), depth + 1, keys, limits)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    let mut keys = vars.len();
+    if keys > limits.max_keys {
+        return Err(RuntimeError::VarsLimit(format!(
+            "{keys} top-level keys exceed max_keys {}",
+            limits.max_keys
+        )));
+    }
+    for (k, v) in vars {
+        walk(v, &format!("$.{k}"), 1, &mut keys, limits)?;
+    }
+
+    let total = crate::canon::canonical_bytes(&serde_json::to_value(vars)?)?.len();
+    if total > limits.max_canonical_bytes {
+        return Err(RuntimeError::VarsLimit(format!(
+            "canonical form is {total} bytes, exceeds max_canonical_bytes {}",
+            limits.max_canonical_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// Validate (and deterministically coerce) one bound value against its
+/// spec metadata. Supported: `type` (string|int|bool|bytes|object) and
+/// `max_len` (bytes for strings, elements for arrays). Coercions are
+/// intentionally narrow — numeric strings to int, "true"/"false" to bool
+/// — so the same vars always produce the same bound bytes. Errors name
+/// the input and the rule it broke.
+fn apply_input_spec(key: &str, meta: &serde_json::Map<String, Value>, v: Value) -> Result<Value> {
+    fn type_err(key: &str, expected: &str, got: &Value) -> RuntimeError {
+        RuntimeError::Validation(format!(
+            "input '{key}': expected {expected}, got {got}"
+        ))
+    }
+
+    let v = match meta.get("type").and_then(Value::as_str) {
+        None => v,
+        Some("string") => {
+            if v.is_string() {
+                v
+            } else {
+                return Err(type_err(key, "string", &v));
+            }
+        }
+        Some("int") => match &v {
+            Value::Number(n) if n.is_i64() || n.is_u64() => v,
+            Value::String(s) => match s.parse::<i64>() {
+                Ok(i) => Value::from(i),
+                Err(_) => return Err(type_err(key, "int", &v)),
+            },
+            _ => return Err(type_err(key, "int", &v)),
+        },
+        Some("bool") => match &v {
+            Value::Bool(_) => v,
+            Value::String(s) if s == "true" => Value::Bool(true),
+            Value::String(s) if s == "false" => Value::Bool(false),
+            _ => return Err(type_err(key, "bool", &v)),
+        },
+        Some("bytes") => {
+            use base64::Engine;
+            match v.as_str() {
+                Some(s) if base64::engine::general_purpose::STANDARD.decode(s).is_ok() => v,
+                _ => return Err(type_err(key, "bytes (base64 string)", &v)),
+            }
+        }
+        Some("object") => {
+            if v.is_object() {
+                v
+            } else {
+                return Err(type_err(key, "object", &v));
+            }
+        }
+        Some(other) => {
+            return Err(RuntimeError::Validation(format!(
+                "input '{key}': unknown type '{other}' in grammar"
+            )))
+        }
+    };
+
+    if let Some(max) = meta.get("max_len").and_then(Value::as_u64) {
+        let len = match &v {
+            Value::String(s) => Some(s.len()),
+            Value::Array(a) => Some(a.len()),
+            _ => None,
+        };
+        if let Some(len) = len {
+            if len as u64 > max {
+                return Err(RuntimeError::Validation(format!(
+                    "input '{key}': length {len} exceeds max_len {max}"
+                )));
+            }
+        }
+    }
+
+    Ok(v)
+}
+
 /// D8: deterministic input binding from vars -> grammar inputs.
+///
+/// An input spec may carry per-input metadata as an object:
+/// `{"default": v}` binds `v` when the caller omits the var,
+/// `{"optional": true}` drops the key from the bound form entirely
+/// (never `null`, so canonical bytes stay stable), and
+/// `{"type": ..., "max_len": ...}` validates/coerces the bound value
+/// (see [`apply_input_spec`]). Defaults live in the manifest and are
+/// therefore covered by its CID — filling one is as deterministic as
+/// the caller sending it.
 pub fn bind_vars_to_inputs(
     vars: &BTreeMap<String, Value>,
     grammar_inputs: &BTreeMap<String, Value>,
 ) -> Result<BTreeMap<String, Value>> {
-    // 1) name match
+    // 0) structural limits — bound canonicalization cost before any cloning
+    enforce_vars_limits(vars, &VarsLimits::from_env())?;
+
+    // 1) name match, then per-input metadata (default / optional)
     let mut bound = BTreeMap::new();
     let mut missing = Vec::new();
-    for k in grammar_inputs.keys() {
+    for (k, spec) in grammar_inputs {
+        let meta = spec.as_object();
         if let Some(v) = vars.get(k) {
-            bound.insert(k.clone(), v.clone());
-        } else {
-            missing.push(k.clone());
+            let v = match meta {
+                Some(meta) => apply_input_spec(k, meta, v.clone())?,
+                None => v.clone(),
+            };
+            bound.insert(k.clone(), v);
+            continue;
+        }
+        if let Some(meta) = meta {
+            if let Some(default) = meta.get("default") {
+                bound.insert(k.clone(), apply_input_spec(k, meta, default.clone())?);
+                continue;
+            }
+            if meta.get("optional").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
         }
+        missing.push(k.clone());
     }
     if missing.is_empty() {
         return Ok(bound);
@@ -23,9 +232,13 @@ pub fn bind_vars_to_inputs(
 
     // 2) fallback 1<->1
     if grammar_inputs.len() == 1 && vars.len() == 1 {
-        let (gin, _) = grammar_inputs.iter().next().unwrap();
+        let (gin, spec) = grammar_inputs.iter().next().unwrap();
         let (_, v) = vars.iter().next().unwrap();
-        bound.insert(gin.clone(), v.clone());
+        let v = match spec.as_object() {
+            Some(meta) => apply_input_spec(gin, meta, v.clone())?,
+            None => v.clone(),
+        };
+        bound.insert(gin.clone(), v);
         return Ok(bound);
     }
 
@@ -63,4 +276,167 @@ mod tests {
         let b = bind_vars_to_inputs(&vars, &ins).unwrap();
         assert!(b.contains_key("raw_b64"));
     }
+
+    // ── per-input metadata: defaults and optional ────────────────
+
+    #[test]
+    fn default_fills_omitted_var() {
+        let vars = map(&[("raw_b64", json!("aGVsbG8="))]);
+        let ins = map(&[
+            ("raw_b64", json!("")),
+            ("encoding", json!({"default": "utf-8"})),
+        ]);
+        let b = bind_vars_to_inputs(&vars, &ins).unwrap();
+        assert_eq!(b["encoding"], json!("utf-8"));
+    }
+
+    #[test]
+    fn explicit_var_overrides_default() {
+        let vars = map(&[
+            ("raw_b64", json!("aGVsbG8=")),
+            ("encoding", json!("latin-1")),
+        ]);
+        let ins = map(&[
+            ("raw_b64", json!("")),
+            ("encoding", json!({"default": "utf-8"})),
+        ]);
+        let b = bind_vars_to_inputs(&vars, &ins).unwrap();
+        assert_eq!(b["encoding"], json!("latin-1"));
+    }
+
+    #[test]
+    fn optional_input_is_omitted_not_nulled() {
+        let vars = map(&[("raw_b64", json!("aGVsbG8="))]);
+        let ins = map(&[
+            ("raw_b64", json!("")),
+            ("note", json!({"optional": true})),
+        ]);
+        let b = bind_vars_to_inputs(&vars, &ins).unwrap();
+        assert!(!b.contains_key("note"), "absent, never null");
+        // Deterministic: two identical binds canonicalize identically
+        let b2 = bind_vars_to_inputs(&vars, &ins).unwrap();
+        let c1 = crate::canon::canonical_bytes(&serde_json::to_value(&b).unwrap()).unwrap();
+        let c2 = crate::canon::canonical_bytes(&serde_json::to_value(&b2).unwrap()).unwrap();
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn required_inputs_still_error_when_missing() {
+        let vars = map(&[("raw_b64", json!("aGVsbG8="))]);
+        let ins = map(&[
+            ("raw_b64", json!("")),
+            ("amount", json!({"type": "number"})),
+        ]);
+        let err = bind_vars_to_inputs(&vars, &ins).unwrap_err();
+        assert!(err.to_string().contains("amount"));
+    }
+
+    // ── typed inputs: validation and coercion ────────────────────
+
+    #[test]
+    fn int_accepts_numbers_and_coerces_numeric_strings() {
+        let ins = map(&[("amount", json!({"type": "int"}))]);
+        let b = bind_vars_to_inputs(&map(&[("amount", json!(42))]), &ins).unwrap();
+        assert_eq!(b["amount"], json!(42));
+        let b = bind_vars_to_inputs(&map(&[("amount", json!("42"))]), &ins).unwrap();
+        assert_eq!(b["amount"], json!(42));
+        let err = bind_vars_to_inputs(&map(&[("amount", json!("forty-two"))]), &ins).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("'amount'"), "{msg}");
+        assert!(msg.contains("expected int"), "{msg}");
+    }
+
+    #[test]
+    fn bool_coerces_only_exact_literals() {
+        let ins = map(&[("ghost", json!({"type": "bool"}))]);
+        let b = bind_vars_to_inputs(&map(&[("ghost", json!("true"))]), &ins).unwrap();
+        assert_eq!(b["ghost"], json!(true));
+        assert!(bind_vars_to_inputs(&map(&[("ghost", json!("yes"))]), &ins).is_err());
+    }
+
+    #[test]
+    fn bytes_requires_valid_base64() {
+        let ins = map(&[("raw_b64", json!({"type": "bytes"}))]);
+        assert!(bind_vars_to_inputs(&map(&[("raw_b64", json!("aGVsbG8="))]), &ins).is_ok());
+        let err =
+            bind_vars_to_inputs(&map(&[("raw_b64", json!("not base64!"))]), &ins).unwrap_err();
+        assert!(err.to_string().contains("base64"));
+    }
+
+    #[test]
+    fn max_len_names_input_and_rule() {
+        let ins = map(&[("note", json!({"type": "string", "max_len": 4}))]);
+        let err = bind_vars_to_inputs(&map(&[("note", json!("too long"))]), &ins).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("'note'"), "{msg}");
+        assert!(msg.contains("max_len 4"), "{msg}");
+    }
+
+    #[test]
+    fn defaults_are_validated_against_the_spec() {
+        let ins = map(&[
+            ("raw_b64", json!("")),
+            ("amount", json!({"type": "int", "default": "oops"})),
+        ]);
+        let err =
+            bind_vars_to_inputs(&map(&[("raw_b64", json!("aGVsbG8="))]), &ins).unwrap_err();
+        assert!(err.to_string().contains("'amount'"));
+    }
+
+    #[test]
+    fn unknown_declared_type_is_a_grammar_error() {
+        let ins = map(&[("x", json!({"type": "float"}))]);
+        let err = bind_vars_to_inputs(&map(&[("x", json!(1))]), &ins).unwrap_err();
+        assert!(err.to_string().contains("unknown type 'float'"));
+    }
+
+    // ── vars limits ──────────────────────────────────────────────
+
+    fn tight() -> VarsLimits {
+        VarsLimits {
+            max_depth: 3,
+            max_keys: 4,
+            max_string_len: 8,
+            max_canonical_bytes: 128,
+        }
+    }
+
+    #[test]
+    fn limits_pass_within_bounds() {
+        let vars = map(&[("a", json!({"b": "short"}))]);
+        assert!(enforce_vars_limits(&vars, &tight()).is_ok());
+    }
+
+    #[test]
+    fn limits_reject_deep_nesting_with_path() {
+        let vars = map(&[("a", json!({"b": {"c": {"d": 1}}}))]);
+        let err = enforce_vars_limits(&vars, &tight()).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("max_depth"), "{msg}");
+        assert!(msg.contains("$.a.b.c"), "path must be precise: {msg}");
+    }
+
+    #[test]
+    fn limits_reject_too_many_keys() {
+        let vars = map(&[("a", json!({"k1": 1, "k2": 2, "k3": 3, "k4": 4}))]);
+        let err = enforce_vars_limits(&vars, &tight()).unwrap_err();
+        assert!(err.to_string().contains("max_keys"));
+    }
+
+    #[test]
+    fn limits_reject_long_string_inside_array() {
+        let vars = map(&[("a", json!(["0123456789"]))]);
+        let err = enforce_vars_limits(&vars, &tight()).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("max_string_len"), "{msg}");
+        assert!(msg.contains("$.a[0]"), "{msg}");
+    }
+
+    #[test]
+    fn limits_reject_oversized_canonical_form() {
+        let elems: Vec<i64> = (0..64).collect();
+        let vars = map(&[("a", json!(elems))]);
+        let err = enforce_vars_limits(&vars, &tight()).unwrap_err();
+        assert!(err.to_string().contains("max_canonical_bytes"));
+    }
 }
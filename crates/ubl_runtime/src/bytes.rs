@@ -0,0 +1,99 @@
+//! Typed bytes in the var model.
+//!
+//! Vars are JSON and JSON has no binary type, so byte payloads travel as a
+//! single-key marker object `{"$bytes": "<base64>"}`. NRF encodes the marker
+//! as a native Bytes value, so canonical bytes carry the payload once instead
+//! of base64-inflated. Helpers here convert between the marker and raw bytes;
+//! host processes register a blob resolver so the `bytes.from_cid` codec can
+//! pull content out of the ledger during parse.
+
+use crate::error::{Result, RuntimeError};
+use base64::Engine;
+use serde_json::{json, Value};
+
+/// Marker key for typed bytes in JSON vars.
+pub const BYTES_KEY: &str = "$bytes";
+
+/// Wrap raw bytes in the `{"$bytes": "<base64>"}` marker.
+pub fn from_bytes(bytes: &[u8]) -> Value {
+    json!({ BYTES_KEY: base64::engine::general_purpose::STANDARD.encode(bytes) })
+}
+
+/// Extract raw bytes from a var value. Accepts the `{"$bytes": ...}` marker
+/// and `"0x<hex>"` strings (the JSON projection of NRF Bytes).
+pub fn as_bytes(v: &Value) -> Option<Vec<u8>> {
+    if let Some(obj) = v.as_object() {
+        if obj.len() == 1 {
+            if let Some(b64) = obj.get(BYTES_KEY).and_then(|b| b.as_str()) {
+                return base64::engine::general_purpose::STANDARD.decode(b64).ok();
+            }
+        }
+        return None;
+    }
+    if let Some(s) = v.as_str() {
+        if let Some(hex_str) = s.strip_prefix("0x") {
+            return hex::decode(hex_str).ok();
+        }
+    }
+    None
+}
+
+/// Byte length of a var value, for policy conditions. Typed bytes measure
+/// their decoded length; plain strings measure their UTF-8 length.
+pub fn byte_len(v: &Value) -> Option<usize> {
+    if let Some(bytes) = as_bytes(v) {
+        return Some(bytes.len());
+    }
+    v.as_str().map(|s| s.len())
+}
+
+/// Blob resolver: given a CID, fetch the blob bytes from the ledger.
+/// Registered once at startup by the host process (gate or CLI).
+type ResolveFn = dyn Fn(&str) -> Option<Vec<u8>> + Send + Sync;
+static BLOB_RESOLVER: std::sync::OnceLock<Box<ResolveFn>> = std::sync::OnceLock::new();
+
+pub fn set_blob_resolver(resolver: impl Fn(&str) -> Option<Vec<u8>> + Send + Sync + 'static) {
+    let _ = BLOB_RESOLVER.set(Box::new(resolver));
+}
+
+/// Fetch blob bytes for the `bytes.from_cid` codec, with distinct errors
+/// for a missing resolver vs. a missing blob.
+pub(crate) fn resolve_blob(cid: &str) -> Result<Vec<u8>> {
+    let resolver = BLOB_RESOLVER.get().ok_or_else(|| {
+        RuntimeError::Validation("bytes.from_cid: no blob resolver installed".into())
+    })?;
+    resolver(cid)
+        .ok_or_else(|| RuntimeError::Validation(format!("bytes.from_cid: blob not found: {cid}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_roundtrips() {
+        let v = from_bytes(&[0x00, 0xff, 0xde, 0xad]);
+        assert_eq!(v[BYTES_KEY], "AP/erQ==");
+        assert_eq!(as_bytes(&v).unwrap(), vec![0x00, 0xff, 0xde, 0xad]);
+    }
+
+    #[test]
+    fn hex_string_projection_accepted() {
+        assert_eq!(as_bytes(&json!("0xdead")).unwrap(), vec![0xde, 0xad]);
+        assert!(as_bytes(&json!("0xnothex")).is_none());
+        assert!(as_bytes(&json!("plain")).is_none());
+    }
+
+    #[test]
+    fn multi_key_object_is_not_bytes() {
+        assert!(as_bytes(&json!({"$bytes": "AA==", "extra": 1})).is_none());
+    }
+
+    #[test]
+    fn byte_len_measures_decoded_and_utf8() {
+        assert_eq!(byte_len(&from_bytes(&[1, 2, 3])), Some(3));
+        assert_eq!(byte_len(&json!("0xdeadbeef")), Some(4));
+        assert_eq!(byte_len(&json!("héllo")), Some(6));
+        assert_eq!(byte_len(&json!(42)), None);
+    }
+}
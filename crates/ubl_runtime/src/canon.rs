@@ -1,38 +1,98 @@
-use serde_json::{Map, Value};
-use unicode_normalization::UnicodeNormalization;
+use serde_json::Value;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
-fn normalize_value(v: &Value) -> Value {
+/// Write one canonical JSON string token (NFC-normalized, serde escaping)
+/// into `out`. ASCII and already-NFC strings skip the normalization
+/// allocation — for receipt bodies that is nearly every string.
+fn write_canonical_str(s: &str, out: &mut Vec<u8>) -> crate::error::Result<()> {
+    if s.is_ascii() || is_nfc(s) {
+        serde_json::to_writer(&mut *out, s)?;
+    } else {
+        let normalized: String = s.nfc().collect();
+        serde_json::to_writer(&mut *out, &normalized)?;
+    }
+    Ok(())
+}
+
+/// Single pass over the value tree, writing canonical bytes straight into
+/// `out`: sorted keys, object nulls dropped, NFC strings, floats rejected.
+/// No intermediate `Value` or `String` is built — the old two-pass
+/// normalize-then-serialize allocated a full copy of every receipt body,
+/// five-plus times per execute.
+fn write_canonical(v: &Value, out: &mut Vec<u8>) -> crate::error::Result<()> {
     match v {
-        Value::Null => Value::Null,
-        Value::Bool(b) => Value::Bool(*b),
+        Value::Null => out.extend_from_slice(b"null"),
+        Value::Bool(true) => out.extend_from_slice(b"true"),
+        Value::Bool(false) => out.extend_from_slice(b"false"),
         Value::Number(n) => {
             if n.is_f64() {
                 panic!("floating point not allowed");
             }
-            Value::Number(n.clone())
+            serde_json::to_writer(&mut *out, n)?;
+        }
+        Value::String(s) => write_canonical_str(s, out)?,
+        Value::Array(arr) => {
+            out.push(b'[');
+            for (i, item) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out)?;
+            }
+            out.push(b']');
         }
-        Value::String(s) => Value::String(s.nfc().collect::<String>()),
-        Value::Array(arr) => Value::Array(arr.iter().map(normalize_value).collect()),
         Value::Object(obj) => {
-            let mut out = Map::new();
-            let mut keys: Vec<_> = obj.keys().cloned().collect();
+            let mut keys: Vec<&String> = obj
+                .iter()
+                .filter(|(_, v)| **v != Value::Null)
+                .map(|(k, _)| k)
+                .collect();
             keys.sort();
-            for k in keys {
-                if let Some(v) = obj.get(&k) {
-                    if *v != Value::Null {
-                        out.insert(k, normalize_value(v));
-                    }
+            out.push(b'{');
+            for (i, k) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
                 }
+                serde_json::to_writer(&mut *out, k)?;
+                out.push(b':');
+                write_canonical(&obj[k], out)?;
             }
-            Value::Object(out)
+            out.push(b'}');
         }
     }
+    Ok(())
 }
 
+/// The canonicalization profile this runtime mints receipts with.
+/// Receipts embed the tag so a future canon (e.g. new number rules) can
+/// ship as "nrf1/v2" without breaking verification of existing chains.
+pub const PROFILE_NRF1_V1: &str = "nrf1/v1";
+
+/// Canonical bytes under the current profile (`nrf1/v1`): NFC strings,
+/// sorted keys, nulls dropped, floats rejected, compact JSON.
 pub fn canonical_bytes(v: &Value) -> crate::error::Result<Vec<u8>> {
-    let norm = normalize_value(v);
-    let s = serde_json::to_string(&norm)?;
-    Ok(s.into_bytes())
+    let mut out = Vec::with_capacity(128);
+    write_canonical(v, &mut out)?;
+    Ok(out)
+}
+
+/// Same bytes, written into a caller-owned buffer (cleared first) so hot
+/// paths hashing many bodies can reuse one allocation.
+pub fn canonical_bytes_into(v: &Value, out: &mut Vec<u8>) -> crate::error::Result<()> {
+    out.clear();
+    write_canonical(v, out)
+}
+
+/// Canonical bytes under an explicit profile tag. Unknown tags are a
+/// validation error so receipts minted by a newer canon never verify
+/// against the wrong byte form.
+pub fn canonical_bytes_for(profile: &str, v: &Value) -> crate::error::Result<Vec<u8>> {
+    match profile {
+        PROFILE_NRF1_V1 => canonical_bytes(v),
+        other => Err(crate::error::RuntimeError::Canon(format!(
+            "unknown canon profile '{other}'"
+        ))),
+    }
 }
 
 #[cfg(test)]
@@ -46,4 +106,80 @@ mod tests {
         let b = json!({"a":1,"b":"x"});
         assert_eq!(canonical_bytes(&a).unwrap(), canonical_bytes(&b).unwrap());
     }
+
+    #[test]
+    fn profile_v1_matches_current_canon() {
+        let v = json!({"k": "v"});
+        assert_eq!(
+            canonical_bytes_for(PROFILE_NRF1_V1, &v).unwrap(),
+            canonical_bytes(&v).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_profile_is_rejected() {
+        let err = canonical_bytes_for("nrf1/v9", &json!({})).unwrap_err();
+        assert!(err.to_string().contains("unknown canon profile"));
+    }
+
+    /// The two-pass normalize-then-serialize this module used before the
+    /// single-pass writer. Kept as a test oracle: the rewrite is only
+    /// valid while it is byte-for-byte invisible.
+    fn reference_bytes(v: &Value) -> Vec<u8> {
+        fn normalize(v: &Value) -> Value {
+            match v {
+                Value::String(s) => Value::String(s.nfc().collect::<String>()),
+                Value::Array(arr) => Value::Array(arr.iter().map(normalize).collect()),
+                Value::Object(obj) => {
+                    let mut out = serde_json::Map::new();
+                    let mut keys: Vec<_> = obj.keys().cloned().collect();
+                    keys.sort();
+                    for k in keys {
+                        if let Some(v) = obj.get(&k) {
+                            if *v != Value::Null {
+                                out.insert(k, normalize(v));
+                            }
+                        }
+                    }
+                    Value::Object(out)
+                }
+                other => other.clone(),
+            }
+        }
+        serde_json::to_string(&normalize(v)).unwrap().into_bytes()
+    }
+
+    #[test]
+    fn single_pass_matches_reference_bytes() {
+        for v in [
+            json!(null),
+            json!(true),
+            json!(-42),
+            json!("plain ascii"),
+            json!("caf\u{0065}\u{0301}"), // NFD é → NFC under canon
+            json!("tab\there\nand \"quotes\" and \u{0007}"),
+            json!([1, null, "x", [], {}]),
+            json!({"z": 1, "a": {"inner": null, "kept": [null]}, "m": "é"}),
+        ] {
+            assert_eq!(
+                canonical_bytes(&v).unwrap(),
+                reference_bytes(&v),
+                "divergence on {v}"
+            );
+        }
+    }
+
+    #[test]
+    fn buffer_reuse_produces_same_bytes() {
+        let mut buf = Vec::new();
+        canonical_bytes_into(&json!({"first": 1}), &mut buf).unwrap();
+        canonical_bytes_into(&json!({"b": 2, "a": [1, 2]}), &mut buf).unwrap();
+        assert_eq!(buf, canonical_bytes(&json!({"a": [1, 2], "b": 2})).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "floating point not allowed")]
+    fn floats_still_panic() {
+        let _ = canonical_bytes(&json!(1.5));
+    }
 }
@@ -0,0 +1,214 @@
+//! Pluggable mapping codecs.
+//!
+//! The engine ships two built-in codecs (`base64.decode`, `bytes.from_cid`);
+//! downstream products add proprietary ones — an EDIFACT parser, say —
+//! by registering a [`Codec`] in the [`CodecRegistry`] carried on
+//! [`ExecuteConfig`](crate::engine::ExecuteConfig). Plugins must declare
+//! themselves deterministic (the registry refuses anything else, because
+//! receipts have to replay byte-for-byte) and carry a version string; every
+//! plugin a manifest touches is pinned as `name@version` in the transition
+//! receipt's witness. A mapping that names a codec nobody registered fails
+//! exactly like any other unknown codec: the run ends in a DENY receipt,
+//! never a crash.
+
+use crate::engine::Manifest;
+use crate::error::{Result, RuntimeError};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Codec names the engine implements itself; plugins cannot shadow them,
+/// or the same manifest would mean different things on different hosts.
+const BUILTIN_CODECS: &[&str] = &["base64.decode", "bytes.from_cid"];
+
+/// One deterministic value-to-value transform, addressable from a grammar
+/// mapping by [`name`](Codec::name).
+pub trait Codec: Send + Sync {
+    /// Mapping-facing name, e.g. `"edifact.parse"`.
+    fn name(&self) -> &str;
+    /// Implementation version pinned into the transition witness, e.g.
+    /// `"1.2.0"`. Bump it whenever output bytes can change.
+    fn version(&self) -> &str;
+    /// Whether the same input always yields the same output. Only
+    /// deterministic codecs may register — replay depends on it.
+    fn deterministic(&self) -> bool;
+    /// Apply the transform. Errors surface as a DENY receipt with the
+    /// message as its reason, so name the input and the rule it broke.
+    fn apply(&self, input: &Value) -> Result<Value>;
+}
+
+/// The set of plugin codecs available to one execution.
+#[derive(Clone, Default)]
+pub struct CodecRegistry {
+    codecs: BTreeMap<String, Arc<dyn Codec>>,
+}
+
+impl std::fmt::Debug for CodecRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CodecRegistry")
+            .field(&self.codecs.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl CodecRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a plugin codec. Rejects non-deterministic codecs, names
+    /// that shadow a built-in, and duplicate registrations — all config
+    /// errors the host should hit at startup, not mid-run.
+    pub fn register(&mut self, codec: Arc<dyn Codec>) -> Result<()> {
+        let name = codec.name().to_string();
+        if !codec.deterministic() {
+            return Err(RuntimeError::Codec {
+                name,
+                detail: "declares itself non-deterministic; receipts must replay".into(),
+            });
+        }
+        if BUILTIN_CODECS.contains(&name.as_str()) {
+            return Err(RuntimeError::Codec {
+                name,
+                detail: "shadows a built-in codec".into(),
+            });
+        }
+        if self.codecs.contains_key(&name) {
+            return Err(RuntimeError::Codec {
+                name,
+                detail: "already registered".into(),
+            });
+        }
+        self.codecs.insert(name, codec);
+        Ok(())
+    }
+
+    /// Look up a plugin codec by mapping name.
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn Codec>> {
+        self.codecs.get(name)
+    }
+
+    /// `name@version` pins for every registered codec the manifest's
+    /// grammars reference, sorted and deduplicated. Empty for manifests
+    /// that only use built-ins, so their transition witnesses — and
+    /// therefore their CIDs — are unchanged from before plugins existed.
+    pub fn versions_for(&self, manifest: &Manifest) -> Vec<String> {
+        let mut pins: Vec<String> = manifest
+            .in_grammar
+            .mappings
+            .iter()
+            .chain(manifest.out_grammar.mappings.iter())
+            .filter_map(|m| self.codecs.get(&m.codec))
+            .map(|c| format!("{}@{}", c.name(), c.version()))
+            .collect();
+        pins.sort();
+        pins.dedup();
+        pins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Upper {
+        deterministic: bool,
+    }
+
+    impl Codec for Upper {
+        fn name(&self) -> &str {
+            "text.upper"
+        }
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+        fn deterministic(&self) -> bool {
+            self.deterministic
+        }
+        fn apply(&self, input: &Value) -> Result<Value> {
+            let s = input.as_str().ok_or_else(|| RuntimeError::Codec {
+                name: "text.upper".into(),
+                detail: "expected string".into(),
+            })?;
+            Ok(Value::String(s.to_uppercase()))
+        }
+    }
+
+    struct Named(&'static str);
+
+    impl Codec for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+        fn version(&self) -> &str {
+            "0.0.1"
+        }
+        fn deterministic(&self) -> bool {
+            true
+        }
+        fn apply(&self, input: &Value) -> Result<Value> {
+            Ok(input.clone())
+        }
+    }
+
+    #[test]
+    fn register_and_dispatch() {
+        let mut reg = CodecRegistry::new();
+        reg.register(Arc::new(Upper { deterministic: true })).unwrap();
+        let out = reg.get("text.upper").unwrap().apply(&json!("hi")).unwrap();
+        assert_eq!(out, json!("HI"));
+        assert!(reg.get("text.lower").is_none());
+    }
+
+    #[test]
+    fn non_deterministic_codecs_refused() {
+        let mut reg = CodecRegistry::new();
+        let err = reg
+            .register(Arc::new(Upper { deterministic: false }))
+            .unwrap_err();
+        assert!(err.to_string().contains("non-deterministic"), "got: {err}");
+    }
+
+    #[test]
+    fn builtins_cannot_be_shadowed() {
+        let mut reg = CodecRegistry::new();
+        let err = reg.register(Arc::new(Named("base64.decode"))).unwrap_err();
+        assert!(err.to_string().contains("shadows a built-in"), "got: {err}");
+    }
+
+    #[test]
+    fn duplicate_registration_refused() {
+        let mut reg = CodecRegistry::new();
+        reg.register(Arc::new(Named("x.y"))).unwrap();
+        let err = reg.register(Arc::new(Named("x.y"))).unwrap_err();
+        assert!(err.to_string().contains("already registered"), "got: {err}");
+    }
+
+    #[test]
+    fn versions_pin_only_referenced_codecs() {
+        use crate::engine::{Grammar, Mapping, Policy};
+        let mut reg = CodecRegistry::new();
+        reg.register(Arc::new(Upper { deterministic: true })).unwrap();
+        reg.register(Arc::new(Named("unused.codec"))).unwrap();
+        let manifest = Manifest {
+            pipeline: "t".into(),
+            in_grammar: Grammar {
+                inputs: BTreeMap::new(),
+                mappings: vec![Mapping {
+                    from: "a".into(),
+                    codec: "text.upper".into(),
+                    to: "b".into(),
+                }],
+                output_from: "b".into(),
+            },
+            out_grammar: Grammar {
+                inputs: BTreeMap::new(),
+                mappings: vec![],
+                output_from: "x".into(),
+            },
+            policy: Policy { allow: true, rules: vec![] },
+        };
+        assert_eq!(reg.versions_for(&manifest), vec!["text.upper@1.0.0"]);
+    }
+}
@@ -24,6 +24,11 @@ pub struct Mapping {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Policy {
     pub allow: bool,
+    /// Optional cascade rules carried by the manifest. Serialization skips
+    /// the empty case so legacy manifests keep their canonical bytes (and
+    /// therefore their CIDs).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<crate::policy::PolicyRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +42,11 @@ pub struct Manifest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecuteConfig {
     pub version: String,
+    /// Plugin codecs available to grammar mappings alongside the
+    /// built-ins. Serialization skips it — which codecs were in play is
+    /// recorded in the transition witness, not in config dumps.
+    #[serde(skip)]
+    pub codecs: crate::codec::CodecRegistry,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +57,56 @@ pub struct ExecuteResult {
     /// Policy trace from cascade evaluation (empty for legacy mode).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub policy_trace: Vec<crate::policy::PolicyTraceEntry>,
+    /// Wall-clock phase breakdown; see [`PhaseTimings`].
+    #[serde(default, skip_serializing_if = "PhaseTimings::is_zero")]
+    pub timings: PhaseTimings,
+}
+
+/// Wall-clock breakdown of one execution, in microseconds per phase.
+///
+/// The engine fills `bind`, `mappings` and `canon`; the receipt pipeline
+/// adds `policy` and `sign`; `persist` is the caller's (the gate times its
+/// store writes). Timings ride outside receipt bodies so they never affect
+/// CIDs.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    pub bind_micros: u64,
+    pub mappings_micros: u64,
+    pub policy_micros: u64,
+    pub canon_micros: u64,
+    pub sign_micros: u64,
+    pub persist_micros: u64,
+}
+
+impl PhaseTimings {
+    /// True when nothing was recorded; lets serialization skip the field
+    /// so pre-existing result shapes are unchanged.
+    pub fn is_zero(&self) -> bool {
+        let Self {
+            bind_micros,
+            mappings_micros,
+            policy_micros,
+            canon_micros,
+            sign_micros,
+            persist_micros,
+        } = self;
+        *bind_micros == 0
+            && *mappings_micros == 0
+            && *policy_micros == 0
+            && *canon_micros == 0
+            && *sign_micros == 0
+            && *persist_micros == 0
+    }
+
+    /// Add another breakdown into this one, phase by phase.
+    pub fn merge(&mut self, other: &PhaseTimings) {
+        self.bind_micros += other.bind_micros;
+        self.mappings_micros += other.mappings_micros;
+        self.policy_micros += other.policy_micros;
+        self.canon_micros += other.canon_micros;
+        self.sign_micros += other.sign_micros;
+        self.persist_micros += other.persist_micros;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,7 +115,11 @@ pub struct Artifacts {
     pub sub_receipts: Vec<Value>,
 }
 
-fn apply_mappings(ctx: &mut BTreeMap<String, Value>, maps: &[Mapping]) -> Result<()> {
+fn apply_mappings(
+    ctx: &mut BTreeMap<String, Value>,
+    maps: &[Mapping],
+    codecs: &crate::codec::CodecRegistry,
+) -> Result<()> {
     for m in maps {
         let src = ctx.get(&m.from).ok_or_else(|| {
             RuntimeError::Validation(format!("mapping: key '{}' not found", m.from))
@@ -68,15 +132,30 @@ fn apply_mappings(ctx: &mut BTreeMap<String, Value>, maps: &[Mapping]) -> Result
                     .ok_or_else(|| RuntimeError::Validation("expected string".into()))?;
                 let bytes = base64::engine::general_purpose::STANDARD
                     .decode(s)
-                    .map_err(|_| RuntimeError::Validation("base64".into()))?;
+                    .map_err(|e| RuntimeError::Codec {
+                        name: "base64".into(),
+                        detail: e.to_string(),
+                    })?;
                 Value::String(String::from_utf8_lossy(&bytes).to_string())
             }
-            _ => {
-                return Err(RuntimeError::Validation(format!(
-                    "unknown codec: {}",
-                    m.codec
-                )))
+            "bytes.from_cid" => {
+                let cid = src.as_str().ok_or_else(|| {
+                    RuntimeError::Validation("bytes.from_cid: expected CID string".into())
+                })?;
+                crate::bytes::from_bytes(&crate::bytes::resolve_blob(cid)?)
             }
+            // Plugin codecs, then the existing rejection: a codec nobody
+            // registered fails the run like any other execute error (DENY
+            // receipt downstream), it never panics or falls through.
+            name => match codecs.get(name) {
+                Some(codec) => codec.apply(src)?,
+                None => {
+                    return Err(RuntimeError::Validation(format!(
+                        "unknown codec: {}",
+                        m.codec
+                    )))
+                }
+            },
         };
         ctx.insert(m.to.clone(), val);
     }
@@ -86,15 +165,43 @@ fn apply_mappings(ctx: &mut BTreeMap<String, Value>, maps: &[Mapping]) -> Result
 pub fn execute(
     manifest: &Manifest,
     vars: &BTreeMap<String, Value>,
-    _cfg: &ExecuteConfig,
+    cfg: &ExecuteConfig,
 ) -> Result<ExecuteResult> {
-    // parse
+    execute_with_ctx(manifest, vars, cfg, None)
+}
+
+/// Like [`execute`], with a gate-injected execution context.
+///
+/// The context is visible to `ctx.*` policy conditions but stays out of the
+/// bound inputs — and so out of every derived CID — unless the manifest
+/// explicitly declares a `__ctx` input in its in-grammar.
+pub fn execute_with_ctx(
+    manifest: &Manifest,
+    vars: &BTreeMap<String, Value>,
+    cfg: &ExecuteConfig,
+    exec_ctx: Option<&Value>,
+) -> Result<ExecuteResult> {
+    let mut timings = PhaseTimings::default();
+    // parse — bind `__ctx` only when the manifest opts in
+    let mut ctx_vars;
+    let bind_source = match exec_ctx {
+        Some(c) if manifest.in_grammar.inputs.contains_key("__ctx") => {
+            ctx_vars = vars.clone();
+            ctx_vars.insert("__ctx".into(), c.clone());
+            &ctx_vars
+        }
+        _ => vars,
+    };
     let mut ctx: BTreeMap<String, Value> = BTreeMap::new();
-    let bound = bind_vars_to_inputs(vars, &manifest.in_grammar.inputs)?;
+    let phase = std::time::Instant::now();
+    let bound = bind_vars_to_inputs(bind_source, &manifest.in_grammar.inputs)?;
+    timings.bind_micros += phase.elapsed().as_micros() as u64;
     for (k, v) in bound {
         ctx.insert(k, v);
     }
-    apply_mappings(&mut ctx, &manifest.in_grammar.mappings)?;
+    let phase = std::time::Instant::now();
+    apply_mappings(&mut ctx, &manifest.in_grammar.mappings, &cfg.codecs)?;
+    timings.mappings_micros += phase.elapsed().as_micros() as u64;
     let parse_out = ctx
         .get(&manifest.in_grammar.output_from)
         .ok_or_else(|| {
@@ -106,11 +213,10 @@ pub fn execute(
         .clone();
 
     // policy — evaluate via cascade resolver for backward compat
-    let cascade = crate::policy::CascadePolicy {
-        allow: manifest.policy.allow,
-        rules: vec![],
-    };
-    let policy_result = crate::policy::resolve(&cascade, vars, None);
+    let phase = std::time::Instant::now();
+    let cascade = cascade_for(manifest);
+    let policy_result = crate::policy::resolve_with_ctx(&cascade, vars, None, exec_ctx);
+    timings.policy_micros += phase.elapsed().as_micros() as u64;
     if policy_result.decision == "DENY" {
         return Err(RuntimeError::PolicyDeny(
             policy_result.reason.unwrap_or_else(|| "policy deny".into()),
@@ -121,11 +227,15 @@ pub fn execute(
     // render: feed only previous stage output via 1<->1 to grammar input
     let mut render_vars = BTreeMap::new();
     render_vars.insert("__prev_output__".into(), parse_out.clone());
+    let phase = std::time::Instant::now();
     let bound = bind_vars_to_inputs(&render_vars, &manifest.out_grammar.inputs)?;
+    timings.bind_micros += phase.elapsed().as_micros() as u64;
     for (k, v) in bound {
         ctx.insert(k, v);
     }
-    apply_mappings(&mut ctx, &manifest.out_grammar.mappings)?;
+    let phase = std::time::Instant::now();
+    apply_mappings(&mut ctx, &manifest.out_grammar.mappings, &cfg.codecs)?;
+    timings.mappings_micros += phase.elapsed().as_micros() as u64;
     let final_out = ctx
         .get(&manifest.out_grammar.output_from)
         .ok_or_else(|| {
@@ -137,8 +247,10 @@ pub fn execute(
         .clone();
 
     // canonicalize and hash for CID
+    let phase = std::time::Instant::now();
     let bytes = crate::canon::canonical_bytes(&final_out)?;
     let cid = cid_b3(&bytes);
+    timings.canon_micros += phase.elapsed().as_micros() as u64;
 
     Ok(ExecuteResult {
         artifacts: Artifacts {
@@ -148,24 +260,39 @@ pub fn execute(
         dimension_stack: vec!["parse".into(), "policy".into(), "render".into()],
         cid,
         policy_trace,
+        timings,
     })
 }
 
+/// The cascade a manifest implies: the allow/deny gate plus any rules the
+/// manifest carries (legacy manifests carry none).
+pub fn cascade_for(manifest: &Manifest) -> crate::policy::CascadePolicy {
+    crate::policy::CascadePolicy {
+        allow: manifest.policy.allow,
+        rules: manifest.policy.rules.clone(),
+    }
+}
+
 /// Execute with a full cascade policy (rules + trace).
 pub fn execute_with_cascade(
     manifest: &Manifest,
     vars: &BTreeMap<String, Value>,
-    _cfg: &ExecuteConfig,
+    cfg: &ExecuteConfig,
     cascade: &crate::policy::CascadePolicy,
     body_size: Option<usize>,
 ) -> Result<ExecuteResult> {
+    let mut timings = PhaseTimings::default();
     // parse
     let mut ctx: BTreeMap<String, Value> = BTreeMap::new();
+    let phase = std::time::Instant::now();
     let bound = bind_vars_to_inputs(vars, &manifest.in_grammar.inputs)?;
+    timings.bind_micros += phase.elapsed().as_micros() as u64;
     for (k, v) in bound {
         ctx.insert(k, v);
     }
-    apply_mappings(&mut ctx, &manifest.in_grammar.mappings)?;
+    let phase = std::time::Instant::now();
+    apply_mappings(&mut ctx, &manifest.in_grammar.mappings, &cfg.codecs)?;
+    timings.mappings_micros += phase.elapsed().as_micros() as u64;
     let parse_out = ctx
         .get(&manifest.in_grammar.output_from)
         .ok_or_else(|| {
@@ -177,7 +304,9 @@ pub fn execute_with_cascade(
         .clone();
 
     // policy — full cascade evaluation
+    let phase = std::time::Instant::now();
     let policy_result = crate::policy::resolve(cascade, vars, body_size);
+    timings.policy_micros += phase.elapsed().as_micros() as u64;
     if policy_result.decision == "DENY" {
         return Err(RuntimeError::PolicyDeny(
             policy_result.reason.unwrap_or_else(|| "policy deny".into()),
@@ -188,11 +317,15 @@ pub fn execute_with_cascade(
     // render
     let mut render_vars = BTreeMap::new();
     render_vars.insert("__prev_output__".into(), parse_out.clone());
+    let phase = std::time::Instant::now();
     let bound = bind_vars_to_inputs(&render_vars, &manifest.out_grammar.inputs)?;
+    timings.bind_micros += phase.elapsed().as_micros() as u64;
     for (k, v) in bound {
         ctx.insert(k, v);
     }
-    apply_mappings(&mut ctx, &manifest.out_grammar.mappings)?;
+    let phase = std::time::Instant::now();
+    apply_mappings(&mut ctx, &manifest.out_grammar.mappings, &cfg.codecs)?;
+    timings.mappings_micros += phase.elapsed().as_micros() as u64;
     let final_out = ctx
         .get(&manifest.out_grammar.output_from)
         .ok_or_else(|| {
@@ -203,8 +336,10 @@ pub fn execute_with_cascade(
         })?
         .clone();
 
+    let phase = std::time::Instant::now();
     let bytes = crate::canon::canonical_bytes(&final_out)?;
     let cid = cid_b3(&bytes);
+    timings.canon_micros += phase.elapsed().as_micros() as u64;
 
     Ok(ExecuteResult {
         artifacts: Artifacts {
@@ -214,6 +349,7 @@ pub fn execute_with_cascade(
         dimension_stack: vec!["parse".into(), "policy".into(), "render".into()],
         cid,
         policy_trace,
+        timings,
     })
 }
 
@@ -225,6 +361,7 @@ mod tests {
     fn cfg() -> ExecuteConfig {
         ExecuteConfig {
             version: "0.1.0".into(),
+            codecs: Default::default(),
         }
     }
 
@@ -248,7 +385,7 @@ mod tests {
             pipeline: "hello".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8="))]);
         (man, vars)
@@ -335,7 +472,7 @@ mod tests {
             pipeline: "t".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("a".into(), json!("ok"))]);
         let err = execute(&m, &vars, &cfg()).unwrap_err();
@@ -369,7 +506,7 @@ mod tests {
             pipeline: "t".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("x".into(), json!("data"))]);
         let err = execute(&m, &vars, &cfg()).unwrap_err();
@@ -396,13 +533,138 @@ mod tests {
             pipeline: "t".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("raw_b64".into(), json!("!!!not-base64!!!"))]);
         let err = execute(&m, &vars, &cfg()).unwrap_err();
         assert!(err.to_string().contains("base64"), "got: {err}");
     }
 
+    // ── Plugin codecs ───────────────────────────────────────────
+
+    struct Upper;
+
+    impl crate::codec::Codec for Upper {
+        fn name(&self) -> &str {
+            "text.upper"
+        }
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+        fn deterministic(&self) -> bool {
+            true
+        }
+        fn apply(&self, input: &Value) -> Result<Value> {
+            let s = input
+                .as_str()
+                .ok_or_else(|| RuntimeError::Validation("text.upper: expected string".into()))?;
+            Ok(Value::String(s.to_uppercase()))
+        }
+    }
+
+    #[test]
+    fn plugin_codec_dispatches_from_mappings() {
+        let in_g = Grammar {
+            inputs: BTreeMap::from([("msg".into(), json!(""))]),
+            mappings: vec![Mapping {
+                from: "msg".into(),
+                codec: "text.upper".into(),
+                to: "loud".into(),
+            }],
+            output_from: "loud".into(),
+        };
+        let out_g = Grammar {
+            inputs: BTreeMap::from([("content".into(), json!(""))]),
+            mappings: vec![],
+            output_from: "content".into(),
+        };
+        let m = Manifest {
+            pipeline: "t".into(),
+            in_grammar: in_g,
+            out_grammar: out_g,
+            policy: Policy { allow: true, rules: vec![] },
+        };
+        let vars = BTreeMap::from([("msg".into(), json!("hello"))]);
+
+        // Without the registry the same manifest is rejected like any
+        // other unknown codec
+        let err = execute(&m, &vars, &cfg()).unwrap_err();
+        assert!(err.to_string().contains("unknown codec"), "got: {err}");
+
+        let mut cfg = cfg();
+        cfg.codecs
+            .register(std::sync::Arc::new(Upper))
+            .unwrap();
+        let r = execute(&m, &vars, &cfg).unwrap();
+        assert_eq!(r.artifacts.output, json!("HELLO"));
+    }
+
+    // ── Typed bytes (bytes.from_cid) ────────────────────────────
+
+    /// Ledger stand-in for the blob resolver. The hook is a process-wide
+    /// OnceLock, so every test shares this one registration.
+    static BLOBS: std::sync::Mutex<std::collections::BTreeMap<String, Vec<u8>>> =
+        std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+    fn install_test_resolver() {
+        crate::bytes::set_blob_resolver(|cid| BLOBS.lock().unwrap().get(cid).cloned());
+    }
+
+    fn bytes_manifest() -> Manifest {
+        let in_g = Grammar {
+            inputs: BTreeMap::from([("blob_cid".into(), json!(""))]),
+            mappings: vec![Mapping {
+                from: "blob_cid".into(),
+                codec: "bytes.from_cid".into(),
+                to: "raw".into(),
+            }],
+            output_from: "raw".into(),
+        };
+        let out_g = Grammar {
+            inputs: BTreeMap::from([("content".into(), json!(""))]),
+            mappings: vec![],
+            output_from: "content".into(),
+        };
+        Manifest {
+            pipeline: "blob".into(),
+            in_grammar: in_g,
+            out_grammar: out_g,
+            policy: Policy { allow: true, rules: vec![] },
+        }
+    }
+
+    #[test]
+    fn bytes_from_cid_pulls_blob_content() {
+        install_test_resolver();
+        BLOBS
+            .lock()
+            .unwrap()
+            .insert("b3:feedbeef".into(), vec![0x01, 0x02, 0x03]);
+        let vars = BTreeMap::from([("blob_cid".into(), json!("b3:feedbeef"))]);
+        let r = execute(&bytes_manifest(), &vars, &cfg()).unwrap();
+        assert_eq!(
+            r.artifacts.output,
+            crate::bytes::from_bytes(&[0x01, 0x02, 0x03]),
+            "output must be the typed bytes marker"
+        );
+    }
+
+    #[test]
+    fn bytes_from_cid_missing_blob_rejected() {
+        install_test_resolver();
+        let vars = BTreeMap::from([("blob_cid".into(), json!("b3:absent"))]);
+        let err = execute(&bytes_manifest(), &vars, &cfg()).unwrap_err();
+        assert!(err.to_string().contains("blob not found"), "got: {err}");
+    }
+
+    #[test]
+    fn bytes_from_cid_requires_string_cid() {
+        install_test_resolver();
+        let vars = BTreeMap::from([("blob_cid".into(), json!(42))]);
+        let err = execute(&bytes_manifest(), &vars, &cfg()).unwrap_err();
+        assert!(err.to_string().contains("expected CID string"), "got: {err}");
+    }
+
     // ── Dimension stack ─────────────────────────────────────────
 
     #[test]
@@ -411,4 +673,38 @@ mod tests {
         let r = execute(&m, &v, &cfg()).unwrap();
         assert_eq!(r.dimension_stack, vec!["parse", "policy", "render"]);
     }
+
+    // ── Phase timings ───────────────────────────────────────────
+
+    #[test]
+    fn phase_timings_merge_adds_per_phase() {
+        let mut a = PhaseTimings {
+            bind_micros: 1,
+            canon_micros: 10,
+            ..Default::default()
+        };
+        a.merge(&PhaseTimings {
+            bind_micros: 2,
+            sign_micros: 5,
+            ..Default::default()
+        });
+        assert_eq!(a.bind_micros, 3);
+        assert_eq!(a.canon_micros, 10);
+        assert_eq!(a.sign_micros, 5);
+        assert!(!a.is_zero());
+        assert!(PhaseTimings::default().is_zero());
+    }
+
+    #[test]
+    fn zero_timings_stay_out_of_serialized_results() {
+        // Results recorded before timings existed must round-trip, and a
+        // zeroed breakdown must not show up in new serializations.
+        let (m, v) = sample_passthrough();
+        let mut r = execute(&m, &v, &cfg()).unwrap();
+        r.timings = PhaseTimings::default();
+        let json = serde_json::to_value(&r).unwrap();
+        assert!(json.get("timings").is_none());
+        let back: ExecuteResult = serde_json::from_value(json).unwrap();
+        assert!(back.timings.is_zero());
+    }
 }
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,12 +10,129 @@ pub enum RuntimeError {
         missing: Vec<String>,
         available: Vec<String>,
     },
+    #[error("vars limit exceeded: {0}")]
+    VarsLimit(String),
     #[error("policy deny: {0}")]
     PolicyDeny(String),
+    #[error("duplicate request (replay): pipeline={pipeline} inputs_cid={inputs_cid}")]
+    Replay { pipeline: String, inputs_cid: String },
+    #[error("canon: {0}")]
+    Canon(String),
+    #[error("codec '{name}': {detail}")]
+    Codec { name: String, detail: String },
+    #[error("fuel exhausted: limit {limit}")]
+    FuelExhausted { limit: u64 },
+    #[error("signing: {0}")]
+    Signing(String),
     #[error("engine: {0}")]
     Engine(String),
     #[error("serde-json: {0}")]
     Json(#[from] serde_json::Error),
 }
 
+/// Machine-readable error codes, stable across releases. The gate maps
+/// these to HTTP statuses deterministically and embeds them in DENY
+/// receipt reasons, so SDKs match on codes instead of error prose.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Same pipeline + inputs already executed (idempotency hit).
+    Replay,
+    /// A policy rule denied the transition.
+    PolicyDeny { rule: String },
+    /// A manifest binding could not be satisfied.
+    Binding { key: String },
+    /// An encoding/decoding step failed (json, base64, tlv, ...).
+    Codec { name: String },
+    /// Canonicalization failed or the canon profile is unknown.
+    Canon,
+    /// The fuel budget ran out before the chip completed.
+    FuelExhausted { limit: u64 },
+    /// Signature or threshold-proof requirements not met.
+    Signing,
+    /// Schema or input validation failed.
+    Validation,
+    /// Engine-level execution failure.
+    Engine,
+}
+
+impl RuntimeError {
+    /// The stable code for this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            RuntimeError::Replay { .. } => ErrorCode::Replay,
+            RuntimeError::PolicyDeny(rule) => ErrorCode::PolicyDeny { rule: rule.clone() },
+            RuntimeError::Binding { missing, .. } => ErrorCode::Binding {
+                key: missing.first().cloned().unwrap_or_default(),
+            },
+            RuntimeError::Codec { name, .. } => ErrorCode::Codec { name: name.clone() },
+            RuntimeError::Json(_) => ErrorCode::Codec {
+                name: "json".into(),
+            },
+            RuntimeError::Canon(_) => ErrorCode::Canon,
+            RuntimeError::FuelExhausted { limit } => ErrorCode::FuelExhausted { limit: *limit },
+            RuntimeError::Signing(_) => ErrorCode::Signing,
+            RuntimeError::Validation(_) | RuntimeError::VarsLimit(_) => ErrorCode::Validation,
+            RuntimeError::Engine(_) => ErrorCode::Engine,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, RuntimeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_machine_readable_json() {
+        let replay = RuntimeError::Replay {
+            pipeline: "p".into(),
+            inputs_cid: "b3:x".into(),
+        };
+        let v = serde_json::to_value(replay.code()).unwrap();
+        assert_eq!(v, serde_json::json!({"code": "replay"}));
+
+        let deny = RuntimeError::PolicyDeny("amount-cap".into());
+        let v = serde_json::to_value(deny.code()).unwrap();
+        assert_eq!(v, serde_json::json!({"code": "policy_deny", "rule": "amount-cap"}));
+    }
+
+    #[test]
+    fn codes_cover_structured_variants() {
+        let binding = RuntimeError::Binding {
+            missing: vec!["amount".into()],
+            available: vec![],
+        };
+        assert_eq!(
+            binding.code(),
+            ErrorCode::Binding {
+                key: "amount".into()
+            }
+        );
+        let json_err = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        assert_eq!(
+            RuntimeError::Json(json_err).code(),
+            ErrorCode::Codec {
+                name: "json".into()
+            }
+        );
+        assert_eq!(RuntimeError::Canon("x".into()).code(), ErrorCode::Canon);
+        assert_eq!(RuntimeError::Signing("x".into()).code(), ErrorCode::Signing);
+        let fuel = RuntimeError::FuelExhausted { limit: 50_000 };
+        assert_eq!(fuel.code(), ErrorCode::FuelExhausted { limit: 50_000 });
+        assert_eq!(
+            serde_json::to_value(fuel.code()).unwrap(),
+            serde_json::json!({"code": "fuel_exhausted", "limit": 50_000})
+        );
+    }
+
+    #[test]
+    fn replay_display_keeps_legacy_text() {
+        let err = RuntimeError::Replay {
+            pipeline: "pix".into(),
+            inputs_cid: "b3:abc".into(),
+        };
+        assert!(err.to_string().contains("duplicate request (replay)"));
+    }
+}
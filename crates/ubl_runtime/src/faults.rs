@@ -0,0 +1,96 @@
+//! Fault injection for chaos testing — env-gated, zero-cost when off.
+//!
+//! `UBL_FAULT_INJECT="sign=1.0,canon=0.5,ledger=1.0"` makes the named
+//! points fail at the given rates so tests can prove the "never 500,
+//! always a receipt" contract under failure. Rates are applied
+//! deterministically (every call is counted; a rate of 0.5 fails every
+//! second call), so chaos runs are reproducible. Production deployments
+//! never set the variable and pay one atomic load per check.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+
+/// Injection points the runtime and ledger consult.
+pub const POINT_SIGN: &str = "sign";
+pub const POINT_CANON: &str = "canon";
+pub const POINT_LEDGER: &str = "ledger";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ENV_INIT: Once = Once::new();
+static CONFIG: Mutex<Option<HashMap<String, PointState>>> = Mutex::new(None);
+
+struct PointState {
+    rate: f64,
+    calls: u64,
+}
+
+/// Parse and install a fault spec ("point=rate,point=rate"). Unknown
+/// points are accepted — the check site decides what it asks about.
+pub fn configure(spec: &str) {
+    let points: HashMap<String, PointState> = spec
+        .split(',')
+        .filter_map(|pair| {
+            let (point, rate) = pair.split_once('=')?;
+            let rate: f64 = rate.trim().parse().ok()?;
+            Some((
+                point.trim().to_string(),
+                PointState { rate: rate.clamp(0.0, 1.0), calls: 0 },
+            ))
+        })
+        .collect();
+    let enabled = !points.is_empty();
+    *CONFIG.lock().unwrap() = Some(points);
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Disable all fault injection (tests call this between scenarios).
+pub fn clear() {
+    *CONFIG.lock().unwrap() = None;
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Should this call at `point` fail? Counts the call either way, so a
+/// rate of 0.25 fails exactly every fourth call at that point.
+pub fn should_fail(point: &str) -> bool {
+    ENV_INIT.call_once(|| {
+        if let Ok(spec) = std::env::var("UBL_FAULT_INJECT") {
+            configure(&spec);
+        }
+    });
+    if !ENABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+    let mut cfg = CONFIG.lock().unwrap();
+    let Some(state) = cfg.as_mut().and_then(|c| c.get_mut(point)) else {
+        return false;
+    };
+    state.calls += 1;
+    let n = state.calls;
+    ((n as f64) * state.rate).floor() > ((n - 1) as f64 * state.rate).floor()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One test fn: the config is process-global, so scenarios run
+    // sequentially instead of racing across test threads.
+    #[test]
+    fn rates_are_deterministic_and_clearable() {
+        configure("sign=1.0,canon=0.5,bogus=");
+        assert!(should_fail("sign"), "rate 1.0 fails every call");
+        assert!(should_fail("sign"));
+        assert!(!should_fail("ledger"), "unconfigured points never fail");
+
+        let hits = (0..8).filter(|_| should_fail("canon")).count();
+        assert_eq!(hits, 4, "rate 0.5 fails every second call");
+
+        clear();
+        assert!(!should_fail("sign"), "cleared config fails nothing");
+
+        configure("canon=0.0");
+        assert!(!should_fail("canon"), "rate 0.0 never fails");
+        clear();
+    }
+}
@@ -0,0 +1,140 @@
+//! RFC 8785 (JCS) serialization for cross-ecosystem interchange.
+//!
+//! Some partners verify canonical JSON with JCS tooling rather than our
+//! NRF profile. The two disagree on key order (JCS sorts by UTF-16 code
+//! units, NRF by UTF-8 bytes), null handling (JCS keeps object nulls,
+//! NRF drops them), and normalization (JCS leaves strings alone). This
+//! module emits the JCS form of a receipt body *alongside* the NRF
+//! bytes, so a receipt can carry dual CIDs — `b3` over NRF canon,
+//! `sha256` over JCS — and verify in either ecosystem.
+//!
+//! Scope matches what receipts can contain: no floats (the NRF profile
+//! rejects them upstream), and integers are capped at ±2⁵³−1 so the
+//! decimal form coincides with the ES6 serialization JCS mandates.
+
+use serde_json::Value;
+use sha2::Digest;
+
+/// Largest integer ES6 serializes exactly (2⁵³ − 1, RFC 8785 §3.2.2.3).
+const MAX_SAFE_INT: u64 = 9_007_199_254_740_991;
+
+/// JCS sorts member names by UTF-16 code units, which disagrees with
+/// UTF-8 byte order whenever supplementary characters (surrogate range)
+/// meet upper-BMP ones.
+fn utf16_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
+fn write_jcs(v: &Value, out: &mut Vec<u8>) -> crate::error::Result<()> {
+    match v {
+        Value::Null => out.extend_from_slice(b"null"),
+        Value::Bool(true) => out.extend_from_slice(b"true"),
+        Value::Bool(false) => out.extend_from_slice(b"false"),
+        Value::Number(n) => {
+            let in_range = n
+                .as_i64()
+                .map(|i| i.unsigned_abs() <= MAX_SAFE_INT)
+                .or_else(|| n.as_u64().map(|u| u <= MAX_SAFE_INT))
+                .unwrap_or(false);
+            if !in_range {
+                return Err(crate::error::RuntimeError::Canon(format!(
+                    "JCS: number {n} is not an integer within ±2^53-1"
+                )));
+            }
+            serde_json::to_writer(&mut *out, n)?;
+        }
+        // serde's escaping (two-char forms, lowercase \u00xx for other
+        // controls) is exactly RFC 8785 §3.2.2.2; no NFC pass here
+        Value::String(s) => serde_json::to_writer(&mut *out, s)?,
+        Value::Array(arr) => {
+            out.push(b'[');
+            for (i, item) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_jcs(item, out)?;
+            }
+            out.push(b']');
+        }
+        Value::Object(obj) => {
+            let mut keys: Vec<&String> = obj.keys().collect();
+            keys.sort_by(|a, b| utf16_cmp(a, b));
+            out.push(b'{');
+            for (i, k) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, k)?;
+                out.push(b':');
+                write_jcs(&obj[k], out)?;
+            }
+            out.push(b'}');
+        }
+    }
+    Ok(())
+}
+
+/// RFC 8785 canonical bytes of `v`. Always valid UTF-8.
+pub fn jcs_bytes(v: &Value) -> crate::error::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(128);
+    write_jcs(v, &mut out)?;
+    Ok(out)
+}
+
+/// The JCS-side CID: sha256 over the canonical bytes, hex-encoded with
+/// a `sha256:` prefix (mirrors the `b3:` convention of [`crate::cid`]).
+pub fn cid_sha256(bytes: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(sha2::Sha256::digest(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn members_sort_by_utf16_code_units() {
+        // U+10000 encodes as a surrogate pair (0xD800…), which sorts
+        // below U+FF61 in UTF-16 but above it in UTF-8 bytes
+        let v = json!({"\u{ff61}": 1, "\u{10000}": 2});
+        let bytes = jcs_bytes(&v).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "{\"\u{10000}\":2,\"\u{ff61}\":1}"
+        );
+        // NRF canon picks the opposite order — that divergence is why
+        // dual CIDs exist
+        let nrf = crate::canon::canonical_bytes(&v).unwrap();
+        assert!(String::from_utf8(nrf).unwrap().starts_with("{\"\u{ff61}\""));
+    }
+
+    #[test]
+    fn nulls_and_escapes_follow_the_rfc() {
+        let v = json!({"b": null, "a": "tab\there\u{7}"});
+        let bytes = jcs_bytes(&v).unwrap();
+        // Object nulls stay (unlike NRF canon); controls use the short
+        // escape where one exists, lowercase \u00xx otherwise
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "{\"a\":\"tab\\there\\u0007\",\"b\":null}"
+        );
+    }
+
+    #[test]
+    fn numbers_are_integer_only_within_es6_range() {
+        let ok = jcs_bytes(&json!([0, -1, 9_007_199_254_740_991i64])).unwrap();
+        assert_eq!(ok, b"[0,-1,9007199254740991]");
+        assert!(jcs_bytes(&json!(9_007_199_254_740_992i64)).is_err());
+        assert!(jcs_bytes(&json!(-9_007_199_254_740_992i64)).is_err());
+        assert!(jcs_bytes(&json!(1.5)).is_err());
+    }
+
+    #[test]
+    fn sha256_cid_is_prefixed_hex() {
+        let cid = cid_sha256(b"");
+        assert_eq!(
+            cid,
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
@@ -24,13 +24,30 @@ pub struct JwsDetached {
 ///
 /// The signing input is `<protected_b64url>.<payload_bytes>` per RFC 7797 (b64=false).
 pub fn sign_detached(payload: &[u8], key: &SigningKey, kid: &str) -> JwsDetached {
-    let header = serde_json::json!({
+    sign_detached_at(payload, key, kid, None)
+}
+
+/// [`sign_detached`] with an optional `issued_at` claim (unix seconds)
+/// in the protected header. The claim is covered by the signature but
+/// never touches the payload, so `body_cid` is unaffected; auditors can
+/// bound when a receipt was produced via [`issued_at`]. Receipt signing
+/// paths feed this from [`crate::clock::issued_at`].
+pub fn sign_detached_at(
+    payload: &[u8],
+    key: &SigningKey,
+    kid: &str,
+    issued_at: Option<u64>,
+) -> JwsDetached {
+    let mut header = serde_json::json!({
         "alg": "EdDSA",
         "b64": false,
         "crit": ["b64"],
         "kid": kid,
         "typ": "ubl/rc+json"
     });
+    if let Some(secs) = issued_at {
+        header["issued_at"] = serde_json::json!(secs);
+    }
     let protected = B64_URL.encode(serde_json::to_vec(&header).unwrap());
 
     // RFC 7797 §5.1: signing input = ASCII(BASE64URL(header)) || '.' || payload_bytes
@@ -49,6 +66,13 @@ pub fn sign_detached(payload: &[u8], key: &SigningKey, kid: &str) -> JwsDetached
     }
 }
 
+/// The `issued_at` claim from a proof's protected header, if present.
+pub fn issued_at(jws: &JwsDetached) -> Option<u64> {
+    let decoded = B64_URL.decode(&jws.protected).ok()?;
+    let header: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
+    header.get("issued_at")?.as_u64()
+}
+
 /// Verify a JWS detached signature against the original payload bytes.
 pub fn verify_detached(
     jws: &JwsDetached,
@@ -133,6 +157,30 @@ mod tests {
         assert_eq!(jws1.protected, jws2.protected);
     }
 
+    #[test]
+    fn issued_at_claim_is_signed_and_readable() {
+        let key = test_key();
+        let payload = b"timestamped";
+        let jws = sign_detached_at(payload, &key, "did:dev#k1", Some(1_700_000_000));
+
+        assert_eq!(issued_at(&jws), Some(1_700_000_000));
+        let vk = key.verifying_key();
+        assert!(verify_detached(&jws, payload, &vk), "claim rides under the signature");
+
+        // Forging the claim invalidates the proof
+        let decoded = B64_URL.decode(&jws.protected).unwrap();
+        let mut header: serde_json::Value = serde_json::from_slice(&decoded).unwrap();
+        header["issued_at"] = serde_json::json!(1);
+        let forged = JwsDetached {
+            protected: B64_URL.encode(serde_json::to_vec(&header).unwrap()),
+            ..jws.clone()
+        };
+        assert!(!verify_detached(&forged, payload, &vk));
+
+        // Claim-free proofs read back as None
+        assert_eq!(issued_at(&sign_detached(payload, &key, "did:dev#k1")), None);
+    }
+
     #[test]
     fn protected_header_contains_b64_false() {
         let key = test_key();
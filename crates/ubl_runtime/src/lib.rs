@@ -1,20 +1,44 @@
 pub mod bind;
+pub mod bytes;
 pub mod canon;
 pub mod cid;
+pub mod clock;
+pub mod codec;
 pub mod engine;
 pub mod error;
+pub mod faults;
+pub mod jcs;
 pub mod jws;
 pub mod nrf_canon;
 pub mod policy;
 pub mod rb_bridge;
 pub mod receipt;
+pub mod schema;
+pub mod session;
+pub mod signer;
 pub mod transition;
 
-pub use engine::{execute, execute_with_cascade, ExecuteConfig, ExecuteResult, Grammar, Manifest, Policy};
-pub use policy::{resolve as resolve_policy, CascadePolicy, PolicyResult, PolicyRule, PolicyTraceEntry};
-pub use rb_bridge::{execute_rb, ExecuteRbReq, ExecuteRbRes};
+pub use codec::{Codec, CodecRegistry};
+pub use engine::{
+    execute, execute_with_cascade, execute_with_ctx, ExecuteConfig, ExecuteResult, Grammar,
+    Manifest, PhaseTimings, Policy,
+};
+pub use policy::{
+    resolve as resolve_policy, resolve_with_ctx as resolve_policy_with_ctx, CascadePolicy,
+    PolicyResult, PolicyRule, PolicyTraceEntry,
+};
+pub use rb_bridge::{
+    chip_cache_stats, estimate_rb, execute_rb, ChipCacheStats, EstimateRbRes, ExecuteRbReq,
+    ExecuteRbRes,
+};
 pub use receipt::{
-    build_receipt, run_with_receipts, run_with_receipts_simple, validate_receipt, verify_body_cid,
-    KeyRing, Logline, LoglineContext, Receipt, RunOpts, RunResult,
+    attest_adapter, build_receipt, run_with_receipts, run_with_receipts_simple, validate_receipt,
+    verify_body_cid,
+    Clock, FixedClock, KeyRing, Logline, LoglineContext, Receipt, RedactionPolicy, RunOpts,
+    RunResult, SystemClock, REDACTED, SYSTEM_CLOCK, VALID_TYPES,
+};
+pub use session::{
+    AuditSummary, DirStore, IngestResult, MemoryStore, Session, SessionBuilder, SessionError,
+    SessionStore,
 };
 pub use transition::{build_transition, TransitionReceiptBody, TransitionWitness};
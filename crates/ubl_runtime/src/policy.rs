@@ -15,6 +15,7 @@ pub struct PolicyRule {
     /// JSONPath-like condition key that must be non-null in vars.
     /// Format: "inputs.<key>" checks vars[key] != null.
     /// Format: "body_size <= N" checks body size constraint.
+    /// Format: "bytes_len(inputs.<key>) <= N" checks byte-length constraint.
     /// Empty string or "true" means always pass.
     #[serde(default = "default_condition")]
     pub condition: String,
@@ -113,6 +114,19 @@ pub fn resolve(
     policy: &CascadePolicy,
     vars: &BTreeMap<String, Value>,
     body_size: Option<usize>,
+) -> PolicyResult {
+    resolve_with_ctx(policy, vars, body_size, None)
+}
+
+/// Like [`resolve`], with the gate-injected execution context (`__ctx`:
+/// tenant, app, client_id, request path) visible to `ctx.*` conditions.
+/// The context never reaches the input CIDs — it exists for policy
+/// evaluation only.
+pub fn resolve_with_ctx(
+    policy: &CascadePolicy,
+    vars: &BTreeMap<String, Value>,
+    body_size: Option<usize>,
+    ctx: Option<&Value>,
 ) -> PolicyResult {
     // Legacy mode: no rules, just allow/deny
     if policy.rules.is_empty() {
@@ -147,7 +161,7 @@ pub fn resolve(
     let mut trace = Vec::with_capacity(policy.rules.len());
 
     for rule in &policy.rules {
-        let pass = evaluate_condition(&rule.condition, vars, body_size);
+        let pass = evaluate_condition(&rule.condition, vars, body_size, ctx);
 
         if pass {
             trace.push(PolicyTraceEntry {
@@ -197,10 +211,16 @@ pub fn resolve(
 /// - "inputs.<key>" or "inputs.<key> != null" → vars[key] exists and is not null
 /// - "body_size <= N" → body_size <= N
 /// - "inputs.<key> == <value>" → vars[key] == value (string comparison)
+/// - "bytes_len(inputs.<key>) <= N" (or ">=") → byte length of vars[key]
+/// - "ctx.<key>", "ctx.<key> != null", "ctx.<key> == <value>" → same as the
+///   inputs forms, against the gate-injected execution context. Unlike
+///   unknown conditions, ctx conditions fail CLOSED when no context was
+///   provided: a rule pinned to a tenant must not pass in a context-free run.
 fn evaluate_condition(
     condition: &str,
     vars: &BTreeMap<String, Value>,
     body_size: Option<usize>,
+    ctx: Option<&Value>,
 ) -> bool {
     let cond = condition.trim();
 
@@ -219,6 +239,43 @@ fn evaluate_condition(
         return true; // unparseable → pass (fail-open for unknown conditions)
     }
 
+    // bytes_len(inputs.<key>) <= N
+    if let Some(rest) = cond.strip_prefix("bytes_len(inputs.") {
+        if let Some((key, expr)) = rest.split_once(')') {
+            let expr = expr.trim();
+            let len = vars.get(key.trim()).and_then(crate::bytes::byte_len);
+            if let Some(n_str) = expr.strip_prefix("<=") {
+                if let Ok(limit) = n_str.trim().parse::<usize>() {
+                    return len.is_none_or(|l| l <= limit);
+                }
+            }
+            if let Some(n_str) = expr.strip_prefix(">=") {
+                if let Ok(limit) = n_str.trim().parse::<usize>() {
+                    return len.is_none_or(|l| l >= limit);
+                }
+            }
+        }
+        return true; // unparseable → pass (fail-open for unknown conditions)
+    }
+
+    // ctx.<key> ...
+    if let Some(key_expr) = cond.strip_prefix("ctx.") {
+        let get = |key: &str| ctx.and_then(|c| c.get(key.trim()));
+        // ctx.<key> != null
+        if let Some(key) = key_expr.strip_suffix("!= null") {
+            return get(key).is_some_and(|v| !v.is_null());
+        }
+        // ctx.<key> == "<value>"
+        if let Some((key, expected)) = key_expr.split_once("==") {
+            let expected = expected.trim().trim_matches('"');
+            return get(key)
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| v == expected);
+        }
+        // ctx.<key> (shorthand for != null)
+        return get(key_expr).is_some_and(|v| !v.is_null());
+    }
+
     // inputs.<key> ...
     if let Some(key_expr) = cond.strip_prefix("inputs.") {
         // inputs.<key> != null
@@ -474,6 +531,114 @@ mod tests {
         assert_eq!(p2.rules[0].id, "R1");
     }
 
+    // ── Byte-length conditions ───────────────────────────────────
+
+    fn bytes_len_policy(condition: &str) -> CascadePolicy {
+        CascadePolicy {
+            allow: true,
+            rules: vec![PolicyRule {
+                id: "MAX_BLOB".into(),
+                level: "tenant".into(),
+                description: "".into(),
+                condition: condition.into(),
+                action: "DENY".into(),
+                reason: "blob too large".into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn bytes_len_within_limit_passes() {
+        let p = bytes_len_policy("bytes_len(inputs.blob) <= 8");
+        let vars = vars_with(&[("blob", crate::bytes::from_bytes(&[0u8; 8]))]);
+        let r = resolve(&p, &vars, None);
+        assert_eq!(r.decision, "ALLOW");
+    }
+
+    #[test]
+    fn bytes_len_over_limit_denies() {
+        let p = bytes_len_policy("bytes_len(inputs.blob) <= 4");
+        let vars = vars_with(&[("blob", crate::bytes::from_bytes(&[0u8; 8]))]);
+        let r = resolve(&p, &vars, None);
+        assert_eq!(r.decision, "DENY");
+        assert_eq!(r.decided_by.as_deref(), Some("MAX_BLOB"));
+    }
+
+    #[test]
+    fn bytes_len_minimum_via_gte() {
+        let p = bytes_len_policy("bytes_len(inputs.blob) >= 1");
+        let vars = vars_with(&[("blob", crate::bytes::from_bytes(&[]))]);
+        let r = resolve(&p, &vars, None);
+        assert_eq!(r.decision, "DENY");
+    }
+
+    #[test]
+    fn bytes_len_measures_hex_projection() {
+        let p = bytes_len_policy("bytes_len(inputs.blob) <= 2");
+        let vars = vars_with(&[("blob", json!("0xdeadbeef"))]);
+        let r = resolve(&p, &vars, None);
+        assert_eq!(r.decision, "DENY", "0xdeadbeef is 4 bytes");
+    }
+
+    #[test]
+    fn bytes_len_missing_key_fails_open() {
+        let p = bytes_len_policy("bytes_len(inputs.blob) <= 4");
+        let r = resolve(&p, &BTreeMap::new(), None);
+        assert_eq!(r.decision, "ALLOW");
+    }
+
+    // ── Execution-context conditions ─────────────────────────────
+
+    fn ctx_policy(condition: &str) -> CascadePolicy {
+        CascadePolicy {
+            allow: true,
+            rules: vec![PolicyRule {
+                id: "CTX_RULE".into(),
+                level: "tenant".into(),
+                description: "".into(),
+                condition: condition.into(),
+                action: "DENY".into(),
+                reason: "wrong caller".into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn ctx_equals_matches_injected_context() {
+        let p = ctx_policy("ctx.tenant == \"acme\"");
+        let ctx = json!({"tenant": "acme", "app": "store"});
+        let r = resolve_with_ctx(&p, &BTreeMap::new(), None, Some(&ctx));
+        assert_eq!(r.decision, "ALLOW");
+
+        let ctx = json!({"tenant": "globex"});
+        let r = resolve_with_ctx(&p, &BTreeMap::new(), None, Some(&ctx));
+        assert_eq!(r.decision, "DENY");
+        assert_eq!(r.decided_by.as_deref(), Some("CTX_RULE"));
+    }
+
+    #[test]
+    fn ctx_shorthand_and_not_null() {
+        let ctx = json!({"client_id": "cli-1", "path": null});
+        let p = ctx_policy("ctx.client_id");
+        assert_eq!(
+            resolve_with_ctx(&p, &BTreeMap::new(), None, Some(&ctx)).decision,
+            "ALLOW"
+        );
+        let p = ctx_policy("ctx.path != null");
+        assert_eq!(
+            resolve_with_ctx(&p, &BTreeMap::new(), None, Some(&ctx)).decision,
+            "DENY"
+        );
+    }
+
+    #[test]
+    fn ctx_conditions_fail_closed_without_context() {
+        let p = ctx_policy("ctx.tenant == \"acme\"");
+        // Plain resolve never sees a context — the rule must deny.
+        let r = resolve(&p, &BTreeMap::new(), None);
+        assert_eq!(r.decision, "DENY");
+    }
+
     #[test]
     fn deserialize_legacy_policy() {
         let json = r#"{"allow": true}"#;
@@ -7,8 +7,10 @@ use crate::nrf_canon::Nrf1Canon;
 use rb_vm::exec::{CasProvider, SignProvider};
 use rb_vm::tlv;
 use rb_vm::{Cid, ExecError, Vm, VmConfig};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 // ── In-memory CAS (deterministic, no filesystem) ─────────────────
 
@@ -61,6 +63,95 @@ impl SignProvider for FixedSigner {
     }
 }
 
+// ── Decoded chip cache ───────────────────────────────────────────
+
+/// Maximum number of decoded chips kept in memory.
+const CHIP_CACHE_CAP: usize = 128;
+
+struct ChipCache {
+    /// bytecode_cid → (decoded chip, last-touch counter for LRU eviction)
+    map: HashMap<String, (Arc<tlv::DecodedChip>, u64)>,
+    touch_ctr: u64,
+    hits: u64,
+    misses: u64,
+}
+
+static CHIP_CACHE: Lazy<Mutex<ChipCache>> = Lazy::new(|| {
+    Mutex::new(ChipCache {
+        map: HashMap::new(),
+        touch_ctr: 0,
+        hits: 0,
+        misses: 0,
+    })
+});
+
+/// Snapshot of the decoded-chip cache counters, for the /metrics exporter.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ChipCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+pub fn chip_cache_stats() -> ChipCacheStats {
+    let cache = CHIP_CACHE.lock().unwrap();
+    ChipCacheStats {
+        hits: cache.hits,
+        misses: cache.misses,
+        entries: cache.map.len(),
+    }
+}
+
+/// Decode a chip, reusing the cached instruction stream for hot chips.
+/// Keyed by content (bytecode_cid), so a cache hit is always exact.
+fn decode_chip_cached(
+    chip: &[u8],
+    bytecode_cid: &str,
+) -> Result<Arc<tlv::DecodedChip>, crate::error::RuntimeError> {
+    {
+        let mut cache = CHIP_CACHE.lock().unwrap();
+        cache.touch_ctr += 1;
+        let touch = cache.touch_ctr;
+        let hit = cache.map.get_mut(bytecode_cid).map(|(decoded, last_touch)| {
+            *last_touch = touch;
+            Arc::clone(decoded)
+        });
+        if let Some(decoded) = hit {
+            cache.hits += 1;
+            return Ok(decoded);
+        }
+    }
+
+    // Decode outside the lock: misses pay the cost, other requests proceed
+    let decoded = Arc::new(
+        tlv::DecodedChip::decode(chip)
+            .map_err(|e| crate::error::RuntimeError::Codec {
+                name: "tlv".into(),
+                detail: e.to_string(),
+            })?,
+    );
+
+    let mut cache = CHIP_CACHE.lock().unwrap();
+    cache.misses += 1;
+    if cache.map.len() >= CHIP_CACHE_CAP {
+        // Evict the least-recently-used entry (deterministic via touch counter)
+        if let Some(lru_key) = cache
+            .map
+            .iter()
+            .min_by_key(|(_, (_, touch))| *touch)
+            .map(|(k, _)| k.clone())
+        {
+            cache.map.remove(&lru_key);
+        }
+    }
+    cache.touch_ctr += 1;
+    let touch = cache.touch_ctr;
+    cache
+        .map
+        .insert(bytecode_cid.to_string(), (Arc::clone(&decoded), touch));
+    Ok(decoded)
+}
+
 // ── Public API ───────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -69,6 +160,8 @@ pub struct ExecuteRbReq {
     pub inputs: Vec<serde_json::Value>,
     pub ghost: Option<bool>,
     pub fuel: Option<u64>,
+    /// Checked integer semantics: overflow denies instead of saturating.
+    pub checked_arith: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -79,9 +172,74 @@ pub struct ExecuteRbRes {
     pub transition_receipt: Option<serde_json::Value>,
 }
 
+/// Fuel ceiling used when estimating a chip's cost (ghost mode only).
+const ESTIMATE_FUEL_CEILING: u64 = 10_000_000;
+
+#[derive(Debug, Serialize)]
+pub struct EstimateRbRes {
+    pub fuel_used: u64,
+    pub steps: u64,
+    /// Step counts grouped by opcode name.
+    pub steps_per_opcode: std::collections::BTreeMap<String, u64>,
+    /// Measured fuel plus a 25% safety margin.
+    pub suggested_fuel_limit: u64,
+}
+
+/// Dry-run a chip in ghost mode with a high fuel ceiling and cost tracing.
+/// Nothing is persisted: the CAS is in-memory and no receipts are built.
+pub fn estimate_rb(req: &ExecuteRbReq) -> Result<EstimateRbRes, crate::error::RuntimeError> {
+    let bytecode_cid = crate::cid::cid_b3(&req.chip);
+    let chip = decode_chip_cached(&req.chip, &bytecode_cid)?;
+    let code = chip.instrs();
+
+    let mut cas = MemCas::new();
+    let signer = FixedSigner::from_seed([7u8; 32]);
+    let canon = Nrf1Canon;
+
+    let input_cids: Vec<Cid> = req
+        .inputs
+        .iter()
+        .map(|v| {
+            let bytes = serde_json::to_vec(v).unwrap_or_default();
+            cas.put(&bytes)
+        })
+        .collect();
+
+    let cfg = VmConfig {
+        fuel_limit: ESTIMATE_FUEL_CEILING,
+        ghost: true,
+        trace: true,
+        checked_arith: req.checked_arith.unwrap_or(false),
+    };
+
+    let mut vm = Vm::new(cfg, cas, &signer, canon, input_cids);
+    let outcome = vm.run(&code).map_err(|e| match e {
+        ExecError::Deny(reason) => crate::error::RuntimeError::PolicyDeny(reason),
+        ExecError::FuelExhausted => crate::error::RuntimeError::FuelExhausted {
+            limit: ESTIMATE_FUEL_CEILING,
+        },
+        other => crate::error::RuntimeError::Engine(other.to_string()),
+    })?;
+
+    let mut steps_per_opcode = std::collections::BTreeMap::new();
+    for step in &outcome.trace {
+        *steps_per_opcode.entry(step.op.clone()).or_insert(0u64) += 1;
+    }
+
+    Ok(EstimateRbRes {
+        fuel_used: outcome.fuel_used,
+        steps: outcome.steps,
+        steps_per_opcode,
+        suggested_fuel_limit: outcome.fuel_used + outcome.fuel_used / 4,
+    })
+}
+
 pub fn execute_rb(req: &ExecuteRbReq) -> Result<ExecuteRbRes, crate::error::RuntimeError> {
-    let code = tlv::decode_stream(&req.chip)
-        .map_err(|e| crate::error::RuntimeError::Engine(format!("TLV decode: {e}")))?;
+    // CID of the chip bytecode itself (content-addressed); doubles as the
+    // cache key for the decoded instruction stream
+    let bytecode_cid = crate::cid::cid_b3(&req.chip);
+    let chip = decode_chip_cached(&req.chip, &bytecode_cid)?;
+    let code = chip.instrs();
 
     let mut cas = MemCas::new();
     let signer = FixedSigner::from_seed([7u8; 32]);
@@ -101,19 +259,18 @@ pub fn execute_rb(req: &ExecuteRbReq) -> Result<ExecuteRbRes, crate::error::Runt
         })
         .collect();
 
-    // CID of the chip bytecode itself (content-addressed)
-    let bytecode_cid = crate::cid::cid_b3(&req.chip);
-
+    let fuel_limit = req.fuel.unwrap_or(50_000);
     let cfg = VmConfig {
-        fuel_limit: req.fuel.unwrap_or(50_000),
+        fuel_limit,
         ghost,
         trace: false,
+        checked_arith: req.checked_arith.unwrap_or(false),
     };
 
     let mut vm = Vm::new(cfg, cas, &signer, canon, input_cids);
     let outcome = vm.run(&code).map_err(|e| match e {
         ExecError::Deny(reason) => crate::error::RuntimeError::PolicyDeny(reason),
-        ExecError::FuelExhausted => crate::error::RuntimeError::Engine("fuel exhausted".into()),
+        ExecError::FuelExhausted => crate::error::RuntimeError::FuelExhausted { limit: fuel_limit },
         other => crate::error::RuntimeError::Engine(other.to_string()),
     })?;
 
@@ -138,7 +295,12 @@ pub fn execute_rb(req: &ExecuteRbReq) -> Result<ExecuteRbRes, crate::error::Runt
 
     // (D) JWS detached signature (b64=false, payload = canonical body bytes)
     let sign_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
-    let jws = crate::jws::sign_detached(&tr_body_bytes, &sign_key, "did:dev#k1");
+    let jws = crate::jws::sign_detached_at(
+        &tr_body_bytes,
+        &sign_key,
+        "did:dev#k1",
+        crate::clock::issued_at(),
+    );
 
     let tr_envelope = serde_json::json!({
         "cid": tr_cid,
@@ -153,3 +315,38 @@ pub fn execute_rb(req: &ExecuteRbReq) -> Result<ExecuteRbRes, crate::error::Runt
         transition_receipt: Some(tr_envelope),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(op: u8, payload: &[u8]) -> Vec<u8> {
+        let mut out = vec![op];
+        out.extend((payload.len() as u16).to_be_bytes());
+        out.extend(payload);
+        out
+    }
+
+    #[test]
+    fn chip_cache_hits_on_repeated_execution() {
+        // Distinctive chip so this test owns its cache entry
+        let mut chip = tlv(0x01, &0x5eed_cafe_i64.to_be_bytes());
+        chip.extend(tlv(0x11, &[]));
+        let req = ExecuteRbReq {
+            chip,
+            inputs: vec![],
+            ghost: Some(true),
+            fuel: None,
+            checked_arith: None,
+        };
+
+        let before = chip_cache_stats();
+        let a = execute_rb(&req).expect("first run");
+        let b = execute_rb(&req).expect("second run");
+        let after = chip_cache_stats();
+
+        assert_eq!(a.fuel_used, b.fuel_used, "cache must not change semantics");
+        assert!(after.hits > before.hits, "second run must hit the cache");
+        assert!(after.entries >= 1);
+    }
+}
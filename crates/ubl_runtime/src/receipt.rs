@@ -9,12 +9,19 @@
 //! - Idempotency: duplicate body_cid is rejected
 //! - Ghost: ghost=true ⇒ observability.ghost=true, ledger skip signaled
 
-use crate::canon::canonical_bytes;
+use crate::canon::{canonical_bytes, canonical_bytes_for, PROFILE_NRF1_V1};
 use crate::cid::cid_b3;
-use crate::jws::{sign_detached, JwsDetached};
+use crate::jws::JwsDetached;
 use serde::{Deserialize, Serialize};
 
-const VALID_TYPES: &[&str] = &["ubl/wa", "ubl/transition", "ubl/wf", "ubl/attestation"];
+pub const VALID_TYPES: &[&str] = &[
+    "ubl/wa",
+    "ubl/transition",
+    "ubl/wf",
+    "ubl/attestation",
+    "ubl/policy",
+    "ubl/saga",
+];
 
 /// LLM-first observability logline.
 /// Attached to `observability.logline` on every receipt.
@@ -33,6 +40,7 @@ pub struct Logline {
 
 impl Logline {
     pub fn now(
+        clock: &dyn Clock,
         who: &str,
         actor_did: &str,
         what: &str,
@@ -45,7 +53,7 @@ impl Logline {
             actor_did: actor_did.into(),
             what: what.into(),
             r#where: where_.into(),
-            when_iso: chrono_now_iso(),
+            when_iso: clock.now_iso(),
             why: why.into(),
             context_id: context_id.into(),
             version: "0.1.0".into(),
@@ -53,30 +61,121 @@ impl Logline {
     }
 }
 
-fn chrono_now_iso() -> String {
-    // Simple ISO 8601 timestamp without chrono dependency
-    use std::time::SystemTime;
-    let d = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = d.as_secs();
-    // Rough UTC: good enough for observability, not for crypto
-    let (s, m, h) = (secs % 60, (secs / 60) % 60, (secs / 3600) % 24);
-    let days = secs / 86400;
-    // Approximate date from epoch days (not leap-second accurate, fine for logs)
-    let y = 1970 + days / 365;
-    let doy = days % 365;
-    let mo = doy / 30 + 1;
-    let day = doy % 30 + 1;
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-        y,
-        mo.min(12),
-        day.min(31),
-        h,
-        m,
-        s
-    )
+/// Placeholder written over redacted logline fields.
+pub const REDACTED: &str = "[redacted]";
+
+/// Field-level redaction for loglines. Loglines ride in observability —
+/// never in a body — but they still persist, and `who`/`why` free text
+/// can carry personal data. The policy is applied when the logline is
+/// attached, so redaction by construction never reaches `body_cid`.
+///
+/// Redactable fields are the caller-supplied ones: `who`, `actor_did`,
+/// `where`, `why`, `context_id`. System-generated fields (`what`,
+/// `when_iso`, `version`) always stay.
+#[derive(Debug, Clone, Default)]
+pub struct RedactionPolicy {
+    /// Allow-list: when non-empty, redactable fields *not* named here
+    /// are redacted.
+    pub allow: Vec<String>,
+    /// Deny-list: fields always redacted, even when allowed.
+    pub deny: Vec<String>,
+    /// Replace `actor_did` with a blake3 hash of the identifier, so runs
+    /// by one actor stay correlatable without persisting who they are.
+    pub hash_actor_did: bool,
+}
+
+impl RedactionPolicy {
+    /// Policy from the environment: `UBL_LOGLINE_REDACT` (comma-separated
+    /// deny-list), `UBL_LOGLINE_ALLOW` (comma-separated allow-list) and
+    /// `UBL_LOGLINE_HASH_ACTOR=1`.
+    pub fn from_env() -> Self {
+        let split = |var: &str| -> Vec<String> {
+            std::env::var(var)
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|f| !f.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Self {
+            allow: split("UBL_LOGLINE_ALLOW"),
+            deny: split("UBL_LOGLINE_REDACT"),
+            hash_actor_did: std::env::var("UBL_LOGLINE_HASH_ACTOR")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+        }
+    }
+
+    fn keeps(&self, field: &str) -> bool {
+        if self.deny.iter().any(|f| f == field) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|f| f == field)
+    }
+
+    /// Scrub a logline in place. Hashing wins over redaction for
+    /// `actor_did`: a hash is already non-identifying, and keeping it
+    /// preserves per-actor correlation.
+    pub fn apply(&self, ll: &mut Logline) {
+        if self.hash_actor_did {
+            ll.actor_did = crate::cid::cid_b3(ll.actor_did.as_bytes());
+        } else if !self.keeps("actor_did") {
+            ll.actor_did = REDACTED.into();
+        }
+        for (field, value) in [
+            ("who", &mut ll.who),
+            ("where", &mut ll.r#where),
+            ("why", &mut ll.why),
+            ("context_id", &mut ll.context_id),
+        ] {
+            if !self.keeps(field) {
+                *value = REDACTED.into();
+            }
+        }
+    }
+}
+
+/// Wall clock behind logline timestamps. Injected through [`RunOpts`] so
+/// tests and ghost replays can pin a [`FixedClock`] and reproduce
+/// byte-identical loglines; production uses [`SystemClock`].
+pub trait Clock: Send + Sync {
+    /// Current instant as an RFC 3339 UTC timestamp.
+    fn now_iso(&self) -> String;
+}
+
+/// Real UTC clock with second precision.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_iso(&self) -> String {
+        chrono::Utc::now()
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    }
+}
+
+/// Clock pinned to a single timestamp, for deterministic replays.
+pub struct FixedClock(pub String);
+
+impl Clock for FixedClock {
+    fn now_iso(&self) -> String {
+        self.0.clone()
+    }
+}
+
+/// Shared default clock, used wherever no clock is injected.
+pub static SYSTEM_CLOCK: SystemClock = SystemClock;
+
+/// Threshold proof for high-value transitions: additional detached JWS
+/// signatures over the same canonical body bytes, plus the number of
+/// distinct signers (including the primary proof) required to accept the
+/// receipt — e.g. threshold 2 with two co-signers is 2-of-3.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CosignProof {
+    pub threshold: u32,
+    pub signatures: Vec<JwsDetached>,
 }
 
 /// Unified receipt envelope used across all pipeline stages.
@@ -90,8 +189,15 @@ pub struct Receipt {
     pub body: serde_json::Value,
     /// CID of the canonical body bytes
     pub body_cid: String,
+    /// Canonicalization profile behind body_cid. Receipts minted before
+    /// the tag existed deserialize as "nrf1/v1".
+    #[serde(default = "default_canon")]
+    pub canon: String,
     /// JWS detached proof
     pub proof: JwsDetached,
+    /// Optional threshold co-signatures (do NOT affect body_cid)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cosign: Option<CosignProof>,
     /// Optional observability (does NOT affect body_cid)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub observability: Option<serde_json::Value>,
@@ -102,11 +208,17 @@ pub struct Receipt {
 pub struct RunResult {
     pub wa: Receipt,
     pub transition: Option<Receipt>,
+    /// Cascade decision as its own auditable receipt, parented to the WA.
+    pub policy: Option<Receipt>,
     pub wf: Receipt,
     /// CID of the WF receipt body (the "tip" of the chain)
     pub tip_cid: String,
     /// Whether this run was in ghost mode (ledger should NOT persist)
     pub ghost: bool,
+    /// Wall-clock phase breakdown — observability only, never in a body.
+    /// `persist` stays zero here; the caller times its own store writes.
+    #[serde(skip_serializing_if = "crate::engine::PhaseTimings::is_zero")]
+    pub timings: crate::engine::PhaseTimings,
 }
 
 /// Signing context: active key + optional next key for rotation.
@@ -116,6 +228,11 @@ pub struct KeyRing {
     pub active_kid: String,
     pub next: Option<ed25519_dalek::SigningKey>,
     pub next_kid: Option<String>,
+    /// Co-signing keys (key, kid) for threshold proofs.
+    pub cosigners: Vec<(ed25519_dalek::SigningKey, String)>,
+    /// Distinct signers (incl. active) required per receipt; None
+    /// disables co-signing even when `cosigners` is populated.
+    pub cosign_threshold: Option<u32>,
 }
 
 impl KeyRing {
@@ -125,19 +242,57 @@ impl KeyRing {
             active_kid: "did:dev#k1".into(),
             next: None,
             next_kid: None,
+            cosigners: Vec::new(),
+            cosign_threshold: None,
         }
     }
+
+    /// Build a keyring from an imported 32-byte seed (BYOK) and kid.
+    pub fn from_seed(seed: [u8; 32], kid: impl Into<String>) -> Self {
+        Self {
+            active: ed25519_dalek::SigningKey::from_bytes(&seed),
+            active_kid: kid.into(),
+            next: None,
+            next_kid: None,
+            cosigners: Vec::new(),
+            cosign_threshold: None,
+        }
+    }
+
+    /// Sign several canonical bodies with the active key, fanned across
+    /// the global [`crate::signer::SigningPool`]. Results come back in
+    /// payload order and are bit-identical to inline signing (Ed25519 is
+    /// deterministic).
+    pub fn sign_batch(&self, payloads: Vec<Vec<u8>>) -> Vec<crate::jws::JwsDetached> {
+        let jobs = payloads
+            .into_iter()
+            .map(|p| (p, self.active.clone(), self.active_kid.clone()))
+            .collect();
+        crate::signer::SigningPool::global().sign_batch(jobs)
+    }
 }
 
 /// Pipeline options for run_with_receipts.
 pub struct RunOpts<'a> {
     pub prev_tip: Option<&'a str>,
+    /// Issuer DID when prev_tip was minted by a foreign gate (federation).
+    pub prev_tip_issuer: Option<&'a str>,
     pub ghost: bool,
     pub keys: &'a KeyRing,
     /// Already-seen keys for idempotency (caller provides)
     pub seen: Option<&'a std::collections::HashSet<String>>,
     /// Optional logline context for observability
     pub logline: Option<LoglineContext<'a>>,
+    /// Scrubs logline fields as they are attached; composes with ghost
+    /// mode and can never affect `body_cid` (loglines live outside bodies).
+    pub redaction: Option<&'a RedactionPolicy>,
+    /// Clock for logline timestamps; inject [`FixedClock`] for
+    /// deterministic replays.
+    pub clock: &'a dyn Clock,
+    /// Gate-injected execution context (tenant, app, client_id, path).
+    /// Surfaced to `ctx.*` policy conditions only; it stays out of the
+    /// input CIDs unless the manifest declares a `__ctx` input.
+    pub ctx: Option<&'a serde_json::Value>,
 }
 
 /// Minimal context for generating loglines per receipt.
@@ -153,16 +308,60 @@ impl<'a> Default for RunOpts<'a> {
     fn default() -> Self {
         Self {
             prev_tip: None,
+            prev_tip_issuer: None,
             ghost: false,
             keys: &DEVKEYS,
             seen: None,
             logline: None,
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
         }
     }
 }
 
 static DEVKEYS: once_cell::sync::Lazy<KeyRing> = once_cell::sync::Lazy::new(KeyRing::dev);
 
+fn default_canon() -> String {
+    PROFILE_NRF1_V1.into()
+}
+
+/// Upper bound on parents per receipt (`UBL_MAX_PARENTS`, default 8).
+/// Executions mint receipts with at most two parents; the headroom is for
+/// imports and attestations that fan in.
+pub fn max_parents() -> usize {
+    std::env::var("UBL_MAX_PARENTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(8)
+}
+
+/// Parents must be b3 CIDs, unique, and bounded — a malformed chain is
+/// rejected when the receipt is built, not when someone later walks it.
+fn validate_parents(parents: &[String]) -> crate::error::Result<()> {
+    let max = max_parents();
+    if parents.len() > max {
+        return Err(crate::error::RuntimeError::Validation(format!(
+            "{} parents exceeds the maximum of {max}",
+            parents.len()
+        )));
+    }
+    let mut seen = std::collections::HashSet::with_capacity(parents.len());
+    for p in parents {
+        if !p.starts_with("b3:") || p.len() == 3 {
+            return Err(crate::error::RuntimeError::Validation(format!(
+                "parent '{p}' is not a b3 CID"
+            )));
+        }
+        if !seen.insert(p.as_str()) {
+            return Err(crate::error::RuntimeError::Validation(format!(
+                "duplicate parent '{p}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Validate a receipt against the canonical schema.
 pub fn validate_receipt(rc: &Receipt) -> crate::error::Result<()> {
     if !VALID_TYPES.contains(&rc.t.as_str()) {
@@ -176,18 +375,22 @@ pub fn validate_receipt(rc: &Receipt) -> crate::error::Result<()> {
             "body_cid must be non-empty and start with 'b3:'".into(),
         ));
     }
+    validate_parents(&rc.parents)?;
+    // Bodies that claim a registered type must match its schema
+    crate::schema::validate_body(&rc.body)?;
     if rc.proof.signature.is_empty() {
-        return Err(crate::error::RuntimeError::Validation(
+        return Err(crate::error::RuntimeError::Signing(
             "proof.signature must not be empty".into(),
         ));
     }
     if rc.proof.kid.is_empty() {
-        return Err(crate::error::RuntimeError::Validation(
+        return Err(crate::error::RuntimeError::Signing(
             "proof.kid must not be empty".into(),
         ));
     }
-    // body_cid must match canonical body
-    let body_bytes = canonical_bytes(&rc.body)?;
+    // body_cid must match the body canonicalized under the receipt's own
+    // profile tag — unknown tags fail here rather than verifying wrong bytes
+    let body_bytes = canonical_bytes_for(&rc.canon, &rc.body)?;
     let expected_cid = cid_b3(&body_bytes);
     if expected_cid != rc.body_cid {
         return Err(crate::error::RuntimeError::Validation(format!(
@@ -195,6 +398,31 @@ pub fn validate_receipt(rc: &Receipt) -> crate::error::Result<()> {
             expected_cid, rc.body_cid
         )));
     }
+    // Threshold proofs: enough distinct signers to meet the declared bar
+    if let Some(cosign) = &rc.cosign {
+        if cosign.threshold == 0 {
+            return Err(crate::error::RuntimeError::Signing(
+                "cosign.threshold must be at least 1".into(),
+            ));
+        }
+        let mut kids: std::collections::HashSet<&str> =
+            std::collections::HashSet::from([rc.proof.kid.as_str()]);
+        for sig in &cosign.signatures {
+            if sig.signature.is_empty() || sig.kid.is_empty() {
+                return Err(crate::error::RuntimeError::Signing(
+                    "cosign signatures must have non-empty signature and kid".into(),
+                ));
+            }
+            kids.insert(&sig.kid);
+        }
+        if (kids.len() as u32) < cosign.threshold {
+            return Err(crate::error::RuntimeError::Signing(format!(
+                "cosign has {} distinct signer(s), threshold is {}",
+                kids.len(),
+                cosign.threshold
+            )));
+        }
+    }
     Ok(())
 }
 
@@ -207,32 +435,153 @@ pub fn build_receipt(
     sign_key: &ed25519_dalek::SigningKey,
     kid: &str,
 ) -> crate::error::Result<Receipt> {
-    let body_bytes = canonical_bytes(&body)?;
+    let (body_bytes, body_cid) = receipt_parts(&body)?;
+    // Trusted time rides in the protected header, not the body, so the
+    // claim never perturbs body_cid (see crate::clock)
+    let proof = crate::jws::sign_detached_at(&body_bytes, sign_key, kid, crate::clock::issued_at());
+    assemble_receipt(t, parents, body, body_cid, proof)
+}
+
+/// Canonical bytes + CID of a receipt body — the signature-independent
+/// half of [`build_receipt`], split out so a run's bodies can all be
+/// computed first and their signatures fanned out as one batch.
+fn receipt_parts(body: &serde_json::Value) -> crate::error::Result<(Vec<u8>, String)> {
+    if crate::faults::should_fail(crate::faults::POINT_CANON) {
+        return Err(crate::error::RuntimeError::Canon(
+            "fault injection: canonicalization failed".into(),
+        ));
+    }
+    let body_bytes = canonical_bytes(body)?;
     let body_cid = cid_b3(&body_bytes);
-    let proof = sign_detached(&body_bytes, sign_key, kid);
+    Ok((body_bytes, body_cid))
+}
+
+/// The other half: wrap a body and its (possibly pooled) proof into a
+/// validated receipt.
+fn assemble_receipt(
+    t: &str,
+    parents: Vec<String>,
+    body: serde_json::Value,
+    body_cid: String,
+    proof: crate::jws::JwsDetached,
+) -> crate::error::Result<Receipt> {
     let rc = Receipt {
         t: t.into(),
         parents,
         body,
         body_cid,
+        canon: default_canon(),
         proof,
+        cosign: None,
         observability: None,
     };
     validate_receipt(&rc)?;
     Ok(rc)
 }
 
-/// Verify a receipt's body_cid matches the canonical body bytes.
+/// Mint a signed `ubl/attestation` receipt over a completed adapter call.
+///
+/// When the call happened inside an execution, pass the requesting WA
+/// receipt's CID so the attestation parents into its chain; standalone
+/// calls attest with no parents. The body is built by the adapter crate
+/// (kind, params CID, response CID, status, policy CID) — this is the
+/// host-side signing step, since keys never cross the Wasm-safe boundary.
+pub fn attest_adapter(
+    resp: &ubl_adapter::AdapterResponse,
+    policy: &ubl_adapter::types::AdapterPolicy,
+    parent_wa: Option<&str>,
+    sign_key: &ed25519_dalek::SigningKey,
+    kid: &str,
+) -> crate::error::Result<Receipt> {
+    let body = ubl_adapter::attestation::attestation_body(resp, policy);
+    let parents = parent_wa.map(|cid| vec![cid.to_string()]).unwrap_or_default();
+    build_receipt("ubl/attestation", parents, body, sign_key, kid)
+}
+
+/// Attach a threshold proof signed by the keyring's co-signers. No-op
+/// unless the ring declares a cosign threshold; revalidates so a ring
+/// that cannot meet its own threshold fails at mint time, not verify time.
+pub fn attach_cosign(rc: &mut Receipt, keys: &KeyRing) -> crate::error::Result<()> {
+    let Some(threshold) = keys.cosign_threshold else {
+        return Ok(());
+    };
+    let body_bytes = canonical_bytes_for(&rc.canon, &rc.body)?;
+    // Co-signatures are independent of each other — batch them too
+    let jobs = keys
+        .cosigners
+        .iter()
+        .map(|(key, kid)| (body_bytes.clone(), key.clone(), kid.clone()))
+        .collect();
+    let signatures = crate::signer::SigningPool::global().sign_batch(jobs);
+    rc.cosign = Some(CosignProof {
+        threshold,
+        signatures,
+    });
+    validate_receipt(rc)
+}
+
+/// Cryptographic threshold verification: count the distinct kids whose
+/// detached signature (primary or co-signature) verifies over the
+/// canonical body bytes, and compare against the declared threshold.
+/// Receipts without a cosign proof verify 1-of-1 against the primary.
+pub fn verify_threshold(
+    receipt: &Receipt,
+    keys: &std::collections::HashMap<String, ed25519_dalek::VerifyingKey>,
+) -> crate::error::Result<bool> {
+    let body_bytes = canonical_bytes_for(&receipt.canon, &receipt.body)?;
+    let threshold = receipt.cosign.as_ref().map(|c| c.threshold).unwrap_or(1);
+    let mut valid: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let cosigs = receipt
+        .cosign
+        .iter()
+        .flat_map(|c| c.signatures.iter());
+    for jws in std::iter::once(&receipt.proof).chain(cosigs) {
+        if let Some(vk) = keys.get(&jws.kid) {
+            if crate::jws::verify_detached(jws, &body_bytes, vk) {
+                valid.insert(&jws.kid);
+            }
+        }
+    }
+    Ok(valid.len() as u32 >= threshold)
+}
+
+/// Verify a receipt's body_cid matches the canonical body bytes under the
+/// receipt's declared canon profile.
 pub fn verify_body_cid(receipt: &Receipt) -> crate::error::Result<bool> {
-    let body_bytes = canonical_bytes(&receipt.body)?;
+    let body_bytes = canonical_bytes_for(&receipt.canon, &receipt.body)?;
     let expected = cid_b3(&body_bytes);
     Ok(expected == receipt.body_cid)
 }
 
+/// Refuse to mix canon profiles within one chain segment: every receipt
+/// minted by a single run (WA → Transition/Policy → WF) must carry the
+/// same tag, otherwise a verifier could not pick one canonicalizer for
+/// the segment.
+pub fn ensure_uniform_canon<'a>(
+    receipts: impl IntoIterator<Item = &'a Receipt>,
+) -> crate::error::Result<()> {
+    let mut first: Option<&str> = None;
+    for rc in receipts {
+        match first {
+            None => first = Some(&rc.canon),
+            Some(tag) if tag != rc.canon => {
+                return Err(crate::error::RuntimeError::Validation(format!(
+                    "canon profile mismatch in chain segment: '{}' vs '{}'",
+                    tag, rc.canon
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
 /// Build the observability JSON for a receipt, merging ghost flag and logline.
 fn make_observability(
     ghost: bool,
     logline_ctx: &Option<LoglineContext>,
+    redaction: Option<&RedactionPolicy>,
+    clock: &dyn Clock,
     what_suffix: &str,
 ) -> Option<serde_json::Value> {
     let has_ghost = ghost;
@@ -245,7 +594,8 @@ fn make_observability(
         obs.insert("ghost".into(), serde_json::Value::Bool(true));
     }
     if let Some(ctx) = logline_ctx {
-        let ll = Logline::now(
+        let mut ll = Logline::now(
+            clock,
             ctx.who,
             ctx.actor_did,
             what_suffix,
@@ -253,6 +603,11 @@ fn make_observability(
             ctx.why,
             ctx.context_id,
         );
+        // Scrubbed before it is ever attached: the unredacted strings
+        // never exist in a receipt
+        if let Some(policy) = redaction {
+            policy.apply(&mut ll);
+        }
         obs.insert("logline".into(), serde_json::to_value(&ll).unwrap());
     }
     Some(serde_json::Value::Object(obs))
@@ -274,18 +629,24 @@ pub fn run_with_receipts(
     cfg: &crate::engine::ExecuteConfig,
     opts: &RunOpts,
 ) -> crate::error::Result<RunResult> {
-    let sign_key = &opts.keys.active;
-    let kid = opts.keys.active_kid.as_str();
     let ghost = opts.ghost;
+    let mut timings = crate::engine::PhaseTimings::default();
+
+    // (0) Vars limits fail fast, before anything is signed, so callers can
+    // surface them as a 4xx instead of a DENY receipt
+    crate::bind::enforce_vars_limits(vars, &crate::bind::VarsLimits::from_env())?;
 
     // (1) WA — write-ahead (ghost/intention)
     let wa_parents = match opts.prev_tip {
         Some(tip) => vec![tip.to_string()],
         None => vec![],
     };
-    let raw_bytes = serde_json::to_vec(vars)?;
+    // Canonical bytes, not serde_json::to_vec: this CID keys idempotency,
+    // so semantically equal vars must always hash the same way — exactly
+    // the guarantee rho_cid already gets from the canon profile
+    let raw_bytes = canonical_bytes(&serde_json::to_value(vars)?)?;
     let inputs_raw_cid = cid_b3(&raw_bytes);
-    let wa_body = serde_json::json!({
+    let mut wa_body = serde_json::json!({
         "type": "ubl/wa",
         "prev_tip": opts.prev_tip,
         "inputs_raw_cid": inputs_raw_cid,
@@ -294,25 +655,33 @@ pub fn run_with_receipts(
             "pipeline": &manifest.pipeline
         }
     });
+    // Foreign tips record their issuer; local chains omit the key so
+    // pre-federation WA bodies keep their CIDs
+    if let Some(issuer) = opts.prev_tip_issuer {
+        wa_body["prev_tip_issuer"] = serde_json::json!(issuer);
+    }
     // Idempotency check: same inputs + pipeline = replay
     let idempotency_key = format!("{}:{}", manifest.pipeline, inputs_raw_cid);
     if let Some(seen) = opts.seen {
         if seen.contains(&idempotency_key) {
-            return Err(crate::error::RuntimeError::Validation(format!(
-                "duplicate request (replay): pipeline={} inputs_cid={}",
-                manifest.pipeline, inputs_raw_cid
-            )));
+            return Err(crate::error::RuntimeError::Replay {
+                pipeline: manifest.pipeline.clone(),
+                inputs_cid: inputs_raw_cid,
+            });
         }
     }
 
-    let mut wa = build_receipt("ubl/wa", wa_parents, wa_body, sign_key, kid)?;
-    wa.observability = make_observability(ghost, &opts.logline, "wa:write-ahead");
+    let canon_started = std::time::Instant::now();
+    let (wa_bytes, wa_cid) = receipt_parts(&wa_body)?;
+    timings.canon_micros += canon_started.elapsed().as_micros() as u64;
 
     // (2) Transition -1→0 (rho.normalize)
     let rho_val = serde_json::to_value(vars)?;
+    let canon_started = std::time::Instant::now();
     let rho_bytes = canonical_bytes(&rho_val)?;
     let rho_cid = cid_b3(&rho_bytes);
-    let tr_body = serde_json::json!({
+    timings.canon_micros += canon_started.elapsed().as_micros() as u64;
+    let mut tr_body = serde_json::json!({
         "t": "ubl/transition",
         "from_layer": "-1:rb",
         "to_layer": "0:rho",
@@ -321,19 +690,54 @@ pub fn run_with_receipts(
         "rho_cid": rho_cid,
         "witness": { "vm": "ubl-runtime@0.1.0" }
     });
-    let mut transition = build_receipt(
-        "ubl/transition",
-        vec![wa.body_cid.clone()],
-        tr_body,
-        sign_key,
-        kid,
-    )?;
-    transition.observability = make_observability(ghost, &opts.logline, "transition:normalize");
+    // Plugin codecs the manifest touches are pinned as name@version in the
+    // witness; the key is absent for builtin-only runs so their transition
+    // CIDs are unchanged from before plugins existed.
+    let codec_pins = cfg.codecs.versions_for(manifest);
+    if !codec_pins.is_empty() {
+        tr_body["witness"]["codecs"] = serde_json::json!(codec_pins);
+    }
+    let canon_started = std::time::Instant::now();
+    let (tr_bytes, tr_cid) = receipt_parts(&tr_body)?;
+    timings.canon_micros += canon_started.elapsed().as_micros() as u64;
+
+    // (3) Policy receipt — the cascade decision as its own signed artifact,
+    // so rule behavior stays auditable even when the WF ends up a DENY.
+    let eval_started = std::time::Instant::now();
+    let cascade = crate::engine::cascade_for(manifest);
+    let policy_result = crate::policy::resolve_with_ctx(&cascade, vars, None, opts.ctx);
+    let eval_micros = eval_started.elapsed().as_micros() as u64;
+    timings.policy_micros += eval_micros;
+    let policy_set_cid = cid_b3(&canonical_bytes(&serde_json::to_value(&cascade)?)?);
+    let policy_body = serde_json::json!({
+        "type": "ubl/policy",
+        "decision": policy_result.decision,
+        "decided_by": policy_result.decided_by,
+        "reason": policy_result.reason,
+        "policy_trace": policy_result.policy_trace,
+        "policy_set_cid": policy_set_cid,
+    });
+    let canon_started = std::time::Instant::now();
+    let (policy_bytes, policy_cid) = receipt_parts(&policy_body)?;
+    timings.canon_micros += canon_started.elapsed().as_micros() as u64;
 
-    // (3) Execute deterministic pipeline (parse → policy → render)
+    // (4) Execute deterministic pipeline (parse → policy → render)
     // On failure → produce DENY WF receipt, never 500
-    let exec_result = match crate::engine::execute(manifest, vars, cfg) {
-        Ok(r) => r,
+    let (wf_body, wf_obs_op) = match crate::engine::execute_with_ctx(manifest, vars, cfg, opts.ctx) {
+        Ok(r) => {
+            // Engine phases fold into the run totals; its own cascade pass
+            // counts toward policy alongside the receipt evaluation in (3)
+            timings.merge(&r.timings);
+            let wf_body = serde_json::json!({
+                "type": "ubl/wf",
+                "rho_cid": rho_cid,
+                "outputs_cid": r.cid,
+                "decision": if r.dimension_stack.contains(&"policy".to_string()) { "ALLOW" } else { "DENY" },
+                "dimension_stack": r.dimension_stack,
+                "policy_trace": r.policy_trace,
+            });
+            (wf_body, "wf:write-final")
+        }
         Err(e) => {
             // DENY WF with error reason
             let wf_body = serde_json::json!({
@@ -344,51 +748,94 @@ pub fn run_with_receipts(
                 "reason": e.to_string(),
                 "dimension_stack": [],
             });
-            let mut wf = build_receipt(
-                "ubl/wf",
-                vec![wa.body_cid.clone(), transition.body_cid.clone()],
-                wf_body,
-                sign_key,
-                kid,
-            )?;
-            wf.observability = make_observability(ghost, &opts.logline, "wf:deny");
-            let tip_cid = wf.body_cid.clone();
-            return Ok(RunResult {
-                wa,
-                transition: Some(transition),
-                wf,
-                tip_cid,
-                ghost,
-            });
+            (wf_body, "wf:deny")
+        }
+    };
+    let canon_started = std::time::Instant::now();
+    let (wf_bytes, wf_cid) = receipt_parts(&wf_body)?;
+    timings.canon_micros += canon_started.elapsed().as_micros() as u64;
+
+    // (5) Every body and CID is now fixed, so the four primary signatures
+    // fan out across the signing pool as one batch instead of queueing on
+    // this task. Proofs come back in submission order.
+    let sign_started = std::time::Instant::now();
+    if crate::faults::should_fail(crate::faults::POINT_SIGN) {
+        return Err(crate::error::RuntimeError::Signing(
+            "fault injection: signing failed".into(),
+        ));
+    }
+    let mut proofs = opts
+        .keys
+        .sign_batch(vec![wa_bytes, tr_bytes, policy_bytes, wf_bytes])
+        .into_iter();
+    let (wa_proof, tr_proof, policy_proof, wf_proof) = match (
+        proofs.next(),
+        proofs.next(),
+        proofs.next(),
+        proofs.next(),
+    ) {
+        (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+        _ => {
+            return Err(crate::error::RuntimeError::Signing(
+                "signing pool returned a short batch".into(),
+            ))
         }
     };
 
-    // (4) WF — write-final (result)
-    let wf_body = serde_json::json!({
-        "type": "ubl/wf",
-        "rho_cid": rho_cid,
-        "outputs_cid": exec_result.cid,
-        "decision": if exec_result.dimension_stack.contains(&"policy".to_string()) { "ALLOW" } else { "DENY" },
-        "dimension_stack": exec_result.dimension_stack,
-        "policy_trace": exec_result.policy_trace,
-    });
-    let mut wf = build_receipt(
+    let mut wa = assemble_receipt("ubl/wa", wa_parents, wa_body, wa_cid, wa_proof)?;
+    wa.observability = make_observability(ghost, &opts.logline, opts.redaction, opts.clock, "wa:write-ahead");
+    attach_cosign(&mut wa, opts.keys)?;
+
+    let mut transition = assemble_receipt(
+        "ubl/transition",
+        vec![wa.body_cid.clone()],
+        tr_body,
+        tr_cid,
+        tr_proof,
+    )?;
+    transition.observability = make_observability(ghost, &opts.logline, opts.redaction, opts.clock, "transition:normalize");
+    attach_cosign(&mut transition, opts.keys)?;
+
+    let mut policy = assemble_receipt(
+        "ubl/policy",
+        vec![wa.body_cid.clone()],
+        policy_body,
+        policy_cid,
+        policy_proof,
+    )?;
+    policy.observability = make_observability(ghost, &opts.logline, opts.redaction, opts.clock, "policy:cascade");
+    // Evaluation latency rides in observability so it never affects body_cid
+    let obs = policy
+        .observability
+        .get_or_insert_with(|| serde_json::json!({}));
+    if let Some(map) = obs.as_object_mut() {
+        map.insert("eval_micros".into(), serde_json::json!(eval_micros));
+    }
+
+    let mut wf = assemble_receipt(
         "ubl/wf",
         vec![wa.body_cid.clone(), transition.body_cid.clone()],
         wf_body,
-        sign_key,
-        kid,
+        wf_cid,
+        wf_proof,
     )?;
-    wf.observability = make_observability(ghost, &opts.logline, "wf:write-final");
+    wf.observability = make_observability(ghost, &opts.logline, opts.redaction, opts.clock, wf_obs_op);
+    attach_cosign(&mut wf, opts.keys)?;
+    timings.sign_micros += sign_started.elapsed().as_micros() as u64;
 
     let tip_cid = wf.body_cid.clone();
 
+    let canon_started = std::time::Instant::now();
+    ensure_uniform_canon([&wa, &transition, &policy, &wf])?;
+    timings.canon_micros += canon_started.elapsed().as_micros() as u64;
     Ok(RunResult {
         wa,
         transition: Some(transition),
+        policy: Some(policy),
         wf,
         tip_cid,
         ghost,
+        timings,
     })
 }
 
@@ -402,10 +849,14 @@ pub fn run_with_receipts_simple(
     let keys = KeyRing::dev();
     let opts = RunOpts {
         prev_tip,
+        prev_tip_issuer: None,
         ghost: false,
         keys: &keys,
         seen: None,
         logline: None,
+        redaction: None,
+        clock: &SYSTEM_CLOCK,
+        ctx: None,
     };
     run_with_receipts(manifest, vars, cfg, &opts)
 }
@@ -434,6 +885,166 @@ mod tests {
         assert!(verify_body_cid(&rc).unwrap());
     }
 
+    #[test]
+    fn adapter_attestation_parents_into_wa_chain() {
+        let resp = ubl_adapter::AdapterResponse {
+            kind: "http".into(),
+            params_cid: "b3:abc".into(),
+            pinned: ubl_adapter::PinnedBlob::from_bytes(b"payload", 200, Default::default()),
+            attempts: Vec::new(),
+        };
+        let policy = ubl_adapter::types::AdapterPolicy::default();
+        let rc = attest_adapter(&resp, &policy, Some("b3:wa"), &test_key(), "did:dev#k1").unwrap();
+        assert_eq!(rc.t, "ubl/attestation");
+        assert_eq!(rc.parents, vec!["b3:wa".to_string()]);
+        assert_eq!(rc.body["response_cid"], resp.pinned.cid);
+        assert!(verify_body_cid(&rc).unwrap());
+
+        let standalone = attest_adapter(&resp, &policy, None, &test_key(), "did:dev#k1").unwrap();
+        assert!(standalone.parents.is_empty());
+    }
+
+    #[test]
+    fn duplicate_parents_rejected_at_build() {
+        let err = build_receipt(
+            "ubl/wa",
+            vec!["b3:same".into(), "b3:same".into()],
+            json!({"a": 1}),
+            &test_key(),
+            "did:dev#k1",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("duplicate parent"), "got: {err}");
+    }
+
+    #[test]
+    fn non_b3_parents_rejected_at_build() {
+        for bad in ["sha256:abc", "b3:", ""] {
+            let err = build_receipt(
+                "ubl/wa",
+                vec![bad.to_string()],
+                json!({"a": 1}),
+                &test_key(),
+                "did:dev#k1",
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains("not a b3 CID"), "got: {err}");
+        }
+    }
+
+    #[test]
+    fn parent_count_is_bounded() {
+        let parents: Vec<String> = (0..max_parents() + 1).map(|i| format!("b3:p{i}")).collect();
+        let err = build_receipt("ubl/wa", parents, json!({"a": 1}), &test_key(), "did:dev#k1")
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum"), "got: {err}");
+    }
+
+    #[test]
+    fn receipts_carry_the_current_canon_profile() {
+        let rc = build_receipt("ubl/wa", vec![], json!({"a": 1}), &test_key(), "did:dev#k1")
+            .unwrap();
+        assert_eq!(rc.canon, PROFILE_NRF1_V1);
+    }
+
+    #[test]
+    fn pre_profile_receipts_deserialize_as_v1_and_validate() {
+        let rc = build_receipt("ubl/wa", vec![], json!({"a": 1}), &test_key(), "did:dev#k1")
+            .unwrap();
+        let mut json = serde_json::to_value(&rc).unwrap();
+        json.as_object_mut().unwrap().remove("canon");
+        let old: Receipt = serde_json::from_value(json).unwrap();
+        assert_eq!(old.canon, PROFILE_NRF1_V1);
+        validate_receipt(&old).unwrap();
+    }
+
+    #[test]
+    fn unknown_canon_profile_fails_validation() {
+        let mut rc = build_receipt("ubl/wa", vec![], json!({"a": 1}), &test_key(), "did:dev#k1")
+            .unwrap();
+        rc.canon = "nrf1/v9".into();
+        let err = validate_receipt(&rc).unwrap_err();
+        assert!(err.to_string().contains("unknown canon profile"));
+    }
+
+    #[test]
+    fn cosigners_produce_verifiable_threshold_proofs() {
+        let co1 = ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]);
+        let co2 = ed25519_dalek::SigningKey::from_bytes(&[12u8; 32]);
+        let mut keys = KeyRing::dev();
+        keys.cosigners = vec![
+            (co1.clone(), "did:co#a".into()),
+            (co2.clone(), "did:co#b".into()),
+        ];
+        keys.cosign_threshold = Some(2);
+
+        let mut rc =
+            build_receipt("ubl/wa", vec![], json!({"high": "value"}), &keys.active, "did:dev#k1")
+                .unwrap();
+        attach_cosign(&mut rc, &keys).unwrap();
+        let cosign = rc.cosign.as_ref().unwrap();
+        assert_eq!(cosign.threshold, 2);
+        assert_eq!(cosign.signatures.len(), 2);
+
+        // 2-of-3 holds with any two of the three keys resolvable
+        let mut resolvable = std::collections::HashMap::new();
+        resolvable.insert("did:dev#k1".to_string(), keys.active.verifying_key());
+        resolvable.insert("did:co#a".to_string(), co1.verifying_key());
+        assert!(verify_threshold(&rc, &resolvable).unwrap());
+
+        // A single resolvable signer misses the threshold
+        let mut lone = std::collections::HashMap::new();
+        lone.insert("did:co#b".to_string(), co2.verifying_key());
+        assert!(!verify_threshold(&rc, &lone).unwrap());
+    }
+
+    #[test]
+    fn validate_rejects_unmet_threshold() {
+        let mut keys = KeyRing::dev();
+        keys.cosigners = vec![(
+            ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]),
+            "did:co#a".into(),
+        )];
+        keys.cosign_threshold = Some(3);
+        let mut rc = build_receipt("ubl/wa", vec![], json!({"a": 1}), &keys.active, "did:dev#k1")
+            .unwrap();
+        // 3-of-2 can never be met: mint must fail, not verify
+        let err = attach_cosign(&mut rc, &keys).unwrap_err();
+        assert!(err.to_string().contains("threshold is 3"));
+    }
+
+    #[test]
+    fn run_receipts_carry_cosignatures_when_ring_has_cosigners() {
+        let mut keys = KeyRing::dev();
+        keys.cosigners = vec![(
+            ed25519_dalek::SigningKey::from_bytes(&[21u8; 32]),
+            "did:co#x".into(),
+        )];
+        keys.cosign_threshold = Some(2);
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let opts = RunOpts {
+            keys: &keys,
+            ..RunOpts::default()
+        };
+        let result = run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap();
+        for rc in [&result.wa, &result.wf] {
+            let cosign = rc.cosign.as_ref().expect("receipt must carry cosign");
+            assert_eq!(cosign.threshold, 2);
+            assert_eq!(cosign.signatures[0].kid, "did:co#x");
+        }
+    }
+
+    #[test]
+    fn mixed_canon_profiles_rejected_in_segment() {
+        let key = test_key();
+        let a = build_receipt("ubl/wa", vec![], json!({"a": 1}), &key, "did:dev#k1").unwrap();
+        let mut b = build_receipt("ubl/wf", vec![], json!({"b": 2}), &key, "did:dev#k1").unwrap();
+        ensure_uniform_canon([&a, &b]).unwrap();
+        b.canon = "nrf1/v2".into();
+        let err = ensure_uniform_canon([&a, &b]).unwrap_err();
+        assert!(err.to_string().contains("canon profile mismatch"));
+    }
+
     #[test]
     fn parents_chain() {
         let key = test_key();
@@ -490,11 +1101,12 @@ mod tests {
             pipeline: "test".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8="))]);
         let cfg = ExecuteConfig {
             version: "0.1.0".into(),
+            codecs: Default::default(),
         };
 
         let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
@@ -551,11 +1163,12 @@ mod tests {
             pipeline: "test".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8="))]);
         let cfg = ExecuteConfig {
             version: "0.1.0".into(),
+            codecs: Default::default(),
         };
 
         let r1 = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
@@ -591,11 +1204,12 @@ mod tests {
             pipeline: "test".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8="))]);
         let cfg = ExecuteConfig {
             version: "0.1.0".into(),
+            codecs: Default::default(),
         };
 
         let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
@@ -635,10 +1249,14 @@ mod tests {
         let keys = KeyRing::dev();
         let opts = RunOpts {
             prev_tip: None,
+            prev_tip_issuer: None,
             ghost: true,
             keys: &keys,
             seen: None,
             logline: None,
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
         };
         let result = run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap();
 
@@ -667,7 +1285,7 @@ mod tests {
         let keys = KeyRing::dev();
 
         // Compute the idempotency key: pipeline:inputs_raw_cid
-        let raw_bytes = serde_json::to_vec(&vars).unwrap();
+        let raw_bytes = canonical_bytes(&serde_json::to_value(&vars).unwrap()).unwrap();
         let inputs_cid = crate::cid::cid_b3(&raw_bytes);
         let idemp_key = format!("{}:{}", manifest.pipeline, inputs_cid);
 
@@ -677,10 +1295,14 @@ mod tests {
         // Run with same input should be rejected as replay
         let opts = RunOpts {
             prev_tip: None,
+            prev_tip_issuer: None,
             ghost: false,
             keys: &keys,
             seen: Some(&seen),
             logline: None,
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
         };
         let err = run_with_receipts(&manifest, &vars, &cfg, &opts);
         assert!(err.is_err());
@@ -690,6 +1312,20 @@ mod tests {
             .contains("duplicate request (replay)"));
     }
 
+    #[test]
+    fn inputs_raw_cid_is_the_canon_of_vars() {
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+        let expected = crate::cid::cid_b3(
+            &canonical_bytes(&serde_json::to_value(&vars).unwrap()).unwrap(),
+        );
+        assert_eq!(result.wa.body["inputs_raw_cid"], json!(expected));
+        // The transition's preimage is the same CID, so the whole chain
+        // agrees on one canonical view of the inputs
+        let tr = result.transition.as_ref().unwrap();
+        assert_eq!(tr.body["preimage_raw_cid"], json!(expected));
+    }
+
     // ── Prev-tip chaining tests ──────────────────────────────────
 
     #[test]
@@ -700,6 +1336,38 @@ mod tests {
         assert_eq!(result.wa.parents[0], "b3:prev_tip_abc");
     }
 
+    #[test]
+    fn foreign_prev_tip_issuer_recorded_in_wa_body() {
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let keys = KeyRing::dev();
+        let opts = RunOpts {
+            prev_tip: Some("b3:foreign_tip"),
+            prev_tip_issuer: Some("did:web:gate.partner.example"),
+            ghost: false,
+            keys: &keys,
+            seen: None,
+            logline: None,
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
+        };
+        let result = run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap();
+        assert_eq!(result.wa.parents[0], "b3:foreign_tip");
+        assert_eq!(
+            result.wa.body["prev_tip_issuer"],
+            json!("did:web:gate.partner.example")
+        );
+    }
+
+    #[test]
+    fn local_prev_tip_omits_issuer_key() {
+        // Absent key (not null) keeps pre-federation WA body CIDs stable
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let result =
+            run_with_receipts_simple(&manifest, &vars, &cfg, Some("b3:prev_tip_abc")).unwrap();
+        assert!(result.wa.body.get("prev_tip_issuer").is_none());
+    }
+
     // ── DENY on engine failure ────────────────────────────────────
 
     #[test]
@@ -725,11 +1393,12 @@ mod tests {
             pipeline: "test".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: false }, // will cause policy deny
+            policy: Policy { allow: false, rules: vec![] }, // will cause policy deny
         };
         let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8="))]);
         let cfg = ExecuteConfig {
             version: "0.1.0".into(),
+            codecs: Default::default(),
         };
 
         // Should NOT return Err — should produce a DENY WF receipt
@@ -740,6 +1409,91 @@ mod tests {
             .unwrap()
             .contains("policy deny"));
         assert!(result.wf.body["outputs_cid"].is_null());
+
+        // The cascade decision survives the deny as its own receipt
+        let policy = result.policy.as_ref().expect("policy receipt");
+        assert_eq!(policy.t, "ubl/policy");
+        assert_eq!(policy.body["decision"], "DENY");
+        assert!(policy.body["decided_by"].is_string());
+        assert_eq!(policy.parents, vec![result.wa.body_cid.clone()]);
+    }
+
+    // ── Policy receipt on the happy path ─────────────────────────
+
+    #[test]
+    fn run_emits_policy_receipt_parented_to_wa() {
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+
+        let policy = result.policy.as_ref().expect("policy receipt");
+        assert_eq!(policy.t, "ubl/policy");
+        assert_eq!(policy.body["decision"], "ALLOW");
+        assert!(policy.body["policy_set_cid"]
+            .as_str()
+            .unwrap()
+            .starts_with("b3:"));
+        assert_eq!(policy.parents, vec![result.wa.body_cid.clone()]);
+        assert!(verify_body_cid(policy).unwrap());
+        // Eval latency is observability-only: present, but outside the body
+        let obs = policy.observability.as_ref().expect("observability");
+        assert!(obs["eval_micros"].is_u64());
+        assert!(policy.body.get("eval_micros").is_none());
+    }
+
+    // ── Plugin codec witness ──────────────────────────────────────
+
+    struct Upper;
+
+    impl crate::codec::Codec for Upper {
+        fn name(&self) -> &str {
+            "text.upper"
+        }
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+        fn deterministic(&self) -> bool {
+            true
+        }
+        fn apply(&self, input: &serde_json::Value) -> crate::error::Result<serde_json::Value> {
+            Ok(json!(input.as_str().unwrap_or_default().to_uppercase()))
+        }
+    }
+
+    #[test]
+    fn plugin_codecs_are_pinned_in_the_transition_witness() {
+        let (mut manifest, vars, mut cfg) = test_manifest_vars_cfg();
+        manifest.out_grammar.mappings.push(crate::engine::Mapping {
+            from: "content".into(),
+            codec: "text.upper".into(),
+            to: "loud".into(),
+        });
+        manifest.out_grammar.output_from = "loud".into();
+        cfg.codecs.register(std::sync::Arc::new(Upper)).unwrap();
+
+        let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+        assert_eq!(result.wf.body["decision"], "ALLOW");
+        let witness = &result.transition.as_ref().unwrap().body["witness"];
+        assert_eq!(witness["codecs"], json!(["text.upper@1.0.0"]));
+    }
+
+    #[test]
+    fn builtin_only_runs_keep_their_witness_shape() {
+        // No "codecs" key ⇒ pre-plugin transition bodies keep their CIDs
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+        let witness = &result.transition.as_ref().unwrap().body["witness"];
+        assert_eq!(witness["vm"], "ubl-runtime@0.1.0");
+        assert!(witness.get("codecs").is_none());
+    }
+
+    #[test]
+    fn unregistered_plugin_codec_denies_like_any_unknown_codec() {
+        let (mut manifest, vars, cfg) = test_manifest_vars_cfg();
+        manifest.in_grammar.mappings[0].codec = "edifact.parse".into();
+        let result = run_with_receipts_simple(&manifest, &vars, &cfg, None).unwrap();
+        assert_eq!(result.wf.body["decision"], "DENY");
+        let reason = result.wf.body["reason"].as_str().unwrap();
+        assert!(reason.contains("unknown codec"), "got: {reason}");
     }
 
     // ── Key rotation test ────────────────────────────────────────
@@ -752,14 +1506,20 @@ mod tests {
             active_kid: "did:custom#k2".into(),
             next: Some(ed25519_dalek::SigningKey::from_bytes(&[99u8; 32])),
             next_kid: Some("did:custom#k3".into()),
+            cosigners: Vec::new(),
+            cosign_threshold: None,
         };
         let (manifest, vars, cfg) = test_manifest_vars_cfg();
         let opts = RunOpts {
             prev_tip: None,
+            prev_tip_issuer: None,
             ghost: false,
             keys: &keys,
             seen: None,
             logline: None,
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
         };
         let result = run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap();
         assert_eq!(result.wa.proof.kid, "did:custom#k2");
@@ -781,10 +1541,14 @@ mod tests {
         };
         let opts = RunOpts {
             prev_tip: None,
+            prev_tip_issuer: None,
             ghost: false,
             keys: &keys,
             seen: None,
             logline: Some(ctx),
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
         };
         let result = run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap();
 
@@ -832,10 +1596,14 @@ mod tests {
         };
         let opts = RunOpts {
             prev_tip: None,
+            prev_tip_issuer: None,
             ghost: true,
             keys: &keys,
             seen: None,
             logline: Some(ctx),
+            redaction: None,
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
         };
         let result = run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap();
         let obs = result.wa.observability.as_ref().unwrap();
@@ -843,6 +1611,128 @@ mod tests {
         assert_eq!(obs["logline"]["who"], "ghost-test");
     }
 
+    #[test]
+    fn system_clock_emits_valid_rfc3339() {
+        // Regression: the old hand-rolled formatter used 30-day months
+        let ts = SYSTEM_CLOCK.now_iso();
+        let parsed = chrono::DateTime::parse_from_rfc3339(&ts).unwrap();
+        assert_eq!(parsed.with_timezone(&chrono::Utc).to_rfc3339_opts(chrono::SecondsFormat::Secs, true), ts);
+    }
+
+    #[test]
+    fn fixed_clock_reproduces_identical_loglines() {
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let keys = KeyRing::dev();
+        let clock = FixedClock("2026-09-01T00:00:00Z".into());
+        let run = |vars: &_| {
+            let opts = RunOpts {
+                ghost: true,
+                keys: &keys,
+                logline: Some(LoglineContext {
+                    who: "replayer",
+                    actor_did: "did:dev#k1",
+                    where_: "unit-test",
+                    why: "ghost replay",
+                    context_id: "ctx-replay",
+                }),
+                redaction: None,
+                clock: &clock,
+                ..RunOpts::default()
+            };
+            run_with_receipts(&manifest, vars, &cfg, &opts).unwrap()
+        };
+        let (a, b) = (run(&vars), run(&vars));
+        assert_eq!(
+            a.wa.observability.as_ref().unwrap()["logline"],
+            b.wa.observability.as_ref().unwrap()["logline"]
+        );
+        assert_eq!(
+            a.wa.observability.as_ref().unwrap()["logline"]["when_iso"],
+            json!("2026-09-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn redaction_lists_compose_allow_then_deny() {
+        let mut ll = Logline::now(
+            &FixedClock("2026-09-01T00:00:00Z".into()),
+            "alice",
+            "did:ex:alice#k1",
+            "wa:write-ahead",
+            "gate",
+            "monthly payroll for alice",
+            "ctx-payroll",
+        );
+        let policy = RedactionPolicy {
+            allow: vec!["who".into(), "context_id".into()],
+            deny: vec!["context_id".into()],
+            hash_actor_did: false,
+        };
+        policy.apply(&mut ll);
+        assert_eq!(ll.who, "alice", "allowed field survives");
+        assert_eq!(ll.context_id, REDACTED, "deny beats allow");
+        assert_eq!(ll.why, REDACTED, "outside the allow-list");
+        assert_eq!(ll.r#where, REDACTED);
+        assert_eq!(ll.actor_did, REDACTED);
+        // System-generated fields are never redacted
+        assert_eq!(ll.what, "wa:write-ahead");
+        assert_eq!(ll.when_iso, "2026-09-01T00:00:00Z");
+    }
+
+    #[test]
+    fn redaction_scrubs_loglines_but_never_bodies() {
+        let (manifest, vars, cfg) = test_manifest_vars_cfg();
+        let keys = KeyRing::dev();
+        let clock = FixedClock("2026-09-01T00:00:00Z".into());
+        let policy = RedactionPolicy {
+            allow: vec![],
+            deny: vec!["who".into(), "why".into()],
+            hash_actor_did: true,
+        };
+        let run = |redaction: Option<&RedactionPolicy>| {
+            let opts = RunOpts {
+                ghost: true,
+                keys: &keys,
+                logline: Some(LoglineContext {
+                    who: "alice example",
+                    actor_did: "did:ex:alice#k1",
+                    where_: "gate",
+                    why: "payroll run for alice",
+                    context_id: "ctx-payroll",
+                }),
+                redaction,
+                clock: &clock,
+                ..RunOpts::default()
+            };
+            run_with_receipts(&manifest, &vars, &cfg, &opts).unwrap()
+        };
+        let plain = run(None);
+        let scrubbed = run(Some(&policy));
+
+        // Redaction lives entirely outside bodies: every CID is untouched
+        assert_eq!(plain.wa.body_cid, scrubbed.wa.body_cid);
+        assert_eq!(
+            plain.transition.as_ref().unwrap().body_cid,
+            scrubbed.transition.as_ref().unwrap().body_cid
+        );
+        assert_eq!(
+            plain.policy.as_ref().unwrap().body_cid,
+            scrubbed.policy.as_ref().unwrap().body_cid
+        );
+        assert_eq!(plain.tip_cid, scrubbed.tip_cid);
+
+        let obs = scrubbed.wa.observability.as_ref().unwrap();
+        assert_eq!(obs["ghost"], true, "ghost and redaction compose");
+        let ll = &obs["logline"];
+        assert_eq!(ll["who"], REDACTED);
+        assert_eq!(ll["why"], REDACTED);
+        assert_eq!(ll["where"], "gate", "undenied fields survive");
+        // Hashed, not redacted: the same actor stays correlatable
+        let hashed = ll["actor_did"].as_str().unwrap();
+        assert!(hashed.starts_with("b3:"));
+        assert_eq!(hashed, cid_b3(b"did:ex:alice#k1"));
+    }
+
     // ── Helper ────────────────────────────────────────────────────
 
     fn test_manifest_vars_cfg() -> (
@@ -871,11 +1761,12 @@ mod tests {
             pipeline: "test".into(),
             in_grammar: in_g,
             out_grammar: out_g,
-            policy: Policy { allow: true },
+            policy: Policy { allow: true, rules: vec![] },
         };
         let vars = BTreeMap::from([("input_data".into(), json!("aGVsbG8="))]);
         let cfg = ExecuteConfig {
             version: "0.1.0".into(),
+            codecs: Default::default(),
         };
         (manifest, vars, cfg)
     }
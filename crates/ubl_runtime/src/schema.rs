@@ -0,0 +1,236 @@
+//! Versioned body schemas for receipt types.
+//!
+//! Receipt bodies were free-form JSON. This registry pins what each
+//! `ubl/*` body must carry, versioned so a future shape can ship as `v2`
+//! without invalidating chains minted under `v1`. A body that names a
+//! registered type through its own tag (`"type"`, or `"t"` for
+//! transitions) is validated when the receipt is built or verified;
+//! bodies with no tag, or a tag outside the registry, stay legacy
+//! free-form. Bodies may carry an explicit `"schema_version"` — absent
+//! means `v1`, matching every body minted before the registry existed so
+//! their CIDs are untouched.
+//!
+//! The registry also serializes as JSON Schema documents for
+//! `GET /.well-known/ubl/schemas`.
+
+use serde_json::{json, Value};
+
+/// The body schema version this runtime mints and validates.
+pub const SCHEMA_V1: &str = "v1";
+
+/// JSON kinds a required field may be pinned to.
+#[derive(Clone, Copy)]
+enum Kind {
+    String,
+    Object,
+}
+
+impl Kind {
+    fn matches(&self, v: &Value) -> bool {
+        match self {
+            Kind::String => v.is_string(),
+            Kind::Object => v.is_object(),
+        }
+    }
+
+    fn as_json_schema_type(&self) -> &'static str {
+        match self {
+            Kind::String => "string",
+            Kind::Object => "object",
+        }
+    }
+}
+
+struct BodySchema {
+    /// Registered type tag, e.g. `ubl/wa`.
+    t: &'static str,
+    /// Which body key carries the tag (`type`, or `t` for transitions).
+    tag_key: &'static str,
+    required: &'static [(&'static str, Kind)],
+    /// Field restricted to a fixed set of values, if any.
+    enum_field: Option<(&'static str, &'static [&'static str])>,
+}
+
+static SCHEMAS: &[BodySchema] = &[
+    BodySchema {
+        t: "ubl/wa",
+        tag_key: "type",
+        required: &[("inputs_raw_cid", Kind::String), ("intention", Kind::Object)],
+        enum_field: None,
+    },
+    BodySchema {
+        t: "ubl/transition",
+        tag_key: "t",
+        required: &[
+            ("op", Kind::String),
+            ("from_layer", Kind::String),
+            ("to_layer", Kind::String),
+        ],
+        enum_field: None,
+    },
+    BodySchema {
+        t: "ubl/wf",
+        tag_key: "type",
+        required: &[("decision", Kind::String)],
+        enum_field: Some(("decision", &["ALLOW", "DENY"])),
+    },
+    BodySchema {
+        t: "ubl/policy",
+        tag_key: "type",
+        required: &[("decision", Kind::String)],
+        enum_field: None,
+    },
+    BodySchema {
+        t: "ubl/attestation",
+        tag_key: "type",
+        required: &[("action", Kind::String)],
+        enum_field: None,
+    },
+    BodySchema {
+        t: "ubl/saga",
+        tag_key: "type",
+        required: &[("phase", Kind::String)],
+        enum_field: Some(("phase", &["open", "close"])),
+    },
+];
+
+/// Validate a body against its registered schema, if it claims one.
+pub fn validate_body(body: &Value) -> crate::error::Result<()> {
+    let tag = body
+        .get("type")
+        .or_else(|| body.get("t"))
+        .and_then(|v| v.as_str());
+    let Some(tag) = tag else { return Ok(()) };
+    let Some(schema) = SCHEMAS.iter().find(|s| s.t == tag) else {
+        return Ok(());
+    };
+    let version = body
+        .get("schema_version")
+        .and_then(|v| v.as_str())
+        .unwrap_or(SCHEMA_V1);
+    if version != SCHEMA_V1 {
+        return Err(crate::error::RuntimeError::Validation(format!(
+            "unknown schema version '{version}' for '{tag}' body"
+        )));
+    }
+    for (field, kind) in schema.required {
+        match body.get(field) {
+            None => {
+                return Err(crate::error::RuntimeError::Validation(format!(
+                    "'{tag}' body is missing required field '{field}'"
+                )))
+            }
+            Some(v) if !kind.matches(v) => {
+                return Err(crate::error::RuntimeError::Validation(format!(
+                    "'{tag}' body field '{field}' must be a {}",
+                    kind.as_json_schema_type()
+                )))
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some((field, allowed)) = schema.enum_field {
+        if let Some(v) = body.get(field).and_then(|v| v.as_str()) {
+            if !allowed.contains(&v) {
+                return Err(crate::error::RuntimeError::Validation(format!(
+                    "'{tag}' body field '{field}' must be one of {allowed:?}, got '{v}'"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The registry as JSON Schema documents, keyed by receipt type — what
+/// the gate serves at `/.well-known/ubl/schemas`.
+pub fn schemas_json() -> Value {
+    let mut out = serde_json::Map::new();
+    for schema in SCHEMAS {
+        let mut properties = serde_json::Map::new();
+        properties.insert(
+            schema.tag_key.to_string(),
+            json!({"const": schema.t}),
+        );
+        let mut required = vec![Value::from(schema.tag_key)];
+        for (field, kind) in schema.required {
+            let mut prop = json!({"type": kind.as_json_schema_type()});
+            if let Some((enum_field, allowed)) = schema.enum_field {
+                if enum_field == *field {
+                    prop["enum"] = json!(allowed);
+                }
+            }
+            properties.insert(field.to_string(), prop);
+            required.push(Value::from(*field));
+        }
+        out.insert(
+            schema.t.to_string(),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$id": format!("{}@{}", schema.t, SCHEMA_V1),
+                "version": SCHEMA_V1,
+                "type": "object",
+                "required": required,
+                "properties": properties,
+            }),
+        );
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untagged_bodies_stay_free_form() {
+        assert!(validate_body(&json!({"a": 1})).is_ok());
+        assert!(validate_body(&json!({"type": "ubl/tombstone"})).is_ok());
+    }
+
+    #[test]
+    fn wa_body_requires_inputs_and_intention() {
+        let ok = json!({
+            "type": "ubl/wa",
+            "inputs_raw_cid": "b3:abc",
+            "intention": {"op": "execute", "pipeline": "p"},
+        });
+        assert!(validate_body(&ok).is_ok());
+        let err = validate_body(&json!({"type": "ubl/wa"})).unwrap_err();
+        assert!(err.to_string().contains("inputs_raw_cid"), "got: {err}");
+    }
+
+    #[test]
+    fn wf_decision_is_enumerated() {
+        let err = validate_body(&json!({"type": "ubl/wf", "decision": "MAYBE"})).unwrap_err();
+        assert!(err.to_string().contains("must be one of"), "got: {err}");
+        assert!(validate_body(&json!({"type": "ubl/wf", "decision": "DENY"})).is_ok());
+    }
+
+    #[test]
+    fn unknown_schema_version_is_rejected() {
+        let err = validate_body(&json!({
+            "type": "ubl/wf",
+            "decision": "ALLOW",
+            "schema_version": "v9",
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown schema version"), "got: {err}");
+    }
+
+    #[test]
+    fn registry_exports_json_schema_documents() {
+        let docs = schemas_json();
+        for t in [
+            "ubl/wa",
+            "ubl/transition",
+            "ubl/wf",
+            "ubl/policy",
+            "ubl/attestation",
+            "ubl/saga",
+        ] {
+            assert_eq!(docs[t]["version"], SCHEMA_V1, "missing schema for {t}");
+            assert!(docs[t]["required"].as_array().unwrap().len() > 1);
+        }
+        assert_eq!(docs["ubl/transition"]["properties"]["t"]["const"], "ubl/transition");
+    }
+}
@@ -0,0 +1,465 @@
+//! High-level embedding API: compose and run pipelines without a gate.
+//!
+//! [`run_with_receipts`](crate::receipt::run_with_receipts) is deliberately
+//! low-level: callers wire a [`KeyRing`], an idempotency seen-set, tip
+//! tracking and persistence themselves — glue that every embedder (the
+//! gate, `ublx --local`) has so far re-implemented. A [`Session`] owns
+//! that glue: it chains each run's tip into the next, rejects replays,
+//! and persists receipts and ingested blobs through a pluggable
+//! [`SessionStore`], so `session.execute(...)` is all an embedder needs.
+//!
+//! The runtime stays ledger-agnostic: [`MemoryStore`] covers tests and
+//! short-lived embedders, [`DirStore`] writes the same layout `ublx
+//! --local` workspaces use, and anything else can implement the trait.
+
+use crate::engine::{ExecuteConfig, Manifest};
+use crate::receipt::{KeyRing, RunOpts, RunResult, SYSTEM_CLOCK};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+/// Session-level failure: either the pipeline itself failed, or the
+/// backing store did. Pipeline errors keep their [`ErrorCode`] so
+/// embedders can match on codes exactly like gate SDKs do.
+///
+/// [`ErrorCode`]: crate::error::ErrorCode
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error(transparent)]
+    Runtime(#[from] crate::error::RuntimeError),
+    #[error("session store: {0}")]
+    Store(#[from] std::io::Error),
+}
+
+/// Where a session persists its artifacts. Receipts are signed JSON
+/// envelopes keyed by body CID; blobs are canonical NRF bytes keyed by
+/// content CID.
+pub trait SessionStore: Send {
+    /// Persist one signed receipt envelope under its body CID.
+    fn put_receipt(&mut self, body_cid: &str, bytes: &[u8]) -> std::io::Result<()>;
+    /// Persist ingested canonical NRF bytes under their content CID.
+    fn put_blob(&mut self, cid: &str, bytes: &[u8]) -> std::io::Result<()>;
+    /// Every stored receipt envelope, in no particular order.
+    fn receipts(&self) -> std::io::Result<Vec<Vec<u8>>>;
+}
+
+/// In-memory store: the default for tests and short-lived embedders.
+#[derive(Default)]
+pub struct MemoryStore {
+    receipts: BTreeMap<String, Vec<u8>>,
+    blobs: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemoryStore {
+    /// Fetch a stored blob by CID (inherent, not part of the trait:
+    /// sessions only ever write through the trait).
+    pub fn blob(&self, cid: &str) -> Option<&[u8]> {
+        self.blobs.get(cid).map(Vec::as_slice)
+    }
+}
+
+impl SessionStore for MemoryStore {
+    fn put_receipt(&mut self, body_cid: &str, bytes: &[u8]) -> std::io::Result<()> {
+        self.receipts.insert(body_cid.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn put_blob(&mut self, cid: &str, bytes: &[u8]) -> std::io::Result<()> {
+        self.blobs.insert(cid.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn receipts(&self) -> std::io::Result<Vec<Vec<u8>>> {
+        Ok(self.receipts.values().cloned().collect())
+    }
+}
+
+/// File-backed store using the `ublx --local` workspace layout
+/// (`receipts/<cid>.json`, `blobs/<cid>`), so a session directory stays
+/// readable by the CLI.
+pub struct DirStore {
+    root: PathBuf,
+}
+
+impl DirStore {
+    pub fn open(root: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let root = root.into();
+        for sub in ["receipts", "blobs"] {
+            std::fs::create_dir_all(root.join(sub))?;
+        }
+        Ok(Self { root })
+    }
+
+    /// CIDs contain ':' which is awkward in paths — same mapping the
+    /// ledger uses for tombstones.
+    fn safe(cid: &str) -> String {
+        cid.replace([':', '/'], "_")
+    }
+}
+
+impl SessionStore for DirStore {
+    fn put_receipt(&mut self, body_cid: &str, bytes: &[u8]) -> std::io::Result<()> {
+        std::fs::write(
+            self.root
+                .join("receipts")
+                .join(format!("{}.json", Self::safe(body_cid))),
+            bytes,
+        )
+    }
+
+    fn put_blob(&mut self, cid: &str, bytes: &[u8]) -> std::io::Result<()> {
+        std::fs::write(self.root.join("blobs").join(Self::safe(cid)), bytes)
+    }
+
+    fn receipts(&self) -> std::io::Result<Vec<Vec<u8>>> {
+        let mut out = Vec::new();
+        for entry in std::fs::read_dir(self.root.join("receipts"))? {
+            out.push(std::fs::read(entry?.path())?);
+        }
+        Ok(out)
+    }
+}
+
+/// Outcome of [`Session::ingest`]: the content CID and how many canonical
+/// NRF bytes were stored — the same pair the gate's `/v1/ingest` answers.
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestResult {
+    pub cid: String,
+    pub bytes_len: usize,
+}
+
+/// Receipt-chain summary computed over the session's store — the same
+/// shape the gate's `/v1/audit` report boils down to.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditSummary {
+    pub total_receipts: u64,
+    pub by_decision: BTreeMap<String, u64>,
+    /// Receipts whose body still hashes to their body CID.
+    pub valid: u64,
+    /// Unparseable envelopes or CID mismatches.
+    pub invalid: u64,
+}
+
+/// Builder for a [`Session`]; see [`Session::builder`].
+pub struct SessionBuilder {
+    store: Box<dyn SessionStore>,
+    keys: KeyRing,
+    chain: String,
+    ghost: bool,
+    redaction: Option<crate::receipt::RedactionPolicy>,
+    codecs: crate::codec::CodecRegistry,
+}
+
+impl SessionBuilder {
+    /// Signing keys for every receipt the session mints (default: dev key).
+    pub fn keyring(mut self, keys: KeyRing) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Name of the receipt chain, surfaced in each receipt's logline
+    /// context (default: "main").
+    pub fn chain(mut self, name: impl Into<String>) -> Self {
+        self.chain = name.into();
+        self
+    }
+
+    /// Ghost mode: runs mint receipts but nothing is persisted and the
+    /// tip does not advance (default: off).
+    pub fn ghost(mut self, ghost: bool) -> Self {
+        self.ghost = ghost;
+        self
+    }
+
+    /// Scrub logline fields on every receipt the session mints (default:
+    /// no redaction).
+    pub fn redaction(mut self, policy: crate::receipt::RedactionPolicy) -> Self {
+        self.redaction = Some(policy);
+        self
+    }
+
+    /// Plugin codecs available to every manifest the session executes
+    /// (default: built-ins only).
+    pub fn codecs(mut self, codecs: crate::codec::CodecRegistry) -> Self {
+        self.codecs = codecs;
+        self
+    }
+
+    pub fn build(self) -> Session {
+        Session {
+            store: self.store,
+            keys: self.keys,
+            chain: self.chain,
+            ghost: self.ghost,
+            redaction: self.redaction,
+            cfg: ExecuteConfig {
+                version: "0.1.0".into(),
+                codecs: self.codecs,
+            },
+            tip: None,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+/// A stateful handle over the receipt-first pipeline: executes manifests,
+/// chains tips, tracks idempotency, and persists through its store.
+pub struct Session {
+    store: Box<dyn SessionStore>,
+    keys: KeyRing,
+    chain: String,
+    ghost: bool,
+    redaction: Option<crate::receipt::RedactionPolicy>,
+    cfg: ExecuteConfig,
+    tip: Option<String>,
+    seen: HashSet<String>,
+}
+
+impl Session {
+    pub fn builder(store: impl SessionStore + 'static) -> SessionBuilder {
+        SessionBuilder {
+            store: Box::new(store),
+            keys: KeyRing::dev(),
+            chain: "main".into(),
+            ghost: false,
+            redaction: None,
+            codecs: crate::codec::CodecRegistry::default(),
+        }
+    }
+
+    /// Dev-keyed in-memory session — the shortest path to a first receipt.
+    pub fn in_memory() -> Session {
+        Session::builder(MemoryStore::default()).build()
+    }
+
+    /// Current tip of the chain (the last WF body CID), if any run has
+    /// landed yet.
+    pub fn tip(&self) -> Option<&str> {
+        self.tip.as_deref()
+    }
+
+    pub fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    /// Run one manifest through the full WA → Transition → Policy → WF
+    /// pipeline. On success the receipts are persisted, the idempotency
+    /// key is recorded, and the tip advances — re-running identical
+    /// inputs yields [`RuntimeError::Replay`].
+    ///
+    /// [`RuntimeError::Replay`]: crate::error::RuntimeError::Replay
+    pub fn execute(
+        &mut self,
+        manifest: &Manifest,
+        vars: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<RunResult, SessionError> {
+        let opts = RunOpts {
+            prev_tip: self.tip.as_deref(),
+            prev_tip_issuer: None,
+            ghost: self.ghost,
+            keys: &self.keys,
+            seen: Some(&self.seen),
+            logline: Some(crate::receipt::LoglineContext {
+                who: "session",
+                actor_did: &self.keys.active_kid,
+                where_: "embedded",
+                why: "session.execute",
+                context_id: &self.chain,
+            }),
+            redaction: self.redaction.as_ref(),
+            clock: &SYSTEM_CLOCK,
+            ctx: None,
+        };
+        let run = crate::receipt::run_with_receipts(manifest, vars, &self.cfg, &opts)?;
+        if !run.ghost {
+            for rc in [Some(&run.wa), run.transition.as_ref(), run.policy.as_ref(), Some(&run.wf)]
+                .into_iter()
+                .flatten()
+            {
+                self.store
+                    .put_receipt(&rc.body_cid, &serde_json::to_vec(rc).map_err(
+                        crate::error::RuntimeError::Json,
+                    )?)?;
+            }
+            // Same idempotency key the gate tracks: pipeline:inputs_raw_cid
+            if let Some(inputs_cid) = run.wa.body.get("inputs_raw_cid").and_then(|v| v.as_str()) {
+                self.seen
+                    .insert(format!("{}:{inputs_cid}", manifest.pipeline));
+            }
+            self.tip = Some(run.tip_cid.clone());
+        }
+        Ok(run)
+    }
+
+    /// Canonicalize a JSON value to NRF, store the bytes under their
+    /// content CID, and return both — the embedded equivalent of the
+    /// gate's `/v1/ingest` (floats, BOMs and other non-canonical input
+    /// are rejected the same way).
+    pub fn ingest(&mut self, value: &serde_json::Value) -> Result<IngestResult, SessionError> {
+        let nrf_val = ubl_ai_nrf1::nrf::json_to_nrf(value).map_err(|e| {
+            crate::error::RuntimeError::Codec {
+                name: "nrf".into(),
+                detail: e.to_string(),
+            }
+        })?;
+        let nrf_bytes = ubl_ai_nrf1::nrf::encode_to_vec(&nrf_val).map_err(|e| {
+            crate::error::RuntimeError::Codec {
+                name: "nrf".into(),
+                detail: format!("encode: {e}"),
+            }
+        })?;
+        let cid = ubl_ai_nrf1::nrf::cid_from_nrf_bytes(&nrf_bytes).to_string();
+        self.store.put_blob(&cid, &nrf_bytes)?;
+        Ok(IngestResult {
+            cid,
+            bytes_len: nrf_bytes.len(),
+        })
+    }
+
+    /// Walk every stored receipt and summarize decisions and integrity.
+    pub fn audit(&self) -> Result<AuditSummary, SessionError> {
+        let mut summary = AuditSummary::default();
+        for bytes in self.store.receipts()? {
+            let Ok(rc) = serde_json::from_slice::<crate::receipt::Receipt>(&bytes) else {
+                summary.invalid += 1;
+                continue;
+            };
+            summary.total_receipts += 1;
+            if let Some(d) = rc.body.get("decision").and_then(|d| d.as_str()) {
+                *summary.by_decision.entry(d.to_string()).or_insert(0) += 1;
+            }
+            match crate::receipt::verify_body_cid(&rc) {
+                Ok(true) => summary.valid += 1,
+                _ => summary.invalid += 1,
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Grammar, Mapping, Policy};
+    use serde_json::json;
+
+    fn manifest(pipeline: &str) -> Manifest {
+        let in_g = Grammar {
+            inputs: BTreeMap::from([("raw_b64".into(), json!(""))]),
+            mappings: vec![Mapping {
+                from: "raw_b64".into(),
+                codec: "base64.decode".into(),
+                to: "raw.bytes".into(),
+            }],
+            output_from: "raw.bytes".into(),
+        };
+        let out_g = Grammar {
+            inputs: BTreeMap::from([("content".into(), json!(""))]),
+            mappings: vec![],
+            output_from: "content".into(),
+        };
+        Manifest {
+            pipeline: pipeline.into(),
+            in_grammar: in_g,
+            out_grammar: out_g,
+            policy: Policy {
+                allow: true,
+                rules: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn session_chains_tips_and_rejects_replays() {
+        let mut session = Session::in_memory();
+        let m = manifest("session-test");
+
+        let vars1 = BTreeMap::from([("raw_b64".into(), json!("aGVsbG8="))]);
+        let r1 = session.execute(&m, &vars1).unwrap();
+        assert!(r1.wa.parents.is_empty(), "first run starts the chain");
+        assert_eq!(session.tip(), Some(r1.tip_cid.as_str()));
+
+        // The next run chains off the previous tip
+        let vars2 = BTreeMap::from([("raw_b64".into(), json!("d29ybGQ="))]);
+        let r2 = session.execute(&m, &vars2).unwrap();
+        assert_eq!(r2.wa.parents, vec![r1.tip_cid.clone()]);
+        assert_eq!(session.tip(), Some(r2.tip_cid.as_str()));
+
+        // Identical inputs replay
+        let err = session.execute(&m, &vars1).unwrap_err();
+        assert!(matches!(
+            err,
+            SessionError::Runtime(crate::error::RuntimeError::Replay { .. })
+        ));
+
+        // Four receipts per run, minus one: the runs share an identical
+        // policy decision, and a content-addressed store dedups it. Both
+        // WF receipts and the shared policy receipt carry ALLOW.
+        let audit = session.audit().unwrap();
+        assert_eq!(audit.total_receipts, 7);
+        assert_eq!(audit.invalid, 0);
+        assert_eq!(audit.by_decision.get("ALLOW"), Some(&3));
+    }
+
+    #[test]
+    fn ghost_sessions_persist_nothing_and_hold_the_tip() {
+        let mut session = Session::builder(MemoryStore::default())
+            .chain("ghost-chain")
+            .ghost(true)
+            .build();
+        let m = manifest("ghost-test");
+        let vars = BTreeMap::from([("raw_b64".into(), json!("aGVsbG8="))]);
+
+        let run = session.execute(&m, &vars).unwrap();
+        assert!(run.ghost);
+        assert!(session.tip().is_none(), "ghost runs never advance the tip");
+        assert_eq!(session.audit().unwrap().total_receipts, 0);
+
+        // Ghost runs don't poison idempotency either
+        session.execute(&m, &vars).unwrap();
+    }
+
+    #[test]
+    fn ingest_stores_canonical_bytes_and_rejects_floats() {
+        let mut session = Session::in_memory();
+        let r = session.ingest(&json!({"hello": "world"})).unwrap();
+        assert!(r.bytes_len > 0);
+        // Content addressing: the same value lands on the same CID
+        let again = session.ingest(&json!({"hello": "world"})).unwrap();
+        assert_eq!(again.cid, r.cid);
+
+        let err = session.ingest(&json!({"bad": 1.5})).unwrap_err();
+        assert!(matches!(
+            err,
+            SessionError::Runtime(crate::error::RuntimeError::Codec { .. })
+        ));
+    }
+
+    #[test]
+    fn dir_store_uses_the_workspace_layout() {
+        let root = std::env::temp_dir().join(format!(
+            "ubl-session-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let mut session = Session::builder(DirStore::open(&root).unwrap())
+            .keyring(KeyRing::from_seed([9u8; 32], "did:test#k1"))
+            .build();
+        let m = manifest("dir-test");
+        let vars = BTreeMap::from([("raw_b64".into(), json!("aGVsbG8="))]);
+        let run = session.execute(&m, &vars).unwrap();
+        let ingested = session.ingest(&json!({"kept": true})).unwrap();
+
+        let safe = run.tip_cid.replace([':', '/'], "_");
+        assert!(root.join("receipts").join(format!("{safe}.json")).exists());
+        assert!(root
+            .join("blobs")
+            .join(ingested.cid.replace([':', '/'], "_"))
+            .exists());
+        assert_eq!(session.audit().unwrap().total_receipts, 4);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,188 @@
+//! Bounded signing worker pool.
+//!
+//! Every execute mints several receipts, and their Ed25519 signatures were
+//! produced one after another on the request task. Signatures are detached
+//! from the body CID, so once the bodies exist the batch can be signed
+//! concurrently. The pool keeps a fixed set of worker threads
+//! (`UBL_SIGN_WORKERS`, default capped at 4) and fans a batch across them;
+//! Ed25519 is deterministic, so pooled output is bit-identical to inline
+//! signing.
+
+use crate::jws::{sign_detached_at, JwsDetached};
+use ed25519_dalek::SigningKey;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+
+struct Job {
+    index: usize,
+    payload: Vec<u8>,
+    key: SigningKey,
+    kid: String,
+    /// Batch-wide `issued_at` claim — read once per batch so every
+    /// receipt of a run carries the same timestamp.
+    issued_at: Option<u64>,
+    reply: mpsc::Sender<(usize, JwsDetached)>,
+}
+
+pub struct SigningPool {
+    tx: Mutex<mpsc::Sender<Job>>,
+    workers: usize,
+}
+
+impl SigningPool {
+    fn new(workers: usize) -> Self {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+        for _ in 0..workers {
+            let rx = Arc::clone(&rx);
+            std::thread::Builder::new()
+                .name("ubl-signer".into())
+                .spawn(move || loop {
+                    let job = rx.lock().unwrap().recv();
+                    let Ok(job) = job else { break };
+                    let proof =
+                        sign_detached_at(&job.payload, &job.key, &job.kid, job.issued_at);
+                    let _ = job.reply.send((job.index, proof));
+                })
+                .expect("spawn signing worker");
+        }
+        Self {
+            tx: Mutex::new(tx),
+            workers,
+        }
+    }
+
+    /// Process-wide pool, spawned lazily on first batch.
+    pub fn global() -> &'static SigningPool {
+        static POOL: OnceLock<SigningPool> = OnceLock::new();
+        POOL.get_or_init(|| SigningPool::new(pool_size()))
+    }
+
+    /// Sign a batch of `(payload, key, kid)` jobs. Results come back in
+    /// input order. The submitting thread hands all but the first job to
+    /// the workers and signs that one itself, so the worker wakeup
+    /// latency overlaps useful work instead of adding to it; a batch of
+    /// one is therefore plain inline signing.
+    pub fn sign_batch(&self, jobs: Vec<(Vec<u8>, SigningKey, String)>) -> Vec<JwsDetached> {
+        // One clock read per batch: receipts of a run share a timestamp
+        self.sign_batch_at(jobs, crate::clock::issued_at())
+    }
+
+    /// [`sign_batch`](Self::sign_batch) with an explicit `issued_at`
+    /// claim for every proof in the batch.
+    pub fn sign_batch_at(
+        &self,
+        jobs: Vec<(Vec<u8>, SigningKey, String)>,
+        issued_at: Option<u64>,
+    ) -> Vec<JwsDetached> {
+        let n = jobs.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        // Without real parallelism the handoff is pure overhead
+        if self.workers < 2 || n == 1 {
+            return jobs
+                .iter()
+                .map(|(payload, key, kid)| sign_detached_at(payload, key, kid, issued_at))
+                .collect();
+        }
+        let mut jobs = jobs.into_iter();
+        let (local_payload, local_key, local_kid) = jobs.next().expect("n > 0");
+        let (reply_tx, reply_rx) = mpsc::channel();
+        {
+            let tx = self.tx.lock().unwrap();
+            for (index, (payload, key, kid)) in jobs.enumerate() {
+                tx.send(Job {
+                    index: index + 1,
+                    payload,
+                    key,
+                    kid,
+                    issued_at,
+                    reply: reply_tx.clone(),
+                })
+                .expect("signing pool workers gone");
+            }
+        }
+        drop(reply_tx);
+        let mut out: Vec<Option<JwsDetached>> = (0..n).map(|_| None).collect();
+        out[0] = Some(sign_detached_at(
+            &local_payload,
+            &local_key,
+            &local_kid,
+            issued_at,
+        ));
+        for _ in 1..n {
+            let (index, proof) = reply_rx.recv().expect("signing worker dropped a job");
+            out[index] = Some(proof);
+        }
+        out.into_iter()
+            .map(|p| p.expect("every batch index signed"))
+            .collect()
+    }
+}
+
+fn pool_size() -> usize {
+    if let Ok(n) = std::env::var("UBL_SIGN_WORKERS") {
+        if let Ok(n) = n.parse::<usize>() {
+            return n.max(1);
+        }
+    }
+    // One worker per core up to 4; a single-core host gets a pool that
+    // sign_batch bypasses entirely
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    #[test]
+    fn batch_output_matches_inline_signing() {
+        let payloads: Vec<Vec<u8>> = (0..16)
+            .map(|i| format!("canonical body {i}").into_bytes())
+            .collect();
+        let inline: Vec<JwsDetached> = payloads
+            .iter()
+            .map(|p| sign_detached_at(p, &key(), "did:dev#k1", Some(1_700_000_000)))
+            .collect();
+        let jobs = payloads
+            .into_iter()
+            .map(|p| (p, key(), "did:dev#k1".to_string()))
+            .collect();
+        // A constructed pool so the worker path runs even on 1-core CI;
+        // a pinned claim so the comparison is clock-independent
+        let pooled = SigningPool::new(2).sign_batch_at(jobs, Some(1_700_000_000));
+        assert_eq!(inline, pooled, "pooled signatures must be bit-identical");
+    }
+
+    #[test]
+    fn batch_preserves_input_order() {
+        let jobs: Vec<(Vec<u8>, SigningKey, String)> = (0..8)
+            .map(|i| (vec![i as u8], key(), format!("did:dev#k{i}")))
+            .collect();
+        let proofs = SigningPool::new(2).sign_batch(jobs);
+        for (i, proof) in proofs.iter().enumerate() {
+            assert_eq!(proof.kid, format!("did:dev#k{i}"));
+        }
+    }
+
+    #[test]
+    fn empty_and_single_batches_work() {
+        assert!(SigningPool::global().sign_batch(vec![]).is_empty());
+        let one = SigningPool::global().sign_batch_at(
+            vec![(b"solo".to_vec(), key(), "did:dev#k1".to_string())],
+            None,
+        );
+        assert_eq!(one.len(), 1);
+        assert_eq!(
+            one[0],
+            sign_detached_at(b"solo", &key(), "did:dev#k1", None)
+        );
+    }
+}
@@ -28,6 +28,24 @@ impl Client {
         req.send().map_err(|e| format!("request failed: {e}"))
     }
 
+    /// GET with an optional `If-None-Match` header — used by `watch` so a
+    /// gate that serves ETags can answer 304 instead of the full registry.
+    fn get_conditional(
+        &self,
+        path: &str,
+        etag: Option<&str>,
+    ) -> Result<reqwest::blocking::Response, String> {
+        let url = format!("{}{}", self.base, path);
+        let mut req = self.http.get(&url);
+        if let Some(ref tok) = self.token {
+            req = req.bearer_auth(tok);
+        }
+        if let Some(tag) = etag {
+            req = req.header("if-none-match", tag);
+        }
+        req.send().map_err(|e| format!("request failed: {e}"))
+    }
+
     fn post(&self, path: &str, body: &Value) -> Result<reqwest::blocking::Response, String> {
         let url = format!("{}{}", self.base, path);
         let mut req = self.http.post(&url).json(body);
@@ -107,7 +125,9 @@ pub fn execute(client: &Client, manifest_path: &str, vars_path: &str, ghost: boo
         "ghost": ghost,
     });
 
-    let resp = client.post("/v1/execute", &body)?;
+    // The gate's default response is minimal; ask for the sections the
+    // summary below prints.
+    let resp = client.post("/v1/execute?include=receipts,artifacts", &body)?;
     let status = resp.status();
     let json: Value = resp.json().map_err(|e| format!("parse response: {e}"))?;
 
@@ -128,8 +148,12 @@ pub fn execute(client: &Client, manifest_path: &str, vars_path: &str, ghost: boo
             println!("{}", "  (ghost mode)".dimmed());
         }
 
-        // Show dimension stack if present
-        if let Some(dims) = json.get("dimension_stack").and_then(|d| d.as_array()) {
+        // Show dimension stack if present (top-level on pre-include gates)
+        if let Some(dims) = json
+            .pointer("/artifacts/dimension_stack")
+            .or_else(|| json.get("dimension_stack"))
+            .and_then(|d| d.as_array())
+        {
             let stack: Vec<&str> = dims.iter().filter_map(|d| d.as_str()).collect();
             println!("{} {}", "Stack:   ".dimmed(), stack.join(" → ").dimmed());
         }
@@ -149,8 +173,26 @@ pub fn execute(client: &Client, manifest_path: &str, vars_path: &str, ghost: boo
     } else {
         let code = status.as_u16();
         let detail = json.get("detail").or_else(|| json.get("error"))
+            .or_else(|| json.get("message"))
             .and_then(|d| d.as_str())
             .unwrap_or("unknown error");
+        // A replay conflict points at the receipt the original run
+        // produced — fetch and print it so the 409 is not opaque
+        if code == 409 {
+            if let Some(cid) = json
+                .pointer("/existing/wf_cid")
+                .and_then(|c| c.as_str())
+            {
+                println!("{} {}", "Already executed — prior result:".yellow(), cid.cyan());
+                if let Ok(resp) = client.get(&format!("/v1/receipt/{cid}")) {
+                    if resp.status().is_success() {
+                        if let Ok(prior) = resp.json::<Value>() {
+                            print_receipt(&prior);
+                        }
+                    }
+                }
+            }
+        }
         return Err(format!("HTTP {code}: {detail}"));
     }
 
@@ -209,8 +251,10 @@ pub fn receipts(client: &Client) -> Result<(), String> {
         };
         println!("{} ({})", label.bold(), entries.len());
         for (cid, receipt) in entries {
-            let decision = receipt.get("body")
-                .and_then(|b| b.get("decision"))
+            // Index entries carry decision at top level; full envelopes
+            // (older gates) keep it in the body
+            let decision = receipt.get("decision")
+                .or_else(|| receipt.get("body").and_then(|b| b.get("decision")))
                 .and_then(|d| d.as_str())
                 .unwrap_or("");
             let badge = match decision {
@@ -227,6 +271,110 @@ pub fn receipts(client: &Client) -> Result<(), String> {
     Ok(())
 }
 
+// ── watch ───────────────────────────────────────────────────────
+
+/// Poll the receipts registry and print receipts as they appear.
+/// With `fail_on_deny` the first DENY terminates the watch with an error,
+/// so the command can be used as a CI gate.
+pub fn watch(
+    client: &Client,
+    pipeline: Option<&str>,
+    decision: Option<&str>,
+    interval_secs: u64,
+    fail_on_deny: bool,
+) -> Result<(), String> {
+    let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut etag: Option<String> = None;
+    let mut first_poll = true;
+
+    loop {
+        let resp = client.get_conditional("/v1/receipts", etag.as_deref())?;
+        let status = resp.status();
+
+        if status.as_u16() == 304 {
+            // Registry unchanged since our last ETag — nothing new.
+            std::thread::sleep(std::time::Duration::from_secs(interval_secs));
+            continue;
+        }
+        if !status.is_success() {
+            return Err(format!("HTTP {}: failed to poll receipts", status.as_u16()));
+        }
+
+        etag = resp
+            .headers()
+            .get("etag")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let json: Value = resp.json().map_err(|e| format!("parse: {e}"))?;
+        let map = json.as_object().ok_or("expected object")?;
+
+        for (cid, receipt) in map {
+            if !seen.insert(cid.clone()) {
+                continue;
+            }
+            // The registry predating the watch is baseline, not news.
+            if first_poll {
+                continue;
+            }
+            // Index entries carry pipeline/decision at top level; full
+            // envelopes (older gates) keep them in the body
+            let body = receipt.get("body");
+            let rec_pipeline = receipt
+                .get("pipeline")
+                .or_else(|| body.and_then(|b| b.get("pipeline")))
+                .and_then(|p| p.as_str());
+            if let Some(want) = pipeline {
+                if rec_pipeline != Some(want) {
+                    continue;
+                }
+            }
+            let rec_decision = receipt
+                .get("decision")
+                .or_else(|| body.and_then(|b| b.get("decision")))
+                .and_then(|d| d.as_str())
+                .unwrap_or("");
+            if let Some(want) = decision {
+                if !rec_decision.eq_ignore_ascii_case(want) {
+                    continue;
+                }
+            }
+
+            let t = receipt.get("t").and_then(|t| t.as_str()).unwrap_or("?");
+            let badge = match rec_decision {
+                "ALLOW" => " ALLOW".green().bold(),
+                "DENY" => " DENY".red().bold(),
+                "" => "".normal(),
+                d => format!(" {d}").yellow().bold(),
+            };
+            let pipe = rec_pipeline
+                .map(|p| format!(" [{p}]"))
+                .unwrap_or_default();
+            println!(
+                "{} {}{}{} {}",
+                "▸".dimmed(),
+                t.cyan(),
+                pipe.dimmed(),
+                badge,
+                cid.dimmed()
+            );
+
+            if fail_on_deny && rec_decision == "DENY" {
+                return Err(format!("DENY observed: {cid}"));
+            }
+        }
+
+        if first_poll {
+            println!(
+                "{} watching from {} existing receipt(s)…",
+                "●".green(),
+                seen.len()
+            );
+            first_poll = false;
+        }
+        std::thread::sleep(std::time::Duration::from_secs(interval_secs));
+    }
+}
+
 // ── transition ──────────────────────────────────────────────────
 
 pub fn transition(client: &Client, cid: &str) -> Result<(), String> {
@@ -312,6 +460,12 @@ pub fn audit(client: &Client) -> Result<(), String> {
         return Err(format!("HTTP {code}: {detail}"));
     }
 
+    print_audit(&json);
+    Ok(())
+}
+
+/// Render an audit report (shared between gate and --local mode).
+pub fn print_audit(json: &Value) {
     // Summary
     if let Some(summary) = json.get("summary") {
         println!("{}", "Audit Summary".bold());
@@ -342,8 +496,6 @@ pub fn audit(client: &Client) -> Result<(), String> {
             println!("  {} {} valid, {} invalid", "\u{2717}".red(), valid, invalid);
         }
     }
-
-    Ok(())
 }
 
 // ── resolve ─────────────────────────────────────────────────────
@@ -393,9 +545,246 @@ pub fn cid(file: &str) -> Result<(), String> {
     Ok(())
 }
 
+// ── nrf encode/decode ───────────────────────────────────────────
+
+/// Canonically encode JSON the way the gate does before hashing:
+/// JSON → NRF → bytes → CID. Prints the exact bytes (hex) so CID
+/// mismatches can be debugged without a gate.
+pub fn nrf_encode(file: &str) -> Result<(), String> {
+    let text = if file == "-" {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .map_err(|e| format!("read stdin: {e}"))?;
+        buf
+    } else {
+        fs::read_to_string(file).map_err(|e| format!("read file: {e}"))?
+    };
+    let payload: Value =
+        serde_json::from_str(&text).map_err(|e| format!("parse JSON: {e}"))?;
+    let nrf_val = ubl_ai_nrf1::nrf::json_to_nrf(&payload).map_err(|e| e.to_string())?;
+    let bytes =
+        ubl_ai_nrf1::nrf::encode_to_vec(&nrf_val).map_err(|e| format!("NRF encode: {e}"))?;
+    let cid = ubl_ai_nrf1::nrf::cid_from_nrf_bytes(&bytes);
+
+    println!("{} {}", "CID:  ".dimmed(), cid.to_string().cyan());
+    println!("{} {}", "Bytes:".dimmed(), bytes.len());
+    println!("{}", hex::encode(&bytes));
+    Ok(())
+}
+
+/// Decode an NRF file back into its JSON view, printing the CID the
+/// bytes hash to.
+pub fn nrf_decode(file: &str) -> Result<(), String> {
+    let bytes = fs::read(file).map_err(|e| format!("read file: {e}"))?;
+    let nrf_val =
+        ubl_ai_nrf1::nrf::decode_from_slice(&bytes).map_err(|e| format!("NRF decode: {e}"))?;
+    let cid = ubl_ai_nrf1::nrf::cid_from_nrf_bytes(&bytes);
+
+    println!("{} {}", "CID:".dimmed(), cid.to_string().cyan());
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&nrf_to_json(&nrf_val)).unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// JSON view of an NRF value (bytes render as 0x-prefixed hex, matching
+/// the gate's `/cid/{cid}.json` endpoint).
+fn nrf_to_json(v: &ubl_ai_nrf1::nrf::NrfValue) -> Value {
+    use ubl_ai_nrf1::nrf::NrfValue;
+    match v {
+        NrfValue::Null => Value::Null,
+        NrfValue::Bool(b) => Value::Bool(*b),
+        NrfValue::Int(i) => serde_json::json!(*i),
+        NrfValue::String(s) => Value::String(s.clone()),
+        NrfValue::Bytes(b) => Value::String(format!("0x{}", hex::encode(b))),
+        NrfValue::Array(arr) => Value::Array(arr.iter().map(nrf_to_json).collect()),
+        NrfValue::Map(map) => {
+            let mut obj = serde_json::Map::new();
+            for (k, v) in map {
+                obj.insert(k.clone(), nrf_to_json(v));
+            }
+            Value::Object(obj)
+        }
+    }
+}
+
+// ── admin migrate-store ─────────────────────────────────────────
+
+/// Re-shard the blob store under `dir` onto digest-based directories.
+/// Every relocated blob is verified against its CID during the move;
+/// corrupt blobs are left in place and reported.
+pub fn migrate_store(dir: &str) -> Result<(), String> {
+    std::env::set_current_dir(dir)
+        .map_err(|e| format!("read directory {dir}: {e}"))?;
+    let report = ubl_ledger::migrate_store()
+        .map_err(|e| format!("migrate store: {e}"))?;
+
+    println!("{}", "Store Migration".bold());
+    println!("  {} {}", "Scanned:".dimmed(), report.scanned);
+    println!("  {} {}", "Moved:".dimmed(), report.moved);
+    println!("  {} {}", "Already placed:".dimmed(), report.already_placed);
+    println!("  {} {}", "Skipped:".dimmed(), report.skipped);
+    if report.corrupt > 0 {
+        println!(
+            "  {} {} (left in place for forensics)",
+            "Corrupt:".red().bold(),
+            report.corrupt
+        );
+        return Err(format!("{} blob(s) failed CID verification", report.corrupt));
+    }
+    println!("{} store migrated", "✓".green().bold());
+    Ok(())
+}
+
+// ── lint ────────────────────────────────────────────────────────
+
+pub fn lint(file: &str) -> Result<(), String> {
+    let bytes = fs::read(file)
+        .map_err(|e| format!("read file: {e}"))?;
+    let report = rb_vm::lint_chip(&bytes);
+
+    println!(
+        "{} {} instruction(s), worst-case fuel {}",
+        "Chip:".bold(),
+        report.instr_count,
+        report.worst_case_fuel
+    );
+
+    for d in &report.diagnostics {
+        let badge = match d.severity {
+            rb_vm::Severity::Error => "error".red().bold(),
+            rb_vm::Severity::Warning => "warning".yellow().bold(),
+        };
+        let loc = d.instr_index
+            .map(|i| format!("[{i}] "))
+            .unwrap_or_default();
+        println!("  {badge} {}{} ({})", loc.dimmed(), d.message, d.code.dimmed());
+    }
+
+    if report.has_errors() {
+        return Err("lint found errors".into());
+    }
+    if report.diagnostics.is_empty() {
+        println!("{} no issues found", "✓".green().bold());
+    }
+    Ok(())
+}
+
+// ── chip ────────────────────────────────────────────────────────
+
+pub fn chip_asm(file: &str, out: Option<&str>) -> Result<(), String> {
+    let text = if file == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)
+            .map_err(|e| format!("read stdin: {e}"))?;
+        buf
+    } else {
+        fs::read_to_string(file)
+            .map_err(|e| format!("read file: {e}"))?
+    };
+    let chip = rb_vm::assemble(&text).map_err(|e| format!("assemble: {e}"))?;
+    let report = rb_vm::lint_chip(&chip);
+    match out {
+        Some(path) => {
+            fs::write(path, &chip).map_err(|e| format!("write {path}: {e}"))?;
+            println!(
+                "{} {} instruction(s), {} bytes → {}",
+                "✓".green().bold(),
+                report.instr_count,
+                chip.len(),
+                path.cyan()
+            );
+        }
+        None => println!("{}", hex::encode(&chip)),
+    }
+    Ok(())
+}
+
+pub fn chip_dis(file: &str) -> Result<(), String> {
+    let bytes = fs::read(file)
+        .map_err(|e| format!("read file: {e}"))?;
+    // Stream line by line: a decode error mid-chip still prints every
+    // instruction before it, which is where the broken byte usually is
+    for line in rb_vm::Disasm::new(&bytes) {
+        println!("{}", line.map_err(|e| format!("disassemble: {e}"))?);
+    }
+    Ok(())
+}
+
+pub fn chip_run(
+    file: &str,
+    inputs_path: Option<&str>,
+    fuel: u64,
+    ghost: bool,
+    cas_dir: &str,
+) -> Result<(), String> {
+    use rb_vm::providers::cas_fs::FsCas;
+    use rb_vm::providers::sign_env::EnvSigner;
+    use rb_vm::CasProvider;
+
+    let bytes = fs::read(file)
+        .map_err(|e| format!("read file: {e}"))?;
+    let chip = rb_vm::DecodedChip::decode(&bytes)
+        .map_err(|e| format!("decode chip: {e}"))?;
+
+    let mut cas = FsCas::new(cas_dir);
+    let input_cids: Vec<rb_vm::Cid> = match inputs_path {
+        Some(path) => {
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("read inputs: {e}"))?;
+            let inputs: Vec<Value> = serde_json::from_str(&content)
+                .map_err(|e| format!("parse inputs (expected JSON array): {e}"))?;
+            inputs
+                .iter()
+                .map(|v| cas.put(&serde_json::to_vec(v).unwrap_or_default()))
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    // Local dev signer — same seed/kid the gate uses in dev mode.
+    let signer = EnvSigner::from_seed_bytes("did:dev#k1", [7u8; 32]);
+    let canon = rb_vm::canon::NaiveCanon;
+    let cfg = rb_vm::VmConfig { fuel_limit: fuel, ghost, trace: false, checked_arith: false };
+
+    let mut vm = rb_vm::Vm::new(cfg, cas, &signer, canon, input_cids);
+    let outcome = vm.run(&chip.instrs()).map_err(|e| format!("run: {e}"))?;
+
+    println!(
+        "{} {} step(s), fuel {}/{}{}",
+        "✓".green().bold(),
+        outcome.steps,
+        outcome.fuel_used,
+        fuel,
+        if ghost { " (ghost)".dimmed().to_string() } else { String::new() }
+    );
+
+    match outcome.rc_cid {
+        Some(cid) => {
+            println!("{} {}", "RC CID:".dimmed(), cid.0.cyan());
+            // Ghost RCs are never persisted, so only look them up otherwise
+            let rc_bytes = FsCas::new(cas_dir).get(&cid);
+            match rc_bytes.and_then(|b| serde_json::from_slice::<Value>(&b).ok()) {
+                Some(payload) => {
+                    let pretty = serde_json::to_string_pretty(&payload).unwrap_or_default();
+                    for line in pretty.lines() {
+                        println!("  {}", line.dimmed());
+                    }
+                }
+                None if ghost => println!("  {}", "(ghost RC not persisted)".dimmed()),
+                None => println!("  {}", "(RC payload not found in CAS)".yellow()),
+            }
+        }
+        None => println!("{}", "no RC emitted".dimmed()),
+    }
+    Ok(())
+}
+
 // ── helpers ─────────────────────────────────────────────────────
 
-fn print_receipt(receipt: &Value) {
+pub fn print_receipt(receipt: &Value) {
     let t = receipt.get("t").and_then(|t| t.as_str()).unwrap_or("?");
     let cid = receipt.get("body_cid").and_then(|c| c.as_str()).unwrap_or("?");
 
@@ -0,0 +1,246 @@
+//! Embedded gate mode: execute/ingest/receipt/audit without a server.
+//!
+//! `ublx --local` constructs the runtime, a file-backed ledger, and a local
+//! KeyRing in-process. Everything lives under a workspace directory:
+//!
+//! ```text
+//! <workspace>/receipts/<cid>.json   signed receipt envelopes
+//! <workspace>/blobs/<cid>           ingested NRF payloads
+//! <workspace>/state.json            last tip + idempotency keys
+//! <workspace>/keyring.json          optional {seed_hex, kid} (default: dev key)
+//! ```
+//!
+//! Receipts are produced by the same `ubl_runtime::run_with_receipts`
+//! pipeline the gate uses, so the format is byte-for-byte identical.
+
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use ubl_ai_nrf1::nrf::{cid_from_nrf_bytes, encode_to_vec, json_to_nrf};
+
+pub struct Workspace {
+    root: PathBuf,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct LocalState {
+    last_tip: Option<String>,
+    seen: HashSet<String>,
+}
+
+impl Workspace {
+    pub fn open(root: &str) -> Result<Self, String> {
+        let root = PathBuf::from(root);
+        for sub in ["receipts", "blobs"] {
+            fs::create_dir_all(root.join(sub))
+                .map_err(|e| format!("create workspace: {e}"))?;
+        }
+        Ok(Self { root })
+    }
+
+    fn receipt_path(&self, cid: &str) -> PathBuf {
+        // CIDs contain ':' which is awkward in paths — same mapping the
+        // ledger uses for tombstones
+        let safe: String = cid.replace([':', '/'], "_");
+        self.root.join("receipts").join(format!("{safe}.json"))
+    }
+
+    fn state(&self) -> LocalState {
+        fs::read(self.root.join("state.json"))
+            .ok()
+            .and_then(|b| serde_json::from_slice(&b).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_state(&self, state: &LocalState) -> Result<(), String> {
+        let bytes = serde_json::to_vec_pretty(state).map_err(|e| e.to_string())?;
+        fs::write(self.root.join("state.json"), bytes)
+            .map_err(|e| format!("write state: {e}"))
+    }
+
+    /// Local signing keys: `keyring.json` ({seed_hex, kid}) when present,
+    /// otherwise the same dev key the gate uses out of the box.
+    fn keyring(&self) -> Result<ubl_runtime::KeyRing, String> {
+        let path = self.root.join("keyring.json");
+        if !path.exists() {
+            return Ok(ubl_runtime::KeyRing::dev());
+        }
+        let v: Value = serde_json::from_slice(
+            &fs::read(&path).map_err(|e| format!("read keyring: {e}"))?,
+        )
+        .map_err(|e| format!("parse keyring: {e}"))?;
+        let seed_hex = v
+            .get("seed_hex")
+            .and_then(|s| s.as_str())
+            .ok_or("keyring.json: missing seed_hex")?;
+        let kid = v
+            .get("kid")
+            .and_then(|k| k.as_str())
+            .unwrap_or("did:local#k1");
+        let seed_bytes = hex::decode(seed_hex)
+            .map_err(|e| format!("keyring.json: bad seed_hex: {e}"))?;
+        let seed: [u8; 32] = seed_bytes
+            .try_into()
+            .map_err(|_| "keyring.json: seed_hex must be 32 bytes".to_string())?;
+        Ok(ubl_runtime::KeyRing::from_seed(seed, kid))
+    }
+
+    fn store_receipt(&self, receipt: &ubl_runtime::Receipt) -> Result<(), String> {
+        let val = serde_json::to_value(receipt).map_err(|e| e.to_string())?;
+        let bytes = serde_json::to_vec_pretty(&val).map_err(|e| e.to_string())?;
+        fs::write(self.receipt_path(&receipt.body_cid), bytes)
+            .map_err(|e| format!("write receipt: {e}"))
+    }
+}
+
+fn read_input(path: &str) -> Result<String, String> {
+    if path == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)
+            .map_err(|e| format!("read stdin: {e}"))?;
+        Ok(buf)
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| format!("read file: {e}"))
+    }
+}
+
+// ── execute ─────────────────────────────────────────────────────
+
+pub fn execute(ws: &Workspace, manifest_path: &str, vars_path: &str, ghost: bool) -> Result<(), String> {
+    let manifest: ubl_runtime::Manifest = serde_json::from_str(
+        &fs::read_to_string(manifest_path).map_err(|e| format!("read manifest: {e}"))?,
+    )
+    .map_err(|e| format!("parse manifest: {e}"))?;
+    let vars: BTreeMap<String, Value> = serde_json::from_str(&read_input(vars_path)?)
+        .map_err(|e| format!("parse vars: {e}"))?;
+
+    let cfg = ubl_runtime::ExecuteConfig { version: "0.1.0".into(), codecs: Default::default() };
+    let keys = ws.keyring()?;
+    let mut state = ws.state();
+
+    let opts = ubl_runtime::RunOpts {
+        prev_tip: state.last_tip.as_deref(),
+        prev_tip_issuer: None,
+        ghost,
+        keys: &keys,
+        seen: Some(&state.seen),
+        logline: None,
+        redaction: None,
+        clock: &ubl_runtime::SYSTEM_CLOCK,
+        ctx: None,
+    };
+
+    let run = ubl_runtime::run_with_receipts(&manifest, &vars, &cfg, &opts)
+        .map_err(|e| format!("runtime: {e}"))?;
+
+    if !run.ghost {
+        ws.store_receipt(&run.wa)?;
+        if let Some(ref tr) = run.transition {
+            ws.store_receipt(tr)?;
+        }
+        if let Some(ref pol) = run.policy {
+            ws.store_receipt(pol)?;
+        }
+        ws.store_receipt(&run.wf)?;
+
+        // Same idempotency key the gate tracks: pipeline:inputs_raw_cid
+        let inputs_cid = run
+            .wa
+            .body
+            .get("inputs_raw_cid")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        state.seen.insert(format!("{}:{inputs_cid}", manifest.pipeline));
+        state.last_tip = Some(run.tip_cid.clone());
+        ws.save_state(&state)?;
+    }
+
+    let decision = run.wf.body.get("decision").and_then(|d| d.as_str()).unwrap_or("?");
+    let badge = match decision {
+        "ALLOW" => "ALLOW".green().bold(),
+        "DENY" => "DENY".red().bold(),
+        _ => decision.yellow().bold(),
+    };
+    println!("{} {}", "Decision:".dimmed(), badge);
+    println!("{} {}", "Tip CID: ".dimmed(), run.tip_cid.cyan());
+    if run.ghost {
+        println!("{}", "  (ghost mode — nothing persisted)".dimmed());
+    }
+    println!();
+    println!("{}", "Receipts:".bold());
+    let rows = [
+        ("  WA", Some(&run.wa)),
+        ("  TR", run.transition.as_ref()),
+        ("  PO", run.policy.as_ref()),
+        ("  WF", Some(&run.wf)),
+    ];
+    for (label, rc) in rows {
+        if let Some(rc) = rc {
+            println!("  {} {} {}", label.blue(), rc.t.dimmed(), rc.body_cid.dimmed());
+        }
+    }
+    Ok(())
+}
+
+// ── ingest ──────────────────────────────────────────────────────
+
+pub fn ingest(ws: &Workspace, file: &str) -> Result<(), String> {
+    let payload: Value = serde_json::from_str(&read_input(file)?)
+        .map_err(|e| format!("parse JSON: {e}"))?;
+    let nrf_val = json_to_nrf(&payload).map_err(|e| format!("NRF: {e}"))?;
+    let nrf_bytes = encode_to_vec(&nrf_val).map_err(|e| format!("NRF encode: {e}"))?;
+    let cid = cid_from_nrf_bytes(&nrf_bytes);
+    let safe: String = cid.to_string().replace([':', '/'], "_");
+    fs::write(ws.root.join("blobs").join(safe), &nrf_bytes)
+        .map_err(|e| format!("write blob: {e}"))?;
+    println!("{} {}", "CID:   ".dimmed(), cid.to_string().cyan());
+    println!("{} {} bytes", "Stored:".dimmed(), nrf_bytes.len());
+    Ok(())
+}
+
+// ── receipt ─────────────────────────────────────────────────────
+
+pub fn receipt(ws: &Workspace, cid: &str) -> Result<Value, String> {
+    let bytes = fs::read(ws.receipt_path(cid))
+        .map_err(|_| format!("receipt not found: {cid}"))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("parse receipt: {e}"))
+}
+
+// ── audit ───────────────────────────────────────────────────────
+
+/// Same shape as the gate's /v1/audit report, computed over the workspace.
+pub fn audit_report(ws: &Workspace) -> Result<Value, String> {
+    let dir = ws.root.join("receipts");
+    let mut total = 0u64;
+    let mut by_decision: BTreeMap<String, u64> = BTreeMap::new();
+    let mut valid = 0u64;
+    let mut invalid = 0u64;
+
+    for entry in fs::read_dir(&dir).map_err(|e| format!("read workspace: {e}"))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let Ok(rc) = serde_json::from_slice::<ubl_runtime::Receipt>(
+            &fs::read(entry.path()).map_err(|e| e.to_string())?,
+        ) else {
+            invalid += 1;
+            continue;
+        };
+        total += 1;
+        if let Some(d) = rc.body.get("decision").and_then(|d| d.as_str()) {
+            *by_decision.entry(d.to_string()).or_insert(0) += 1;
+        }
+        match ubl_runtime::verify_body_cid(&rc) {
+            Ok(true) => valid += 1,
+            _ => invalid += 1,
+        }
+    }
+
+    Ok(json!({
+        "summary": { "total_receipts": total },
+        "by_decision": by_decision,
+        "integrity": { "valid": valid, "invalid": invalid },
+    }))
+}
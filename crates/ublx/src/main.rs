@@ -3,6 +3,7 @@ use colored::Colorize;
 use std::process;
 
 mod commands;
+mod local;
 
 /// Standardized exit codes for CLI.
 /// 0 = OK, 2 = input error, 3 = conflict (409), 4 = auth (401/403), 5 = rate limit (429), 1 = other.
@@ -25,6 +26,14 @@ struct Cli {
     #[arg(long, env = "UBL_TOKEN")]
     token: Option<String>,
 
+    /// Run against a local workspace instead of a gate server
+    #[arg(long)]
+    local: bool,
+
+    /// Workspace directory for --local mode
+    #[arg(long, env = "UBL_WORKSPACE", default_value = ".ubl")]
+    workspace: String,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -82,6 +91,107 @@ enum Commands {
         /// Path to file
         file: String,
     },
+    /// Statically lint a TLV chip file (no execution)
+    Lint {
+        /// Path to TLV chip file
+        file: String,
+    },
+    /// Inspect NRF canonical encoding (the bytes the gate hashes)
+    Nrf {
+        #[command(subcommand)]
+        command: NrfCommands,
+    },
+    /// Chip development workflow: assemble, disassemble, lint, run locally
+    Chip {
+        #[command(subcommand)]
+        command: ChipCommands,
+    },
+    /// Operator tooling for the local store (no gate required)
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommands,
+    },
+    /// Watch the chain, printing new receipts as they commit
+    Watch {
+        /// Only show receipts from this pipeline
+        #[arg(long)]
+        pipeline: Option<String>,
+        /// Only show receipts with this decision (ALLOW/DENY)
+        #[arg(long)]
+        decision: Option<String>,
+        /// Poll interval in seconds
+        #[arg(long, default_value = "2")]
+        interval: u64,
+        /// Exit non-zero on the first DENY (CI gate mode)
+        #[arg(long)]
+        fail_on_deny: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AdminCommands {
+    /// Re-shard the blob store onto digest-based directories,
+    /// verifying every blob's CID during the move
+    MigrateStore {
+        /// Ledger root directory (the parent of `store/`)
+        #[arg(long, default_value = ".")]
+        dir: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum NrfCommands {
+    /// Canonically encode JSON to NRF: hex bytes + CID
+    Encode {
+        /// Path to JSON file (or - for stdin)
+        #[arg(default_value = "-")]
+        file: String,
+    },
+    /// Decode an NRF bytes file back into its JSON view
+    Decode {
+        /// Path to NRF bytes file
+        file: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ChipCommands {
+    /// Assemble chip text into TLV bytecode
+    Asm {
+        /// Path to chip assembly text (or - for stdin)
+        #[arg(default_value = "-")]
+        file: String,
+        /// Output path for the TLV chip (default: stdout as hex)
+        #[arg(long, short)]
+        out: Option<String>,
+    },
+    /// Disassemble a TLV chip back into text
+    Dis {
+        /// Path to TLV chip file
+        file: String,
+    },
+    /// Statically lint a TLV chip (same as top-level `lint`)
+    Lint {
+        /// Path to TLV chip file
+        file: String,
+    },
+    /// Run a chip locally in rb_vm with a filesystem CAS
+    Run {
+        /// Path to TLV chip file
+        file: String,
+        /// Path to inputs JSON array file
+        #[arg(long)]
+        inputs: Option<String>,
+        /// Fuel limit
+        #[arg(long, default_value = "50000")]
+        fuel: u64,
+        /// Run in ghost mode (RC not persisted)
+        #[arg(long)]
+        ghost: bool,
+        /// CAS root directory
+        #[arg(long, default_value = ".ubl-cas")]
+        cas: String,
+    },
 }
 
 /// Map error strings to exit codes based on HTTP status patterns.
@@ -99,8 +209,50 @@ fn exit_code_for(err: &str) -> i32 {
     }
 }
 
+/// Run a command against the embedded in-process gate (--local).
+fn run_local(workspace: &str, command: Commands) -> Result<(), String> {
+    let ws = local::Workspace::open(workspace)?;
+    match command {
+        Commands::Execute { manifest, vars, ghost } => {
+            local::execute(&ws, &manifest, &vars, ghost)
+        }
+        Commands::Ingest { file, certify: _ } => local::ingest(&ws, &file),
+        Commands::Receipt { cid } => {
+            let rc = local::receipt(&ws, &cid)?;
+            commands::print_receipt(&rc);
+            Ok(())
+        }
+        Commands::Audit => {
+            let report = local::audit_report(&ws)?;
+            commands::print_audit(&report);
+            Ok(())
+        }
+        // File-only commands work the same with or without a gate
+        Commands::Verify { file } => commands::verify(&file),
+        Commands::Cid { file } => commands::cid(&file),
+        Commands::Lint { file } => commands::lint(&file),
+        Commands::Nrf { command } => match command {
+            NrfCommands::Encode { file } => commands::nrf_encode(&file),
+            NrfCommands::Decode { file } => commands::nrf_decode(&file),
+        },
+        Commands::Admin { command } => match command {
+            AdminCommands::MigrateStore { dir } => commands::migrate_store(&dir),
+        },
+        _ => Err("this command is not supported in --local mode".into()),
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
+
+    if cli.local {
+        if let Err(e) = run_local(&cli.workspace, cli.command) {
+            eprintln!("{} {}", "error:".red().bold(), e);
+            process::exit(exit_code_for(&e));
+        }
+        return;
+    }
+
     let client = commands::Client::new(&cli.gate, cli.token.as_deref());
 
     let result = match cli.command {
@@ -116,6 +268,25 @@ fn main() {
         Commands::Verify { file } => commands::verify(&file),
         Commands::Health => commands::health(&client),
         Commands::Cid { file } => commands::cid(&file),
+        Commands::Lint { file } => commands::lint(&file),
+        Commands::Nrf { command } => match command {
+            NrfCommands::Encode { file } => commands::nrf_encode(&file),
+            NrfCommands::Decode { file } => commands::nrf_decode(&file),
+        },
+        Commands::Admin { command } => match command {
+            AdminCommands::MigrateStore { dir } => commands::migrate_store(&dir),
+        },
+        Commands::Chip { command } => match command {
+            ChipCommands::Asm { file, out } => commands::chip_asm(&file, out.as_deref()),
+            ChipCommands::Dis { file } => commands::chip_dis(&file),
+            ChipCommands::Lint { file } => commands::lint(&file),
+            ChipCommands::Run { file, inputs, fuel, ghost, cas } => {
+                commands::chip_run(&file, inputs.as_deref(), fuel, ghost, &cas)
+            }
+        },
+        Commands::Watch { pipeline, decision, interval, fail_on_deny } => {
+            commands::watch(&client, pipeline.as_deref(), decision.as_deref(), interval, fail_on_deny)
+        }
     };
 
     if let Err(e) = result {
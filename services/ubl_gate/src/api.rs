@@ -1,5 +1,6 @@
-use crate::{AppState, ClientInfo};
+use crate::error::AppError;
 use crate::scope::Scope;
+use crate::{AppState, ClientInfo};
 use axum::{
     extract::{Path, State},
     http::{header, StatusCode},
@@ -11,6 +12,7 @@ use cid::Cid;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use ubl_ai_nrf1::nrf::{self, NrfValue};
 use ubl_ai_nrf1::nrf::{cid_from_nrf_bytes, encode_to_vec, json_to_nrf};
 use ubl_config::BASE_URL;
@@ -30,33 +32,124 @@ pub struct IngestReq {
     pub certify: Option<bool>,
 }
 
+#[derive(Debug, Deserialize, Default)]
+pub struct IngestQuery {
+    /// Emit a signed `ubl/transition` receipt proving the raw → NRF
+    /// normalization (preimage = canonical JSON of the submitted
+    /// payload, rho = the stored NRF bytes).
+    pub transition: Option<bool>,
+}
+
 pub async fn ingest(
+    State(state): State<AppState>,
     scope: Scope,
     client: Option<Extension<ClientInfo>>,
+    axum::extract::Query(query): axum::extract::Query<IngestQuery>,
+    headers: axum::http::HeaderMap,
     Json(req): Json<IngestReq>,
 ) -> impl IntoResponse {
     let tenant = client
         .as_ref()
         .map(|Extension(ci)| ci.tenant_id.as_str())
         .unwrap_or(&scope.tenant);
-    let nrf_val = match json_to_nrf(&req.payload) {
-        Ok(v) => v,
-        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
-    };
-    let nrf_bytes = match encode_to_vec(&nrf_val) {
-        Ok(b) => b,
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    // Budget gate: storage is metered per tenant
+    if let Err(denial) = state
+        .quota
+        .check(tenant, &[crate::quota::Resource::StoredBytes])
+    {
+        return AppError::quota_exceeded(
+            format!(
+                "quota_exceeded: {} {}/{} in current window",
+                denial.resource, denial.used, denial.limit
+            ),
+            denial.retry_after_secs,
+        )
+        .into_response();
+    }
+    // Back-pressure mode: park the payload on the bounded queue, answer
+    // 202 with a tracking id, and let the background job do the storage
+    // work. Producers poll GET /v1/ingest/status/:id.
+    let wants_async = headers
+        .get("prefer")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|p| p.trim().eq_ignore_ascii_case("respond-async"))
+        });
+    if wants_async {
+        return ingest_enqueue(&state, &scope, tenant, &headers, req).await;
+    }
+    let (cid, nrf_bytes) = match ingest_store(&state, tenant, &req.payload).await {
+        Ok(stored) => stored,
+        Err(e) => return e.into_response(),
     };
-    let cid = cid_from_nrf_bytes(&nrf_bytes);
-    if !ubl_ledger::tenant_exists(tenant, &cid).await {
-        if let Err(e) = ubl_ledger::tenant_put(tenant, &cid, &nrf_bytes).await {
-            return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+    if req.certify.unwrap_or(false) {
+        // Unified attestation chained to the tip; the standalone JWS
+        // stays available behind the compat header
+        if headers.contains_key("x-ubl-compat") {
+            let _ = ubl_receipt::issue_receipt(&cid, nrf_bytes.len()).await;
+        } else if let Err(e) =
+            certify_attestation(&state, &scope, tenant, &cid, nrf_bytes.len()).await
+        {
+            return e.into_response();
         }
     }
-    if req.certify.unwrap_or(false) {
-        let _ = ubl_receipt::issue_receipt(&cid, nrf_bytes.len()).await;
+    // Optional provenance: sign a transition receipt for the raw → NRF
+    // jump, so ingested content carries the same normalization proof an
+    // execution does.
+    let mut transition: Option<Value> = None;
+    if query.transition.unwrap_or(false) {
+        let preimage = match ubl_runtime::canon::canonical_bytes(&req.payload) {
+            Ok(b) => b,
+            Err(e) => {
+                return AppError::bad_request(format!("canonicalize payload: {e}"))
+                    .into_response()
+            }
+        };
+        let body = ubl_runtime::build_transition(
+            &preimage,
+            &nrf_bytes,
+            "ai-nrf1@0.1.0",
+            None,
+            None,
+            false,
+        );
+        let body_val = match serde_json::to_value(&body) {
+            Ok(v) => v,
+            Err(e) => return AppError::internal(format!("transition body: {e}")).into_response(),
+        };
+        let keys = state.keyring_store.resolve_for_scope(&scope);
+        let rc = match ubl_runtime::build_receipt(
+            "ubl/transition",
+            vec![],
+            body_val,
+            &keys.active,
+            &keys.active_kid,
+        ) {
+            Ok(rc) => rc,
+            Err(e) => return AppError::internal(format!("sign transition: {e}")).into_response(),
+        };
+        let rc_val = serde_json::to_value(&rc).unwrap_or(Value::Null);
+        let tr_cid = rc.body_cid.clone();
+        // The preimage bytes land in the ledger next to the NRF blob so
+        // the normalization can be replayed forensically
+        let preimage_cid = ubl_runtime::cid::cid_b3(&preimage);
+        let mut batch = ubl_ledger::WriteBatch::new();
+        batch.put_body(tenant, &preimage_cid, preimage);
+        let _ = batch.commit().await;
+        index_receipts(tenant, None, None, &[(tr_cid.clone(), rc_val.clone())]).await;
+        {
+            let mut store = state.receipt_chain.write().unwrap();
+            store.insert(scope.scoped_cid(&tr_cid), rc_val.clone());
+            store.insert(tr_cid.clone(), rc_val);
+        }
+        transition = Some(json!({
+            "cid": tr_cid,
+            "preimage_raw_cid": preimage_cid,
+            "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), tr_cid),
+        }));
     }
-    let resp = json!({
+    let mut resp = json!({
         "cid": cid.to_string(),
         "did": format!("did:cid:{}", cid),
         "tenant_id": tenant,
@@ -65,9 +158,304 @@ pub async fn ingest(
         "url": format!("{}/cid/{}", BASE_URL.as_str(), cid),
         "receipt_url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), cid),
     });
+    if let Some(tr) = transition {
+        resp["transition"] = tr;
+    }
     (StatusCode::OK, Json(resp)).into_response()
 }
 
+/// Normalize and store one payload: raw JSON → NRF canon → CID → tenant
+/// ledger, charging stored bytes on first write. Shared by synchronous
+/// ingest and the async-mode background job.
+async fn ingest_store(
+    state: &AppState,
+    tenant: &str,
+    payload: &Value,
+) -> Result<(Cid, Vec<u8>), AppError> {
+    let nrf_val = json_to_nrf(payload).map_err(|e| AppError::bad_request(e.to_string()))?;
+    let nrf_bytes =
+        encode_to_vec(&nrf_val).map_err(|e| AppError::internal(format!("NRF encode: {e}")))?;
+    let cid = cid_from_nrf_bytes(&nrf_bytes);
+    if !ubl_ledger::tenant_exists(tenant, &cid).await {
+        ubl_ledger::tenant_put(tenant, &cid, &nrf_bytes)
+            .await
+            .map_err(|e| AppError::internal(format!("ledger put: {e}")))?;
+        state.quota.charge(tenant, 0, 0, nrf_bytes.len() as u64);
+    }
+    Ok((cid, nrf_bytes))
+}
+
+/// `Prefer: respond-async` branch of ingest: claim a queue slot, park
+/// the job, and answer 202 with a tracking id. The permit rides inside
+/// the spawned task, so queue depth bounds queued + running jobs and a
+/// full queue sheds the producer with 429.
+async fn ingest_enqueue(
+    state: &AppState,
+    scope: &Scope,
+    tenant: &str,
+    headers: &axum::http::HeaderMap,
+    req: IngestReq,
+) -> axum::response::Response {
+    let Some(permit) = state.ingest_queue.try_enqueue() else {
+        metrics::counter!(
+            "ubl_ingest_async_total",
+            "tenant" => tenant.to_string(),
+            "outcome" => "shed",
+        )
+        .increment(1);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(axum::http::header::RETRY_AFTER, "1".to_string())],
+            Json(json!({"error": "ingest_queue_full"})),
+        )
+            .into_response();
+    };
+    let id = {
+        use rand::RngCore;
+        let mut bytes = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        format!("ing_{}", hex::encode(bytes))
+    };
+    state.ingest_jobs.write().unwrap().insert(
+        id.clone(),
+        crate::IngestJob {
+            tenant: tenant.to_string(),
+            status: "queued",
+            cid: None,
+            bytes_len: None,
+            error: None,
+        },
+    );
+    metrics::counter!(
+        "ubl_ingest_async_total",
+        "tenant" => tenant.to_string(),
+        "outcome" => "queued",
+    )
+    .increment(1);
+
+    let task_state = state.clone();
+    let task_scope = scope.clone();
+    let task_tenant = tenant.to_string();
+    // Compat callers keep their standalone-JWS certify semantics
+    let compat = headers.contains_key("x-ubl-compat");
+    let certify = req.certify.unwrap_or(false);
+    let job_id = id.clone();
+    tokio::spawn(async move {
+        let _slot = permit;
+        let mut result = ingest_store(&task_state, &task_tenant, &req.payload).await;
+        if certify {
+            if let Ok((cid, nrf_bytes)) = &result {
+                if compat {
+                    let _ = ubl_receipt::issue_receipt(cid, nrf_bytes.len()).await;
+                } else if let Err(e) =
+                    certify_attestation(&task_state, &task_scope, &task_tenant, cid, nrf_bytes.len())
+                        .await
+                {
+                    result = Err(e);
+                }
+            }
+        }
+        let mut jobs = task_state.ingest_jobs.write().unwrap();
+        if let Some(job) = jobs.get_mut(&job_id) {
+            match result {
+                Ok((cid, nrf_bytes)) => {
+                    job.status = "done";
+                    job.cid = Some(cid.to_string());
+                    job.bytes_len = Some(nrf_bytes.len());
+                }
+                Err(e) => {
+                    job.status = "failed";
+                    job.error = Some(e.message);
+                }
+            }
+        }
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "id": id,
+            "status": "queued",
+            "status_url": format!("{}/v1/ingest/status/{}", BASE_URL.as_str(), id),
+        })),
+    )
+        .into_response()
+}
+
+/// Poll one async-mode ingest job. 404 until the id exists and for ids
+/// enqueued by other tenants.
+pub async fn get_ingest_status(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let jobs = state.ingest_jobs.read().unwrap();
+    match jobs.get(&id) {
+        Some(job) if job.tenant == scope.tenant => {
+            let mut resp = json!({"id": id, "status": job.status});
+            if let Some(cid) = &job.cid {
+                resp["cid"] = json!(cid);
+                resp["bytes_len"] = json!(job.bytes_len);
+                resp["url"] = json!(format!("{}/cid/{}", BASE_URL.as_str(), cid));
+            }
+            if let Some(error) = &job.error {
+                resp["error"] = json!(error);
+            }
+            (StatusCode::OK, Json(resp)).into_response()
+        }
+        _ => (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "ingest job not found"})),
+        )
+            .into_response(),
+    }
+}
+
+/// Aggregate caps for `POST /v1/ingest/bulk`.
+const BULK_MAX_LINES: usize = 10_000;
+const BULK_MAX_LINE_BYTES: usize = 65_536;
+
+/// Store one bulk-ingest line: parse → NRF canon → CID → ledger put,
+/// charging stored bytes on first write like single-payload ingest.
+async fn bulk_ingest_line(
+    state: &AppState,
+    tenant: &str,
+    raw: &[u8],
+) -> Result<(String, usize), String> {
+    let payload: Value =
+        serde_json::from_slice(raw).map_err(|e| format!("invalid JSON: {e}"))?;
+    let nrf_val = json_to_nrf(&payload).map_err(|e| e.to_string())?;
+    let nrf_bytes = encode_to_vec(&nrf_val).map_err(|e| format!("NRF encode: {e}"))?;
+    let cid = cid_from_nrf_bytes(&nrf_bytes);
+    if !ubl_ledger::tenant_exists(tenant, &cid).await {
+        ubl_ledger::tenant_put(tenant, &cid, &nrf_bytes)
+            .await
+            .map_err(|e| format!("ledger put: {e}"))?;
+        state.quota.charge(tenant, 0, 0, nrf_bytes.len() as u64);
+    }
+    Ok((cid.to_string(), nrf_bytes.len()))
+}
+
+/// Bulk ingest: NDJSON in (one payload per line), NDJSON out (one result
+/// per line — `cid` on success, `error` on failure). Lines are processed
+/// incrementally as body chunks arrive; a bad line never aborts the
+/// batch. Blank lines are skipped without consuming a line number.
+pub async fn ingest_bulk(
+    State(state): State<AppState>,
+    scope: Scope,
+    client: Option<Extension<ClientInfo>>,
+    body: axum::body::Body,
+) -> impl IntoResponse {
+    use futures_util::StreamExt;
+    let tenant = client
+        .as_ref()
+        .map(|Extension(ci)| ci.tenant_id.clone())
+        .unwrap_or_else(|| scope.tenant.clone());
+
+    let mut stream = body.into_data_stream();
+    let mut buf: Vec<u8> = Vec::new();
+    let mut out = String::new();
+    let mut line_no = 0usize;
+    let mut bytes_in = 0u64;
+
+    // Shared per-line step so the chunk loop and the trailing (unterminated)
+    // line take the same path; evaluates to true when the batch must stop.
+    macro_rules! handle_line {
+        ($line:expr) => {{
+            let line: &[u8] = $line;
+            let mut stop = false;
+            if !line.iter().all(|b| b.is_ascii_whitespace()) {
+                line_no += 1;
+                if line_no > BULK_MAX_LINES {
+                    out.push_str(
+                        &json!({"line": line_no, "error": format!("line limit exceeded ({BULK_MAX_LINES})")})
+                            .to_string(),
+                    );
+                    out.push('\n');
+                    stop = true;
+                } else if let Err(denial) = state
+                    .quota
+                    .check(&tenant, &[crate::quota::Resource::StoredBytes])
+                {
+                    // Same budget gate as single-payload ingest; once the
+                    // tenant is over budget no later line can store either,
+                    // so stop the batch here
+                    out.push_str(
+                        &json!({"line": line_no, "error": format!(
+                            "quota_exceeded: {} {}/{} in current window",
+                            denial.resource, denial.used, denial.limit
+                        )})
+                        .to_string(),
+                    );
+                    out.push('\n');
+                    metrics::counter!(
+                        "ubl_bulk_ingest_lines_total",
+                        "tenant" => tenant.clone(),
+                        "outcome" => "quota_exceeded",
+                    )
+                    .increment(1);
+                    stop = true;
+                } else {
+                    let result = if line.len() > BULK_MAX_LINE_BYTES {
+                        Err(format!("line exceeds {BULK_MAX_LINE_BYTES} bytes"))
+                    } else {
+                        bulk_ingest_line(&state, &tenant, line).await
+                    };
+                    let (entry, outcome) = match result {
+                        Ok((cid, bytes_len)) => (
+                            json!({"line": line_no, "cid": cid, "bytes_len": bytes_len}),
+                            "stored",
+                        ),
+                        Err(e) => (json!({"line": line_no, "error": e}), "error"),
+                    };
+                    metrics::counter!(
+                        "ubl_bulk_ingest_lines_total",
+                        "tenant" => tenant.clone(),
+                        "outcome" => outcome,
+                    )
+                    .increment(1);
+                    out.push_str(&entry.to_string());
+                    out.push('\n');
+                }
+            }
+            stop
+        }};
+    }
+
+    let mut done = false;
+    'outer: while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(c) => c,
+            Err(e) => return AppError::bad_request(format!("body read: {e}")).into_response(),
+        };
+        bytes_in += chunk.len() as u64;
+        buf.extend_from_slice(&chunk);
+        while let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=pos).collect();
+            if handle_line!(&line[..line.len() - 1]) {
+                done = true;
+                break 'outer;
+            }
+        }
+    }
+    if !done && !buf.is_empty() {
+        let line = std::mem::take(&mut buf);
+        let _ = handle_line!(&line[..]);
+    }
+
+    // Per-tenant throughput accounting: operators budget bulk loaders on
+    // these, independent of the per-line outcome counters
+    metrics::counter!("ubl_bulk_ingest_bytes_total", "tenant" => tenant.clone())
+        .increment(bytes_in);
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        out,
+    )
+        .into_response()
+}
+
 /// Resolve raw bytes for a CID: try tenant path first, then legacy.
 async fn resolve_raw(tenant: &str, cid: &Cid) -> Option<Vec<u8>> {
     if let Some(b) = ubl_ledger::tenant_get_raw(tenant, cid).await {
@@ -76,53 +464,366 @@ async fn resolve_raw(tenant: &str, cid: &Cid) -> Option<Vec<u8>> {
     ubl_ledger::get_raw(cid).await
 }
 
+/// Representations served by /cid/:cid content negotiation.
+#[derive(PartialEq)]
+enum CidFormat {
+    Nrf,
+    Json,
+    Cbor,
+}
+
+/// Pick a representation from the Accept header. First recognized media
+/// type in client order wins; anything else (including `*/*` or no header)
+/// serves the canonical NRF bytes.
+fn negotiate_cid_format(accept: Option<&str>) -> CidFormat {
+    let Some(accept) = accept else {
+        return CidFormat::Nrf;
+    };
+    for part in accept.split(',') {
+        let mt = part.split(';').next().unwrap_or("").trim();
+        match mt.to_ascii_lowercase().as_str() {
+            "application/x-nrf" | "application/octet-stream" => return CidFormat::Nrf,
+            "application/json" => return CidFormat::Json,
+            "application/cbor" => return CidFormat::Cbor,
+            _ => {}
+        }
+    }
+    CidFormat::Nrf
+}
+
 pub async fn get_cid_dispatch(
+    State(state): State<AppState>,
     scope: Scope,
     client: Option<Extension<ClientInfo>>,
     Path(cid_raw): Path<String>,
+    headers: axum::http::HeaderMap,
 ) -> impl IntoResponse {
     let cid_str = normalize_cid_in_path(&cid_raw);
     let tenant = client
         .as_ref()
         .map(|Extension(ci)| ci.tenant_id.as_str())
         .unwrap_or(&scope.tenant);
-    if let Some(bare) = cid_str.strip_suffix(".json") {
-        return get_cid_json_inner(tenant, bare).await;
+    // Legacy .json suffix still works and overrides Accept
+    let (cid_str, format) = match cid_str.strip_suffix(".json") {
+        Some(bare) => (bare.to_string(), CidFormat::Json),
+        None => {
+            let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+            (cid_str.clone(), negotiate_cid_format(accept))
+        }
+    };
+
+    // Content is immutable per CID, so the CID itself is the ETag
+    let etag = format!("\"{cid_str}\"");
+    let revalidated = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|inm| inm.split(',').any(|t| t.trim() == etag || t.trim() == "*"));
+    if revalidated {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
     }
-    get_cid_inner(tenant, &cid_str).await
+
+    // Range applies to the canonical byte representation only
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let fmt = match format {
+        CidFormat::Nrf => "nrf",
+        CidFormat::Json => "json",
+        CidFormat::Cbor => "cbor",
+    };
+    let cache_key = format!("cid:{tenant}:{cid_str}:{fmt}");
+    // Full-body requests are served from the response cache; ranges bypass it
+    let cached = if range.is_none() {
+        state.response_cache.get(&cache_key)
+    } else {
+        None
+    };
+    let mut resp = match cached {
+        Some((content_type, body)) => {
+            let mut r = (
+                [
+                    (header::CONTENT_TYPE, content_type),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+                body.as_ref().clone(),
+            )
+                .into_response();
+            if format != CidFormat::Nrf {
+                r.headers_mut().remove(header::ACCEPT_RANGES);
+            }
+            r
+        }
+        None => {
+            let cache = &state.response_cache;
+            match format {
+                CidFormat::Nrf => get_cid_inner(tenant, &cid_str, range, cache, &cache_key).await,
+                CidFormat::Json => get_cid_json_inner(tenant, &cid_str, cache, &cache_key).await,
+                CidFormat::Cbor => get_cid_cbor_inner(tenant, &cid_str, cache, &cache_key).await,
+            }
+        }
+    };
+    if resp.status() == StatusCode::OK || resp.status() == StatusCode::PARTIAL_CONTENT {
+        if let Ok(v) = etag.parse() {
+            resp.headers_mut().insert(header::ETAG, v);
+        }
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            axum::http::HeaderValue::from_static("public, max-age=31536000, immutable"),
+        );
+    }
+    resp
+}
+
+/// Single-range parse of a Range header against a blob of `len` bytes.
+/// `Ok(None)` = no usable range (serve the full blob, per RFC 9110 a server
+/// may ignore malformed or multi-part ranges); `Err(())` = unsatisfiable.
+fn parse_byte_range(spec: &str, len: u64) -> std::result::Result<Option<(u64, u64)>, ()> {
+    let Some(r) = spec.strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    let r = r.trim();
+    if r.contains(',') {
+        return Ok(None);
+    }
+    let Some((start_s, end_s)) = r.split_once('-') else {
+        return Ok(None);
+    };
+    if start_s.is_empty() {
+        // Suffix form: last N bytes
+        let Ok(n) = end_s.parse::<u64>() else {
+            return Ok(None);
+        };
+        if n == 0 || len == 0 {
+            return Err(());
+        }
+        return Ok(Some((len.saturating_sub(n), len - 1)));
+    }
+    let Ok(start) = start_s.parse::<u64>() else {
+        return Ok(None);
+    };
+    if start >= len {
+        return Err(());
+    }
+    let end = if end_s.is_empty() {
+        len - 1
+    } else {
+        match end_s.parse::<u64>() {
+            Ok(e) => e.min(len - 1),
+            Err(_) => return Ok(None),
+        }
+    };
+    if end < start {
+        return Err(());
+    }
+    Ok(Some((start, end)))
 }
 
-async fn get_cid_inner(tenant: &str, cid_str: &str) -> axum::response::Response {
+fn range_not_satisfiable(len: u64) -> axum::response::Response {
+    (
+        StatusCode::RANGE_NOT_SATISFIABLE,
+        [(header::CONTENT_RANGE, format!("bytes */{len}"))],
+        (),
+    )
+        .into_response()
+}
+
+async fn get_cid_inner(
+    tenant: &str,
+    cid_str: &str,
+    range: Option<String>,
+    cache: &crate::cache::ResponseCache,
+    cache_key: &str,
+) -> axum::response::Response {
     let cid = match Cid::try_from(cid_str) {
         Ok(c) => c,
-        Err(_) => return (StatusCode::BAD_REQUEST, "invalid CID").into_response(),
+        Err(_) => return AppError::bad_request("invalid CID").into_response(),
+    };
+    // Verified deployments trade streaming for whole-blob verification
+    if ubl_ledger::verify_reads_enabled() {
+        return match resolve_raw(tenant, &cid).await {
+            Some(bytes) => {
+                if range.is_none() {
+                    cache.put(cache_key, "application/x-nrf", Arc::new(bytes.clone()));
+                }
+                serve_blob_buffered(bytes, range.as_deref())
+            }
+            None => cid_miss(tenant, cid_str).await,
+        };
+    }
+    let Some(blob) = ubl_ledger::tenant_open_raw(tenant, &cid).await else {
+        return cid_miss(tenant, cid_str).await;
+    };
+    let (mut file, len) = match blob {
+        // Stored compressed: the ledger already decoded the whole blob,
+        // so serve it buffered like the verified path does
+        ubl_ledger::RawBlob::Buffered(bytes) => {
+            if range.is_none() {
+                cache.put(cache_key, "application/x-nrf", Arc::new(bytes.clone()));
+            }
+            return serve_blob_buffered(bytes, range.as_deref());
+        }
+        ubl_ledger::RawBlob::Stream(file, len) => (file, len),
     };
-    match resolve_raw(tenant, &cid).await {
-        Some(bytes) => ([(header::CONTENT_TYPE, "application/x-nrf")], bytes).into_response(),
-        None => (StatusCode::NOT_FOUND, "not found").into_response(),
+    // Small full-body reads are buffered so the cache can keep them hot;
+    // large blobs and ranges keep the streaming path
+    if range.is_none() && len as usize <= crate::cache::MAX_ENTRY_BYTES {
+        use tokio::io::AsyncReadExt;
+        let mut bytes = Vec::with_capacity(len as usize);
+        if file.read_to_end(&mut bytes).await.is_ok() {
+            cache.put(cache_key, "application/x-nrf", Arc::new(bytes.clone()));
+            return serve_blob_buffered(bytes, None);
+        }
+        return AppError::internal("blob read failed").into_response();
+    }
+    match range.as_deref().map(|r| parse_byte_range(r, len)) {
+        Some(Err(())) => range_not_satisfiable(len),
+        Some(Ok(Some((start, end)))) => {
+            use tokio::io::{AsyncReadExt, AsyncSeekExt};
+            if file.seek(std::io::SeekFrom::Start(start)).await.is_err() {
+                return AppError::internal("blob seek failed").into_response();
+            }
+            let span = end - start + 1;
+            let stream = tokio_util::io::ReaderStream::new(file.take(span));
+            (
+                StatusCode::PARTIAL_CONTENT,
+                [
+                    (header::CONTENT_TYPE, "application/x-nrf".to_string()),
+                    (header::CONTENT_LENGTH, span.to_string()),
+                    (header::CONTENT_RANGE, format!("bytes {start}-{end}/{len}")),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+                axum::body::Body::from_stream(stream),
+            )
+                .into_response()
+        }
+        _ => {
+            // Backpressure-aware: the blob streams chunk by chunk
+            let stream = tokio_util::io::ReaderStream::new(file);
+            (
+                [
+                    (header::CONTENT_TYPE, "application/x-nrf".to_string()),
+                    (header::CONTENT_LENGTH, len.to_string()),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+                axum::body::Body::from_stream(stream),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// In-memory variant of `get_cid_inner` for verified reads.
+fn serve_blob_buffered(bytes: Vec<u8>, range: Option<&str>) -> axum::response::Response {
+    let len = bytes.len() as u64;
+    // Content-Length is set explicitly: the compression layer wraps every
+    // body, which hides the exact size hint hyper would otherwise use.
+    // (When a response *is* compressed the layer strips the header again.)
+    match range.map(|r| parse_byte_range(r, len)) {
+        Some(Err(())) => range_not_satisfiable(len),
+        Some(Ok(Some((start, end)))) => (
+            StatusCode::PARTIAL_CONTENT,
+            [
+                (header::CONTENT_TYPE, "application/x-nrf".to_string()),
+                (header::CONTENT_LENGTH, (end - start + 1).to_string()),
+                (header::CONTENT_RANGE, format!("bytes {start}-{end}/{len}")),
+                (header::ACCEPT_RANGES, "bytes".to_string()),
+            ],
+            bytes[start as usize..=end as usize].to_vec(),
+        )
+            .into_response(),
+        _ => (
+            [
+                (header::CONTENT_TYPE, "application/x-nrf".to_string()),
+                (header::CONTENT_LENGTH, len.to_string()),
+                (header::ACCEPT_RANGES, "bytes".to_string()),
+            ],
+            bytes,
+        )
+            .into_response(),
     }
 }
 
-async fn get_cid_json_inner(tenant: &str, cid_str: &str) -> axum::response::Response {
+/// Shared miss path: redaction tombstones answer 410, everything else 404.
+async fn cid_miss(tenant: &str, cid_str: &str) -> axum::response::Response {
+    if let Some(tombstone) = redaction_tombstone(tenant, cid_str).await {
+        return (StatusCode::GONE, Json(tombstone)).into_response();
+    }
+    AppError::not_found("content").into_response()
+}
+
+async fn get_cid_json_inner(
+    tenant: &str,
+    cid_str: &str,
+    cache: &crate::cache::ResponseCache,
+    cache_key: &str,
+) -> axum::response::Response {
+    let cid = match Cid::try_from(cid_str) {
+        Ok(c) => c,
+        Err(_) => return AppError::bad_request("invalid CID").into_response(),
+    };
+    let bytes = match resolve_raw(tenant, &cid).await {
+        Some(b) => b,
+        None => return cid_miss(tenant, cid_str).await,
+    };
+    let view = match nrf::decode_from_slice(&bytes) {
+        Ok(nrf_val) => nrf_value_to_json(&nrf_val),
+        // Fallback: base64 view when NRF decoder can't parse the bytes
+        Err(_) => json!({
+            "cid": cid.to_string(),
+            "content_type": "application/x-nrf",
+            "nrf_base64": base64::engine::general_purpose::STANDARD.encode(&bytes),
+            "note": "NRF decode failed; returning base64 view."
+        }),
+    };
+    let Ok(out) = serde_json::to_vec(&view) else {
+        return AppError::internal("JSON encode failed").into_response();
+    };
+    cache.put(cache_key, "application/json", Arc::new(out.clone()));
+    ([(header::CONTENT_TYPE, "application/json")], out).into_response()
+}
+
+async fn get_cid_cbor_inner(
+    tenant: &str,
+    cid_str: &str,
+    cache: &crate::cache::ResponseCache,
+    cache_key: &str,
+) -> axum::response::Response {
     let cid = match Cid::try_from(cid_str) {
         Ok(c) => c,
-        Err(_) => return (StatusCode::BAD_REQUEST, "invalid CID").into_response(),
+        Err(_) => return AppError::bad_request("invalid CID").into_response(),
     };
     let bytes = match resolve_raw(tenant, &cid).await {
         Some(b) => b,
-        None => return (StatusCode::NOT_FOUND, "not found").into_response(),
+        None => return cid_miss(tenant, cid_str).await,
+    };
+    // Fallback: raw bytes as a CBOR byte string when the decoder can't parse
+    let cbor_val = match nrf::decode_from_slice(&bytes) {
+        Ok(nrf_val) => nrf_value_to_cbor(&nrf_val),
+        Err(_) => ciborium::Value::Bytes(bytes),
     };
-    if let Ok(nrf_val) = nrf::decode_from_slice(&bytes) {
-        return (StatusCode::OK, Json(nrf_value_to_json(&nrf_val))).into_response();
+    let mut out = Vec::new();
+    if ciborium::into_writer(&cbor_val, &mut out).is_err() {
+        return AppError::internal("CBOR encode failed").into_response();
+    }
+    cache.put(cache_key, "application/cbor", Arc::new(out.clone()));
+    ([(header::CONTENT_TYPE, "application/cbor")], out).into_response()
+}
+
+fn nrf_value_to_cbor(v: &NrfValue) -> ciborium::Value {
+    match v {
+        NrfValue::Null => ciborium::Value::Null,
+        NrfValue::Bool(b) => ciborium::Value::Bool(*b),
+        NrfValue::Int(i) => ciborium::Value::Integer((*i).into()),
+        NrfValue::String(s) => ciborium::Value::Text(s.clone()),
+        NrfValue::Bytes(b) => ciborium::Value::Bytes(b.clone()),
+        NrfValue::Array(arr) => ciborium::Value::Array(arr.iter().map(nrf_value_to_cbor).collect()),
+        NrfValue::Map(map) => ciborium::Value::Map(
+            map.iter()
+                .map(|(k, v)| (ciborium::Value::Text(k.clone()), nrf_value_to_cbor(v)))
+                .collect(),
+        ),
     }
-    // Fallback: base64 view when NRF decoder can't parse the bytes
-    let view = json!({
-        "cid": cid.to_string(),
-        "content_type": "application/x-nrf",
-        "nrf_base64": base64::engine::general_purpose::STANDARD.encode(&bytes),
-        "note": "NRF decode failed; returning base64 view."
-    });
-    Json(view).into_response()
 }
 
 fn nrf_value_to_json(v: &NrfValue) -> Value {
@@ -143,9 +844,56 @@ fn nrf_value_to_json(v: &NrfValue) -> Value {
     }
 }
 
+/// Sign an `ubl/attestation` receipt certifying stored content. Parented
+/// to the current chain tip (when one exists) so certification hangs off
+/// the same provenance graph as executions, and registered under both its
+/// own body_cid and the certified content CID — `GET /v1/receipt/<cid>`
+/// keeps working for either handle.
+async fn certify_attestation(
+    state: &AppState,
+    scope: &Scope,
+    tenant: &str,
+    cid: &Cid,
+    bytes_len: usize,
+) -> Result<ubl_runtime::Receipt, AppError> {
+    let certified_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let keys = state.keyring_store.resolve_for_scope(scope);
+    let parents: Vec<String> = state.last_tip.read().unwrap().clone().into_iter().collect();
+    let body = json!({
+        "type": "ubl/attestation",
+        "action": "certify",
+        "cid": cid.to_string(),
+        "bytes_len": bytes_len,
+        "tenant": tenant,
+        "certified_at": certified_at,
+    });
+    let receipt = ubl_runtime::build_receipt(
+        "ubl/attestation",
+        parents,
+        body,
+        &keys.active,
+        &keys.active_kid,
+    )
+    .map_err(|e| AppError::internal(format!("attestation receipt: {e}")))?;
+    if let Ok(val) = serde_json::to_value(&receipt) {
+        index_receipts(tenant, None, None, &[(receipt.body_cid.clone(), val.clone())]).await;
+        let mut store = state.receipt_chain.write().unwrap();
+        for key in [receipt.body_cid.clone(), cid.to_string()] {
+            store.insert(scope.scoped_cid(&key), val.clone());
+            store.insert(key, val.clone());
+        }
+    }
+    Ok(receipt)
+}
+
 pub async fn certify_cid(
+    State(state): State<AppState>,
     scope: Scope,
     client: Option<Extension<ClientInfo>>,
+    headers: axum::http::HeaderMap,
     Json(payload): Json<Value>,
 ) -> impl IntoResponse {
     let tenant = client
@@ -154,44 +902,329 @@ pub async fn certify_cid(
         .unwrap_or(&scope.tenant);
     let cid_str = match payload.get("cid").and_then(|v| v.as_str()) {
         Some(s) => s,
-        None => return (StatusCode::BAD_REQUEST, "missing cid").into_response(),
+        None => return AppError::bad_request("missing cid").into_response(),
     };
     let cid = match Cid::try_from(cid_str) {
         Ok(c) => c,
-        Err(_) => return (StatusCode::BAD_REQUEST, "invalid CID").into_response(),
+        Err(_) => return AppError::bad_request("invalid CID").into_response(),
     };
     let bytes = match resolve_raw(tenant, &cid).await {
         Some(b) => b,
-        None => return (StatusCode::NOT_FOUND, "content not found").into_response(),
+        None => return AppError::not_found("content").into_response(),
     };
-    match ubl_receipt::issue_receipt(&cid, bytes.len()).await {
-        Ok(jws) => Json(json!({ "receipt": jws })).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("certify failed: {e}"),
-        )
-            .into_response(),
+    // Legacy standalone JWS for callers that haven't migrated off it
+    if headers.contains_key("x-ubl-compat") {
+        return match ubl_receipt::issue_receipt(&cid, bytes.len()).await {
+            Ok(jws) => Json(json!({ "receipt": jws })).into_response(),
+            Err(e) => AppError::internal(format!("certify failed: {e}")).into_response(),
+        };
+    }
+    match certify_attestation(&state, &scope, tenant, &cid, bytes.len()).await {
+        Ok(receipt) => Json(json!({
+            "receipt": receipt,
+            "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), receipt.body_cid),
+        }))
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// ── Redaction (GDPR) ────────────────────────────────────────────────
+
+/// Look up the redaction tombstone for a CID, parsed as JSON.
+async fn redaction_tombstone(tenant: &str, cid_str: &str) -> Option<Value> {
+    let bytes = ubl_ledger::tenant_get_tombstone(tenant, cid_str).await?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Redact a stored blob: delete the bytes, leave a tombstone recording the
+/// original CID, and emit a signed `ubl/attestation` receipt as proof of
+/// processing. Idempotent; blocked while the CID is under legal hold.
+pub async fn redact_cid(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid_raw): Path<String>,
+) -> Result<axum::response::Response, AppError> {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    {
+        let holds = state.legal_holds.read().unwrap();
+        if holds.contains(&scope.scoped_cid(&cid_str)) || holds.contains(&cid_str) {
+            return Err(AppError::forbidden("cid is under legal hold"));
+        }
+    }
+    if !ubl_ledger::tenant_redact(&scope.tenant, &cid_str).await {
+        // No blob left to delete — idempotent if a tombstone already exists
+        if let Some(tombstone) = redaction_tombstone(&scope.tenant, &cid_str).await {
+            return Ok((
+                StatusCode::OK,
+                Json(json!({"redacted": true, "tombstone": tombstone})),
+            )
+                .into_response());
+        }
+        return Err(AppError::not_found("content"));
+    }
+    let redacted_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let keys = state.keyring_store.resolve_for_scope(&scope);
+    let attestation_body = json!({
+        "type": "ubl/attestation",
+        "action": "redact",
+        "original_cid": cid_str,
+        "tenant": scope.tenant,
+        "redacted_at": redacted_at,
+    });
+    let receipt = ubl_runtime::build_receipt(
+        "ubl/attestation",
+        vec![],
+        attestation_body,
+        &keys.active,
+        &keys.active_kid,
+    )
+    .map_err(|e| AppError::internal(format!("attestation receipt: {e}")))?;
+    let tombstone = json!({
+        "type": "ubl/tombstone",
+        "original_cid": cid_str,
+        "tenant": scope.tenant,
+        "redacted_at": redacted_at,
+        "attestation_cid": receipt.body_cid,
+    });
+    let bytes = serde_json::to_vec(&tombstone)
+        .map_err(|e| AppError::internal(format!("tombstone encode: {e}")))?;
+    ubl_ledger::tenant_put_tombstone(&scope.tenant, &cid_str, &bytes)
+        .await
+        .map_err(|e| AppError::internal(format!("tombstone write: {e}")))?;
+    // Redaction is the one mutation of stored content — drop cached copies
+    state.response_cache.invalidate_cid(&cid_str);
+    if let Ok(val) = serde_json::to_value(&receipt) {
+        index_receipts(&scope.tenant, None, None, &[(receipt.body_cid.clone(), val.clone())]).await;
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(scope.scoped_cid(&receipt.body_cid), val.clone());
+        store.insert(receipt.body_cid.clone(), val);
+    }
+    Ok((
+        StatusCode::OK,
+        Json(json!({"redacted": true, "tombstone": tombstone, "receipt": receipt})),
+    )
+        .into_response())
+}
+
+/// Flag a CID as under legal hold, blocking redaction until cleared.
+pub async fn admin_set_hold(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid_raw): Path<String>,
+) -> impl IntoResponse {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    let key = scope.scoped_cid(&cid_str);
+    state.legal_holds.write().unwrap().insert(key);
+    Json(json!({"cid": cid_str, "held": true}))
+}
+
+/// Release a legal hold.
+pub async fn admin_clear_hold(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid_raw): Path<String>,
+) -> impl IntoResponse {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    let key = scope.scoped_cid(&cid_str);
+    let removed = state.legal_holds.write().unwrap().remove(&key);
+    Json(json!({"cid": cid_str, "held": false, "removed": removed}))
+}
+
+/// Queue an oversized receipt body for detachment: the canonical body
+/// bytes join `batch`, and the returned length tells the caller to apply
+/// [`apply_body_ref`] once the batch commits. A failed commit therefore
+/// leaves the body inline instead of dangling a reference.
+fn stage_detached_body(
+    tenant: &str,
+    body_cid: &str,
+    receipt: &Value,
+    threshold: usize,
+    batch: &mut ubl_ledger::WriteBatch,
+) -> Option<usize> {
+    let body = receipt.get("body")?;
+    let bytes = ubl_runtime::canon::canonical_bytes(body).ok()?;
+    if bytes.len() <= threshold {
+        return None;
+    }
+    let len = bytes.len();
+    batch.put_body(tenant, body_cid, bytes);
+    Some(len)
+}
+
+/// Registry half of body detachment: drop the inline body and keep only
+/// body_cid plus a retrieval hint.
+fn apply_body_ref(receipt: &mut Value, bytes_len: usize) {
+    if let Some(obj) = receipt.as_object_mut() {
+        obj.remove("body");
+        obj.insert(
+            "body_ref".into(),
+            json!({"detached": true, "store": "ledger", "bytes_len": bytes_len}),
+        );
+    }
+}
+
+/// Transparently re-embed a detached body (inverse of apply_body_ref).
+async fn rehydrate_body(tenant: &str, receipt: &mut Value) {
+    let detached = receipt
+        .get("body_ref")
+        .and_then(|r| r.get("detached"))
+        .and_then(|d| d.as_bool())
+        .unwrap_or(false);
+    if !detached {
+        return;
+    }
+    let Some(cid) = receipt.get("body_cid").and_then(|c| c.as_str()) else {
+        return;
+    };
+    let cid = cid.to_string();
+    if let Some(bytes) = ubl_ledger::tenant_get_body(tenant, &cid).await {
+        if let Ok(body) = serde_json::from_slice::<Value>(&bytes) {
+            if let Some(obj) = receipt.as_object_mut() {
+                obj.insert("body".into(), body);
+                obj.remove("body_ref");
+            }
+        }
     }
 }
 
+#[derive(Deserialize, Default)]
+pub struct ReceiptQuery {
+    /// Optional comma-separated response sections: "proof", "jcs".
+    pub include: Option<String>,
+}
+
+/// The RFC 8785 interchange section: the receipt body re-canonicalized
+/// under JCS, with both CIDs side by side so either ecosystem can
+/// verify (`b3` over NRF canon, `sha256` over the JCS bytes).
+fn build_jcs_section(receipt: &Value) -> Result<Value, AppError> {
+    let body = receipt.get("body").cloned().unwrap_or(Value::Null);
+    let bytes = ubl_runtime::jcs::jcs_bytes(&body)
+        .map_err(|e| AppError::unprocessable(format!("JCS conversion: {e}")))?;
+    let cid_sha256 = ubl_runtime::jcs::cid_sha256(&bytes);
+    let canonical = String::from_utf8(bytes)
+        .map_err(|e| AppError::internal(format!("JCS produced invalid UTF-8: {e}")))?;
+    Ok(json!({
+        "canonical": canonical,
+        "body_cid_sha256": cid_sha256,
+        "body_cid_b3": receipt.get("body_cid").cloned().unwrap_or(Value::Null),
+    }))
+}
+
+/// Walk the first-parent chain from `receipt` toward the chain anchor
+/// (a receipt with no parents), collecting each hop's CID and parent
+/// list. The walk stops where the registry has no receipt — archived or
+/// pruned ancestry — and reports the boundary instead of failing, so a
+/// client can verify linkage up to the last retained receipt without
+/// fetching every intermediate.
+fn build_inclusion_proof(state: &AppState, scope: &Scope, cid: &str, receipt: &Value) -> Value {
+    // Defensive bound: no legitimate chain is this deep per request
+    const MAX_HOPS: usize = 1024;
+    let store = state.receipt_chain.read().unwrap();
+    let mut path = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut current = receipt.clone();
+    let mut cid = cid.to_string();
+    let (anchor, complete) = loop {
+        let parents: Vec<String> = current
+            .get("parents")
+            .and_then(|p| p.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        seen.insert(cid.clone());
+        path.push(json!({"cid": cid, "parents": parents}));
+        let Some(next) = parents.first().cloned() else {
+            // A parentless receipt is the chain anchor
+            break (cid, true);
+        };
+        // Identical deterministic runs can repeat CIDs; never loop
+        if path.len() >= MAX_HOPS || seen.contains(&next) {
+            break (next, false);
+        }
+        match store
+            .get(&scope.scoped_cid(&next))
+            .or_else(|| store.get(&next))
+        {
+            Some(parent) => {
+                current = parent.clone();
+                cid = next;
+            }
+            // Pruned/archived boundary: linkage is proven up to here
+            None => break (next, false),
+        }
+    };
+    json!({
+        "path": path,
+        "anchor": anchor,
+        "complete": complete,
+        "hops": path.len(),
+    })
+}
+
 pub async fn get_receipt(
     State(state): State<AppState>,
     scope: Scope,
     _client: Option<Extension<ClientInfo>>,
     Path(cid_raw): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<ReceiptQuery>,
+    headers: axum::http::HeaderMap,
 ) -> impl IntoResponse {
     let cid_str = normalize_cid_in_path(&cid_raw);
+    let (mut with_proof, mut with_jcs) = (false, false);
+    for section in query.include.as_deref().unwrap_or("").split(',') {
+        match section.trim() {
+            "" => {}
+            "proof" => with_proof = true,
+            "jcs" => with_jcs = true,
+            other => {
+                return AppError::bad_request(format!(
+                    "unknown include '{other}'; known sections: proof, jcs"
+                ))
+                .into_response()
+            }
+        }
+    }
+
+    // Receipts are immutable per CID, so the CID itself is the ETag
+    let etag = format!("\"{cid_str}\"");
+    let revalidated = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|inm| inm.split(',').any(|t| t.trim() == etag || t.trim() == "*"));
+    let with_sections = with_proof || with_jcs;
+    // A proof's completeness can shift as ancestry is pruned, so only
+    // plain receipt responses honor conditional revalidation
+    if revalidated && !with_sections {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+    // Sectioned responses (proofs depend on how much ancestry the
+    // registry still holds) bypass the immutable-receipt response cache.
+    let cache_key = format!("receipt:{}", scope.scoped_cid(&cid_str));
+    if !with_sections {
+        if let Some((content_type, body)) = state.response_cache.get(&cache_key) {
+            return receipt_cache_headers(
+                ([(header::CONTENT_TYPE, content_type)], body.as_ref().clone()).into_response(),
+                &etag,
+            );
+        }
+    }
 
     // First check the receipt chain (populated by /v1/execute)
     // Storage key is scoped: "app:tenant:cid"
+    let mut found: Option<Value> = None;
     {
         let store = state.receipt_chain.read().unwrap();
         let scoped_key = scope.scoped_cid(&cid_str);
         if let Some(receipt) = store.get(&scoped_key) {
-            return (StatusCode::OK, Json(receipt.clone())).into_response();
-        }
-        // Fallback: try unscoped key (legacy data)
-        if let Some(receipt) = store.get(&cid_str) {
+            found = Some(receipt.clone());
+        } else if let Some(receipt) = store.get(&cid_str) {
             // Legacy receipt — check __tenant_id for backward compat
             let receipt_tenant = receipt
                 .get("__tenant_id")
@@ -208,8 +1241,36 @@ pub async fn get_receipt(
             if let Some(obj) = clean.as_object_mut() {
                 obj.remove("__tenant_id");
             }
-            return (StatusCode::OK, Json(clean)).into_response();
+            found = Some(clean);
+        }
+    }
+    if let Some(mut receipt) = found {
+        rehydrate_body(&scope.tenant, &mut receipt).await;
+        if with_jcs {
+            let section = match build_jcs_section(&receipt) {
+                Ok(s) => s,
+                Err(e) => return e.into_response(),
+            };
+            if let Some(obj) = receipt.as_object_mut() {
+                obj.insert("jcs".into(), section);
+            }
+        }
+        if with_proof {
+            // "proof" is taken by the envelope's JWS, so the chain path
+            // rides under its own key
+            let inclusion = build_inclusion_proof(&state, &scope, &cid_str, &receipt);
+            if let Some(obj) = receipt.as_object_mut() {
+                obj.insert("inclusion_proof".into(), inclusion);
+            }
+        }
+        if !with_sections {
+            if let Ok(bytes) = serde_json::to_vec(&receipt) {
+                state
+                    .response_cache
+                    .put(&cache_key, "application/json", Arc::new(bytes));
+            }
         }
+        return receipt_cache_headers((StatusCode::OK, Json(receipt)).into_response(), &etag);
     }
 
     // Fallback: legacy receipt store (ubl_receipt)
@@ -224,12 +1285,22 @@ pub async fn get_receipt(
         }
     };
     match ubl_receipt::get_receipt(&cid).await {
-        Some(jws) => (
-            StatusCode::OK,
-            [(header::CONTENT_TYPE, "application/jose+json")],
-            jws,
-        )
-            .into_response(),
+        Some(jws) => {
+            state.response_cache.put(
+                &cache_key,
+                "application/jose+json",
+                Arc::new(jws.clone().into_bytes()),
+            );
+            receipt_cache_headers(
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/jose+json")],
+                    jws,
+                )
+                    .into_response(),
+                &etag,
+            )
+        }
         None => (
             StatusCode::NOT_FOUND,
             Json(json!({"error": "receipt not found"})),
@@ -238,13 +1309,1001 @@ pub async fn get_receipt(
     }
 }
 
-pub async fn resolve(Json(payload): Json<Value>) -> impl IntoResponse {
+#[derive(Deserialize)]
+pub struct CosignRequest {
+    /// Declared threshold when the receipt has no cosign proof yet
+    /// (defaults to 2-of-N).
+    pub threshold: Option<u32>,
+}
+
+/// Async co-signing: append this scope's detached signature to a stored
+/// receipt's threshold proof. Co-signatures ride outside the canonical
+/// body, so body_cid and the chain stay untouched; signers accumulate
+/// across calls until the declared threshold is met.
+pub async fn cosign_receipt(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid_raw): Path<String>,
+    Json(req): Json<CosignRequest>,
+) -> Result<axum::response::Response, AppError> {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    if let Some(t) = req.threshold {
+        if t == 0 {
+            return Err(AppError::bad_request("threshold must be at least 1"));
+        }
+    }
+
+    let scoped_key = scope.scoped_cid(&cid_str);
+    let (store_key, mut receipt) = {
+        let store = state.receipt_chain.read().unwrap();
+        if let Some(rc) = store.get(&scoped_key) {
+            (scoped_key.clone(), rc.clone())
+        } else if let Some(rc) = store.get(&cid_str) {
+            (cid_str.clone(), rc.clone())
+        } else {
+            return Err(AppError::not_found("receipt"));
+        }
+    };
+
+    // Canonical bytes come from a rehydrated view; the stored envelope
+    // (possibly with a detached body_ref) is what gets the signature
+    let mut view = receipt.clone();
+    rehydrate_body(&scope.tenant, &mut view).await;
+    let canon = view
+        .get("canon")
+        .and_then(|c| c.as_str())
+        .unwrap_or(ubl_runtime::canon::PROFILE_NRF1_V1);
+    let body = view
+        .get("body")
+        .ok_or_else(|| AppError::unprocessable("receipt has no inline body"))?;
+    let body_bytes = ubl_runtime::canon::canonical_bytes_for(canon, body)
+        .map_err(|e| AppError::unprocessable(format!("body not canonicalizable: {e}")))?;
+
+    let keys = state.keyring_store.resolve_for_scope(&scope);
+    let kid = keys.active_kid.clone();
+    let mut signers: std::collections::HashSet<String> = std::collections::HashSet::new();
+    if let Some(primary) = receipt
+        .pointer("/proof/kid")
+        .and_then(|k| k.as_str())
+    {
+        signers.insert(primary.to_string());
+    }
+    for sig in receipt
+        .pointer("/cosign/signatures")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(k) = sig.get("kid").and_then(|k| k.as_str()) {
+            signers.insert(k.to_string());
+        }
+    }
+    if signers.contains(&kid) {
+        return Err(AppError::conflict(format!(
+            "kid {kid} has already signed this receipt"
+        )));
+    }
+
+    let jws = ubl_runtime::jws::sign_detached(&body_bytes, &keys.active, &kid);
+    let obj = receipt
+        .as_object_mut()
+        .ok_or_else(|| AppError::unprocessable("receipt is not an object"))?;
+    let cosign = obj.entry("cosign").or_insert_with(|| {
+        json!({"threshold": req.threshold.unwrap_or(2), "signatures": []})
+    });
+    if let Some(sigs) = cosign
+        .get_mut("signatures")
+        .and_then(|s| s.as_array_mut())
+    {
+        sigs.push(serde_json::to_value(&jws).unwrap_or_default());
+    }
+    signers.insert(kid.clone());
+    let threshold = cosign
+        .get("threshold")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(1);
+    let satisfied = signers.len() as u64 >= threshold;
+    let cosign_out = cosign.clone();
+
+    {
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(store_key, receipt);
+    }
+    // The cached /v1/receipt response no longer reflects the proof set
+    state.response_cache.invalidate_cid(&cid_str);
+
+    Ok(Json(json!({
+        "cid": cid_str,
+        "kid": kid,
+        "signers": signers.len(),
+        "threshold": threshold,
+        "satisfied": satisfied,
+        "cosign": cosign_out,
+    }))
+    .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AttestRequest {
+    pub target_cid: String,
+    pub claim: String,
+    /// Ledger CIDs backing the claim; each must already be stored.
+    #[serde(default)]
+    pub evidence: Vec<String>,
+}
+
+/// Mint a signed ubl/attestation receipt over stored content. Evidence
+/// CIDs are canonicalized (sorted, deduplicated) into the attestation
+/// body and must exist in the ledger at creation time, so a claim can
+/// never point at evidence that was never stored.
+pub async fn attest(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<AttestRequest>,
+) -> Result<axum::response::Response, AppError> {
+    let target = Cid::try_from(req.target_cid.as_str())
+        .map_err(|_| AppError::bad_request("invalid target_cid"))?;
+    if resolve_raw(&scope.tenant, &target).await.is_none() {
+        return Err(AppError::not_found("target content"));
+    }
+    if req.claim.trim().is_empty() {
+        return Err(AppError::bad_request("claim must not be empty"));
+    }
+    let mut evidence = req.evidence.clone();
+    evidence.sort();
+    evidence.dedup();
+    for ev in &evidence {
+        let cid = Cid::try_from(ev.as_str())
+            .map_err(|_| AppError::bad_request(format!("invalid evidence CID: {ev}")))?;
+        if resolve_raw(&scope.tenant, &cid).await.is_none() {
+            return Err(AppError::unprocessable(format!(
+                "evidence not found in ledger: {ev}"
+            )));
+        }
+    }
+
+    let attested_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let keys = state.keyring_store.resolve_for_scope(&scope);
+    let body = json!({
+        "type": "ubl/attestation",
+        "action": "attest",
+        "target_cid": req.target_cid,
+        "claim": req.claim,
+        "evidence": evidence,
+        "tenant": scope.tenant,
+        "attested_at": attested_at,
+    });
+    let receipt = ubl_runtime::build_receipt(
+        "ubl/attestation",
+        vec![],
+        body,
+        &keys.active,
+        &keys.active_kid,
+    )
+    .map_err(|e| AppError::internal(format!("attestation receipt: {e}")))?;
+    if let Ok(val) = serde_json::to_value(&receipt) {
+        index_receipts(&scope.tenant, None, None, &[(receipt.body_cid.clone(), val.clone())]).await;
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(scope.scoped_cid(&receipt.body_cid), val.clone());
+        store.insert(receipt.body_cid.clone(), val);
+    }
+    Ok((
+        StatusCode::OK,
+        Json(json!({"cid": receipt.body_cid, "receipt": receipt})),
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AckKeyRequest {
+    pub client_id: String,
+    /// Ed25519 public key, standard base64 (32 bytes).
+    pub public_key_b64: String,
+}
+
+/// Register the acknowledgement key a client will counter-sign with.
+/// Scoped to the caller's app/tenant, so the same client id in another
+/// tenant is a different signer.
+pub async fn admin_put_ack_key(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<AckKeyRequest>,
+) -> Result<axum::response::Response, AppError> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(&req.public_key_b64)
+        .map_err(|_| AppError::bad_request("invalid base64 public key"))?;
+    let key: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| AppError::bad_request("public key must be exactly 32 bytes"))?;
+    ed25519_dalek::VerifyingKey::from_bytes(&key)
+        .map_err(|_| AppError::bad_request("not a valid Ed25519 public key"))?;
+    state
+        .ack_keys
+        .write()
+        .unwrap()
+        .insert(scope.scoped_cid(&req.client_id), key);
+    Ok((
+        StatusCode::OK,
+        Json(json!({"client_id": req.client_id, "registered": true})),
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AdminTokenReq {
+    pub token: String,
+    pub client_id: String,
+    /// Defaults to the caller's tenant.
+    pub tenant_id: Option<String>,
+    #[serde(default)]
+    pub allowed_kids: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Ed25519 public key, standard base64 (32 bytes). Registered keys
+    /// are discoverable at /.well-known/ubl-clients/:client_id/did.json.
+    pub public_key_b64: Option<String>,
+    pub did: Option<String>,
+}
+
+/// Mint a bearer token with its client record, including the optional
+/// public key and DID that verification flows (acks, imported receipts)
+/// resolve through the token store.
+pub async fn admin_put_token(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<AdminTokenReq>,
+) -> Result<axum::response::Response, AppError> {
+    if req.token.len() < 16 {
+        return Err(AppError::bad_request("token must be at least 16 characters"));
+    }
+    if let Some(b64) = &req.public_key_b64 {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(b64)
+            .map_err(|_| AppError::bad_request("invalid base64 public key"))?;
+        let key: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| AppError::bad_request("public key must be exactly 32 bytes"))?;
+        ed25519_dalek::VerifyingKey::from_bytes(&key)
+            .map_err(|_| AppError::bad_request("not a valid Ed25519 public key"))?;
+    }
+    let info = ClientInfo {
+        client_id: req.client_id.clone(),
+        tenant_id: req.tenant_id.unwrap_or_else(|| scope.tenant.clone()),
+        allowed_kids: req.allowed_kids,
+        scopes: req.scopes,
+        public_key_b64: req.public_key_b64,
+        did: req.did,
+    };
+    let did = info.did();
+    state.token_store.register(&req.token, info);
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "client_id": req.client_id,
+            "did": did,
+            "discovery": format!("/.well-known/ubl-clients/{}/did.json", req.client_id),
+        })),
+    )
+        .into_response())
+}
+
+/// DID document for a registered client, publishing its key under `kid`.
+fn client_did_document(client: &ClientInfo, kid: &str) -> Option<Value> {
+    let key = client.verifying_key()?;
+    Some(json!({
+        "@context": ["https://www.w3.org/ns/did/v1"],
+        "id": client.did(),
+        "verificationMethod": [ubl_did::tenant_verification_method(kid, &key.to_bytes())],
+        "assertionMethod": [kid],
+    }))
+}
+
+/// Public key discovery: serve the DID document for a registered client,
+/// so peers (and this gate's own verification flows) can resolve the key
+/// a client counter-signs with.
+pub async fn well_known_client_did(
+    State(state): State<AppState>,
+    Path(client_id): Path<String>,
+) -> Result<axum::response::Response, AppError> {
+    let client = state
+        .token_store
+        .find_client_any(&client_id)
+        .ok_or_else(|| AppError::not_found("client"))?;
+    let did = client.did();
+    let doc = client_did_document(&client, &format!("{did}#key-1"))
+        .ok_or_else(|| AppError::not_found("client key"))?;
+    Ok((StatusCode::OK, Json(doc)).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AckRequest {
+    pub client_id: String,
+    /// Client-produced detached JWS over the target's body_cid bytes.
+    pub jws: ubl_runtime::jws::JwsDetached,
+}
+
+/// Counter-sign a receipt: the client acknowledges a WF result on-chain
+/// by submitting a detached JWS over the target's body_cid. The
+/// signature is verified against the client's registered key, then
+/// recorded as a signed `ubl/attestation` receipt parented to the
+/// target, so the acknowledgement travels with the chain.
+pub async fn ack_receipt(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid_raw): Path<String>,
+    Json(req): Json<AckRequest>,
+) -> Result<axum::response::Response, AppError> {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    let target_known = {
+        let store = state.receipt_chain.read().unwrap();
+        store.contains_key(&scope.scoped_cid(&cid_str)) || store.contains_key(&cid_str)
+    };
+    if !target_known {
+        return Err(AppError::not_found("receipt"));
+    }
+
+    // An explicitly registered ack key wins; otherwise fall back to key
+    // discovery on the client's token record
+    let explicit = state
+        .ack_keys
+        .read()
+        .unwrap()
+        .get(&scope.scoped_cid(&req.client_id))
+        .copied();
+    let verifying = match explicit {
+        Some(key) => ed25519_dalek::VerifyingKey::from_bytes(&key)
+            .map_err(|_| AppError::internal("stored acknowledgement key is invalid"))?,
+        None => state
+            .token_store
+            .find_client(&scope.tenant, &req.client_id)
+            .and_then(|c| c.verifying_key())
+            .ok_or_else(|| {
+                AppError::forbidden(format!(
+                    "no acknowledgement key registered for client {}",
+                    req.client_id
+                ))
+            })?,
+    };
+    if !ubl_runtime::jws::verify_detached(&req.jws, cid_str.as_bytes(), &verifying) {
+        return Err(AppError::unprocessable(
+            "acknowledgement signature does not verify over the body_cid",
+        ));
+    }
+
+    let acked_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let keys = state.keyring_store.resolve_for_scope(&scope);
+    let body = json!({
+        "type": "ubl/attestation",
+        "action": "ack",
+        "target_cid": cid_str,
+        "client_id": req.client_id,
+        "client_jws": req.jws,
+        "tenant": scope.tenant,
+        "acked_at": acked_at,
+    });
+    let receipt = ubl_runtime::build_receipt(
+        "ubl/attestation",
+        vec![cid_str.clone()],
+        body,
+        &keys.active,
+        &keys.active_kid,
+    )
+    .map_err(|e| AppError::internal(format!("ack receipt: {e}")))?;
+    if let Ok(val) = serde_json::to_value(&receipt) {
+        index_receipts(&scope.tenant, None, None, &[(receipt.body_cid.clone(), val.clone())]).await;
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(scope.scoped_cid(&receipt.body_cid), val.clone());
+        store.insert(receipt.body_cid.clone(), val);
+    }
+    Ok((
+        StatusCode::OK,
+        Json(json!({"cid": receipt.body_cid, "receipt": receipt})),
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ShareRequest {
+    /// Grant lifetime in seconds (default 24h, capped at 30 days).
+    pub ttl_secs: Option<u64>,
+}
+
+/// Mint a capability URL opening one receipt to unauthenticated auditors.
+/// The token is returned exactly once; losing it means minting a new one.
+pub async fn share_receipt(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid_raw): Path<String>,
+    Json(req): Json<ShareRequest>,
+) -> Result<axum::response::Response, AppError> {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    let ttl = req.ttl_secs.unwrap_or(crate::share::DEFAULT_TTL_SECS);
+    if ttl == 0 || ttl > crate::share::MAX_TTL_SECS {
+        return Err(AppError::bad_request(format!(
+            "ttl_secs must be between 1 and {}",
+            crate::share::MAX_TTL_SECS
+        )));
+    }
+    let scoped_key = scope.scoped_cid(&cid_str);
+    let store_key = {
+        let store = state.receipt_chain.read().unwrap();
+        if store.contains_key(&scoped_key) {
+            scoped_key
+        } else if store.contains_key(&cid_str) {
+            cid_str.clone()
+        } else {
+            return Err(AppError::not_found("receipt"));
+        }
+    };
+    let expires_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        + ttl;
+    let token = state.share_store.mint(crate::share::ShareGrant {
+        scoped_cid: store_key,
+        cid: cid_str.clone(),
+        tenant: scope.tenant.clone(),
+        expires_at,
+    });
+    Ok(Json(json!({
+        "token": token,
+        "url": format!("/v1/share/{token}"),
+        "cid": cid_str,
+        "expires_at": expires_at,
+    }))
+    .into_response())
+}
+
+/// Redeem a share token: the full receipt plus an envelope-only proof of
+/// its ancestor chain (CIDs, parents, and signatures — no bodies, so a
+/// grant never leaks sibling content).
+pub async fn get_shared(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<axum::response::Response, AppError> {
+    let grant = state
+        .share_store
+        .redeem(&token)
+        .ok_or_else(|| AppError::not_found("share"))?;
+    let mut receipt = {
+        let store = state.receipt_chain.read().unwrap();
+        store
+            .get(&grant.scoped_cid)
+            .cloned()
+            .ok_or_else(|| AppError::not_found("receipt"))?
+    };
+    rehydrate_body(&grant.tenant, &mut receipt).await;
+
+    // Walk parents breadth-first, envelope fields only, bounded depth
+    let mut chain = Vec::new();
+    let mut frontier: Vec<String> = receipt
+        .get("parents")
+        .and_then(|p| p.as_array())
+        .map(|ps| {
+            ps.iter()
+                .filter_map(|p| p.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut seen: std::collections::HashSet<String> = frontier.iter().cloned().collect();
+    // Parents live under the same scope prefix as the shared receipt
+    let scope_prefix = grant
+        .scoped_cid
+        .strip_suffix(&format!(":{}", grant.cid))
+        .map(str::to_string);
+    let store = state.receipt_chain.read().unwrap();
+    while let Some(cid) = frontier.pop() {
+        if chain.len() >= 32 {
+            break;
+        }
+        let scoped = scope_prefix.as_ref().map(|p| format!("{p}:{cid}"));
+        let Some(parent) = scoped
+            .as_ref()
+            .and_then(|k| store.get(k))
+            .or_else(|| store.get(&cid))
+        else {
+            continue;
+        };
+        chain.push(json!({
+            "cid": cid,
+            "t": parent.get("t"),
+            "parents": parent.get("parents"),
+            "body_cid": parent.get("body_cid"),
+            "proof": parent.get("proof"),
+        }));
+        for p in parent
+            .get("parents")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.as_str())
+        {
+            if seen.insert(p.to_string()) {
+                frontier.push(p.to_string());
+            }
+        }
+    }
+    drop(store);
+
+    Ok(Json(json!({
+        "cid": grant.cid,
+        "receipt": receipt,
+        "chain": chain,
+        "expires_at": grant.expires_at,
+    }))
+    .into_response())
+}
+
+/// Revoke a share token before its expiry.
+pub async fn revoke_share(
+    State(state): State<AppState>,
+    Path((cid_raw, token)): Path<(String, String)>,
+) -> Result<axum::response::Response, AppError> {
+    let cid_str = normalize_cid_in_path(&cid_raw);
+    if state.share_store.revoke(&cid_str, &token) {
+        Ok(Json(json!({"revoked": true})).into_response())
+    } else {
+        Err(AppError::not_found("share"))
+    }
+}
+
+/// Stamp immutable caching headers on a successful receipt response.
+fn receipt_cache_headers(mut resp: axum::response::Response, etag: &str) -> axum::response::Response {
+    if let Ok(v) = etag.parse() {
+        resp.headers_mut().insert(header::ETAG, v);
+    }
+    resp.headers_mut().insert(
+        header::CACHE_CONTROL,
+        axum::http::HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+    resp
+}
+
+pub async fn resolve(
+    State(state): State<AppState>,
+    Json(payload): Json<Value>,
+) -> impl IntoResponse {
     let id = payload.get("id").and_then(|v| v.as_str()).unwrap_or("");
-    Json(ubl_did::resolve_did_or_cid(id, &ubl_config::BASE_URL))
+    let mut doc = ubl_did::resolve_did_or_cid(id, &ubl_config::BASE_URL);
+    // Locally known receipts enrich the resolution with chain linkage,
+    // including the foreign issuer recorded for federated prev_tips
+    if id.starts_with("b3:") {
+        let store = state.receipt_chain.read().unwrap();
+        if let Some(receipt) = store.get(id) {
+            doc["parents"] = receipt.get("parents").cloned().unwrap_or(json!([]));
+            if let Some(body) = receipt.get("body") {
+                if let Some(prev) = body.get("prev_tip") {
+                    doc["prev_tip"] = prev.clone();
+                }
+                if let Some(issuer) = body.get("prev_tip_issuer") {
+                    doc["prev_tip_issuer"] = issuer.clone();
+                }
+                // Attestation evidence is part of the provenance trail
+                if let Some(evidence) = body.get("evidence") {
+                    doc["evidence"] = evidence.clone();
+                }
+            }
+        }
+    }
+    Json(doc)
+}
+
+/// GET /.well-known/ubl/schemas — the versioned JSON Schemas receipt
+/// bodies are validated against, so clients can validate before posting.
+pub async fn well_known_schemas() -> impl IntoResponse {
+    Json(json!({
+        "version": ubl_runtime::schema::SCHEMA_V1,
+        "schemas": ubl_runtime::schema::schemas_json(),
+    }))
+}
+
+/// GET /.well-known/ubl.json — signed gate identity for verification
+/// bootstrapping. Clients learn the issuer DID, the kids in rotation,
+/// canon profile, API version, and endpoint map from one document, and
+/// check its proof against the key published at /.well-known/did.json.
+pub async fn well_known_ubl_json(State(state): State<AppState>) -> impl IntoResponse {
+    let keys = state.keyring_store.global();
+    let mut kids = vec![json!({
+        "kid": keys.active_kid,
+        "status": "active",
+        "public_key_multibase": key_multibase(&keys.active_kid, &keys.active.verifying_key().to_bytes()),
+    })];
+    // The next key is published before it signs anything, so verifiers
+    // can pre-trust it across a rotation
+    if let (Some(next), Some(next_kid)) = (&keys.next, &keys.next_kid) {
+        kids.push(json!({
+            "kid": next_kid,
+            "status": "next",
+            "public_key_multibase": key_multibase(next_kid, &next.verifying_key().to_bytes()),
+        }));
+    }
+    for (scope_key, ring) in state.keyring_store.list_scoped() {
+        kids.push(json!({
+            "kid": ring.active_kid,
+            "status": "active",
+            "scope": scope_key,
+            "public_key_multibase": key_multibase(&ring.active_kid, &ring.active.verifying_key().to_bytes()),
+        }));
+    }
+    let body = json!({
+        "issuer": ubl_receipt::ISSUER_DID.as_str(),
+        "api_version": "v1",
+        "canon": ubl_runtime::canon::PROFILE_NRF1_V1,
+        "schema_version": ubl_runtime::schema::SCHEMA_V1,
+        "receipt_types": ubl_runtime::VALID_TYPES,
+        "keys": kids,
+        "endpoints": {
+            "execute": "/v1/execute",
+            "ingest": "/v1/ingest",
+            "receipts": "/v1/receipts",
+            "receipt": "/v1/receipt/{cid}",
+            "did": "/.well-known/did.json",
+            "schemas": "/.well-known/ubl/schemas",
+        },
+    });
+    // Signed like a receipt body: canonical bytes under the active key
+    let bytes = match ubl_runtime::canon::canonical_bytes(&body) {
+        Ok(b) => b,
+        Err(e) => return AppError::internal(e.to_string()).into_response(),
+    };
+    let body_cid = ubl_runtime::cid::cid_b3(&bytes);
+    let proof = ubl_runtime::jws::sign_detached(&bytes, &keys.active, &keys.active_kid);
+    (
+        StatusCode::OK,
+        Json(json!({"body": body, "body_cid": body_cid, "proof": proof})),
+    )
+        .into_response()
+}
+
+/// Multibase encoding of an Ed25519 public key, shared with the DID
+/// document so the two endpoints can never disagree on key material.
+fn key_multibase(kid: &str, pubkey: &[u8; 32]) -> Value {
+    ubl_did::tenant_verification_method(kid, pubkey)
+        .get("publicKeyMultibase")
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+pub async fn well_known_did_json(State(state): State<AppState>) -> impl IntoResponse {
+    let mut doc = ubl_did::runtime_did_document();
+    // Surface the global and provisioned tenant keys so every receipt this
+    // gate mints resolves here — federated peers verify against this document
+    let global = state.keyring_store.global();
+    let mut keyrings = vec![global];
+    keyrings.extend(state.keyring_store.list_scoped().into_iter().map(|(_, k)| k));
+    for keyring in keyrings {
+        let pubkey = keyring.active.verifying_key().to_bytes();
+        let method = ubl_did::tenant_verification_method(&keyring.active_kid, &pubkey);
+        if let Some(methods) = doc
+            .get_mut("verificationMethod")
+            .and_then(|m| m.as_array_mut())
+        {
+            methods.push(method);
+        }
+        if let Some(assertions) = doc
+            .get_mut("assertionMethod")
+            .and_then(|a| a.as_array_mut())
+        {
+            assertions.push(json!(keyring.active_kid));
+        }
+    }
+    Json(doc)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminKeyRingReq {
+    /// App namespace; defaults to the request scope's app.
+    pub app: Option<String>,
+    /// Tenant namespace; defaults to the request scope's tenant.
+    pub tenant: Option<String>,
+    /// Key id; defaults to `did:ubl:<app>:<tenant>#k1`.
+    pub kid: Option<String>,
+    /// BYOK import: base64-encoded 32-byte Ed25519 seed.
+    pub seed_b64: String,
+}
+
+/// Provision a tenant-scoped signing keyring (BYOK import).
+pub async fn admin_put_keyring(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<AdminKeyRingReq>,
+) -> impl IntoResponse {
+    let app = req.app.unwrap_or_else(|| scope.app.clone());
+    let tenant = req.tenant.unwrap_or_else(|| scope.tenant.clone());
+    let seed_bytes =
+        match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &req.seed_b64) {
+            Ok(b) => b,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid base64 seed"})),
+                )
+                    .into_response()
+            }
+        };
+    let seed: [u8; 32] = match seed_bytes.as_slice().try_into() {
+        Ok(s) => s,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "seed must be exactly 32 bytes"})),
+            )
+                .into_response()
+        }
+    };
+    let kid = req
+        .kid
+        .unwrap_or_else(|| format!("did:ubl:{app}:{tenant}#k1"));
+    let keyring = ubl_runtime::KeyRing::from_seed(seed, kid.clone());
+    let pubkey = keyring.active.verifying_key().to_bytes();
+    state.keyring_store.set_scoped(&app, &tenant, keyring);
+    (
+        StatusCode::OK,
+        Json(json!({
+            "app": app,
+            "tenant": tenant,
+            "kid": kid,
+            "verificationMethod": ubl_did::tenant_verification_method(&kid, &pubkey),
+        })),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RetentionReq {
+    /// Target tenant; defaults to the request scope.
+    pub tenant: Option<String>,
+    pub warm_after_secs: Option<u64>,
+    pub archive_after_secs: Option<u64>,
+}
+
+/// Set the tenant's retention policy (hot/warm/archive thresholds). The
+/// background sweeper picks it up on its next pass; archived blobs keep
+/// serving through the ledger's read-through.
+pub async fn admin_put_retention(
+    scope: Scope,
+    Json(req): Json<RetentionReq>,
+) -> impl IntoResponse {
+    let tenant = req.tenant.unwrap_or_else(|| scope.tenant.clone());
+    if let (Some(warm), Some(archive)) = (req.warm_after_secs, req.archive_after_secs) {
+        if warm >= archive {
+            return AppError::bad_request("warm_after_secs must be below archive_after_secs")
+                .into_response();
+        }
+    }
+    let policy = ubl_ledger::RetentionPolicy {
+        warm_after_secs: req.warm_after_secs,
+        archive_after_secs: req.archive_after_secs,
+    };
+    if let Err(e) = ubl_ledger::tenant_set_retention(&tenant, policy).await {
+        return AppError::internal(format!("retention write: {e}")).into_response();
+    }
+    (
+        StatusCode::OK,
+        Json(json!({
+            "tenant": tenant,
+            "warm_after_secs": policy.warm_after_secs,
+            "archive_after_secs": policy.archive_after_secs,
+        })),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayCacheReq {
+    /// Tenant to configure; defaults to the caller's scope.
+    pub tenant: Option<String>,
+    pub enabled: bool,
+}
+
+/// POST /v1/admin/replay-cache — opt a tenant in or out of the
+/// deterministic replay cache on `/v1/execute`. Disabling also drops the
+/// tenant's cached pairs, so re-enabling starts cold.
+pub async fn admin_put_replay_cache(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<ReplayCacheReq>,
+) -> impl IntoResponse {
+    let tenant = req.tenant.unwrap_or_else(|| scope.tenant.clone());
+    {
+        let mut tenants = state.replay_cache_tenants.write().unwrap();
+        if req.enabled {
+            tenants.insert(tenant.clone());
+        } else {
+            tenants.remove(&tenant);
+        }
+    }
+    if !req.enabled {
+        // Keys are scoped "app:tenant:manifest_cid:inputs_cid"
+        state
+            .replay_cache
+            .write()
+            .unwrap()
+            .retain(|k, _| k.split(':').nth(1) != Some(tenant.as_str()));
+    }
+    (
+        StatusCode::OK,
+        Json(json!({ "tenant": tenant, "enabled": req.enabled })),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChipAllowlistReq {
+    /// Tenant to configure; defaults to the caller's scope.
+    pub tenant: Option<String>,
+    pub enabled: bool,
+    /// Bytecode CIDs the tenant may execute. Replaces the current list;
+    /// an empty list with enabled=true denies every chip.
+    #[serde(default)]
+    pub cids: Vec<String>,
+}
+
+/// POST /v1/admin/chip-allowlist — set or clear a tenant's reviewed-
+/// bytecode policy for `/v1/execute/rb`. While enabled, chips outside
+/// the list are refused with a signed DENY receipt naming the missing
+/// approval; disabling restores run-anything.
+pub async fn admin_put_chip_allowlist(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<ChipAllowlistReq>,
+) -> impl IntoResponse {
+    let tenant = req.tenant.unwrap_or_else(|| scope.tenant.clone());
+    let mut cids = req.cids;
+    cids.sort();
+    cids.dedup();
+    {
+        let mut lists = state.chip_allowlists.write().unwrap();
+        if req.enabled {
+            lists.insert(tenant.clone(), cids.iter().cloned().collect());
+        } else {
+            lists.remove(&tenant);
+        }
+    }
+    (
+        StatusCode::OK,
+        Json(json!({ "tenant": tenant, "enabled": req.enabled, "cids": cids })),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuotaReq {
+    /// Tenant to configure; defaults to the caller's scope.
+    pub tenant: Option<String>,
+    #[serde(default)]
+    pub period: crate::quota::Period,
+    pub max_executions: Option<u64>,
+    pub max_fuel: Option<u64>,
+    pub max_stored_bytes: Option<u64>,
+}
+
+/// Set the tenant's budget policy. Counters keep their current window;
+/// new limits apply from the next check.
+pub async fn admin_put_quota(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<QuotaReq>,
+) -> impl IntoResponse {
+    let tenant = req.tenant.unwrap_or_else(|| scope.tenant.clone());
+    let policy = crate::quota::QuotaPolicy {
+        period: req.period,
+        max_executions: req.max_executions,
+        max_fuel: req.max_fuel,
+        max_stored_bytes: req.max_stored_bytes,
+    };
+    state.quota.set_policy(&tenant, policy.clone());
+    (StatusCode::OK, Json(json!({ "tenant": tenant, "quota": policy }))).into_response()
+}
+
+/// Current-window usage and limits for the caller's tenant.
+pub async fn get_quota_usage(State(state): State<AppState>, scope: Scope) -> impl IntoResponse {
+    let usage = state.quota.usage(&scope.tenant);
+    let limits = state.quota.policy(&scope.tenant);
+    (
+        StatusCode::OK,
+        Json(json!({
+            "tenant": scope.tenant,
+            "usage": usage,
+            "limits": limits,
+        })),
+    )
+        .into_response()
+}
+
+/// Budget gate for the execute path: a blown budget denies with 429 and
+/// leaves a signed DENY receipt noting `quota_exceeded`, same as any
+/// other refused execution.
+async fn deny_quota(
+    state: &AppState,
+    scope: &Scope,
+    keys: &ubl_runtime::KeyRing,
+    pipeline: &str,
+    denial: crate::quota::QuotaDenial,
+) -> axum::response::Response {
+    let detail = format!(
+        "quota_exceeded: {} {}/{} in current window",
+        denial.resource, denial.used, denial.limit
+    );
+    let deny_body = json!({
+        "type": "ubl/wf",
+        "rho_cid": null,
+        "outputs_cid": null,
+        "decision": "DENY",
+        "reason": detail,
+        "reason_code": {"code": "quota_exceeded", "resource": denial.resource},
+        "pipeline": pipeline,
+        "inputs_raw_cid": null,
+        "dimension_stack": [],
+    });
+    let err = AppError::quota_exceeded(detail, denial.retry_after_secs);
+    let Ok(rc) = ubl_runtime::build_receipt("ubl/wf", vec![], deny_body, &keys.active, &keys.active_kid)
+    else {
+        return err.into_response();
+    };
+    let Ok(rc_val) = serde_json::to_value(&rc) else {
+        return err.into_response();
+    };
+    index_receipts(&scope.tenant, Some(pipeline), None, &[(rc.body_cid.clone(), rc_val.clone())])
+        .await;
+    {
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(scope.scoped_cid(&rc.body_cid), rc_val.clone());
+        store.insert(rc.body_cid.clone(), rc_val.clone());
+    }
+    err.with_deny_receipt(rc_val).into_response()
 }
 
-pub async fn well_known_did_json() -> impl IntoResponse {
-    Json(ubl_did::runtime_did_document())
+/// Mint and register a signed DENY receipt for bytecode outside the
+/// tenant's allowlist — same shape as a quota denial, with the missing
+/// approval named so the reviewer knows exactly what to list.
+async fn deny_unlisted_chip(
+    state: &AppState,
+    scope: &Scope,
+    keys: &ubl_runtime::KeyRing,
+    bytecode_cid: &str,
+) -> axum::response::Response {
+    let detail = format!("chip not allowlisted: {bytecode_cid} has no approval for this tenant");
+    metrics::counter!(
+        "ubl_chip_allowlist_denies_total",
+        "tenant" => scope.tenant.clone(),
+    )
+    .increment(1);
+    let deny_body = json!({
+        "type": "ubl/wf",
+        "rho_cid": null,
+        "outputs_cid": null,
+        "decision": "DENY",
+        "reason": detail,
+        "reason_code": {"code": "chip_not_allowlisted", "bytecode_cid": bytecode_cid},
+        "pipeline": null,
+        "inputs_raw_cid": null,
+        "dimension_stack": [],
+    });
+    let err = AppError::forbidden(detail);
+    let Ok(rc) = ubl_runtime::build_receipt("ubl/wf", vec![], deny_body, &keys.active, &keys.active_kid)
+    else {
+        return err.into_response();
+    };
+    let Ok(rc_val) = serde_json::to_value(&rc) else {
+        return err.into_response();
+    };
+    index_receipts(&scope.tenant, None, None, &[(rc.body_cid.clone(), rc_val.clone())]).await;
+    {
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(scope.scoped_cid(&rc.body_cid), rc_val.clone());
+        store.insert(rc.body_cid.clone(), rc_val.clone());
+    }
+    err.with_deny_receipt(rc_val).into_response()
 }
 
 #[derive(Debug, Deserialize)]
@@ -260,183 +2319,1641 @@ pub struct ExecResponse {
     pub dimension_stack: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ExecRbRequest {
-    pub chip_b64: String,
-    pub inputs: Vec<Value>,
-    pub ghost: Option<bool>,
-    pub fuel: Option<u64>,
+#[derive(Debug, Deserialize)]
+pub struct ExecRbRequest {
+    pub chip_b64: String,
+    pub inputs: Vec<Value>,
+    pub ghost: Option<bool>,
+    pub fuel: Option<u64>,
+    /// Checked integer semantics: overflow denies instead of saturating.
+    pub checked_arith: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExecRbResponse {
+    pub rc_cid: Option<String>,
+    pub steps: u64,
+    pub fuel_used: u64,
+    pub transition_receipt: Option<Value>,
+}
+
+pub async fn execute_rb(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<ExecRbRequest>,
+) -> impl IntoResponse {
+    // Budget gate: fuel is metered per tenant
+    if let Err(denial) = state
+        .quota
+        .check(&scope.tenant, &[crate::quota::Resource::Fuel])
+    {
+        return AppError::quota_exceeded(
+            format!(
+                "quota_exceeded: {} {}/{} in current window",
+                denial.resource, denial.used, denial.limit
+            ),
+            denial.retry_after_secs,
+        )
+        .into_response();
+    }
+    let chip =
+        match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &req.chip_b64) {
+            Ok(b) => b,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid base64 chip"})),
+                )
+                    .into_response()
+            }
+        };
+    // Bytecode CID doubles as the chip's registry handle: bytes and
+    // stats persist under it for GET /v1/chips/:cid
+    let bytecode_cid = ubl_runtime::cid::cid_b3(&chip);
+    let chip_bytes = chip.clone();
+    // Chip policy: tenants with an allowlist only run reviewed bytecode
+    if !state.chip_allowlist_bypass {
+        let listed = state
+            .chip_allowlists
+            .read()
+            .unwrap()
+            .get(&scope.tenant)
+            .map(|set| set.contains(&bytecode_cid));
+        if listed == Some(false) {
+            let keys = state.keyring_store.resolve_for_scope(&scope);
+            return deny_unlisted_chip(&state, &scope, &keys, &bytecode_cid).await;
+        }
+    }
+    let rb_req = ubl_runtime::ExecuteRbReq {
+        chip,
+        inputs: req.inputs,
+        ghost: req.ghost,
+        fuel: req.fuel,
+        checked_arith: req.checked_arith,
+    };
+    // CPU-bound chip run goes onto the bounded blocking pool; shed with
+    // 503 + Retry-After when no slot frees up within the queue timeout
+    let result = state
+        .rb_executor
+        .run(move || ubl_runtime::execute_rb(&rb_req))
+        .await;
+    let result = match result {
+        Ok(r) => r,
+        Err(()) => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(
+                    axum::http::header::RETRY_AFTER,
+                    state.rb_executor.retry_after_secs().to_string(),
+                )],
+                Json(json!({"error": "rb_pool_saturated"})),
+            )
+                .into_response()
+        }
+    };
+    match result {
+        Ok(res) => {
+            // Store transition receipt for GET /v1/transition/:cid
+            if let Some(ref tr) = res.transition_receipt {
+                if let Some(cid) = tr.get("cid").and_then(|c| c.as_str()) {
+                    let mut store = state.transition_receipts.write().unwrap();
+                    store.insert(cid.to_string(), tr.clone());
+                    // Also index by rho_cid for lookup convenience
+                    if let Some(rho) = tr
+                        .get("body")
+                        .and_then(|b| b.get("rho_cid"))
+                        .and_then(|r| r.as_str())
+                    {
+                        store.insert(rho.to_string(), tr.clone());
+                    }
+                }
+            }
+            state.quota.charge(&scope.tenant, 0, res.fuel_used, 0);
+            // Register the chip and its run for later inspection
+            let _ = ubl_ledger::tenant_put_body(&scope.tenant, &bytecode_cid, &chip_bytes).await;
+            {
+                let mut stats = state.chip_stats.write().unwrap();
+                let entry = stats.entry(scope.scoped_cid(&bytecode_cid)).or_default();
+                entry.runs += 1;
+                entry.fuel_total += res.fuel_used;
+                entry.last_fuel_used = res.fuel_used;
+            }
+            let resp = ExecRbResponse {
+                rc_cid: res.rc_cid,
+                steps: res.steps,
+                fuel_used: res.fuel_used,
+                transition_receipt: res.transition_receipt,
+            };
+            (StatusCode::OK, Json(resp)).into_response()
+        }
+        Err(e) => {
+            use ubl_runtime::error::ErrorCode;
+            let code = e.code();
+            // One counter per error class — DENY spikes, starved fuel
+            // budgets and malformed chips alert on different runbooks
+            let class = match &code {
+                ErrorCode::PolicyDeny { .. } => "policy_deny",
+                ErrorCode::FuelExhausted { .. } => "fuel_exhausted",
+                ErrorCode::Codec { .. } => "malformed_chip",
+                _ => "engine",
+            };
+            metrics::counter!(
+                "ubl_rb_errors_total",
+                "class" => class,
+                "tenant" => scope.tenant.clone(),
+            )
+            .increment(1);
+            let code_json = serde_json::to_value(&code).unwrap_or(Value::Null);
+            match code {
+                // A deny is a decision, not a failure: the chip ran to
+                // completion and refused — same 200 + DENY contract as
+                // the pipeline execute
+                ErrorCode::PolicyDeny { rule } => {
+                    // A deny is still a run; it counts toward the
+                    // chip's inspection history
+                    let _ = ubl_ledger::tenant_put_body(&scope.tenant, &bytecode_cid, &chip_bytes)
+                        .await;
+                    {
+                        let mut stats = state.chip_stats.write().unwrap();
+                        let entry = stats.entry(scope.scoped_cid(&bytecode_cid)).or_default();
+                        entry.runs += 1;
+                        entry.denies += 1;
+                    }
+                    (
+                        StatusCode::OK,
+                        Json(json!({
+                            "decision": "DENY",
+                            "rule": rule,
+                            "error_code": code_json,
+                        })),
+                    )
+                        .into_response()
+                }
+                // The fuel budget ran out mid-chip: the request is
+                // well-formed, the budget isn't — 507, not 422
+                ErrorCode::FuelExhausted { limit } => (
+                    StatusCode::INSUFFICIENT_STORAGE,
+                    Json(json!({
+                        "error": "fuel_exhausted",
+                        "fuel_limit": limit,
+                        "error_code": code_json,
+                        "detail": e.to_string(),
+                    })),
+                )
+                    .into_response(),
+                // TLV decode failure: the chip bytes themselves are
+                // malformed, a client bug like bad base64
+                ErrorCode::Codec { .. } => (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "malformed_chip",
+                        "error_code": code_json,
+                        "detail": e.to_string(),
+                    })),
+                )
+                    .into_response(),
+                _ => (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "error": "execute_rb_failed",
+                        "error_code": code_json,
+                        "detail": e.to_string(),
+                    })),
+                )
+                    .into_response(),
+            }
+        }
+    }
+}
+
+pub async fn estimate_rb(Json(req): Json<ExecRbRequest>) -> impl IntoResponse {
+    let chip =
+        match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &req.chip_b64) {
+            Ok(b) => b,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid base64 chip"})),
+                )
+                    .into_response()
+            }
+        };
+    let rb_req = ubl_runtime::ExecuteRbReq {
+        chip,
+        inputs: req.inputs,
+        ghost: Some(true),
+        fuel: None,
+        checked_arith: req.checked_arith,
+    };
+    match ubl_runtime::estimate_rb(&rb_req) {
+        Ok(est) => (StatusCode::OK, Json(json!(est))).into_response(),
+        Err(e) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "estimate_rb_failed",
+                "detail": e.to_string()
+            })),
+        )
+            .into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LintRbRequest {
+    pub chip_b64: String,
+}
+
+pub async fn lint_rb(Json(req): Json<LintRbRequest>) -> impl IntoResponse {
+    let chip =
+        match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &req.chip_b64) {
+            Ok(b) => b,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid base64 chip"})),
+                )
+                    .into_response()
+            }
+        };
+    let report = rb_vm::lint_chip(&chip);
+    // Pretty-printed assembly alongside the diagnostics, when the TLV
+    // decodes — instr_index diagnostics point into these lines
+    let disasm = rb_vm::disasm::format(&chip).ok();
+    (
+        StatusCode::OK,
+        Json(json!({
+            "ok": !report.has_errors(),
+            "report": report,
+            "disasm": disasm,
+        })),
+    )
+        .into_response()
+}
+
+/// GET /v1/chips/standard — the curated chip library shipped with rb_vm.
+///
+/// Static crate data: names, input contracts and pinned bytecode CIDs,
+/// plus the bytecode itself (`chip_b64`, same encoding the lint endpoint
+/// accepts) so callers can run a standard chip without assembling anything.
+pub async fn list_standard_chips() -> impl IntoResponse {
+    let chips: Vec<_> = rb_vm::stdchips::all()
+        .iter()
+        .map(|chip| {
+            json!({
+                "name": chip.name,
+                "description": chip.description,
+                "inputs": chip.inputs,
+                "bytecode_cid": chip.bytecode_cid(),
+                "chip_b64": base64::Engine::encode(
+                    &base64::engine::general_purpose::STANDARD,
+                    chip.bytecode(),
+                ),
+            })
+        })
+        .collect();
+    Json(json!({ "chips": chips }))
+}
+
+/// GET /v1/chips/:cid — everything a reviewer needs before allowing a
+/// chip: disassembly, lint diagnostics, static fuel bounds, and the
+/// tenant's execution history. Resolves standard-library chips for
+/// everyone; other bytecode only for the tenant that ran it.
+pub async fn get_chip(
+    State(state): State<AppState>,
+    scope: Scope,
+    Path(cid): Path<String>,
+) -> impl IntoResponse {
+    let cid = normalize_cid_in_path(&cid);
+    let standard = rb_vm::stdchips::all()
+        .iter()
+        .find(|c| c.bytecode_cid() == cid);
+    let bytes = match standard {
+        Some(chip) => chip.bytecode().to_vec(),
+        None => match ubl_ledger::tenant_get_body(&scope.tenant, &cid).await {
+            Some(b) => b,
+            None => return AppError::not_found("chip").into_response(),
+        },
+    };
+    let report = rb_vm::lint_chip(&bytes);
+    let worst_case_fuel = report.worst_case_fuel;
+    let disasm = rb_vm::disasm::format(&bytes).ok();
+    let stats = state
+        .chip_stats
+        .read()
+        .unwrap()
+        .get(&scope.scoped_cid(&cid))
+        .cloned();
+    let mut resp = json!({
+        "cid": cid,
+        "bytes_len": bytes.len(),
+        "disasm": disasm,
+        "lint": { "ok": !report.has_errors(), "report": report },
+        "fuel": {
+            "worst_case": worst_case_fuel,
+            // Same 25% margin the estimate endpoint suggests
+            "suggested_limit": worst_case_fuel + worst_case_fuel / 4,
+        },
+        "stats": stats,
+    });
+    if let Some(chip) = standard {
+        resp["standard"] = json!({
+            "name": chip.name,
+            "description": chip.description,
+            "inputs": chip.inputs,
+        });
+    }
+    (StatusCode::OK, Json(resp)).into_response()
+}
+
+/// Append listing entries for freshly stored receipts to the tenant's
+/// persisted index. Must run before bodies are detached, while decision
+/// and pipeline are still inline. Every receipt that passes through here
+/// is also logged via [`crate::receipt_log`], so `latency_ms` (where the
+/// handler measured one) rides along for the operational log.
+async fn index_receipts(
+    tenant: &str,
+    pipeline: Option<&str>,
+    latency_ms: Option<u64>,
+    entries: &[(String, Value)],
+) {
+    for (cid, val) in entries {
+        crate::receipt_log::log_commit(tenant, cid, val, latency_ms);
+        let entry = index_entry(pipeline, cid, val);
+        let _ = ubl_ledger::tenant_index_append(tenant, entry.to_string().as_bytes()).await;
+    }
+}
+
+/// Build one listing line for the tenant's persisted index.
+fn index_entry(pipeline: Option<&str>, cid: &str, val: &Value) -> Value {
+    json!({
+        "cid": cid,
+        "t": val.get("t").cloned().unwrap_or(Value::Null),
+        "decision": val.pointer("/body/decision").cloned().unwrap_or(Value::Null),
+        "pipeline": val
+            .pointer("/body/pipeline")
+            .or_else(|| val.pointer("/body/intention/pipeline"))
+            .cloned()
+            .or_else(|| pipeline.map(Value::from))
+            .unwrap_or(Value::Null),
+        "rule": val
+            .pointer("/body/reason_code/code")
+            .cloned()
+            .unwrap_or(Value::Null),
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "parent": val
+            .get("parents")
+            .and_then(|p| p.as_array())
+            .and_then(|a| a.first())
+            .cloned()
+            .unwrap_or(Value::Null),
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReceiptListQuery {
+    /// Time-travel: reconstruct the registry as it looked when this tip
+    /// was current, by walking parents from the tip.
+    pub as_of_tip: Option<String>,
+    /// Exact-match filters on the index fields, shared between the
+    /// receipt listing and the audit export.
+    pub t: Option<String>,
+    pub decision: Option<String>,
+    pub pipeline: Option<String>,
+    /// Inclusive RFC 3339 bounds on the index timestamp.
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Audit export format: "csv" or "ndjson". Omit for the JSON report.
+    pub format: Option<String>,
+    /// Comma-separated column subset for tabular exports.
+    pub columns: Option<String>,
+}
+
+/// Does one receipt index entry pass the query's filters?
+fn index_entry_matches(entry: &Value, query: &ReceiptListQuery) -> bool {
+    let field = |key: &str| entry.get(key).and_then(|v| v.as_str());
+    let exact = |want: &Option<String>, key: &str| match want {
+        Some(w) => field(key) == Some(w.as_str()),
+        None => true,
+    };
+    exact(&query.t, "t")
+        && exact(&query.decision, "decision")
+        && exact(&query.pipeline, "pipeline")
+        // RFC 3339 timestamps order lexicographically
+        && query
+            .since
+            .as_deref()
+            .is_none_or(|s| field("ts").is_some_and(|ts| ts >= s))
+        && query
+            .until
+            .as_deref()
+            .is_none_or(|u| field("ts").is_some_and(|ts| ts <= u))
+}
+
+/// Reconstruct the registry view at the moment `tip` was the chain head:
+/// every receipt reachable from the tip through `parents`. Parents this
+/// gate does not hold (foreign tips, detached history) are skipped — the
+/// snapshot is what this registry knew, not a global claim.
+fn snapshot_at_tip(
+    state: &AppState,
+    scope: &Scope,
+    tip: &str,
+) -> Result<BTreeMap<String, Value>, AppError> {
+    let store = state.receipt_chain.read().unwrap();
+    let lookup = |cid: &str| -> Option<Value> {
+        store
+            .get(&scope.scoped_cid(cid))
+            .or_else(|| store.get(cid))
+            .cloned()
+    };
+    if lookup(tip).is_none() {
+        return Err(AppError::not_found(&format!("tip {tip}")));
+    }
+    let mut snapshot = BTreeMap::new();
+    let mut queue = vec![tip.to_string()];
+    while let Some(cid) = queue.pop() {
+        if snapshot.contains_key(&cid) {
+            continue;
+        }
+        let Some(mut receipt) = lookup(&cid) else {
+            continue;
+        };
+        if let Some(obj) = receipt.as_object_mut() {
+            obj.remove("__tenant_id");
+        }
+        for parent in receipt
+            .get("parents")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let Some(p) = parent.as_str() {
+                queue.push(p.to_string());
+            }
+        }
+        snapshot.insert(cid, receipt);
+    }
+    Ok(snapshot)
+}
+
+pub async fn list_receipts(
+    State(state): State<AppState>,
+    scope: Scope,
+    _client: Option<Extension<ClientInfo>>,
+    axum::extract::Query(query): axum::extract::Query<ReceiptListQuery>,
+) -> impl IntoResponse {
+    if let Some(ref tip) = query.as_of_tip {
+        return match snapshot_at_tip(&state, &scope, tip) {
+            Ok(snapshot) => (
+                StatusCode::OK,
+                Json(json!({"as_of_tip": tip, "receipts": snapshot})),
+            )
+                .into_response(),
+            Err(e) => e.into_response(),
+        };
+    }
+    // Index-backed listing: one line per receipt, memory stays flat no
+    // matter how long the chain grows. Entries carry (t, decision,
+    // pipeline, ts, parent); full envelopes live at /v1/receipt/:cid.
+    let lines = ubl_ledger::tenant_index_lines(&scope.tenant).await;
+    if !lines.is_empty() {
+        let mut listing = serde_json::Map::new();
+        for line in lines {
+            if let Ok(mut entry) = serde_json::from_str::<Value>(&line) {
+                if !index_entry_matches(&entry, &query) {
+                    continue;
+                }
+                let Some(cid) = entry.get("cid").and_then(|c| c.as_str()).map(str::to_string)
+                else {
+                    continue;
+                };
+                if let Some(obj) = entry.as_object_mut() {
+                    obj.remove("cid");
+                }
+                // Re-executions may re-index a CID; the latest entry wins
+                listing.insert(cid, entry);
+            }
+        }
+        return (StatusCode::OK, Json(Value::Object(listing))).into_response();
+    }
+
+    // Legacy fallback: stores predating the index list from memory
+    let prefix = scope.key_prefix(); // "app:tenant"
+    let store = state.receipt_chain.read().unwrap();
+    let filtered: serde_json::Map<String, Value> = store
+        .iter()
+        .filter(|(k, v)| {
+            // Match scoped keys "app:tenant:cid"
+            if k.starts_with(&prefix) {
+                return true;
+            }
+            // Legacy fallback: match __tenant_id
+            v.get("__tenant_id")
+                .and_then(|t| t.as_str())
+                .map(|t| t == scope.tenant)
+                .unwrap_or(scope.tenant == "default")
+        })
+        .map(|(k, v)| {
+            // Strip scope prefix from key for clean output
+            let clean_key = k.strip_prefix(&format!("{prefix}:")).unwrap_or(k).to_string();
+            let mut clean = v.clone();
+            if let Some(obj) = clean.as_object_mut() {
+                obj.remove("__tenant_id");
+            }
+            (clean_key, clean)
+        })
+        .collect();
+    (StatusCode::OK, Json(json!(filtered))).into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportReceiptReq {
+    /// Foreign receipt envelope (inline body required).
+    pub receipt: Value,
+    /// Issuer DID document carrying the verification method for proof.kid.
+    /// Optional when the issuer is a registered client — the document is
+    /// then synthesized from the key discovery registry.
+    pub did_document: Option<Value>,
+}
+
+/// Import a receipt minted by a foreign gate: verify its signature against
+/// the issuer's DID document, check the canonical body_cid, store it
+/// flagged as external, and link it into the local chain via a signed
+/// attestation receipt.
+pub async fn import_receipt(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<ImportReceiptReq>,
+) -> Result<axum::response::Response, AppError> {
+    let receipt = req.receipt;
+    let did_document = match req.did_document {
+        Some(doc) => doc,
+        None => {
+            let kid = receipt
+                .pointer("/proof/kid")
+                .and_then(|k| k.as_str())
+                .ok_or_else(|| AppError::bad_request("receipt has no proof.kid"))?;
+            let did = kid.split('#').next().unwrap_or(kid);
+            let client = state.token_store.find_by_did(did).ok_or_else(|| {
+                AppError::bad_request(
+                    "no did_document given and the issuer is not a registered client",
+                )
+            })?;
+            client_did_document(&client, kid).ok_or_else(|| {
+                AppError::unprocessable("registered client has no public key")
+            })?
+        }
+    };
+    // Canonical body + proof checks are shared with federation prev_tips
+    let issuer = crate::federation::verify_foreign_receipt(&receipt, &did_document)?;
+    let body_cid = receipt
+        .get("body_cid")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| AppError::bad_request("receipt has no body_cid"))?
+        .to_string();
+
+    // Stored flagged as external, so audits can tell provenance apart
+    let mut stored = receipt.clone();
+    if let Some(obj) = stored.as_object_mut() {
+        obj.insert("external".into(), json!(true));
+        obj.insert("issuer".into(), json!(issuer));
+    }
+
+    // link into the local chain via a signed attestation
+    let keys = state.keyring_store.resolve_for_scope(&scope);
+    let attestation_body = json!({
+        "type": "ubl/attestation",
+        "action": "import",
+        "external_cid": body_cid,
+        "issuer": issuer,
+        "imported_at": chrono::Utc::now().to_rfc3339(),
+    });
+    let attestation = ubl_runtime::build_receipt(
+        "ubl/attestation",
+        vec![body_cid.clone()],
+        attestation_body,
+        &keys.active,
+        &keys.active_kid,
+    )
+    .map_err(|e| AppError::internal(format!("attestation receipt: {e}")))?;
+    let att_val = serde_json::to_value(&attestation)
+        .map_err(|e| AppError::internal(format!("attestation encode: {e}")))?;
+
+    let entries = vec![
+        (body_cid.clone(), stored),
+        (attestation.body_cid.clone(), att_val),
+    ];
+    index_receipts(&scope.tenant, None, None, &entries).await;
+    {
+        let mut store = state.receipt_chain.write().unwrap();
+        for (cid, val) in entries {
+            store.insert(scope.scoped_cid(&cid), val.clone());
+            store.insert(cid, val);
+        }
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "imported": true,
+            "cid": body_cid,
+            "issuer": issuer,
+            "attestation": attestation,
+        })),
+    )
+        .into_response())
+}
+
+pub async fn audit_report(
+    State(state): State<AppState>,
+    scope: Scope,
+    _client: Option<Extension<ClientInfo>>,
+    axum::extract::Query(query): axum::extract::Query<ReceiptListQuery>,
+) -> impl IntoResponse {
+    // Tabular exports stream straight off the receipt index — no
+    // in-memory report, however long the chain
+    let format = query.format.clone();
+    match format.as_deref() {
+        None => {}
+        Some(fmt @ ("csv" | "ndjson")) => {
+            if query.as_of_tip.is_some() {
+                return AppError::bad_request(
+                    "as_of_tip snapshots are JSON-only; drop format= to use them",
+                )
+                .into_response();
+            }
+            return stream_audit_export(&scope, query, fmt == "csv").await;
+        }
+        Some(other) => {
+            return AppError::bad_request(format!(
+                "unknown format '{other}'; omit it or use 'csv' or 'ndjson'"
+            ))
+            .into_response()
+        }
+    }
+    // Snapshot variant: report over the chain as it stood at a past tip
+    if let Some(ref tip) = query.as_of_tip {
+        return match snapshot_at_tip(&state, &scope, tip) {
+            Ok(mut chain) => {
+                for receipt in chain.values_mut() {
+                    rehydrate_body(&scope.tenant, receipt).await;
+                }
+                let report = crate::audit::generate_report(&chain);
+                (
+                    StatusCode::OK,
+                    Json(json!({"as_of_tip": tip, "report": report})),
+                )
+                    .into_response()
+            }
+            Err(e) => e.into_response(),
+        };
+    }
+    let prefix = scope.key_prefix();
+    let mut chain: BTreeMap<String, Value> = {
+        let store = state.receipt_chain.read().unwrap();
+        store
+            .iter()
+            .filter(|(k, v)| {
+                if k.starts_with(&prefix) {
+                    return true;
+                }
+                // Legacy fallback
+                v.get("__tenant_id")
+                    .and_then(|t| t.as_str())
+                    .map(|t| t == scope.tenant)
+                    .unwrap_or(scope.tenant == "default")
+            })
+            .map(|(k, v)| {
+                let clean_key = k.strip_prefix(&format!("{prefix}:")).unwrap_or(k).to_string();
+                let mut clean = v.clone();
+                if let Some(obj) = clean.as_object_mut() {
+                    obj.remove("__tenant_id");
+                }
+                (clean_key, clean)
+            })
+            .collect()
+    };
+    // Detached bodies must be rehydrated before integrity checks
+    for receipt in chain.values_mut() {
+        rehydrate_body(&scope.tenant, receipt).await;
+    }
+    let report = crate::audit::generate_report(&chain);
+    (StatusCode::OK, Json(json!(report))).into_response()
+}
+
+/// Stream the tenant's receipt index as CSV or NDJSON, one row per index
+/// line: entries are read, filtered and rendered lazily so the export
+/// never materializes the chain. Unparseable lines are skipped, matching
+/// the listing endpoint.
+async fn stream_audit_export(
+    scope: &Scope,
+    query: ReceiptListQuery,
+    csv: bool,
+) -> axum::response::Response {
+    use futures_util::StreamExt;
+    use tokio::io::AsyncBufReadExt;
+
+    let columns = match crate::audit::parse_columns(query.columns.as_deref()) {
+        Ok(c) => c,
+        Err(e) => return AppError::bad_request(e).into_response(),
+    };
+    let content_type = if csv {
+        "text/csv; charset=utf-8"
+    } else {
+        "application/x-ndjson"
+    };
+    let header = if csv {
+        format!("{}\n", columns.join(","))
+    } else {
+        String::new()
+    };
+    let head = futures_util::stream::iter(
+        (!header.is_empty()).then(|| Ok(axum::body::Bytes::from(header))),
+    );
+
+    let Some(file) = ubl_ledger::tenant_index_open(&scope.tenant).await else {
+        // No index yet: an empty export, not an error
+        return (
+            StatusCode::OK,
+            [(axum::http::header::CONTENT_TYPE, content_type)],
+            axum::body::Body::from_stream(head),
+        )
+            .into_response();
+    };
+
+    let lines = tokio::io::BufReader::new(file).lines();
+    let rows = futures_util::stream::unfold(
+        (lines, query, columns),
+        move |(mut lines, query, columns)| async move {
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) => {
+                        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+                            continue;
+                        };
+                        if !index_entry_matches(&entry, &query) {
+                            continue;
+                        }
+                        let row = if csv {
+                            crate::audit::csv_row(&entry, &columns)
+                        } else {
+                            crate::audit::ndjson_row(&entry, &columns)
+                        };
+                        return Some((
+                            Ok::<_, std::io::Error>(axum::body::Bytes::from(row + "\n")),
+                            (lines, query, columns),
+                        ));
+                    }
+                    Ok(None) | Err(_) => return None,
+                }
+            }
+        },
+    );
+
+    (
+        StatusCode::OK,
+        [(axum::http::header::CONTENT_TYPE, content_type)],
+        axum::body::Body::from_stream(head.chain(rows)),
+    )
+        .into_response()
+}
+
+/// Run a fresh integrity pass over the chain window and return it.
+/// The same result feeds the Prometheus gauges at the next scrape.
+pub async fn integrity_report(State(state): State<AppState>) -> impl IntoResponse {
+    let status = crate::integrity::verify_window(&state, crate::integrity::configured_window()).await;
+    *state.integrity.write().unwrap() = status.clone();
+    let code = if status.is_clean() {
+        StatusCode::OK
+    } else {
+        StatusCode::CONFLICT
+    };
+    (code, Json(json!(status)))
+}
+
+pub async fn get_transition(
+    State(state): State<AppState>,
+    Path(cid_raw): Path<String>,
+) -> impl IntoResponse {
+    let cid = normalize_cid_in_path(&cid_raw);
+    let store = state.transition_receipts.read().unwrap();
+    match store.get(&cid) {
+        Some(envelope) => (StatusCode::OK, Json(envelope.clone())).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "transition_not_found",
+                "cid": cid
+            })),
+        )
+            .into_response(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecRequestFull {
+    pub manifest: ubl_runtime::Manifest,
+    pub vars: BTreeMap<String, Value>,
+    pub ghost: Option<bool>,
+    /// Chain onto a tip minted by another gate: `<did:web:...>#<b3:cid>`.
+    /// Omit to chain onto this gate's own last tip.
+    pub prev_tip: Option<String>,
+    /// Link this execution into an open saga (see `POST /v1/saga`).
+    pub saga_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCorsReq {
+    /// Origin to add or remove, e.g. "https://app.acme.com".
+    pub origin: String,
+    /// Allowlist level: "scoped" (default), "app", or "global".
+    pub level: Option<String>,
+    /// App namespace; defaults to the request scope's app.
+    pub app: Option<String>,
+    /// Tenant namespace; defaults to the request scope's tenant.
+    pub tenant: Option<String>,
+}
+
+impl AdminCorsReq {
+    /// Resolve the (app, tenant) pair the level maps onto.
+    fn target(&self, scope: &Scope) -> Result<(Option<String>, Option<String>), String> {
+        let app = self.app.clone().unwrap_or_else(|| scope.app.clone());
+        let tenant = self.tenant.clone().unwrap_or_else(|| scope.tenant.clone());
+        match self.level.as_deref().unwrap_or("scoped") {
+            "scoped" => Ok((Some(app), Some(tenant))),
+            "app" => Ok((Some(app), None)),
+            "global" => Ok((None, None)),
+            other => Err(format!("unknown level '{other}'")),
+        }
+    }
+}
+
+/// List the effective CORS origins for the request scope.
+pub async fn admin_list_cors(State(state): State<AppState>, scope: Scope) -> impl IntoResponse {
+    let cfg = state.cors.load();
+    let scope_key = format!("{}:{}", scope.app, scope.tenant);
+    Json(json!({
+        "global": cfg.global_origins,
+        "app": cfg.app_origins.get(&scope.app).cloned().unwrap_or_default(),
+        "scoped": cfg.scoped_origins.get(&scope_key).cloned().unwrap_or_default(),
+        "effective": cfg.allowed_origins_for(&scope),
+    }))
+}
+
+/// Add an origin to the allowlist at runtime (no restart needed).
+pub async fn admin_add_cors(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<AdminCorsReq>,
+) -> impl IntoResponse {
+    let (app, tenant) = match req.target(&scope) {
+        Ok(t) => t,
+        Err(detail) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({"error": detail}))).into_response()
+        }
+    };
+    let added = state
+        .cors
+        .add_origin(app.as_deref(), tenant.as_deref(), &req.origin);
+    (
+        StatusCode::OK,
+        Json(json!({"origin": req.origin, "added": added})),
+    )
+        .into_response()
+}
+
+/// Remove an origin from the allowlist at runtime.
+pub async fn admin_remove_cors(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<AdminCorsReq>,
+) -> impl IntoResponse {
+    let (app, tenant) = match req.target(&scope) {
+        Ok(t) => t,
+        Err(detail) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({"error": detail}))).into_response()
+        }
+    };
+    let removed = state
+        .cors
+        .remove_origin(app.as_deref(), tenant.as_deref(), &req.origin);
+    if !removed {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "origin_not_found", "origin": req.origin})),
+        )
+            .into_response();
+    }
+    (
+        StatusCode::OK,
+        Json(json!({"origin": req.origin, "removed": true})),
+    )
+        .into_response()
+}
+
+#[derive(Deserialize)]
+pub struct ReplayRequest {
+    /// WF receipt body CID ("b3:…") to replay.
+    pub cid: String,
+}
+
+/// POST /v1/replay — re-run a recorded execution deterministically and
+/// verify that the recomputed receipt CIDs match the stored ones.
+///
+/// The replay uses the manifest + raw inputs persisted at execute time,
+/// runs in an isolated context (ghost, no prev_tip, dev keys — the WF
+/// body_cid is independent of both), and reports match/mismatch.
+pub async fn replay(
+    State(state): State<AppState>,
+    scope: Scope,
+    Json(req): Json<ReplayRequest>,
+) -> impl IntoResponse {
+    // Look up the replay context indexed at execute time (scoped → legacy)
+    let ctx = {
+        let index = state.replay_index.read().unwrap();
+        index
+            .get(&scope.scoped_cid(&req.cid))
+            .or_else(|| index.get(&req.cid))
+            .cloned()
+    };
+    let Some(ctx) = ctx else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "replay_context_not_found", "cid": req.cid})),
+        )
+            .into_response();
+    };
+    let manifest_cid = ctx["manifest_cid"].as_str().unwrap_or("").to_string();
+    let inputs_cid = ctx["inputs_cid"].as_str().unwrap_or("").to_string();
+
+    // Fetch manifest + inputs back from the ledger
+    let (manifest_bytes, inputs_bytes) = tokio::join!(
+        ubl_ledger::tenant_get_body(&scope.tenant, &manifest_cid),
+        ubl_ledger::tenant_get_body(&scope.tenant, &inputs_cid),
+    );
+    let (Some(manifest_bytes), Some(inputs_bytes)) = (manifest_bytes, inputs_bytes) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "replay_artifacts_missing", "cid": req.cid})),
+        )
+            .into_response();
+    };
+    let manifest: ubl_runtime::Manifest = match serde_json::from_slice(&manifest_bytes) {
+        Ok(m) => m,
+        Err(e) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({"error": "replay_failed", "detail": format!("manifest parse: {e}")})),
+            )
+                .into_response()
+        }
+    };
+    let vars: BTreeMap<String, Value> = match serde_json::from_slice(&inputs_bytes) {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({"error": "replay_failed", "detail": format!("inputs parse: {e}")})),
+            )
+                .into_response()
+        }
+    };
+
+    // Isolated re-run: ghost, fresh chain, dev signing keys
+    let cfg = ubl_runtime::ExecuteConfig {
+        version: "0.1.0".into(),
+        codecs: Default::default(),
+    };
+    let keys = ubl_runtime::KeyRing::dev();
+    let opts = ubl_runtime::RunOpts {
+        prev_tip: None,
+        prev_tip_issuer: None,
+        ghost: true,
+        keys: &keys,
+        seen: None,
+        logline: None,
+        redaction: None,
+        clock: &ubl_runtime::SYSTEM_CLOCK,
+        ctx: None,
+    };
+    let run = match ubl_runtime::run_with_receipts(&manifest, &vars, &cfg, &opts) {
+        Ok(r) => r,
+        Err(e) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({"error": "replay_failed", "detail": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    // Compare recomputed CIDs against the stored WF receipt
+    let stored_wf = {
+        let store = state.receipt_chain.read().unwrap();
+        store
+            .get(&scope.scoped_cid(&req.cid))
+            .or_else(|| store.get(&req.cid))
+            .cloned()
+    };
+    let mut expected = json!({"wf_body_cid": req.cid});
+    let mut computed = json!({"wf_body_cid": run.wf.body_cid});
+    let mut diverging: Vec<String> = Vec::new();
+    if run.wf.body_cid != req.cid {
+        diverging.push("wf_body_cid".into());
+    }
+    for key in ["rho_cid", "outputs_cid"] {
+        let stored = stored_wf
+            .as_ref()
+            .and_then(|wf| wf.get("body"))
+            .and_then(|b| b.get(key))
+            .cloned();
+        if let Some(stored) = stored {
+            let recomputed = run.wf.body.get(key).cloned().unwrap_or(Value::Null);
+            if stored != recomputed {
+                diverging.push(key.into());
+            }
+            expected[key] = stored;
+            computed[key] = recomputed;
+        }
+    }
+
+    let verdict = if diverging.is_empty() {
+        "match"
+    } else {
+        "mismatch"
+    };
+    (
+        StatusCode::OK,
+        Json(json!({
+            "verdict": verdict,
+            "expected": expected,
+            "computed": computed,
+            "diverging": diverging,
+        })),
+    )
+        .into_response()
+}
+
+/// Turn a runtime replay error into a 409 that points at the receipt the
+/// original run produced, so clients can fetch the prior result instead of
+/// treating the conflict as opaque.
+fn replay_conflict(
+    state: &AppState,
+    e: &ubl_runtime::error::RuntimeError,
+    detail: String,
+) -> AppError {
+    let err = AppError::conflict(detail);
+    let ubl_runtime::error::RuntimeError::Replay { pipeline, inputs_cid } = e else {
+        return err;
+    };
+    let key = format!("{pipeline}:{inputs_cid}");
+    match state.seen_tips.read().unwrap().get(&key) {
+        Some(tip) => err.with_existing(json!({
+            "tip_cid": tip,
+            "wf_cid": tip,
+            "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), tip),
+        })),
+        None => err,
+    }
+}
+
+// ── Interop test vectors ────────────────────────────────────────────
+
+/// GET /v1/vectors — versioned interop vectors, generated on demand from
+/// the exact canonicalization, CID, JWS and receipt code paths the gate
+/// mints with. A third-party verifier (JS, Python, ...) that reproduces
+/// every expected value here is byte-compatible with this gate; the
+/// fixed seed exists only so signatures are reproducible — it signs
+/// nothing outside this endpoint.
+pub async fn get_vectors() -> impl IntoResponse {
+    let seed = [7u8; 32];
+    let key = ed25519_dalek::SigningKey::from_bytes(&seed);
+    let kid = "did:ubl:vectors#k1";
+
+    // Canonicalization: the shapes implementations get wrong — key
+    // ordering, null dropping, NFC strings, nesting. Floats are a
+    // validation error under nrf1/v1 and have no vector.
+    let canon_inputs = [
+        json!({"b": 1, "a": [true, null, "x"]}),
+        json!({"drop": null, "keep": 0}),
+        json!({"unicode": "cafe\u{301}"}),
+        json!({"nested": {"z": -42, "a": [1, [2], {}]}}),
+    ];
+    let canonicalization: Vec<Value> = canon_inputs
+        .iter()
+        .map(|input| {
+            let bytes = ubl_runtime::canon::canonical_bytes(input).unwrap_or_default();
+            json!({
+                "input": input,
+                "canonical": String::from_utf8_lossy(&bytes),
+                "cid": ubl_runtime::cid::cid_b3(&bytes),
+            })
+        })
+        .collect();
+
+    // CID: BLAKE3 over raw bytes, "b3:" + lowercase hex
+    let cid: Vec<Value> = [&b""[..], b"hello", &[0xde, 0xad, 0xbe, 0xef]]
+        .iter()
+        .map(|bytes| {
+            json!({
+                "bytes_hex": hex::encode(bytes),
+                "cid": ubl_runtime::cid::cid_b3(bytes),
+            })
+        })
+        .collect();
+
+    // JWS detached (RFC 7797 b64=false): signing input is
+    // ASCII(protected) || '.' || payload bytes
+    let jws_payload = ubl_runtime::canon::canonical_bytes(&json!({"msg": "interop", "n": 1}))
+        .unwrap_or_default();
+    let jws = ubl_runtime::jws::sign_detached(&jws_payload, &key, kid);
+    let jws_vector = json!({
+        "seed_hex": hex::encode(seed),
+        "public_key_hex": hex::encode(key.verifying_key().to_bytes()),
+        "kid": kid,
+        "payload": String::from_utf8_lossy(&jws_payload),
+        "protected": jws.protected,
+        "signature": jws.signature,
+    });
+
+    // Receipt: body_cid = CID of the canonical body bytes, proof = JWS
+    // detached over those same bytes. Ed25519 is deterministic, so the
+    // whole envelope is a stable vector.
+    let body = json!({
+        "type": "ubl/attestation",
+        "action": "certify",
+        "cid": ubl_runtime::cid::cid_b3(b"interop"),
+        "bytes_len": 7,
+        "tenant": "vectors",
+        "certified_at": 0,
+    });
+    let receipt = ubl_runtime::build_receipt(
+        "ubl/attestation",
+        vec![],
+        body,
+        &key,
+        kid,
+    )
+    .ok();
+
+    Json(json!({
+        "version": "1",
+        "canon_profile": ubl_runtime::canon::PROFILE_NRF1_V1,
+        "canonicalization": canonicalization,
+        "cid": cid,
+        "jws": jws_vector,
+        "receipt": receipt,
+    }))
+}
+
+// ── Stats aggregates ────────────────────────────────────────────────
+
+/// How long a computed `/v1/stats/*` aggregate is served before the
+/// receipt index is rescanned. Dashboards poll; 30 s of staleness is
+/// the bargain for not rescanning per request.
+const STATS_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+fn stats_cache_get(state: &AppState, key: &str) -> Option<Value> {
+    let cache = state.stats_cache.read().unwrap();
+    let (at, v) = cache.get(key)?;
+    (at.elapsed() < STATS_TTL).then(|| v.clone())
+}
+
+fn stats_cache_put(state: &AppState, key: String, v: Value) {
+    state
+        .stats_cache
+        .write()
+        .unwrap()
+        .insert(key, (std::time::Instant::now(), v));
+}
+
+/// The tenant's parsed receipt-index entries — the raw material every
+/// aggregate is computed from.
+async fn stats_index_entries(tenant: &str) -> Vec<Value> {
+    ubl_ledger::tenant_index_lines(tenant)
+        .await
+        .iter()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect()
+}
+
+/// Receipts per hour for the dashboard's activity chart.
+pub async fn stats_receipts(State(state): State<AppState>, scope: Scope) -> impl IntoResponse {
+    let key = format!("receipts:{}", scope.tenant);
+    if let Some(v) = stats_cache_get(&state, &key) {
+        return Json(v);
+    }
+    let entries = stats_index_entries(&scope.tenant).await;
+    let mut per_hour: BTreeMap<String, u64> = BTreeMap::new();
+    for e in &entries {
+        // RFC 3339 timestamps bucket by their "YYYY-MM-DDTHH" prefix
+        if let Some(ts) = e.get("ts").and_then(|t| t.as_str()) {
+            if ts.len() >= 13 {
+                *per_hour.entry(format!("{}:00:00Z", &ts[..13])).or_default() += 1;
+            }
+        }
+    }
+    let v = json!({
+        "total": entries.len(),
+        "per_hour": per_hour
+            .into_iter()
+            .map(|(hour, count)| json!({"hour": hour, "count": count}))
+            .collect::<Vec<_>>(),
+    });
+    stats_cache_put(&state, key, v.clone());
+    Json(v)
+}
+
+/// Decisions per pipeline (WF receipts only — they carry a decision).
+pub async fn stats_pipelines(State(state): State<AppState>, scope: Scope) -> impl IntoResponse {
+    let key = format!("pipelines:{}", scope.tenant);
+    if let Some(v) = stats_cache_get(&state, &key) {
+        return Json(v);
+    }
+    let entries = stats_index_entries(&scope.tenant).await;
+    let mut per_pipeline: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+    for e in &entries {
+        let Some(decision) = e.get("decision").and_then(|d| d.as_str()) else {
+            continue;
+        };
+        let pipeline = e
+            .get("pipeline")
+            .and_then(|p| p.as_str())
+            .unwrap_or("(none)");
+        *per_pipeline
+            .entry(pipeline.to_string())
+            .or_default()
+            .entry(decision.to_string())
+            .or_default() += 1;
+    }
+    let v = json!({
+        "pipelines": per_pipeline
+            .into_iter()
+            .map(|(pipeline, decisions)| {
+                let total: u64 = decisions.values().sum();
+                json!({"pipeline": pipeline, "decisions": decisions, "total": total})
+            })
+            .collect::<Vec<_>>(),
+    });
+    stats_cache_put(&state, key, v.clone());
+    Json(v)
+}
+
+/// Top deny rules, most-hit first (capped at 20).
+pub async fn stats_denies(State(state): State<AppState>, scope: Scope) -> impl IntoResponse {
+    let key = format!("denies:{}", scope.tenant);
+    if let Some(v) = stats_cache_get(&state, &key) {
+        return Json(v);
+    }
+    let entries = stats_index_entries(&scope.tenant).await;
+    let mut rules: BTreeMap<String, u64> = BTreeMap::new();
+    let mut total = 0u64;
+    for e in &entries {
+        if e.get("decision").and_then(|d| d.as_str()) != Some("DENY") {
+            continue;
+        }
+        total += 1;
+        let rule = e
+            .get("rule")
+            .and_then(|r| r.as_str())
+            // Entries indexed before rules were recorded
+            .unwrap_or("(unspecified)");
+        *rules.entry(rule.to_string()).or_default() += 1;
+    }
+    let mut top: Vec<(String, u64)> = rules.into_iter().collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    top.truncate(20);
+    let v = json!({
+        "total_denies": total,
+        "top_rules": top
+            .into_iter()
+            .map(|(rule, count)| json!({"rule": rule, "count": count}))
+            .collect::<Vec<_>>(),
+    });
+    stats_cache_put(&state, key, v.clone());
+    Json(v)
+}
+
+/// Fuel and storage consumption inside the current quota window,
+/// alongside the limits they count against.
+pub async fn stats_usage(State(state): State<AppState>, scope: Scope) -> impl IntoResponse {
+    let usage = state.quota.usage(&scope.tenant);
+    let policy = state.quota.policy(&scope.tenant);
+    Json(json!({
+        "tenant_id": scope.tenant,
+        "usage": usage,
+        "limits": policy,
+    }))
 }
 
-#[derive(Debug, Serialize)]
-pub struct ExecRbResponse {
-    pub rc_cid: Option<String>,
-    pub steps: u64,
-    pub fuel_used: u64,
-    pub transition_receipt: Option<Value>,
+// ── Sagas ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SagaOpenReq {
+    /// Human-readable label carried in the open receipt body.
+    pub name: Option<String>,
 }
 
-pub async fn execute_rb(
-    State(state): State<AppState>,
-    Json(req): Json<ExecRbRequest>,
-) -> impl IntoResponse {
-    let chip =
-        match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &req.chip_b64) {
-            Ok(b) => b,
-            Err(_) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({"error": "invalid base64 chip"})),
-                )
-                    .into_response()
-            }
-        };
-    let rb_req = ubl_runtime::ExecuteRbReq {
-        chip,
-        inputs: req.inputs,
-        ghost: req.ghost,
-        fuel: req.fuel,
-    };
-    match ubl_runtime::execute_rb(&rb_req) {
-        Ok(res) => {
-            // Store transition receipt for GET /v1/transition/:cid
-            if let Some(ref tr) = res.transition_receipt {
-                if let Some(cid) = tr.get("cid").and_then(|c| c.as_str()) {
-                    let mut store = state.transition_receipts.write().unwrap();
-                    store.insert(cid.to_string(), tr.clone());
-                    // Also index by rho_cid for lookup convenience
-                    if let Some(rho) = tr
-                        .get("body")
-                        .and_then(|b| b.get("rho_cid"))
-                        .and_then(|r| r.as_str())
-                    {
-                        store.insert(rho.to_string(), tr.clone());
-                    }
+/// Merkle root over a saga's step tip CIDs: blake3 leaves over the CID
+/// strings, pairwise blake3(left ‖ right) upward, an odd node promotes
+/// unchanged. Empty sagas root at the hash of no bytes.
+fn saga_steps_root(steps: &[String]) -> String {
+    if steps.is_empty() {
+        return ubl_runtime::cid::cid_b3(&[]);
+    }
+    let mut level: Vec<[u8; 32]> = steps
+        .iter()
+        .map(|s| *blake3::hash(s.as_bytes()).as_bytes())
+        .collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut h = blake3::Hasher::new();
+                    h.update(left);
+                    h.update(right);
+                    *h.finalize().as_bytes()
                 }
-            }
-            let resp = ExecRbResponse {
-                rc_cid: res.rc_cid,
-                steps: res.steps,
-                fuel_used: res.fuel_used,
-                transition_receipt: res.transition_receipt,
-            };
-            (StatusCode::OK, Json(resp)).into_response()
-        }
-        Err(e) => (
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(json!({
-                "error": "execute_rb_failed",
-                "detail": e.to_string()
-            })),
+                [odd] => *odd,
+                _ => unreachable!("chunks(2) yields 1 or 2 nodes"),
+            })
+            .collect();
+    }
+    format!("b3:{}", hex::encode(level[0]))
+}
+
+/// Mint a signed `ubl/saga` receipt and register it in the chain under
+/// its body_cid (scoped + unscoped), same as attestations: parented to
+/// the current tip without advancing it.
+async fn mint_saga_receipt(
+    state: &AppState,
+    scope: &Scope,
+    parents: Vec<String>,
+    body: Value,
+) -> Result<ubl_runtime::Receipt, AppError> {
+    let keys = state.keyring_store.resolve_for_scope(scope);
+    let receipt =
+        ubl_runtime::build_receipt("ubl/saga", parents, body, &keys.active, &keys.active_kid)
+            .map_err(|e| AppError::internal(format!("saga receipt: {e}")))?;
+    if let Ok(val) = serde_json::to_value(&receipt) {
+        index_receipts(
+            &scope.tenant,
+            None,
+            None,
+            &[(receipt.body_cid.clone(), val.clone())],
         )
-            .into_response(),
+        .await;
+        let mut store = state.receipt_chain.write().unwrap();
+        store.insert(scope.scoped_cid(&receipt.body_cid), val.clone());
+        store.insert(receipt.body_cid.clone(), val);
     }
+    Ok(receipt)
 }
 
-pub async fn list_receipts(
+/// POST /v1/saga — open a saga: a signed receipt grouping the executions
+/// that will reference its id via `saga_id` on `/v1/execute`. The open
+/// receipt hangs off the current tip but does not advance it.
+pub async fn open_saga(
     State(state): State<AppState>,
     scope: Scope,
-    _client: Option<Extension<ClientInfo>>,
+    Json(req): Json<SagaOpenReq>,
 ) -> impl IntoResponse {
-    let prefix = scope.key_prefix(); // "app:tenant"
-    let store = state.receipt_chain.read().unwrap();
-    let filtered: serde_json::Map<String, Value> = store
-        .iter()
-        .filter(|(k, v)| {
-            // Match scoped keys "app:tenant:cid"
-            if k.starts_with(&prefix) {
-                return true;
-            }
-            // Legacy fallback: match __tenant_id
-            v.get("__tenant_id")
-                .and_then(|t| t.as_str())
-                .map(|t| t == scope.tenant)
-                .unwrap_or(scope.tenant == "default")
-        })
-        .map(|(k, v)| {
-            // Strip scope prefix from key for clean output
-            let clean_key = k.strip_prefix(&format!("{prefix}:")).unwrap_or(k).to_string();
-            let mut clean = v.clone();
-            if let Some(obj) = clean.as_object_mut() {
-                obj.remove("__tenant_id");
-            }
-            (clean_key, clean)
-        })
-        .collect();
-    (StatusCode::OK, Json(json!(filtered)))
+    let opened_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let parents: Vec<String> = state.last_tip.read().unwrap().clone().into_iter().collect();
+    let mut body = json!({
+        "type": "ubl/saga",
+        "phase": "open",
+        "tenant": scope.tenant,
+        "opened_at": opened_at,
+    });
+    if let Some(name) = req.name {
+        body["name"] = json!(name);
+    }
+    let receipt = match mint_saga_receipt(&state, &scope, parents, body).await {
+        Ok(r) => r,
+        Err(e) => return e.into_response(),
+    };
+    state.sagas.write().unwrap().insert(
+        receipt.body_cid.clone(),
+        crate::SagaEntry {
+            open_cid: receipt.body_cid.clone(),
+            tenant: scope.tenant.clone(),
+            steps: Vec::new(),
+            closed_cid: None,
+        },
+    );
+    (
+        StatusCode::OK,
+        Json(json!({
+            "saga_id": receipt.body_cid,
+            "receipt": receipt,
+            "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), receipt.body_cid),
+        })),
+    )
+        .into_response()
 }
 
-pub async fn audit_report(
+/// GET /v1/saga/:id — the saga as a unit: its open receipt, linked step
+/// tip CIDs in execution order, and the close receipt once closed.
+pub async fn get_saga(
     State(state): State<AppState>,
     scope: Scope,
-    _client: Option<Extension<ClientInfo>>,
+    Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let prefix = scope.key_prefix();
-    let store = state.receipt_chain.read().unwrap();
-    let chain: BTreeMap<String, Value> = store
-        .iter()
-        .filter(|(k, v)| {
-            if k.starts_with(&prefix) {
-                return true;
-            }
-            // Legacy fallback
-            v.get("__tenant_id")
-                .and_then(|t| t.as_str())
-                .map(|t| t == scope.tenant)
-                .unwrap_or(scope.tenant == "default")
-        })
-        .map(|(k, v)| {
-            let clean_key = k.strip_prefix(&format!("{prefix}:")).unwrap_or(k).to_string();
-            let mut clean = v.clone();
-            if let Some(obj) = clean.as_object_mut() {
-                obj.remove("__tenant_id");
-            }
-            (clean_key, clean)
-        })
-        .collect();
-    let report = crate::audit::generate_report(&chain);
-    (StatusCode::OK, Json(json!(report)))
+    let id = normalize_cid_in_path(&id);
+    let entry = {
+        let sagas = state.sagas.read().unwrap();
+        sagas.get(&id).cloned()
+    };
+    let Some(entry) = entry.filter(|e| e.tenant == scope.tenant) else {
+        return AppError::not_found("saga").into_response();
+    };
+    let fetch = |cid: &str| {
+        let store = state.receipt_chain.read().unwrap();
+        store
+            .get(&scope.scoped_cid(cid))
+            .or_else(|| store.get(cid))
+            .cloned()
+    };
+    let mut resp = json!({
+        "saga_id": entry.open_cid,
+        "open": fetch(&entry.open_cid),
+        "steps": entry.steps,
+        "closed": entry.closed_cid.is_some(),
+    });
+    if let Some(ref close_cid) = entry.closed_cid {
+        resp["close_cid"] = json!(close_cid);
+        resp["close"] = fetch(close_cid).unwrap_or(Value::Null);
+    }
+    (StatusCode::OK, Json(resp)).into_response()
 }
 
-pub async fn get_transition(
+/// POST /v1/saga/:id/close — close a saga with a signed receipt that
+/// summarizes its steps under a Merkle root, parented to the open
+/// receipt. A second close is a 409; new steps are refused once closed.
+pub async fn close_saga(
     State(state): State<AppState>,
-    Path(cid_raw): Path<String>,
+    scope: Scope,
+    Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let cid = normalize_cid_in_path(&cid_raw);
-    let store = state.transition_receipts.read().unwrap();
-    match store.get(&cid) {
-        Some(envelope) => (StatusCode::OK, Json(envelope.clone())).into_response(),
-        None => (
-            StatusCode::NOT_FOUND,
+    let id = normalize_cid_in_path(&id);
+    let entry = {
+        let sagas = state.sagas.read().unwrap();
+        sagas.get(&id).cloned()
+    };
+    let Some(entry) = entry.filter(|e| e.tenant == scope.tenant) else {
+        return AppError::not_found("saga").into_response();
+    };
+    if let Some(closed_cid) = entry.closed_cid {
+        return (
+            StatusCode::CONFLICT,
             Json(json!({
-                "error": "transition_not_found",
-                "cid": cid
+                "error": "saga_closed",
+                "detail": format!("saga '{id}' is already closed"),
+                "close_cid": closed_cid,
             })),
         )
-            .into_response(),
+            .into_response();
     }
+    let closed_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let steps_root = saga_steps_root(&entry.steps);
+    let body = json!({
+        "type": "ubl/saga",
+        "phase": "close",
+        "saga_cid": entry.open_cid,
+        "tenant": scope.tenant,
+        "steps": entry.steps,
+        "steps_root": steps_root,
+        "closed_at": closed_at,
+    });
+    let receipt =
+        match mint_saga_receipt(&state, &scope, vec![entry.open_cid.clone()], body).await {
+            Ok(r) => r,
+            Err(e) => return e.into_response(),
+        };
+    if let Some(live) = state.sagas.write().unwrap().get_mut(&id) {
+        live.closed_cid = Some(receipt.body_cid.clone());
+    }
+    (
+        StatusCode::OK,
+        Json(json!({
+            "saga_id": entry.open_cid,
+            "steps": entry.steps,
+            "steps_root": steps_root,
+            "receipt": receipt,
+            "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), receipt.body_cid),
+        })),
+    )
+        .into_response()
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ExecRequestFull {
-    pub manifest: ubl_runtime::Manifest,
-    pub vars: BTreeMap<String, Value>,
-    pub ghost: Option<bool>,
+#[derive(Deserialize, Default)]
+pub struct ExecQuery {
+    /// Response shape: unset for the native response, "tdln" for the
+    /// legacy `ubl_tdln_svc` contract (see [`crate::tdln`]).
+    pub format: Option<String>,
+    /// Two-phase flow: "prepare" dry-runs and parks the request under a
+    /// single-use token redeemed via `POST /v1/execute/commit`.
+    pub phase: Option<String>,
+    /// Comma-separated response sections beyond the minimal envelope:
+    /// "receipts", "trace", "artifacts". The default response carries
+    /// only the decision, tip CID and receipt URL.
+    pub include: Option<String>,
+}
+
+/// Parsed `?include=` sections for the execute response.
+#[derive(Default, Clone, Copy)]
+struct ExecInclude {
+    receipts: bool,
+    trace: bool,
+    artifacts: bool,
+}
+
+impl ExecInclude {
+    fn parse(spec: &str) -> Result<Self, String> {
+        let mut inc = Self::default();
+        for part in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match part {
+                "receipts" => inc.receipts = true,
+                "trace" => inc.trace = true,
+                "artifacts" => inc.artifacts = true,
+                other => {
+                    return Err(format!(
+                        "unknown include '{other}'; known sections: receipts, trace, artifacts"
+                    ))
+                }
+            }
+        }
+        Ok(inc)
+    }
 }
 
 pub async fn execute_runtime(
     State(state): State<AppState>,
     scope: Scope,
     client: Option<Extension<ClientInfo>>,
+    uri: axum::extract::OriginalUri,
+    axum::extract::Query(query): axum::extract::Query<ExecQuery>,
+    headers: axum::http::HeaderMap,
     Json(req): Json<ExecRequestFull>,
 ) -> impl IntoResponse {
+    let started = std::time::Instant::now();
+    // Legacy full response shape (receipts always inline) for callers
+    // that haven't migrated to `?include=`; the CORS layer already
+    // allows the header.
+    let compat = headers.contains_key("x-ubl-compat");
+    let include = match query.include.as_deref() {
+        None => ExecInclude::default(),
+        Some(spec) => match ExecInclude::parse(spec) {
+            Ok(i) => i,
+            Err(msg) => return AppError::bad_request(msg).into_response(),
+        },
+    };
+    let tdln = match query.format.as_deref() {
+        None => false,
+        Some(crate::tdln::FORMAT) => true,
+        Some(other) => {
+            return AppError::bad_request(format!(
+                "unknown format '{other}'; omit it or use 'tdln'"
+            ))
+            .into_response()
+        }
+    };
+    let prepare = match query.phase.as_deref() {
+        None => false,
+        Some("prepare") => {
+            if tdln {
+                return AppError::bad_request(
+                    "phase=prepare cannot be combined with format=tdln",
+                )
+                .into_response();
+            }
+            true
+        }
+        Some(other) => {
+            return AppError::bad_request(format!(
+                "unknown phase '{other}'; omit it or use 'prepare'"
+            ))
+            .into_response()
+        }
+    };
     let cfg = ubl_runtime::ExecuteConfig {
         version: "0.1.0".into(),
+        codecs: Default::default(),
     };
 
+    // Per-tenant signing: resolve the keyring by scope (scoped → app → global)
+    let keys = state.keyring_store.resolve_for_scope(&scope);
+
     // Kid-scope check: if client has allowed_kids, verify active signing kid
     if let Some(Extension(ref ci)) = client {
-        let active_kid = &state.keys.active_kid;
+        let active_kid = &keys.active_kid;
         if !ci.kid_allowed(active_kid) {
             return (StatusCode::FORBIDDEN, Json(json!({
                 "error": "kid_scope_denied",
@@ -445,39 +3962,308 @@ pub async fn execute_runtime(
         }
     }
 
-    // Read prev_tip and seen_cids for chaining + idempotency
-    let prev_tip = state.last_tip.read().unwrap().clone();
+    // Budget gate: executions and stored bytes are hard limits
+    if let Err(denial) = state.quota.check(
+        &scope.tenant,
+        &[
+            crate::quota::Resource::Executions,
+            crate::quota::Resource::StoredBytes,
+        ],
+    ) {
+        return deny_quota(&state, &scope, &keys, &req.manifest.pipeline, denial).await;
+    }
+
+    // Deterministic replay cache (opt-in per tenant): the same canonical
+    // (manifest_cid, inputs_cid) pair re-serves its prior WF receipt —
+    // flagged as replayed — instead of recomputing and forking the chain.
+    // Ghost, prepare and tdln runs always execute, as do saga steps —
+    // a saga records each execution, so nothing may be served from cache.
+    if !prepare && !tdln && !req.ghost.unwrap_or(false) && req.saga_id.is_none()
+        && state
+            .replay_cache_tenants
+            .read()
+            .unwrap()
+            .contains(&scope.tenant)
+    {
+        let manifest_val = serde_json::to_value(&req.manifest).unwrap_or(Value::Null);
+        let manifest_cid = ubl_runtime::cid::cid_b3(
+            &ubl_runtime::canon::canonical_bytes(&manifest_val).unwrap_or_default(),
+        );
+        let inputs_cid =
+            ubl_runtime::cid::cid_b3(&serde_json::to_vec(&req.vars).unwrap_or_default());
+        let key = scope.scoped_cid(&format!("{manifest_cid}:{inputs_cid}"));
+        let hit = state.replay_cache.read().unwrap().get(&key).cloned();
+        if let Some(wf_cid) = hit {
+            let wf = {
+                let store = state.receipt_chain.read().unwrap();
+                store
+                    .get(&scope.scoped_cid(&wf_cid))
+                    .or_else(|| store.get(&wf_cid))
+                    .cloned()
+            };
+            // A cache entry without its receipt (restart, eviction) falls
+            // through to a normal execution
+            if let Some(wf) = wf {
+                metrics::counter!(
+                    "ubl_replay_cache_hits_total",
+                    "tenant" => scope.tenant.clone(),
+                )
+                .increment(1);
+                let decision = wf
+                    .get("body")
+                    .and_then(|b| b.get("decision"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                let mut resp = json!({
+                    "cid": wf_cid,
+                    "tip_cid": wf_cid,
+                    "decision": decision,
+                    "ghost": false,
+                    "replayed": true,
+                    "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), wf_cid),
+                });
+                if compat || include.receipts {
+                    resp["receipts"] = json!({ "wf": wf });
+                }
+                return (StatusCode::OK, Json(resp)).into_response();
+            }
+        }
+    }
+
+    // Saga linking: the saga must exist, belong to this tenant, and still
+    // be open — checked before running so a bad reference costs nothing.
+    if let Some(ref saga_id) = req.saga_id {
+        let sagas = state.sagas.read().unwrap();
+        match sagas.get(saga_id) {
+            None => return AppError::not_found("saga").into_response(),
+            Some(entry) if entry.tenant != scope.tenant => {
+                return AppError::not_found("saga").into_response()
+            }
+            Some(entry) if entry.closed_cid.is_some() => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "saga_closed",
+                        "detail": format!("saga '{saga_id}' is already closed"),
+                    })),
+                )
+                    .into_response()
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Read prev_tip and seen_cids for chaining + idempotency.
+    // A caller-supplied prev_tip must be a did:web-qualified foreign tip:
+    // we fetch and verify the foreign receipt before chaining onto it, and
+    // record its issuer so the WA body carries the cross-gate linkage.
+    let mut foreign_issuer: Option<String> = None;
+    let prev_tip = match req.prev_tip.as_deref() {
+        // A bare b3 CID chains onto a receipt this gate already holds —
+        // it must exist in the local registry, or the chain is malformed
+        Some(spec) if spec.starts_with("b3:") => {
+            let known = {
+                let store = state.receipt_chain.read().unwrap();
+                store.contains_key(&scope.scoped_cid(spec)) || store.contains_key(spec)
+            };
+            if !known {
+                return AppError::unprocessable(format!(
+                    "prev_tip '{spec}' not found in the local registry; \
+                     pass '<did:web:...>#<b3:cid>' for an external tip"
+                ))
+                .into_response();
+            }
+            Some(spec.to_string())
+        }
+        Some(spec) => {
+            let tip = match crate::federation::parse_foreign_tip(spec) {
+                Some(t) => t,
+                None => {
+                    return AppError::unprocessable(
+                        "prev_tip must be a local 'b3:…' CID or '<did:web:...>#<b3:cid>'; \
+                         omit it to chain from the current tip",
+                    )
+                    .into_response()
+                }
+            };
+            match crate::federation::resolve_and_verify(&tip).await {
+                Ok(verified) => {
+                    foreign_issuer = Some(verified.issuer);
+                    Some(verified.cid)
+                }
+                Err(e) => return e.into_response(),
+            }
+        }
+        None => state.last_tip.read().unwrap().clone(),
+    };
     let seen_snapshot = state.seen_cids.read().unwrap().clone();
     let ghost = req.ghost.unwrap_or(false);
 
+    // Execution context for `ctx.*` policy conditions — who is calling and
+    // through which route. Policy-only: it never reaches receipt bodies
+    // unless the manifest declares a `__ctx` input.
+    let exec_ctx = json!({
+        "tenant": scope.tenant,
+        "app": scope.app,
+        "client_id": client
+            .as_ref()
+            .map(|Extension(ci)| ci.client_id.as_str())
+            .unwrap_or("anonymous"),
+        "path": uri.0.path(),
+    });
+
     let opts = ubl_runtime::RunOpts {
         prev_tip: prev_tip.as_deref(),
+        prev_tip_issuer: foreign_issuer.as_deref(),
         ghost,
-        keys: &state.keys,
+        keys: &keys,
         seen: Some(&seen_snapshot),
         logline: None,
+        redaction: None,
+        clock: &ubl_runtime::SYSTEM_CLOCK,
+        ctx: Some(&exec_ctx),
     };
 
+    // Prepare phase: dry-run as ghost (nothing stored, tip and
+    // idempotency untouched) and park the request for commit. The
+    // runtime is deterministic, so the committed body CIDs match the
+    // preview unless another execution moves the tip first.
+    if prepare {
+        let ghost_opts = ubl_runtime::RunOpts { ghost: true, ..opts };
+        return match ubl_runtime::run_with_receipts(&req.manifest, &req.vars, &cfg, &ghost_opts) {
+            Ok(run) => {
+                let token = state.prepare_store.insert(crate::prepare::PrepareEntry {
+                    req: serde_json::to_value(&req).unwrap_or(Value::Null),
+                    tenant: scope.tenant.clone(),
+                    created: std::time::Instant::now(),
+                });
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "status": "prepared",
+                        "prepare_token": token,
+                        "expires_in_secs": crate::prepare::ttl_secs(),
+                        "preview": {
+                            "tip_cid": run.tip_cid,
+                            "decision": run.wf.body.get("decision").cloned().unwrap_or(Value::Null),
+                            "receipts": {
+                                "wa": run.wa,
+                                "transition": run.transition,
+                                "policy": run.policy,
+                                "wf": run.wf,
+                            },
+                        },
+                    })),
+                )
+                    .into_response()
+            }
+            Err(e) => {
+                let detail = e.to_string();
+                if e.code() == ubl_runtime::error::ErrorCode::Replay {
+                    replay_conflict(&state, &e, detail).into_response()
+                } else {
+                    AppError::unprocessable(detail).into_response()
+                }
+            }
+        };
+    }
+
     match ubl_runtime::run_with_receipts(&req.manifest, &req.vars, &cfg, &opts) {
         Ok(run) => {
+            let persist_started = std::time::Instant::now();
             // Store receipts + update seen_cids + update last_tip (unless ghost)
             if !run.ghost {
+                let mut entries = vec![
+                    (run.wa.body_cid.clone(), serde_json::to_value(&run.wa).unwrap()),
+                    (run.wf.body_cid.clone(), serde_json::to_value(&run.wf).unwrap()),
+                ];
+                if let Some(ref tr) = run.transition {
+                    entries.push((tr.body_cid.clone(), serde_json::to_value(tr).unwrap()));
+                }
+                if let Some(ref pol) = run.policy {
+                    entries.push((pol.body_cid.clone(), serde_json::to_value(pol).unwrap()));
+                }
+                // Everything this execution writes to the ledger — index
+                // lines, detached bodies, replay context — commits as one
+                // atomic batch, so a crash can never leave half a run
+                let mut batch = ubl_ledger::WriteBatch::new();
+                let latency_ms = started.elapsed().as_millis() as u64;
+                for (cid, val) in &entries {
+                    // Index before detaching, while decision/pipeline are inline
+                    crate::receipt_log::log_commit(&scope.tenant, cid, val, Some(latency_ms));
+                    let entry = index_entry(Some(&req.manifest.pipeline), cid, val);
+                    batch.index_line(&scope.tenant, entry.to_string().into_bytes());
+                }
+                // Oversized bodies go to the ledger; the registry keeps a
+                // reference once the batch lands
+                let mut detached = Vec::new();
+                for (i, (cid, val)) in entries.iter().enumerate() {
+                    if let Some(len) = stage_detached_body(
+                        &scope.tenant,
+                        cid,
+                        val,
+                        state.detach_body_bytes,
+                        &mut batch,
+                    ) {
+                        detached.push((i, len));
+                    }
+                }
+                // Replay context (manifest + raw inputs), indexed by WF
+                // body_cid for POST /v1/replay
+                let manifest_val = serde_json::to_value(&req.manifest).unwrap_or(Value::Null);
+                let manifest_bytes =
+                    ubl_runtime::canon::canonical_bytes(&manifest_val).unwrap_or_default();
+                let manifest_cid = ubl_runtime::cid::cid_b3(&manifest_bytes);
+                let vars_bytes = serde_json::to_vec(&req.vars).unwrap_or_default();
+                let inputs_cid = ubl_runtime::cid::cid_b3(&vars_bytes);
+                batch.put_body(&scope.tenant, &manifest_cid, manifest_bytes);
+                batch.put_body(&scope.tenant, &inputs_cid, vars_bytes);
+
+                // Commit point: on failure (ledger outage) bodies stay
+                // inline and the run is still served from the registry
+                if batch.commit().await.is_ok() {
+                    for &(i, len) in &detached {
+                        apply_body_ref(&mut entries[i].1, len);
+                    }
+                    let ctx = json!({"manifest_cid": manifest_cid, "inputs_cid": inputs_cid});
+                    let mut index = state.replay_index.write().unwrap();
+                    index.insert(scope.scoped_cid(&run.wf.body_cid), ctx.clone());
+                    index.insert(run.wf.body_cid.clone(), ctx);
+                    drop(index);
+                    // Inverse entry for the opt-in replay cache, so the
+                    // next identical pair is served without re-running
+                    if state
+                        .replay_cache_tenants
+                        .read()
+                        .unwrap()
+                        .contains(&scope.tenant)
+                    {
+                        state.replay_cache.write().unwrap().insert(
+                            scope.scoped_cid(&format!("{manifest_cid}:{inputs_cid}")),
+                            run.wf.body_cid.clone(),
+                        );
+                    }
+                }
+                // Charge the run against the tenant's budget: one
+                // execution plus the bytes its receipts occupy
+                let stored_bytes: u64 = entries
+                    .iter()
+                    .map(|(_, v)| serde_json::to_vec(v).map(|b| b.len() as u64).unwrap_or(0))
+                    .sum();
+                state.quota.charge(&scope.tenant, 1, 0, stored_bytes);
                 let mut store = state.receipt_chain.write().unwrap();
                 // Store with scoped keys: "app:tenant:cid"
-                for (cid, val) in [
-                    (&run.wa.body_cid, serde_json::to_value(&run.wa).unwrap()),
-                    (&run.wf.body_cid, serde_json::to_value(&run.wf).unwrap()),
-                ] {
-                    let scoped_key = scope.scoped_cid(cid);
-                    store.insert(scoped_key, val.clone());
+                for (cid, val) in entries {
+                    store.insert(scope.scoped_cid(&cid), val.clone());
                     // Also store unscoped for legacy compat
-                    store.insert(cid.clone(), val);
+                    store.insert(cid, val);
                 }
-                if let Some(ref tr) = run.transition {
-                    let v = serde_json::to_value(tr).unwrap();
-                    let scoped_key = scope.scoped_cid(&tr.body_cid);
-                    store.insert(scoped_key, v.clone());
-                    store.insert(tr.body_cid.clone(), v);
+                drop(store);
+                // Link the committed step into its saga, in execution order
+                if let Some(ref saga_id) = req.saga_id {
+                    if let Some(entry) = state.sagas.write().unwrap().get_mut(saga_id) {
+                        entry.steps.push(run.tip_cid.clone());
+                    }
                 }
             }
 
@@ -497,15 +4283,73 @@ pub async fn execute_runtime(
                     .and_then(|v| v.as_str())
                     .unwrap_or("");
                 let key = format!("{pipeline}:{inputs_cid}");
+                state
+                    .seen_tips
+                    .write()
+                    .unwrap()
+                    .insert(key.clone(), run.tip_cid.clone());
                 let mut seen = state.seen_cids.write().unwrap();
                 seen.insert(key);
             }
 
             // Update tip
             *state.last_tip.write().unwrap() = Some(run.tip_cid.clone());
+            let persist_micros = persist_started.elapsed().as_micros() as u64;
 
             // Get artifacts from the WF body (already computed inside run_with_receipts)
             let decision = run.wf.body.get("decision").cloned().unwrap_or(json!(null));
+
+            // Decision counters + policy-eval histogram: these are what
+            // operators alert on for DENY spikes or a runaway rule
+            {
+                let pipeline = req.manifest.pipeline.clone();
+                let decision_label = decision.as_str().unwrap_or("?").to_string();
+                let decided_by = run
+                    .policy
+                    .as_ref()
+                    .and_then(|p| p.body.get("decided_by"))
+                    .and_then(|d| d.as_str())
+                    .unwrap_or("default")
+                    .to_string();
+                metrics::counter!(
+                    "ubl_policy_decisions_total",
+                    "pipeline" => pipeline.clone(),
+                    "decision" => decision_label,
+                    "decided_by" => decided_by,
+                    "tenant" => scope.tenant.clone(),
+                )
+                .increment(1);
+                if let Some(micros) = run
+                    .policy
+                    .as_ref()
+                    .and_then(|p| p.observability.as_ref())
+                    .and_then(|o| o.get("eval_micros"))
+                    .and_then(|m| m.as_u64())
+                {
+                    metrics::histogram!("ubl_policy_eval_seconds", "pipeline" => pipeline)
+                        .record(micros as f64 / 1_000_000.0);
+                }
+            }
+
+            // Phase breakdown: where the execute pipeline spends its time.
+            // The aggregate request_duration histogram hides a slow
+            // canonicalization or signing regression; these don't.
+            for (phase, micros) in [
+                ("bind", run.timings.bind_micros),
+                ("mappings", run.timings.mappings_micros),
+                ("policy", run.timings.policy_micros),
+                ("canon", run.timings.canon_micros),
+                ("sign", run.timings.sign_micros),
+                ("persist", persist_micros),
+            ] {
+                metrics::histogram!(
+                    "ubl_execute_phase_seconds",
+                    "phase" => phase,
+                    "route" => "/v1/execute",
+                    "tenant" => scope.tenant.clone(),
+                )
+                .record(micros as f64 / 1_000_000.0);
+            }
             let dimension_stack = run
                 .wf
                 .body
@@ -513,36 +4357,175 @@ pub async fn execute_runtime(
                 .cloned()
                 .unwrap_or(json!([]));
 
-            let resp = json!({
-                "cid": run.tip_cid,
-                "tip_cid": run.tip_cid,
-                "decision": decision,
-                "dimension_stack": dimension_stack,
-                "ghost": run.ghost,
-                "receipts": {
-                    "wa": run.wa,
-                    "transition": run.transition,
-                    "wf": run.wf,
-                },
-                "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), run.tip_cid),
-            });
+            let resp = if compat {
+                json!({
+                    "cid": run.tip_cid,
+                    "tip_cid": run.tip_cid,
+                    "decision": decision,
+                    "dimension_stack": dimension_stack,
+                    "ghost": run.ghost,
+                    "receipts": {
+                        "wa": &run.wa,
+                        "transition": &run.transition,
+                        "policy": &run.policy,
+                        "wf": &run.wf,
+                    },
+                    "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), run.tip_cid),
+                })
+            } else {
+                let mut resp = json!({
+                    "cid": run.tip_cid,
+                    "tip_cid": run.tip_cid,
+                    "decision": decision,
+                    "ghost": run.ghost,
+                    "url": format!("{}/v1/receipt/{}", BASE_URL.as_str(), run.tip_cid),
+                });
+                if include.receipts {
+                    resp["receipts"] = json!({
+                        "wa": &run.wa,
+                        "transition": &run.transition,
+                        "policy": &run.policy,
+                        "wf": &run.wf,
+                    });
+                }
+                if include.trace {
+                    resp["trace"] = json!({
+                        "policy_trace": run
+                            .policy
+                            .as_ref()
+                            .and_then(|p| p.body.get("policy_trace"))
+                            .cloned()
+                            .unwrap_or(json!([])),
+                        "phase_micros": {
+                            "bind": run.timings.bind_micros,
+                            "mappings": run.timings.mappings_micros,
+                            "policy": run.timings.policy_micros,
+                            "canon": run.timings.canon_micros,
+                            "sign": run.timings.sign_micros,
+                            "persist": persist_micros,
+                        },
+                    });
+                }
+                if include.artifacts {
+                    resp["artifacts"] = json!({
+                        "dimension_stack": dimension_stack,
+                        "rho_cid": run.wf.body.get("rho_cid").cloned().unwrap_or(Value::Null),
+                        "outputs_cid": run.wf.body.get("outputs_cid").cloned().unwrap_or(Value::Null),
+                    });
+                }
+                resp
+            };
+            if tdln {
+                return crate::tdln::execute_ok(
+                    &run.tip_cid,
+                    &decision,
+                    run.ghost,
+                    &serde_json::to_value(&run.wf).unwrap_or(Value::Null),
+                );
+            }
             (StatusCode::OK, Json(resp)).into_response()
         }
         Err(e) => {
             let detail = e.to_string();
-            let status = if detail.contains("duplicate request") {
-                StatusCode::CONFLICT
+            let code = e.code();
+            // Even infrastructure failures leave an auditable trace: sign a
+            // DENY WF receipt referencing the request and store it
+            let inputs_raw_cid =
+                ubl_runtime::cid::cid_b3(&serde_json::to_vec(&req.vars).unwrap_or_default());
+            let deny_body = json!({
+                "type": "ubl/wf",
+                "rho_cid": null,
+                "outputs_cid": null,
+                "decision": "DENY",
+                "reason": detail,
+                "reason_code": serde_json::to_value(&code).unwrap_or(Value::Null),
+                "pipeline": req.manifest.pipeline,
+                "inputs_raw_cid": inputs_raw_cid,
+                "dimension_stack": [],
+            });
+            let deny_receipt =
+                ubl_runtime::build_receipt("ubl/wf", vec![], deny_body, &keys.active, &keys.active_kid)
+                    .ok()
+                    .and_then(|rc| serde_json::to_value(&rc).ok());
+            if let Some(ref rc) = deny_receipt {
+                if let Some(cid) = rc.get("body_cid").and_then(|c| c.as_str()) {
+                    index_receipts(
+                        &scope.tenant,
+                        Some(&req.manifest.pipeline),
+                        Some(started.elapsed().as_millis() as u64),
+                        &[(cid.to_string(), rc.clone())],
+                    )
+                    .await;
+                    let mut store = state.receipt_chain.write().unwrap();
+                    store.insert(scope.scoped_cid(cid), rc.clone());
+                    store.insert(cid.to_string(), rc.clone());
+                }
+            }
+            let replay = code == ubl_runtime::error::ErrorCode::Replay;
+            if tdln {
+                let deny_cid = deny_receipt
+                    .as_ref()
+                    .and_then(|rc| rc.get("body_cid"))
+                    .and_then(|c| c.as_str());
+                return crate::tdln::execute_err(&detail, replay, deny_cid);
+            }
+            let err = if replay {
+                replay_conflict(&state, &e, detail)
             } else {
-                StatusCode::UNPROCESSABLE_ENTITY
+                AppError::unprocessable(detail)
             };
-            (
-                status,
-                Json(json!({
-                    "error": "execute_failed",
-                    "detail": detail
-                })),
-            )
-                .into_response()
+            match deny_receipt {
+                Some(rc) => err.with_deny_receipt(rc).into_response(),
+                None => err.into_response(),
+            }
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct CommitReq {
+    /// Token returned by `POST /v1/execute?phase=prepare`.
+    pub prepare_token: String,
+}
+
+/// Commit a prepared execution: redeem the single-use token and run the
+/// parked request for real, signing and chaining its receipts atomically.
+/// Expired or foreign-tenant tokens 404 — unredeemable either way.
+pub async fn execute_commit(
+    State(state): State<AppState>,
+    scope: Scope,
+    client: Option<Extension<ClientInfo>>,
+    uri: axum::extract::OriginalUri,
+    axum::extract::Query(query): axum::extract::Query<ExecQuery>,
+    headers: axum::http::HeaderMap,
+    Json(body): Json<CommitReq>,
+) -> impl IntoResponse {
+    let Some(entry) = state.prepare_store.take(&body.prepare_token) else {
+        return AppError::not_found("prepare token").into_response();
+    };
+    if entry.tenant != scope.tenant {
+        return AppError::not_found("prepare token").into_response();
+    }
+    let req: ExecRequestFull = match serde_json::from_value(entry.req) {
+        Ok(r) => r,
+        Err(e) => return AppError::internal(format!("prepared request decode: {e}")).into_response(),
+    };
+    // Forward `?include=` and the compat header so commit responses take
+    // the same shape the caller negotiated at prepare time. `format` and
+    // `phase` stay unset: the parked request already passed that gate.
+    let query = ExecQuery {
+        include: query.include,
+        ..ExecQuery::default()
+    };
+    execute_runtime(
+        State(state),
+        scope,
+        client,
+        uri,
+        axum::extract::Query(query),
+        headers,
+        Json(req),
+    )
+    .await
+    .into_response()
+}
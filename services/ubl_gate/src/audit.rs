@@ -54,6 +54,67 @@ pub struct IntegrityFailure {
     pub computed_body_cid: String,
 }
 
+/// Columns available to tabular audit exports — the fields every receipt
+/// index entry carries.
+pub const EXPORT_COLUMNS: &[&str] = &["cid", "t", "decision", "pipeline", "ts", "parent"];
+
+/// Parse a `columns=` selection into an ordered column list, defaulting to
+/// every export column. Unknown or empty selections come back as an error
+/// message ready for a 400.
+pub fn parse_columns(spec: Option<&str>) -> Result<Vec<String>, String> {
+    let Some(spec) = spec else {
+        return Ok(EXPORT_COLUMNS.iter().map(|c| c.to_string()).collect());
+    };
+    let mut columns = Vec::new();
+    for name in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if !EXPORT_COLUMNS.contains(&name) {
+            return Err(format!(
+                "unknown column '{name}'; available: {}",
+                EXPORT_COLUMNS.join(", ")
+            ));
+        }
+        columns.push(name.to_string());
+    }
+    if columns.is_empty() {
+        return Err("empty column selection".into());
+    }
+    Ok(columns)
+}
+
+/// Render one index entry as a CSV record over the selected columns
+/// (no trailing newline). RFC 4180: fields containing a comma, quote or
+/// newline are quoted, with embedded quotes doubled.
+pub fn csv_row(entry: &Value, columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|c| csv_field(entry.get(c).unwrap_or(&Value::Null)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn csv_field(v: &Value) -> String {
+    let s = match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s
+    }
+}
+
+/// Render one index entry as an NDJSON record restricted to the selected
+/// columns (no trailing newline).
+pub fn ndjson_row(entry: &Value, columns: &[String]) -> String {
+    let mut obj = serde_json::Map::new();
+    for c in columns {
+        obj.insert(c.clone(), entry.get(c).cloned().unwrap_or(Value::Null));
+    }
+    Value::Object(obj).to_string()
+}
+
 /// Generate an audit report from the receipt chain.
 pub fn generate_report(chain: &BTreeMap<String, Value>) -> AuditReport {
     let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
@@ -280,6 +341,43 @@ mod tests {
         assert!(json.contains("integrity"));
     }
 
+    #[test]
+    fn column_selection_defaults_and_rejects_unknowns() {
+        assert_eq!(parse_columns(None).unwrap(), EXPORT_COLUMNS);
+        assert_eq!(
+            parse_columns(Some("cid, decision")).unwrap(),
+            vec!["cid", "decision"]
+        );
+        assert!(parse_columns(Some("cid,nope")).unwrap_err().contains("nope"));
+        assert!(parse_columns(Some(" , ")).is_err());
+    }
+
+    #[test]
+    fn csv_rows_escape_per_rfc4180() {
+        let entry = json!({
+            "cid": "b3:abc",
+            "pipeline": "with,comma",
+            "decision": "say \"no\"",
+            "ts": null,
+        });
+        let cols: Vec<String> = ["cid", "pipeline", "decision", "ts"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            csv_row(&entry, &cols),
+            "b3:abc,\"with,comma\",\"say \"\"no\"\"\","
+        );
+    }
+
+    #[test]
+    fn ndjson_rows_carry_only_selected_columns() {
+        let entry = json!({"cid": "b3:abc", "t": "ubl/wf", "decision": "ALLOW"});
+        let cols: Vec<String> = ["cid", "decision"].iter().map(|c| c.to_string()).collect();
+        let row: Value = serde_json::from_str(&ndjson_row(&entry, &cols)).unwrap();
+        assert_eq!(row, json!({"cid": "b3:abc", "decision": "ALLOW"}));
+    }
+
     #[test]
     fn empty_chain_report() {
         let chain = BTreeMap::new();
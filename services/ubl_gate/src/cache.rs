@@ -0,0 +1,176 @@
+//! In-process response cache for immutable GETs.
+//!
+//! Content under `/cid/:cid` and `/v1/receipt/:cid` never changes for a
+//! given CID, so hot responses can be served straight from memory with
+//! `ETag` = CID and `Cache-Control: immutable`. The cache is bounded by
+//! total payload bytes; eviction is deterministic LRU (monotonic touch
+//! counter, same scheme as the idempotency store). Redaction is the one
+//! mutation in the system — redact handlers must call `invalidate_cid`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Entries larger than this never enter the cache: one giant blob would
+/// otherwise evict every hot receipt.
+pub const MAX_ENTRY_BYTES: usize = 1 << 20; // 1 MiB
+
+struct Entry {
+    content_type: String,
+    body: Arc<Vec<u8>>,
+    last_touch: u64,
+}
+
+struct Inner {
+    entries: HashMap<String, Entry>,
+    bytes: usize,
+    max_bytes: usize,
+    touch_ctr: u64,
+}
+
+impl Inner {
+    fn next_touch(&mut self) -> u64 {
+        let n = self.touch_ctr;
+        self.touch_ctr += 1;
+        n
+    }
+
+    fn evict_until_fits(&mut self, incoming: usize) {
+        while self.bytes + incoming > self.max_bytes {
+            let Some(victim) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_touch)
+                .map(|(k, _)| k.clone())
+            else {
+                return;
+            };
+            if let Some(e) = self.entries.remove(&victim) {
+                self.bytes -= e.body.len();
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ResponseCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl ResponseCache {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                entries: HashMap::new(),
+                bytes: 0,
+                max_bytes,
+                touch_ctr: 0,
+            })),
+        }
+    }
+
+    /// Budget from `RESPONSE_CACHE_MAX_BYTES` (default 16 MiB, 0 disables).
+    pub fn from_env() -> Self {
+        let max_bytes: usize = std::env::var("RESPONSE_CACHE_MAX_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(16 << 20);
+        Self::new(max_bytes)
+    }
+
+    /// Look up a cached response body. Hits refresh LRU position.
+    pub fn get(&self, key: &str) -> Option<(String, Arc<Vec<u8>>)> {
+        let mut inner = self.inner.lock().unwrap();
+        let touch = inner.next_touch();
+        match inner.entries.get_mut(key) {
+            Some(e) => {
+                e.last_touch = touch;
+                metrics::counter!("ubl_response_cache_hits_total").increment(1);
+                Some((e.content_type.clone(), Arc::clone(&e.body)))
+            }
+            None => {
+                metrics::counter!("ubl_response_cache_misses_total").increment(1);
+                None
+            }
+        }
+    }
+
+    /// Insert a response body. Oversized bodies are silently skipped.
+    pub fn put(&self, key: &str, content_type: &str, body: Arc<Vec<u8>>) {
+        let len = body.len();
+        let mut inner = self.inner.lock().unwrap();
+        if len > MAX_ENTRY_BYTES || len > inner.max_bytes || inner.entries.contains_key(key) {
+            return;
+        }
+        inner.evict_until_fits(len);
+        let touch = inner.next_touch();
+        inner.entries.insert(
+            key.to_string(),
+            Entry {
+                content_type: content_type.to_string(),
+                body,
+                last_touch: touch,
+            },
+        );
+        inner.bytes += len;
+        metrics::gauge!("ubl_response_cache_bytes").set(inner.bytes as f64);
+    }
+
+    /// Drop every representation of a CID (all tenants/formats). Called on
+    /// redaction, the only way stored content disappears.
+    pub fn invalidate_cid(&self, cid: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries.retain(|k, _| !k.contains(cid));
+        inner.bytes = inner.entries.values().map(|e| e.body.len()).sum();
+        metrics::gauge!("ubl_response_cache_bytes").set(inner.bytes as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(s: &str) -> Arc<Vec<u8>> {
+        Arc::new(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn hit_returns_stored_body_and_type() {
+        let cache = ResponseCache::new(1024);
+        cache.put("cid:t:b3:aa:nrf", "application/x-nrf", body("blob"));
+        let (ct, b) = cache.get("cid:t:b3:aa:nrf").unwrap();
+        assert_eq!(ct, "application/x-nrf");
+        assert_eq!(&*b, b"blob");
+        assert!(cache.get("cid:t:b3:bb:nrf").is_none());
+    }
+
+    #[test]
+    fn byte_budget_evicts_least_recently_used() {
+        let cache = ResponseCache::new(10);
+        cache.put("k1", "t", body("aaaa")); // 4 bytes
+        cache.put("k2", "t", body("bbbb")); // 4 bytes
+        cache.get("k1"); // k2 becomes LRU
+        cache.put("k3", "t", body("cccc")); // needs room → evicts k2
+        assert!(cache.get("k1").is_some());
+        assert!(cache.get("k2").is_none());
+        assert!(cache.get("k3").is_some());
+    }
+
+    #[test]
+    fn oversized_bodies_are_skipped() {
+        let cache = ResponseCache::new(4);
+        cache.put("big", "t", body("too large"));
+        assert!(cache.get("big").is_none());
+    }
+
+    #[test]
+    fn invalidate_cid_drops_all_representations() {
+        let cache = ResponseCache::new(1024);
+        cache.put("cid:t1:b3:aa:nrf", "t", body("x"));
+        cache.put("cid:t1:b3:aa:json", "t", body("y"));
+        cache.put("receipt:a:t1:b3:bb", "t", body("z"));
+        cache.invalidate_cid("b3:aa");
+        assert!(cache.get("cid:t1:b3:aa:nrf").is_none());
+        assert!(cache.get("cid:t1:b3:aa:json").is_none());
+        assert!(cache.get("receipt:a:t1:b3:bb").is_some());
+    }
+}
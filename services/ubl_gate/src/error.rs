@@ -19,6 +19,14 @@ pub struct ApiErrorBody {
     pub request_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_after_secs: Option<u64>,
+    /// Signed DENY WF receipt for execution-path failures: even when the
+    /// gate cannot complete a run, the denial leaves an auditable trace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deny_receipt: Option<serde_json::Value>,
+    /// Pointer to the receipt a conflicting request already produced
+    /// (replay 409s): {"tip_cid", "wf_cid", "url"}.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub existing: Option<serde_json::Value>,
 }
 
 #[derive(Debug)]
@@ -29,6 +37,11 @@ pub struct AppError {
     pub retry_after_secs: Option<u64>,
     /// Extra headers to include (e.g. Allow, Retry-After).
     pub extra_headers: Vec<(String, String)>,
+    /// Optional signed DENY WF receipt attached to the error body.
+    /// Boxed: JSON payloads would otherwise dominate the error's size.
+    pub deny_receipt: Option<Box<serde_json::Value>>,
+    /// Optional pointer to the already-existing receipt (conflicts).
+    pub existing: Option<Box<serde_json::Value>>,
 }
 
 impl AppError {
@@ -39,6 +52,8 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -49,6 +64,8 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -59,6 +76,8 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -69,6 +88,8 @@ impl AppError {
             message: format!("{resource} not found"),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -79,6 +100,8 @@ impl AppError {
             message: "method not allowed".into(),
             retry_after_secs: None,
             extra_headers: vec![("allow".into(), allowed.into())],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -89,6 +112,8 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -99,6 +124,20 @@ impl AppError {
             message: "content-type must be application/json".into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
+        }
+    }
+
+    pub fn payload_too_large(limit_bytes: usize) -> Self {
+        Self {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            code: "payload_too_large",
+            message: format!("request body exceeds the {limit_bytes}-byte limit for this route"),
+            retry_after_secs: None,
+            extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -109,6 +148,20 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: Some(retry_after),
             extra_headers: vec![("retry-after".into(), retry_after.to_string())],
+            deny_receipt: None,
+            existing: None,
+        }
+    }
+
+    pub fn quota_exceeded(msg: impl Into<String>, retry_after: u64) -> Self {
+        Self {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            code: "quota_exceeded",
+            message: msg.into(),
+            retry_after_secs: Some(retry_after),
+            extra_headers: vec![("retry-after".into(), retry_after.to_string())],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -119,6 +172,8 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
 
@@ -129,8 +184,22 @@ impl AppError {
             message: msg.into(),
             retry_after_secs: None,
             extra_headers: vec![],
+            deny_receipt: None,
+            existing: None,
         }
     }
+
+    /// Attach a signed DENY WF receipt to the error body.
+    pub fn with_deny_receipt(mut self, receipt: serde_json::Value) -> Self {
+        self.deny_receipt = Some(Box::new(receipt));
+        self
+    }
+
+    /// Point a conflict at the receipt the original request produced.
+    pub fn with_existing(mut self, pointer: serde_json::Value) -> Self {
+        self.existing = Some(Box::new(pointer));
+        self
+    }
 }
 
 impl IntoResponse for AppError {
@@ -140,6 +209,8 @@ impl IntoResponse for AppError {
             message: self.message,
             request_id: None, // TODO: extract from x-request-id extension
             retry_after_secs: self.retry_after_secs,
+            deny_receipt: self.deny_receipt.map(|b| *b),
+            existing: self.existing.map(|b| *b),
         };
         let mut resp = (self.status, Json(body)).into_response();
         resp.headers_mut().insert(
@@ -177,6 +248,8 @@ mod tests {
             message: "receipt not found".into(),
             request_id: None,
             retry_after_secs: None,
+            deny_receipt: None,
+            existing: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert_eq!(json["code"], "not_found");
@@ -192,6 +265,8 @@ mod tests {
             message: "too many requests".into(),
             request_id: None,
             retry_after_secs: Some(5),
+            deny_receipt: None,
+            existing: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert_eq!(json["retry_after_secs"], 5);
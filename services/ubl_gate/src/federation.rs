@@ -0,0 +1,211 @@
+//! Cross-gate federation: resolve and verify receipts minted by other gates.
+//!
+//! A foreign tip is written as `<did:web:...>#<b3:cid>` — the DID names the
+//! gate that minted the receipt, the fragment is its WF body_cid. Before a
+//! local execute chains onto one, we fetch the foreign gate's DID document
+//! and the receipt envelope, recompute the canonical body CID, and check
+//! the detached proof against the verification method matching `proof.kid`.
+//! The same envelope checks back `/v1/receipts/import`.
+
+use crate::error::AppError;
+use serde_json::Value;
+
+/// A prev_tip that lives on another gate: issuer DID plus receipt CID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignTip {
+    pub did: String,
+    pub cid: String,
+}
+
+/// Parse `<did:web:...>#<b3:cid>`. Returns None for local tips or
+/// malformed specs — callers decide whether that is an error.
+pub fn parse_foreign_tip(spec: &str) -> Option<ForeignTip> {
+    let (did, cid) = spec.split_once('#')?;
+    if !did.starts_with("did:web:") || !cid.starts_with("b3:") {
+        return None;
+    }
+    Some(ForeignTip {
+        did: did.to_string(),
+        cid: cid.to_string(),
+    })
+}
+
+/// Base URL for a did:web identifier. Ports are percent-encoded in the
+/// method-specific id (`%3A`); further `:` segments become path segments.
+/// Loopback hosts resolve over http so local federations work in dev.
+pub fn did_web_base_url(did: &str) -> Option<String> {
+    let rest = did.strip_prefix("did:web:")?;
+    let mut segments = rest.split(':');
+    let host = segments.next()?.replace("%3A", ":").replace("%3a", ":");
+    if host.is_empty() {
+        return None;
+    }
+    let scheme = if host.starts_with("localhost") || host.starts_with("127.0.0.1") {
+        "http"
+    } else {
+        "https"
+    };
+    let mut url = format!("{scheme}://{host}");
+    for seg in segments {
+        url.push('/');
+        url.push_str(seg);
+    }
+    Some(url)
+}
+
+/// Verify a foreign receipt envelope against an issuer DID document:
+/// the inline body must canonicalize back to its claimed body_cid and the
+/// detached proof must verify under the verification method whose id
+/// matches `proof.kid`. Returns the issuer DID on success.
+pub fn verify_foreign_receipt(receipt: &Value, did_document: &Value) -> Result<String, AppError> {
+    let body = receipt
+        .get("body")
+        .cloned()
+        .ok_or_else(|| AppError::bad_request("receipt has no inline body"))?;
+    let body_cid = receipt
+        .get("body_cid")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| AppError::bad_request("receipt has no body_cid"))?;
+    let jws: ubl_runtime::jws::JwsDetached = receipt
+        .get("proof")
+        .cloned()
+        .and_then(|p| serde_json::from_value(p).ok())
+        .ok_or_else(|| AppError::bad_request("receipt has no detached proof"))?;
+
+    // Chaining onto a foreign tip makes it part of our segment, so its
+    // canon profile must match the one this gate mints with
+    let canon = receipt
+        .get("canon")
+        .and_then(|c| c.as_str())
+        .unwrap_or(ubl_runtime::canon::PROFILE_NRF1_V1);
+    if canon != ubl_runtime::canon::PROFILE_NRF1_V1 {
+        return Err(AppError::unprocessable(format!(
+            "cannot mix canon profiles in one chain segment: tip uses '{canon}', local segment uses '{}'",
+            ubl_runtime::canon::PROFILE_NRF1_V1
+        )));
+    }
+
+    // (1) the body must canonicalize back to its claimed CID
+    let body_bytes = ubl_runtime::canon::canonical_bytes_for(canon, &body)
+        .map_err(|e| AppError::unprocessable(format!("body not canonicalizable: {e}")))?;
+    let computed = ubl_runtime::cid::cid_b3(&body_bytes);
+    if computed != body_cid {
+        return Err(AppError::unprocessable(format!(
+            "body_cid mismatch: computed {computed}"
+        )));
+    }
+
+    // (2) the proof must verify against the issuer's DID document
+    let issuer = did_document
+        .get("id")
+        .and_then(|i| i.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let method = did_document
+        .get("verificationMethod")
+        .and_then(|m| m.as_array())
+        .and_then(|ms| {
+            ms.iter()
+                .find(|m| m.get("id").and_then(|i| i.as_str()) == Some(jws.kid.as_str()))
+        })
+        .ok_or_else(|| {
+            AppError::unprocessable(format!("no verification method for kid {}", jws.kid))
+        })?;
+    let pubkey = method
+        .get("publicKeyMultibase")
+        .and_then(|k| k.as_str())
+        .and_then(ubl_did::decode_public_key_multibase)
+        .ok_or_else(|| AppError::unprocessable("no decodable publicKeyMultibase"))?;
+    let vk = ed25519_dalek::VerifyingKey::from_bytes(&pubkey)
+        .map_err(|_| AppError::unprocessable("invalid Ed25519 public key"))?;
+    if !ubl_runtime::jws::verify_detached(&jws, &body_bytes, &vk) {
+        return Err(AppError::unprocessable("signature verification failed"));
+    }
+    Ok(issuer)
+}
+
+/// A foreign tip that passed fetch + verification.
+#[derive(Debug, Clone)]
+pub struct VerifiedTip {
+    pub cid: String,
+    pub issuer: String,
+}
+
+/// Fetch the foreign gate's DID document and receipt, then verify the
+/// envelope. Any fetch or verification failure maps to 422 — the caller
+/// asked to chain onto evidence we cannot confirm.
+pub async fn resolve_and_verify(tip: &ForeignTip) -> Result<VerifiedTip, AppError> {
+    let base = did_web_base_url(&tip.did)
+        .ok_or_else(|| AppError::unprocessable(format!("unresolvable gate DID {}", tip.did)))?;
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| AppError::internal(format!("federation client: {e}")))?;
+
+    let did_document: Value = client
+        .get(format!("{base}/.well-known/did.json"))
+        .send()
+        .await
+        .map_err(|e| AppError::unprocessable(format!("foreign DID document fetch: {e}")))?
+        .error_for_status()
+        .map_err(|e| AppError::unprocessable(format!("foreign DID document fetch: {e}")))?
+        .json()
+        .await
+        .map_err(|e| AppError::unprocessable(format!("foreign DID document parse: {e}")))?;
+
+    let receipt: Value = client
+        .get(format!("{base}/v1/receipt/{}", tip.cid))
+        .send()
+        .await
+        .map_err(|e| AppError::unprocessable(format!("foreign receipt fetch: {e}")))?
+        .error_for_status()
+        .map_err(|e| {
+            AppError::unprocessable(format!("foreign receipt {} not served: {e}", tip.cid))
+        })?
+        .json()
+        .await
+        .map_err(|e| AppError::unprocessable(format!("foreign receipt parse: {e}")))?;
+
+    let issuer = verify_foreign_receipt(&receipt, &did_document)?;
+    Ok(VerifiedTip {
+        cid: tip.cid.clone(),
+        issuer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_foreign_tip_splits_did_and_cid() {
+        let tip = parse_foreign_tip("did:web:gate.acme.com#b3:abc123").unwrap();
+        assert_eq!(tip.did, "did:web:gate.acme.com");
+        assert_eq!(tip.cid, "b3:abc123");
+    }
+
+    #[test]
+    fn parse_foreign_tip_rejects_local_and_malformed() {
+        assert!(parse_foreign_tip("b3:abc123").is_none());
+        assert!(parse_foreign_tip("did:key:z6Mk#b3:abc").is_none());
+        assert!(parse_foreign_tip("did:web:gate.acme.com#sha256:abc").is_none());
+        assert!(parse_foreign_tip("did:web:gate.acme.com").is_none());
+    }
+
+    #[test]
+    fn did_web_base_url_decodes_port_and_paths() {
+        assert_eq!(
+            did_web_base_url("did:web:gate.acme.com").as_deref(),
+            Some("https://gate.acme.com")
+        );
+        assert_eq!(
+            did_web_base_url("did:web:127.0.0.1%3A8080").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(
+            did_web_base_url("did:web:gate.acme.com:gates:eu").as_deref(),
+            Some("https://gate.acme.com/gates/eu")
+        );
+        assert!(did_web_base_url("did:key:z6Mk").is_none());
+    }
+}
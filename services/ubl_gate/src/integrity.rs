@@ -0,0 +1,238 @@
+//! Background receipt-chain integrity verifier.
+//!
+//! Re-verifies a sliding window of the chain — walked backwards from the
+//! current tip through parent links — checking body_cid integrity, parent
+//! linkage, and detached signatures. Results are served at `GET /v1/integrity`
+//! and published as gauges at metrics-scrape time, so tampering or silent
+//! corruption surfaces between audits instead of at the next one.
+
+use crate::AppState;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Keep reports readable: everything is counted, but only the first
+/// few failures carry detail.
+const MAX_FAILURE_DETAILS: usize = 20;
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IntegrityStatus {
+    /// RFC 3339 timestamp of the last verification pass.
+    pub last_run: Option<String>,
+    pub runs: u64,
+    /// Configured window size (receipts walked back from the tip).
+    pub window: usize,
+    pub checked: usize,
+    pub body_cid_invalid: usize,
+    pub signature_invalid: usize,
+    /// Signatures by a kid no configured keyring can verify.
+    pub signature_unverifiable: usize,
+    /// Parent CIDs referenced but absent from the registry.
+    pub missing_parents: usize,
+    /// Detached bodies that could not be rehydrated for checking.
+    pub skipped_detached: usize,
+    pub failures: Vec<IntegrityFailure>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IntegrityFailure {
+    pub cid: String,
+    pub kind: String,
+    pub detail: String,
+}
+
+impl IntegrityStatus {
+    pub fn is_clean(&self) -> bool {
+        self.body_cid_invalid == 0 && self.signature_invalid == 0 && self.missing_parents == 0
+    }
+}
+
+/// Verifying keys for every kid the gate knows about (global + scoped).
+fn known_keys(state: &AppState) -> HashMap<String, ed25519_dalek::VerifyingKey> {
+    let mut keys = HashMap::new();
+    keys.insert(
+        state.keys.active_kid.clone(),
+        state.keys.active.verifying_key(),
+    );
+    for (_, ring) in state.keyring_store.list_scoped() {
+        keys.insert(ring.active_kid.clone(), ring.active.verifying_key());
+        if let (Some(next), Some(kid)) = (&ring.next, &ring.next_kid) {
+            keys.insert(kid.clone(), next.verifying_key());
+        }
+    }
+    keys
+}
+
+/// Tenants per CID, recovered from the scoped registry keys
+/// (`app:tenant:b3:…`) — needed to rehydrate detached bodies.
+fn tenants_by_cid(chain: &HashMap<String, Value>) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for key in chain.keys() {
+        if key.starts_with("b3:") {
+            continue;
+        }
+        let mut parts = key.splitn(3, ':');
+        if let (Some(_app), Some(tenant), Some(cid)) = (parts.next(), parts.next(), parts.next()) {
+            out.insert(cid.to_string(), tenant.to_string());
+        }
+    }
+    out
+}
+
+/// Walk `window` receipts back from the tip and verify each one.
+pub async fn verify_window(state: &AppState, window: usize) -> IntegrityStatus {
+    let chain = state.receipt_chain.read().unwrap().clone();
+    let tip = state.last_tip.read().unwrap().clone();
+    let keys = known_keys(state);
+    let tenants = tenants_by_cid(&chain);
+
+    let mut status = IntegrityStatus {
+        window,
+        ..Default::default()
+    };
+    let fail = |status: &mut IntegrityStatus, cid: &str, kind: &str, detail: String| {
+        if status.failures.len() < MAX_FAILURE_DETAILS {
+            status.failures.push(IntegrityFailure {
+                cid: cid.to_string(),
+                kind: kind.into(),
+                detail,
+            });
+        }
+    };
+
+    let mut frontier: VecDeque<String> = tip.into_iter().collect();
+    let mut visited: HashSet<String> = frontier.iter().cloned().collect();
+
+    while let Some(cid) = frontier.pop_front() {
+        if status.checked >= window {
+            break;
+        }
+        let Some(receipt) = chain.get(&cid) else {
+            // Referenced by a child but absent — broken linkage
+            status.missing_parents += 1;
+            fail(&mut status, &cid, "missing_parent", "parent receipt not in registry".into());
+            continue;
+        };
+        status.checked += 1;
+        let mut receipt = receipt.clone();
+
+        // Queue parents before any skips so linkage is walked fully
+        if let Some(parents) = receipt.get("parents").and_then(|p| p.as_array()) {
+            for p in parents.iter().filter_map(|p| p.as_str()) {
+                if visited.insert(p.to_string()) {
+                    frontier.push_back(p.to_string());
+                }
+            }
+        }
+
+        // Detached bodies must be rehydrated before integrity checks
+        let detached = receipt
+            .pointer("/body_ref/detached")
+            .and_then(|d| d.as_bool())
+            .unwrap_or(false);
+        if detached {
+            let tenant = tenants.get(&cid).map(|t| t.as_str()).unwrap_or("default");
+            match ubl_ledger::tenant_get_body(tenant, &cid).await {
+                Some(bytes) => {
+                    if let (Ok(body), Some(obj)) = (
+                        serde_json::from_slice::<Value>(&bytes),
+                        receipt.as_object_mut(),
+                    ) {
+                        obj.insert("body".into(), body);
+                    }
+                }
+                None => {
+                    status.skipped_detached += 1;
+                    continue;
+                }
+            }
+        }
+
+        // (1) body_cid matches the canonical body
+        let Some(body) = receipt.get("body") else {
+            status.skipped_detached += 1;
+            continue;
+        };
+        let canon = receipt
+            .get("canon")
+            .and_then(|c| c.as_str())
+            .unwrap_or(ubl_runtime::canon::PROFILE_NRF1_V1);
+        let Ok(body_bytes) = ubl_runtime::canon::canonical_bytes_for(canon, body) else {
+            status.body_cid_invalid += 1;
+            fail(&mut status, &cid, "body_cid", "body is not canonicalizable".into());
+            continue;
+        };
+        let computed = ubl_runtime::cid::cid_b3(&body_bytes);
+        if computed != cid {
+            status.body_cid_invalid += 1;
+            fail(
+                &mut status,
+                &cid,
+                "body_cid",
+                format!("computed {computed}"),
+            );
+            continue;
+        }
+
+        // (2) detached JWS signature over the canonical body bytes
+        match receipt
+            .get("proof")
+            .and_then(|p| serde_json::from_value::<ubl_runtime::jws::JwsDetached>(p.clone()).ok())
+        {
+            Some(jws) => match keys.get(&jws.kid) {
+                Some(vk) => {
+                    if !ubl_runtime::jws::verify_detached(&jws, &body_bytes, vk) {
+                        status.signature_invalid += 1;
+                        fail(
+                            &mut status,
+                            &cid,
+                            "signature",
+                            format!("invalid signature by {}", jws.kid),
+                        );
+                    }
+                }
+                None => status.signature_unverifiable += 1,
+            },
+            None => {
+                status.signature_invalid += 1;
+                fail(&mut status, &cid, "signature", "missing proof".into());
+            }
+        }
+    }
+
+    status.runs = state.integrity.read().unwrap().runs + 1;
+    status.last_run = Some(chrono::Utc::now().to_rfc3339());
+    status
+}
+
+fn env_usize(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+pub fn configured_window() -> usize {
+    env_usize("UBL_INTEGRITY_WINDOW", 256)
+}
+
+/// Spawn the periodic verifier. Interval via `UBL_INTEGRITY_INTERVAL_SECS`
+/// (default 300), window via `UBL_INTEGRITY_WINDOW` (default 256).
+pub fn spawn_verifier(state: AppState) {
+    let interval = env_usize("UBL_INTEGRITY_INTERVAL_SECS", 300) as u64;
+    let window = configured_window();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+            let status = verify_window(&state, window).await;
+            if !status.is_clean() {
+                tracing::warn!(
+                    body_cid_invalid = status.body_cid_invalid,
+                    signature_invalid = status.signature_invalid,
+                    missing_parents = status.missing_parents,
+                    "chain integrity check found problems"
+                );
+            }
+            *state.integrity.write().unwrap() = status;
+        }
+    });
+}
@@ -4,9 +4,12 @@
 //!   1. `(app, tenant)` override
 //!   2. `app` default
 //!   3. Global fallback (dev keyring)
+//!
+//! Keyrings can be provisioned at runtime (admin API / BYOK import), so the
+//! maps live behind an RwLock shared across AppState clones.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use ubl_runtime::KeyRing;
 
 /// Hierarchical keyring store: app → KeyRing, with optional (app, tenant) overrides.
@@ -14,10 +17,15 @@ use ubl_runtime::KeyRing;
 pub struct KeyRingStore {
     /// Global fallback keyring (used when no app-specific keyring is configured)
     pub global: Arc<KeyRing>,
+    inner: Arc<RwLock<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
     /// Per-app keyrings: app_id → KeyRing
-    pub app_keyrings: HashMap<String, Arc<KeyRing>>,
+    app_keyrings: HashMap<String, Arc<KeyRing>>,
     /// Per-(app, tenant) overrides: "app:tenant" → KeyRing
-    pub scoped_keyrings: HashMap<String, Arc<KeyRing>>,
+    scoped_keyrings: HashMap<String, Arc<KeyRing>>,
 }
 
 impl KeyRingStore {
@@ -25,8 +33,7 @@ impl KeyRingStore {
     pub fn new(global: KeyRing) -> Self {
         Self {
             global: Arc::new(global),
-            app_keyrings: HashMap::new(),
-            scoped_keyrings: HashMap::new(),
+            inner: Arc::new(RwLock::new(Inner::default())),
         }
     }
 
@@ -36,26 +43,35 @@ impl KeyRingStore {
     }
 
     /// Register a keyring for a specific app.
-    pub fn set_app(&mut self, app: &str, keyring: KeyRing) {
-        self.app_keyrings.insert(app.to_string(), Arc::new(keyring));
+    pub fn set_app(&self, app: &str, keyring: KeyRing) {
+        self.inner
+            .write()
+            .unwrap()
+            .app_keyrings
+            .insert(app.to_string(), Arc::new(keyring));
     }
 
     /// Register a keyring override for a specific (app, tenant).
-    pub fn set_scoped(&mut self, app: &str, tenant: &str, keyring: KeyRing) {
+    pub fn set_scoped(&self, app: &str, tenant: &str, keyring: KeyRing) {
         let key = format!("{app}:{tenant}");
-        self.scoped_keyrings.insert(key, Arc::new(keyring));
+        self.inner
+            .write()
+            .unwrap()
+            .scoped_keyrings
+            .insert(key, Arc::new(keyring));
     }
 
     /// Resolve the effective keyring for a given (app, tenant).
     /// Lookup: scoped → app → global.
     pub fn resolve(&self, app: &str, tenant: &str) -> Arc<KeyRing> {
+        let inner = self.inner.read().unwrap();
         // 1. Scoped override
         let scoped_key = format!("{app}:{tenant}");
-        if let Some(kr) = self.scoped_keyrings.get(&scoped_key) {
+        if let Some(kr) = inner.scoped_keyrings.get(&scoped_key) {
             return Arc::clone(kr);
         }
         // 2. App-level
-        if let Some(kr) = self.app_keyrings.get(app) {
+        if let Some(kr) = inner.app_keyrings.get(app) {
             return Arc::clone(kr);
         }
         // 3. Global fallback
@@ -66,6 +82,24 @@ impl KeyRingStore {
     pub fn resolve_for_scope(&self, scope: &crate::scope::Scope) -> Arc<KeyRing> {
         self.resolve(&scope.app, &scope.tenant)
     }
+
+    /// The global fallback keyring (signs default-scope receipts).
+    pub fn global(&self) -> Arc<KeyRing> {
+        Arc::clone(&self.global)
+    }
+
+    /// Snapshot of all scoped keyrings: ("app:tenant", keyring) pairs,
+    /// sorted by key for deterministic output (did.json).
+    pub fn list_scoped(&self) -> Vec<(String, Arc<KeyRing>)> {
+        let inner = self.inner.read().unwrap();
+        let mut out: Vec<(String, Arc<KeyRing>)> = inner
+            .scoped_keyrings
+            .iter()
+            .map(|(k, v)| (k.clone(), Arc::clone(v)))
+            .collect();
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        out
+    }
 }
 
 #[cfg(test)]
@@ -87,7 +121,7 @@ mod tests {
 
     #[test]
     fn app_level_override() {
-        let mut store = KeyRingStore::new(make_keyring("global#k1"));
+        let store = KeyRingStore::new(make_keyring("global#k1"));
         store.set_app("ubl", make_keyring("ubl#k1"));
         // App match
         let kr = store.resolve("ubl", "any");
@@ -99,7 +133,7 @@ mod tests {
 
     #[test]
     fn scoped_override() {
-        let mut store = KeyRingStore::new(make_keyring("global#k1"));
+        let store = KeyRingStore::new(make_keyring("global#k1"));
         store.set_app("ubl", make_keyring("ubl#k1"));
         store.set_scoped("ubl", "acme", make_keyring("ubl:acme#k1"));
         // Scoped match
@@ -115,10 +149,21 @@ mod tests {
 
     #[test]
     fn resolve_for_scope() {
-        let mut store = KeyRingStore::new(make_keyring("global#k1"));
+        let store = KeyRingStore::new(make_keyring("global#k1"));
         store.set_app("ubl", make_keyring("ubl#k1"));
         let scope = crate::scope::Scope::new("ubl", "acme");
         let kr = store.resolve_for_scope(&scope);
         assert_eq!(kr.active_kid, "ubl#k1");
     }
+
+    #[test]
+    fn list_scoped_is_sorted() {
+        let store = KeyRingStore::dev();
+        store.set_scoped("ubl", "zeta", make_keyring("ubl:zeta#k1"));
+        store.set_scoped("ubl", "acme", make_keyring("ubl:acme#k1"));
+        let listed = store.list_scoped();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].0, "ubl:acme");
+        assert_eq!(listed[1].0, "ubl:zeta");
+    }
 }
@@ -1,9 +1,20 @@
 pub mod api;
 pub mod audit;
+pub mod cache;
 pub mod error;
+pub mod federation;
 pub mod idempotency;
+pub mod integrity;
 pub mod keyring_store;
+pub mod listener;
+pub mod prepare;
+pub mod quota;
+pub mod receipt_log;
+pub mod retention;
 pub mod scope;
+pub mod share;
+pub mod tdln;
+pub mod tls;
 
 use axum::http::HeaderValue;
 use axum::{
@@ -14,21 +25,145 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
-use metrics::{counter, histogram};
+use metrics::{counter, gauge, histogram};
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::CorsLayer;
-use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::timeout::TimeoutLayer;
 
-/// Max request body size: 1 MiB
-const MAX_BODY_BYTES: usize = 1_048_576;
+/// Default request body limits per route class. Execute bodies carry
+/// manifests and can legitimately run past 1 MiB; admin and control
+/// calls never should.
+const BODY_LIMIT_INGEST: usize = 1_048_576; // 1 MiB
+const BODY_LIMIT_EXECUTE: usize = 4_194_304; // 4 MiB
+const BODY_LIMIT_ADMIN: usize = 262_144; // 256 KiB
 /// Request timeout
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Receipt bodies larger than this are stored in the ledger by reference
+const DETACH_BODY_BYTES: usize = 16_384;
 /// Dev bearer token (only active when UBL_AUTH_DISABLED is not set)
 const DEV_TOKEN: &str = "ubl-dev-token-001";
+/// Concurrent RB-VM executions before requests queue
+const RB_CONCURRENCY: usize = 4;
+/// How long a queued RB execution waits for a slot before 503
+const RB_QUEUE_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ── RB-VM execution pool ─────────────────────────────────────────
+
+/// Bounded pool for RB-VM runs. Chip execution is CPU-bound and
+/// synchronous, so it goes onto `spawn_blocking` behind a semaphore;
+/// callers that cannot get a slot within the queue timeout are shed
+/// with 503 + Retry-After.
+#[derive(Clone)]
+pub struct RbExecutor {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    queue_timeout: Duration,
+}
+
+impl RbExecutor {
+    pub fn new(concurrency: usize, queue_timeout: Duration) -> Self {
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(concurrency.max(1))),
+            queue_timeout,
+        }
+    }
+
+    /// Reads `UBL_RB_CONCURRENCY` and `UBL_RB_QUEUE_TIMEOUT_MS`.
+    pub fn from_env() -> Self {
+        let concurrency = std::env::var("UBL_RB_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(RB_CONCURRENCY);
+        let queue_timeout = std::env::var("UBL_RB_QUEUE_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(RB_QUEUE_TIMEOUT);
+        Self::new(concurrency, queue_timeout)
+    }
+
+    /// Suggested Retry-After (seconds) when the pool is saturated.
+    pub fn retry_after_secs(&self) -> u64 {
+        self.queue_timeout.as_secs().max(1)
+    }
+
+    /// Run a blocking VM job on the pool. Returns `Err(())` when no slot
+    /// frees up within the queue timeout (pool saturated).
+    pub async fn run<T, F>(&self, job: F) -> Result<T, ()>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let queued_at = Instant::now();
+        let permit = match tokio::time::timeout(
+            self.queue_timeout,
+            self.semaphore.clone().acquire_owned(),
+        )
+        .await
+        {
+            Ok(Ok(p)) => p,
+            _ => {
+                counter!("ubl_rb_exec_shed_total").increment(1);
+                return Err(());
+            }
+        };
+        histogram!("ubl_rb_queue_wait_seconds").record(queued_at.elapsed().as_secs_f64());
+        let out = tokio::task::spawn_blocking(move || {
+            let result = job();
+            drop(permit);
+            result
+        })
+        .await
+        .expect("rb execution task panicked");
+        Ok(out)
+    }
+}
+
+// ── Async ingest queue ───────────────────────────────────────────
+
+/// In-flight async-mode ingest jobs before producers are shed
+const INGEST_QUEUE_DEPTH: usize = 64;
+
+/// Bounded admission for `Prefer: respond-async` ingest. A permit is
+/// claimed at enqueue time and held for the life of the background job,
+/// so the depth bounds queued + running work; producers that find the
+/// queue full are shed immediately with 429 + Retry-After.
+#[derive(Clone)]
+pub struct IngestQueue {
+    semaphore: Arc<tokio::sync::Semaphore>,
+}
+
+impl IngestQueue {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(depth.max(1))),
+        }
+    }
+
+    /// Reads `UBL_INGEST_QUEUE_DEPTH`.
+    pub fn from_env() -> Self {
+        let depth = std::env::var("UBL_INGEST_QUEUE_DEPTH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(INGEST_QUEUE_DEPTH);
+        Self::new(depth)
+    }
+
+    /// Claim a queue slot, or `None` when the queue is full. The slot is
+    /// released when the returned permit drops.
+    pub fn try_enqueue(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+}
+
+impl Default for IngestQueue {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
 
 // ── Rate limiting ────────────────────────────────────────────────
 
@@ -201,14 +336,27 @@ impl RateLimiter {
     }
 }
 
-/// Client identity resolved from a bearer token.
-#[derive(Debug, Clone)]
+/// Client identity resolved from a bearer token or an mTLS certificate.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ClientInfo {
     pub client_id: String,
     /// Tenant namespace for data isolation.
     pub tenant_id: String,
     /// Which key IDs this client is allowed to use. Empty = all.
+    #[serde(default)]
     pub allowed_kids: Vec<String>,
+    /// Route scopes this token grants (e.g. "ingest", "execute",
+    /// "receipts:read", "admin"). Empty = unrestricted (legacy tokens).
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Registered Ed25519 public key (standard base64, 32 bytes) used to
+    /// verify client-produced signatures (acks, imported receipts).
+    #[serde(default)]
+    pub public_key_b64: Option<String>,
+    /// DID this client signs under; advertised by the client discovery
+    /// document. Defaults to `did:ubl:client:<client_id>`.
+    #[serde(default)]
+    pub did: Option<String>,
 }
 
 impl ClientInfo {
@@ -217,6 +365,29 @@ impl ClientInfo {
     pub fn kid_allowed(&self, kid: &str) -> bool {
         self.allowed_kids.is_empty() || self.allowed_kids.iter().any(|k| k == kid)
     }
+
+    /// Check if this token grants a route scope. Empty scopes means
+    /// unrestricted; "admin" implies every other scope.
+    pub fn scope_allowed(&self, required: &str) -> bool {
+        self.scopes.is_empty() || self.scopes.iter().any(|s| s == required || s == "admin")
+    }
+
+    /// Decode the registered public key, if any.
+    pub fn verifying_key(&self) -> Option<ed25519_dalek::VerifyingKey> {
+        use base64::Engine;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(self.public_key_b64.as_deref()?)
+            .ok()?;
+        let key: [u8; 32] = bytes.as_slice().try_into().ok()?;
+        ed25519_dalek::VerifyingKey::from_bytes(&key).ok()
+    }
+
+    /// The DID this client is discoverable under.
+    pub fn did(&self) -> String {
+        self.did
+            .clone()
+            .unwrap_or_else(|| format!("did:ubl:client:{}", self.client_id))
+    }
 }
 
 /// In-memory token store mapping bearer tokens → client info.
@@ -235,6 +406,9 @@ impl TokenStore {
                 client_id: "dev-client".into(),
                 tenant_id: "default".into(),
                 allowed_kids: vec![], // empty = unrestricted
+                scopes: vec![],       // empty = unrestricted
+                public_key_b64: None,
+                did: None,
             },
         );
         Self {
@@ -251,6 +425,39 @@ impl TokenStore {
     pub fn lookup(&self, token: &str) -> Option<ClientInfo> {
         self.tokens.read().unwrap().get(token).cloned()
     }
+
+    /// Look up a client by id within one tenant (key discovery does not
+    /// cross tenant boundaries).
+    pub fn find_client(&self, tenant: &str, client_id: &str) -> Option<ClientInfo> {
+        self.tokens
+            .read()
+            .unwrap()
+            .values()
+            .find(|c| c.client_id == client_id && c.tenant_id == tenant)
+            .cloned()
+    }
+
+    /// Look up a client by id alone, for the public discovery document
+    /// (client ids are globally unique across tokens).
+    pub fn find_client_any(&self, client_id: &str) -> Option<ClientInfo> {
+        self.tokens
+            .read()
+            .unwrap()
+            .values()
+            .find(|c| c.client_id == client_id)
+            .cloned()
+    }
+
+    /// Look up a client by the DID it signs under (any tenant — DIDs are
+    /// globally unique identifiers, not tenant-scoped names).
+    pub fn find_by_did(&self, did: &str) -> Option<ClientInfo> {
+        self.tokens
+            .read()
+            .unwrap()
+            .values()
+            .find(|c| c.did() == did)
+            .cloned()
+    }
 }
 
 // ── CORS config: (app, tenant) scoped ──────────────────────────
@@ -263,7 +470,7 @@ impl TokenStore {
 ///   3. Global "safe" origins
 ///
 /// Legacy `/v1/*` routes use `(default, default)`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct CorsConfig {
     /// Origins allowed for all apps/tenants.
     pub global_origins: Vec<String>,
@@ -382,21 +589,238 @@ impl CorsConfig {
     }
 }
 
+// ── CORS hot-reload store ────────────────────────────────────────
+
+/// Lock-free handle to the live `CorsConfig`.
+///
+/// Reads (the per-request origin predicate) go through `arc_swap` and never
+/// block; writes (admin CRUD, file reload) build a fresh config and swap it
+/// in atomically. When `UBL_CORS_CONFIG_FILE` is set, changes are persisted
+/// there and an optional poll-based watcher picks up external edits.
+#[derive(Clone)]
+pub struct CorsStore {
+    current: Arc<arc_swap::ArcSwap<CorsConfig>>,
+    /// JSON file the config is persisted to / reloaded from (optional).
+    persist_path: Option<std::path::PathBuf>,
+}
+
+impl CorsStore {
+    pub fn new(cfg: CorsConfig) -> Self {
+        Self {
+            current: Arc::new(arc_swap::ArcSwap::from_pointee(cfg)),
+            persist_path: None,
+        }
+    }
+
+    /// Env bootstrap: origins from `CORS_*` vars, overridden by the
+    /// persisted file (`UBL_CORS_CONFIG_FILE`) when present.
+    pub fn from_env() -> Self {
+        let mut store = Self::new(CorsConfig::from_env());
+        if let Ok(path) = std::env::var("UBL_CORS_CONFIG_FILE") {
+            let path = std::path::PathBuf::from(path);
+            if let Some(cfg) = Self::read_file(&path) {
+                store.current.store(Arc::new(cfg));
+            }
+            store.persist_path = Some(path);
+        }
+        store
+    }
+
+    /// Current config snapshot (cheap, lock-free).
+    pub fn load(&self) -> Arc<CorsConfig> {
+        self.current.load_full()
+    }
+
+    /// Swap in a new config and persist it if a file is configured.
+    pub fn replace(&self, cfg: CorsConfig) {
+        if let Some(path) = &self.persist_path {
+            if let Ok(bytes) = serde_json::to_vec_pretty(&cfg) {
+                let _ = std::fs::write(path, bytes);
+            }
+        }
+        self.current.store(Arc::new(cfg));
+    }
+
+    /// Add an origin at the given level. `app`/`tenant` of `None` mean the
+    /// global list; `app` alone means app-level. Returns false on duplicates.
+    pub fn add_origin(&self, app: Option<&str>, tenant: Option<&str>, origin: &str) -> bool {
+        let mut cfg = (*self.load()).clone();
+        let list = match (app, tenant) {
+            (Some(a), Some(t)) => cfg
+                .scoped_origins
+                .entry(format!("{a}:{t}"))
+                .or_default(),
+            (Some(a), None) => cfg.app_origins.entry(a.to_string()).or_default(),
+            _ => &mut cfg.global_origins,
+        };
+        if list.iter().any(|o| o == origin) {
+            return false;
+        }
+        list.push(origin.to_string());
+        self.replace(cfg);
+        true
+    }
+
+    /// Remove an origin at the given level. Returns false if absent.
+    pub fn remove_origin(&self, app: Option<&str>, tenant: Option<&str>, origin: &str) -> bool {
+        let mut cfg = (*self.load()).clone();
+        let list = match (app, tenant) {
+            (Some(a), Some(t)) => match cfg.scoped_origins.get_mut(&format!("{a}:{t}")) {
+                Some(l) => l,
+                None => return false,
+            },
+            (Some(a), None) => match cfg.app_origins.get_mut(a) {
+                Some(l) => l,
+                None => return false,
+            },
+            _ => &mut cfg.global_origins,
+        };
+        let before = list.len();
+        list.retain(|o| o != origin);
+        if list.len() == before {
+            return false;
+        }
+        self.replace(cfg);
+        true
+    }
+
+    fn read_file(path: &std::path::Path) -> Option<CorsConfig> {
+        let bytes = std::fs::read(path).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Spawn a poll-based watcher that reloads the persisted file when its
+    /// mtime changes. No-op when no file is configured.
+    pub fn spawn_file_watch(&self, interval: Duration) -> Option<tokio::task::JoinHandle<()>> {
+        let path = self.persist_path.clone()?;
+        let current = Arc::clone(&self.current);
+        Some(tokio::spawn(async move {
+            let mut last_mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            loop {
+                tokio::time::sleep(interval).await;
+                let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                if mtime != last_mtime {
+                    last_mtime = mtime;
+                    if let Some(cfg) = Self::read_file(&path) {
+                        current.store(Arc::new(cfg));
+                    }
+                }
+            }
+        }))
+    }
+}
+
+impl Default for CorsStore {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// An open (or closed) saga: a named group of executions queryable as a
+/// unit. Keyed in `AppState::sagas` by the opening receipt's body_cid.
+#[derive(Clone, Debug)]
+pub struct SagaEntry {
+    /// body_cid of the `ubl/saga` open receipt.
+    pub open_cid: String,
+    /// Tenant that opened the saga — other tenants get 404.
+    pub tenant: String,
+    /// WF tip CIDs of the executions linked so far, in order.
+    pub steps: Vec<String>,
+    /// body_cid of the close receipt, once closed.
+    pub closed_cid: Option<String>,
+}
+
+/// One async-mode ingest job, keyed in `AppState::ingest_jobs` by
+/// tracking id — what `GET /v1/ingest/status/:id` reports.
+#[derive(Clone, Debug)]
+pub struct IngestJob {
+    /// Tenant that enqueued — other tenants get 404 on status.
+    pub tenant: String,
+    /// "queued" | "done" | "failed".
+    pub status: &'static str,
+    /// CID of the stored NRF blob, once processed.
+    pub cid: Option<String>,
+    pub bytes_len: Option<usize>,
+    pub error: Option<String>,
+}
+
+/// Per-chip execution counters, keyed in `AppState::chip_stats` by the
+/// scoped bytecode CID — what `GET /v1/chips/:cid` reports as history.
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct ChipStats {
+    pub runs: u64,
+    pub denies: u64,
+    pub fuel_total: u64,
+    pub last_fuel_used: u64,
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub transition_receipts: Arc<RwLock<HashMap<String, serde_json::Value>>>,
     pub receipt_chain: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+    /// Replay context per WF body_cid: {"manifest_cid", "inputs_cid"}.
+    pub replay_index: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+    /// Deterministic result cache: scoped "manifest_cid:inputs_cid" pair →
+    /// the WF body_cid that pair already produced. Identical re-executions
+    /// are served from it instead of forking the chain; opt-in per tenant
+    /// via `replay_cache_tenants`.
+    pub replay_cache: Arc<RwLock<HashMap<String, String>>>,
+    /// Tenants opted into the replay cache (default: nobody).
+    pub replay_cache_tenants: Arc<RwLock<HashSet<String>>>,
+    /// Open and closed sagas, keyed by the opening receipt's body_cid.
+    pub sagas: Arc<RwLock<HashMap<String, SagaEntry>>>,
+    /// Execution counters per chip bytecode CID (scoped per tenant).
+    pub chip_stats: Arc<RwLock<HashMap<String, ChipStats>>>,
+    /// Per-tenant reviewed-bytecode policy: tenants present here may only
+    /// execute the listed bytecode CIDs on `/v1/execute/rb`; absent
+    /// tenants run anything. See `POST /v1/admin/chip-allowlist`.
+    pub chip_allowlists: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+    /// Dev-mode escape hatch: skip allowlist enforcement entirely.
+    /// Set UBL_CHIP_ALLOWLIST_BYPASS=1.
+    pub chip_allowlist_bypass: bool,
+    /// Short-lived cache for `/v1/stats/*` aggregates: key →
+    /// (computed-at, payload). Aggregates rescan the receipt index, so
+    /// dashboard polling is answered from here within the TTL.
+    pub stats_cache: Arc<RwLock<HashMap<String, (Instant, serde_json::Value)>>>,
+    /// Bounded admission for `Prefer: respond-async` ingest.
+    pub ingest_queue: IngestQueue,
+    /// Async-mode ingest jobs by tracking id.
+    pub ingest_jobs: Arc<RwLock<HashMap<String, IngestJob>>>,
     pub seen_cids: Arc<RwLock<HashSet<String>>>,
+    /// Tip produced per idempotency key ("pipeline:inputs_raw_cid") — lets
+    /// a 409 point the caller at the receipt the original run produced.
+    pub seen_tips: Arc<RwLock<HashMap<String, String>>>,
     pub keys: Arc<ubl_runtime::KeyRing>,
     pub keyring_store: keyring_store::KeyRingStore,
     pub last_tip: Arc<RwLock<Option<String>>>,
     pub token_store: TokenStore,
     /// When true, auth middleware is bypassed (for tests / dev)
     pub auth_disabled: bool,
+    /// Operator configuration: public paths and auth exemptions.
+    pub gate_config: Arc<GateConfig>,
     pub rate_limiter: RateLimiter,
-    pub cors_config: CorsConfig,
+    pub cors: CorsStore,
     pub idempotency_store: idempotency::IdempotencyStore,
+    /// Byte-bounded LRU for immutable GET responses (receipts and CAS blobs).
+    pub response_cache: cache::ResponseCache,
+    /// Capability tokens opening single receipts to unauthenticated auditors.
+    pub share_store: share::ShareStore,
+    /// Parked two-phase executions awaiting commit.
+    pub prepare_store: prepare::PrepareStore,
+    /// Per-tenant budgets and usage counters.
+    pub quota: quota::QuotaStore,
     pub metrics_handle: Option<metrics_exporter_prometheus::PrometheusHandle>,
+    /// Receipt bodies above this size (bytes) are detached into the ledger.
+    pub detach_body_bytes: usize,
+    /// Bounded spawn_blocking pool for RB-VM chip execution.
+    pub rb_executor: RbExecutor,
+    /// Scoped CIDs under legal hold — redaction is refused while flagged.
+    pub legal_holds: Arc<RwLock<HashSet<String>>>,
+    /// Latest chain-integrity verification result (background task + /v1/integrity).
+    pub integrity: Arc<RwLock<integrity::IntegrityStatus>>,
+    /// Registered acknowledgement keys: scoped client id → Ed25519
+    /// public key, for verifying client counter-signatures on receipts.
+    pub ack_keys: Arc<RwLock<HashMap<String, [u8; 32]>>>,
 }
 
 impl Default for AppState {
@@ -408,16 +832,42 @@ impl Default for AppState {
         Self {
             transition_receipts: Default::default(),
             receipt_chain: Default::default(),
+            replay_index: Default::default(),
+            replay_cache: Default::default(),
+            replay_cache_tenants: Default::default(),
+            sagas: Default::default(),
+            chip_stats: Default::default(),
+            chip_allowlists: Default::default(),
+            chip_allowlist_bypass: std::env::var("UBL_CHIP_ALLOWLIST_BYPASS")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            stats_cache: Default::default(),
+            ingest_queue: IngestQueue::from_env(),
+            ingest_jobs: Default::default(),
             seen_cids: Default::default(),
+            seen_tips: Default::default(),
             keys: Arc::new(ubl_runtime::KeyRing::dev()),
             keyring_store: keyring_store::KeyRingStore::dev(),
             last_tip: Default::default(),
             token_store: TokenStore::with_dev_token(),
             auth_disabled,
+            gate_config: Arc::new(GateConfig::from_env()),
             rate_limiter: RateLimiter::from_env(),
-            cors_config: CorsConfig::from_env(),
+            cors: CorsStore::from_env(),
             idempotency_store: idempotency::IdempotencyStore::from_env(),
+            response_cache: cache::ResponseCache::from_env(),
+            share_store: share::ShareStore::default(),
+            prepare_store: prepare::PrepareStore::default(),
+            quota: quota::QuotaStore::default(),
             metrics_handle: init_metrics(),
+            detach_body_bytes: std::env::var("UBL_DETACH_BODY_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DETACH_BODY_BYTES),
+            rb_executor: RbExecutor::from_env(),
+            legal_holds: Default::default(),
+            integrity: Default::default(),
+            ack_keys: Default::default(),
         }
     }
 }
@@ -437,14 +887,60 @@ pub fn init_metrics() -> Option<metrics_exporter_prometheus::PrometheusHandle> {
 fn v1_routes() -> Router<AppState> {
     Router::new()
         .route("/ingest", post(api::ingest))
+        .route("/ingest/bulk", post(api::ingest_bulk))
+        .route("/ingest/status/:id", get(api::get_ingest_status))
         .route("/certify", post(api::certify_cid))
+        .route("/attest", post(api::attest))
         .route("/receipts", get(api::list_receipts))
+        .route("/receipts/import", post(api::import_receipt))
         .route("/receipt/:cid", get(api::get_receipt))
+        .route("/receipt/:cid/cosign", post(api::cosign_receipt))
+        .route("/receipt/:cid/ack", post(api::ack_receipt))
+        .route("/receipt/:cid/share", post(api::share_receipt))
+        .route(
+            "/receipt/:cid/share/:token",
+            axum::routing::delete(api::revoke_share),
+        )
+        .route("/share/:token", get(api::get_shared))
         .route("/audit", get(api::audit_report))
+        .route("/integrity", get(api::integrity_report))
         .route("/resolve", post(api::resolve))
         .route("/execute", post(api::execute_runtime))
+        .route("/execute/commit", post(api::execute_commit))
+        .route("/replay", post(api::replay))
+        .route("/saga", post(api::open_saga))
+        .route("/saga/:id", get(api::get_saga))
+        .route("/saga/:id/close", post(api::close_saga))
         .route("/execute/rb", post(api::execute_rb))
+        .route("/execute/rb/estimate", post(api::estimate_rb))
+        .route("/execute/rb/lint", post(api::lint_rb))
+        .route("/chips/standard", get(api::list_standard_chips))
+        .route("/chips/:cid", get(api::get_chip))
+        .route("/vectors", get(api::get_vectors))
+        .route("/stats/receipts", get(api::stats_receipts))
+        .route("/stats/pipelines", get(api::stats_pipelines))
+        .route("/stats/denies", get(api::stats_denies))
+        .route("/stats/usage", get(api::stats_usage))
         .route("/transition/:cid", get(api::get_transition))
+        .route("/redact/:cid", post(api::redact_cid))
+        .route("/admin/keyrings", post(api::admin_put_keyring))
+        .route("/admin/ack-keys", post(api::admin_put_ack_key))
+        .route("/admin/tokens", post(api::admin_put_token))
+        .route("/admin/retention", post(api::admin_put_retention))
+        .route("/admin/replay-cache", post(api::admin_put_replay_cache))
+        .route("/admin/chip-allowlist", post(api::admin_put_chip_allowlist))
+        .route("/admin/quota", post(api::admin_put_quota))
+        .route("/quota/usage", get(api::get_quota_usage))
+        .route(
+            "/admin/hold/:cid",
+            post(api::admin_set_hold).delete(api::admin_clear_hold),
+        )
+        .route(
+            "/admin/cors",
+            get(api::admin_list_cors)
+                .post(api::admin_add_cors)
+                .delete(api::admin_remove_cors),
+        )
 }
 
 /// Middleware: inject Scope from path params :app and :tenant into request extensions.
@@ -452,21 +948,31 @@ async fn inject_scope_from_path(req: Request, next: Next) -> Response {
     let mut req = req;
     // Extract :app and :tenant from Axum's matched path params
     // These are available when routes are nested under /a/:app/t/:tenant/v1
-    let path = req.uri().path().to_string();
+    // Inside a nested router the URI is prefix-stripped, so prefer OriginalUri.
+    let path = req
+        .extensions()
+        .get::<axum::extract::OriginalUri>()
+        .map(|u| u.0.path().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
     let scope = parse_scope_from_path(&path).unwrap_or_default();
     req.extensions_mut().insert(scope);
     next.run(req).await
 }
 
 /// Parse (app, tenant) from a path like /a/<app>/t/<tenant>/v1/...
+/// The a/…/t/…/v1 window may sit behind a mount prefix (see
+/// [`router_with_prefix`]), so it is located by scanning rather than by
+/// fixed position.
 fn parse_scope_from_path(path: &str) -> Option<scope::Scope> {
     let parts: Vec<&str> = path.split('/').collect();
-    // Expected: ["", "a", "<app>", "t", "<tenant>", "v1", ...]
-    if parts.len() >= 6 && parts[1] == "a" && parts[3] == "t" && parts[5] == "v1" {
-        Some(scope::Scope::new(parts[2], parts[4]))
-    } else {
-        None
-    }
+    // Expected somewhere in the path: ["a", "<app>", "t", "<tenant>", "v1"]
+    parts.windows(5).find_map(|w| {
+        if w[0] == "a" && w[2] == "t" && w[4] == "v1" {
+            Some(scope::Scope::new(w[1], w[3]))
+        } else {
+            None
+        }
+    })
 }
 
 /// Middleware: inject legacy Scope (default, default) into request extensions.
@@ -479,9 +985,29 @@ async fn inject_legacy_scope(req: Request, next: Next) -> Response {
 }
 
 pub fn app_with_state(state: AppState) -> Router {
+    // A typo'd exemption would silently expose or lock routes — refuse to
+    // boot instead.
+    if let Err(e) = state.gate_config.validate(&route_patterns()) {
+        panic!("invalid gate configuration: {e}");
+    }
+    // The runtime's `bytes.from_cid` codec pulls blob content through this
+    // hook; set is idempotent, so test routers can call it repeatedly.
+    ubl_runtime::bytes::set_blob_resolver(ubl_ledger::find_raw_blocking);
+    // Chaos testing: ledger writes consult the same env-gated fault
+    // config as the runtime's sign/canon points (UBL_FAULT_INJECT)
+    ubl_ledger::set_fault_hook(ubl_runtime::faults::should_fail);
     let auth_state = state.clone();
     let rl_state = state.clone();
-    let cors_config = state.cors_config.clone();
+    let sign_state = state.clone();
+    let limit_state = state.clone();
+    let digest_state = state.clone();
+    let cors = state.cors.clone();
+    // Optional file-watch reload for the persisted CORS config
+    if let Ok(ms) = std::env::var("UBL_CORS_WATCH_MS") {
+        if let Ok(ms) = ms.parse() {
+            let _ = state.cors.spawn_file_watch(Duration::from_millis(ms));
+        }
+    }
 
     // Scoped routes: /a/:app/t/:tenant/v1/*
     // The :app and :tenant are parsed by inject_scope_from_path middleware.
@@ -500,20 +1026,42 @@ pub fn app_with_state(state: AppState) -> Router {
         .route("/healthz", get(healthz))
         .route("/metrics", get(metrics_endpoint))
         .route("/.well-known/did.json", get(api::well_known_did_json))
+        .route("/.well-known/ubl.json", get(api::well_known_ubl_json))
+        .route("/.well-known/ubl/schemas", get(api::well_known_schemas))
+        .route(
+            "/.well-known/ubl-clients/:client_id/did.json",
+            get(api::well_known_client_did),
+        )
         // Legacy CID dispatch (outside v1 namespace)
         .route("/cid/:cid", get(api::get_cid_dispatch))
         // Scoped v1 routes: /a/:app/t/:tenant/v1/*
         .nest("/a/:app/t/:tenant/v1", scoped_v1)
         // Legacy v1 routes: /v1/* → (default, default)
         .nest("/v1", legacy_v1)
-        .layer(RequestBodyLimitLayer::new(MAX_BODY_BYTES))
+        .layer(middleware::from_fn(move |req, next| {
+            let st = limit_state.clone();
+            enforce_body_limit(st, req, next)
+        }))
         .layer(TimeoutLayer::new(REQUEST_TIMEOUT))
+        .layer(middleware::from_fn(move |req, next| {
+            let st = sign_state.clone();
+            sign_response_body(st, req, next)
+        }))
+        // Transport compression sits *outside* the response signer, so
+        // signatures (and body CIDs) always cover the canonical bytes;
+        // Content-Encoding is negotiated per request and never signed.
+        .layer(CompressionLayer::new())
+        .layer(middleware::from_fn(move |req, next| {
+            let st = digest_state.clone();
+            verify_content_digest(st, req, next)
+        }))
         .layer(middleware::from_fn(require_json_content_type))
         .layer(middleware::from_fn(move |req, next| {
             let st = rl_state.clone();
             rate_limit_middleware(st, req, next)
         }))
         .layer(middleware::from_fn(metrics_middleware))
+        .layer(middleware::from_fn(enforce_token_scopes))
         .layer(middleware::from_fn(move |req, next| {
             let st = auth_state.clone();
             require_bearer_auth(st, req, next)
@@ -527,7 +1075,7 @@ pub fn app_with_state(state: AppState) -> Router {
                         let scope = parse_scope_from_path(parts.uri.path());
                         origin
                             .to_str()
-                            .map(|o| cors_config.is_origin_allowed(o, scope.as_ref()))
+                            .map(|o| cors.load().is_origin_allowed(o, scope.as_ref()))
                             .unwrap_or(false)
                     },
                 ))
@@ -557,6 +1105,165 @@ pub fn app_with_state(state: AppState) -> Router {
         .with_state(state)
 }
 
+/// Build the gate as an embeddable router mounted under `prefix`, e.g.
+/// `router_with_prefix(state, "/ubl")`. The caller `.merge()`s the result
+/// into its own `Router` and keeps ownership of the server; every gate
+/// middleware (auth, scopes, rate limiting, CORS, body limits) rides
+/// along inside the returned router and applies only to gate routes.
+///
+/// State construction is the embedder's job: `AppState::default()` reads
+/// the usual `UBL_*` environment. The background tasks the binary spawns
+/// — `integrity::spawn_verifier(state.clone())` and
+/// `retention::spawn_sweeper()` — are optional and must be started by the
+/// host process if chain verification or archival tiering is wanted.
+///
+/// A `prefix` of `""` or `"/"` returns the root-mounted router unchanged
+/// (axum does not allow nesting at the root).
+pub fn router_with_prefix(state: AppState, prefix: &str) -> Router {
+    let app = app_with_state(state);
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() {
+        return app;
+    }
+    assert!(
+        prefix.starts_with('/'),
+        "mount prefix must start with '/', got '{prefix}'"
+    );
+    Router::new().nest(prefix, app)
+}
+
+/// Middleware: enforce the per-route-class request body limit. Runs
+/// innermost, so a declared Content-Length over the limit is rejected
+/// before the handler sees anything; undeclared bodies are buffered here
+/// (every handler consumes JSON anyway) and cut off at the limit with
+/// the standard error shape instead of tower-http's bare 413.
+async fn enforce_body_limit(state: AppState, req: Request, next: Next) -> Response {
+    let limit = state.gate_config.body_limits.for_path(req.uri().path());
+    let declared = req
+        .headers()
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared.is_some_and(|len| len > limit) {
+        return error::AppError::payload_too_large(limit).into_response();
+    }
+    let (parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, limit).await {
+        Ok(b) => b,
+        Err(_) => return error::AppError::payload_too_large(limit).into_response(),
+    };
+    next.run(Request::from_parts(parts, axum::body::Body::from(bytes)))
+        .await
+}
+
+/// Middleware: validate `Content-Digest` / `Repr-Digest` request headers
+/// (RFC 9530). Opt-in per request: absent headers pass through untouched.
+/// Supported algorithms: sha-256, sha-512; a header carrying only unknown
+/// algorithms is rejected rather than silently skipped.
+async fn verify_content_digest(state: AppState, req: Request, next: Next) -> Response {
+    let digest_header = req
+        .headers()
+        .get("content-digest")
+        .or_else(|| req.headers().get("repr-digest"))
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let Some(digest_header) = digest_header else {
+        return next.run(req).await;
+    };
+
+    // Buffer the body to hash it, then reassemble the request. Capped at
+    // the largest class limit; the per-class check happens further in.
+    let (parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, state.gate_config.body_limits.max()).await {
+        Ok(b) => b,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "unreadable_body"})),
+            )
+                .into_response()
+        }
+    };
+
+    // Dictionary field: `sha-256=:BASE64:, sha-512=:BASE64:`
+    let mut checked = false;
+    for member in digest_header.split(',') {
+        let Some((alg, val)) = member.trim().split_once('=') else {
+            continue;
+        };
+        let expected_b64 = val.trim().trim_matches(':');
+        let actual = match alg.trim().to_ascii_lowercase().as_str() {
+            "sha-256" => {
+                use sha2::Digest;
+                sha2::Sha256::digest(&bytes).to_vec()
+            }
+            "sha-512" => {
+                use sha2::Digest;
+                sha2::Sha512::digest(&bytes).to_vec()
+            }
+            _ => continue,
+        };
+        checked = true;
+        let expected = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            expected_b64,
+        )
+        .unwrap_or_default();
+        if expected != actual {
+            counter!("ubl_gate_digest_mismatch_total").increment(1);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "content_digest_mismatch", "alg": alg.trim()})),
+            )
+                .into_response();
+        }
+    }
+    if !checked {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "unsupported_digest_algorithm"})),
+        )
+            .into_response();
+    }
+
+    let req = Request::from_parts(parts, axum::body::Body::from(bytes));
+    next.run(req).await
+}
+
+/// Middleware: detached-JWS response signing for tamper-evident transport.
+/// Opt-in per request via `x-ubl-sign-response: 1`; the response body bytes
+/// are signed with the gate issuer key and the envelope travels in
+/// `x-ubl-signature` (`<protected>..<signature>`, RFC 7797 b64=false) plus
+/// `x-ubl-signature-kid`.
+async fn sign_response_body(state: AppState, req: Request, next: Next) -> Response {
+    let wants_signature = req
+        .headers()
+        .get("x-ubl-sign-response")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if !wants_signature {
+        return next.run(req).await;
+    }
+
+    let resp = next.run(req).await;
+    let (mut parts, body) = resp.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(b) => b,
+        Err(_) => return Response::from_parts(parts, axum::body::Body::empty()),
+    };
+
+    let jws = ubl_runtime::jws::sign_detached(&bytes, &state.keys.active, &state.keys.active_kid);
+    if let (Ok(sig), Ok(kid)) = (
+        HeaderValue::from_str(&format!("{}..{}", jws.protected, jws.signature)),
+        HeaderValue::from_str(&jws.kid),
+    ) {
+        parts.headers.insert("x-ubl-signature", sig);
+        parts.headers.insert("x-ubl-signature-kid", kid);
+    }
+    Response::from_parts(parts, axum::body::Body::from(bytes))
+}
+
 /// Middleware: reject POST/PUT requests without application/json content-type.
 /// OPTIONS requests are always passed through (CORS preflight).
 async fn require_json_content_type(req: Request, next: Next) -> Response {
@@ -564,12 +1271,17 @@ async fn require_json_content_type(req: Request, next: Next) -> Response {
     if req.method() == axum::http::Method::OPTIONS {
         return next.run(req).await;
     }
+    // Bulk ingest takes NDJSON, not a single JSON document
+    let ndjson_route = req.uri().path().ends_with("/ingest/bulk");
     let dominated_by_json = match req.method().as_str() {
         "POST" | "PUT" | "PATCH" => req
             .headers()
             .get("content-type")
             .and_then(|v| v.to_str().ok())
-            .map(|ct| ct.starts_with("application/json"))
+            .map(|ct| {
+                ct.starts_with("application/json")
+                    || (ndjson_route && ct.starts_with("application/x-ndjson"))
+            })
             .unwrap_or(false),
         _ => true, // GET, DELETE, etc. don't need content-type
     };
@@ -583,8 +1295,282 @@ async fn require_json_content_type(req: Request, next: Next) -> Response {
     next.run(req).await
 }
 
-/// Paths that do NOT require authentication.
-const PUBLIC_PATHS: &[&str] = &["/healthz", "/.well-known/did.json", "/metrics"];
+/// Default paths that do NOT require authentication. Operators override
+/// the set through [`GateConfig`].
+const PUBLIC_PATHS: &[&str] = &[
+    "/healthz",
+    "/.well-known/did.json",
+    "/.well-known/ubl.json",
+    "/.well-known/ubl/schemas",
+    "/v1/vectors",
+    "/metrics",
+];
+
+/// Default prefix-matched auth exemptions: share capability URLs carry
+/// their own authorization (possession of the token), client key
+/// discovery is public by design.
+const PUBLIC_PREFIXES: &[&str] = &["/v1/share/", "/.well-known/ubl-clients/"];
+
+/// Per-route-class request body limits (bytes). Classes map onto the
+/// route table: bulk data routes (`ingest*`, `receipts/import`) take the
+/// ingest limit, manifest-carrying routes (`execute*`, `replay`,
+/// `resolve`) the execute limit, and everything else — including
+/// `certify` and the `admin/*` surface — the admin limit.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BodyLimits {
+    pub ingest: usize,
+    pub execute: usize,
+    pub admin: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            ingest: BODY_LIMIT_INGEST,
+            execute: BODY_LIMIT_EXECUTE,
+            admin: BODY_LIMIT_ADMIN,
+        }
+    }
+}
+
+impl BodyLimits {
+    /// Resolve the limit for a concrete request path. The scoped
+    /// (`/a/:app/t/:tenant/v1/*`) and legacy (`/v1/*`) mounts share the
+    /// `/v1` tail; paths outside both (health, well-known, CID dispatch)
+    /// never carry meaningful bodies and fall to the admin limit.
+    pub fn for_path(&self, path: &str) -> usize {
+        let tail = match path.find("/v1/") {
+            Some(i) => &path[i + 3..],
+            None => return self.admin,
+        };
+        if tail == "/ingest" || tail.starts_with("/ingest/") || tail == "/receipts/import" {
+            self.ingest
+        } else if tail == "/execute"
+            || tail.starts_with("/execute/")
+            || tail == "/replay"
+            || tail == "/resolve"
+        {
+            self.execute
+        } else {
+            self.admin
+        }
+    }
+
+    /// The largest configured limit — caps body buffering in middleware
+    /// that runs before the per-class check (e.g. digest verification).
+    pub fn max(&self) -> usize {
+        self.ingest.max(self.execute).max(self.admin)
+    }
+}
+
+/// Operator-tunable gate behavior, resolved once at startup and validated
+/// against the route table before the router is served.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct GateConfig {
+    /// Exact-match paths served without authentication.
+    pub public_paths: Vec<String>,
+    /// Prefix-match auth exemptions.
+    pub public_prefixes: Vec<String>,
+    /// Require a Bearer token on /metrics even when it is listed public.
+    pub metrics_auth: bool,
+    /// Request body limits per route class.
+    #[serde(default)]
+    pub body_limits: BodyLimits,
+}
+
+impl Default for GateConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl GateConfig {
+    /// Build from environment variables:
+    /// - `UBL_PUBLIC_PATHS`: comma-separated, REPLACES the default list
+    /// - `UBL_AUTH_EXEMPT`: comma-separated additions; a trailing `*`
+    ///   marks a prefix (e.g. `/v1/openapi*`)
+    /// - `UBL_METRICS_AUTH=1`: lock down /metrics behind auth
+    /// - `UBL_BODY_LIMIT_INGEST` / `UBL_BODY_LIMIT_EXECUTE` /
+    ///   `UBL_BODY_LIMIT_ADMIN`: per-class body limits in bytes
+    pub fn from_env() -> Self {
+        let split = |v: String| -> Vec<String> {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        let mut public_paths = std::env::var("UBL_PUBLIC_PATHS")
+            .map(split)
+            .unwrap_or_else(|_| PUBLIC_PATHS.iter().map(|p| p.to_string()).collect());
+        let mut public_prefixes: Vec<String> =
+            PUBLIC_PREFIXES.iter().map(|p| p.to_string()).collect();
+        if let Ok(extra) = std::env::var("UBL_AUTH_EXEMPT") {
+            for entry in split(extra) {
+                match entry.strip_suffix('*') {
+                    Some(prefix) => public_prefixes.push(prefix.to_string()),
+                    None => public_paths.push(entry),
+                }
+            }
+        }
+        let limit = |var: &str, default: usize| -> usize {
+            std::env::var(var)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            public_paths,
+            public_prefixes,
+            metrics_auth: std::env::var("UBL_METRICS_AUTH")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            body_limits: BodyLimits {
+                ingest: limit("UBL_BODY_LIMIT_INGEST", BODY_LIMIT_INGEST),
+                execute: limit("UBL_BODY_LIMIT_EXECUTE", BODY_LIMIT_EXECUTE),
+                admin: limit("UBL_BODY_LIMIT_ADMIN", BODY_LIMIT_ADMIN),
+            },
+        }
+    }
+
+    /// Does this path skip authentication?
+    pub fn is_public(&self, path: &str) -> bool {
+        if self.metrics_auth && path == "/metrics" {
+            return false;
+        }
+        self.public_paths.iter().any(|p| p == path)
+            || self.public_prefixes.iter().any(|p| path.starts_with(p.as_str()))
+    }
+
+    /// Check every configured path against the registered route patterns,
+    /// so a typo'd exemption fails the boot instead of silently guarding
+    /// (or exposing) nothing.
+    pub fn validate(&self, patterns: &[String]) -> Result<(), String> {
+        for path in &self.public_paths {
+            if !path.starts_with('/') {
+                return Err(format!("public path '{path}' must start with '/'"));
+            }
+            if !patterns.iter().any(|p| route_pattern_matches(p, path)) {
+                return Err(format!("public path '{path}' matches no registered route"));
+            }
+        }
+        for prefix in &self.public_prefixes {
+            if !prefix.starts_with('/') {
+                return Err(format!("auth-exempt prefix '{prefix}' must start with '/'"));
+            }
+            if !patterns.iter().any(|p| route_pattern_covers_prefix(p, prefix)) {
+                return Err(format!(
+                    "auth-exempt prefix '{prefix}' matches no registered route"
+                ));
+            }
+        }
+        for (class, bytes) in [
+            ("ingest", self.body_limits.ingest),
+            ("execute", self.body_limits.execute),
+            ("admin", self.body_limits.admin),
+        ] {
+            if bytes == 0 {
+                return Err(format!("body limit for class '{class}' must be positive"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Does a concrete path match a route pattern? `:param` segments match
+/// any single segment.
+fn route_pattern_matches(pattern: &str, path: &str) -> bool {
+    let pat: Vec<&str> = pattern.split('/').collect();
+    let seg: Vec<&str> = path.split('/').collect();
+    pat.len() == seg.len()
+        && pat
+            .iter()
+            .zip(&seg)
+            .all(|(p, s)| p.starts_with(':') || p == s)
+}
+
+/// Does some route live under the prefix? Compares leading segments,
+/// ignoring the prefix's trailing empty segment from a trailing slash.
+fn route_pattern_covers_prefix(pattern: &str, prefix: &str) -> bool {
+    let pat: Vec<&str> = pattern.split('/').collect();
+    let pre: Vec<&str> = prefix
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    // pattern leads with "" from its leading slash
+    pat.len() > pre.len()
+        && pat[1..=pre.len()]
+            .iter()
+            .zip(&pre)
+            .all(|(p, s)| p.starts_with(':') || p == s)
+}
+
+/// Every path pattern the router registers, including both the legacy
+/// `/v1/*` and scoped `/a/:app/t/:tenant/v1/*` mounts. Mirrors
+/// [`v1_routes`] and the top-level routes in [`app_with_state`]; the
+/// hardened suite probes each pattern to keep the two in sync.
+pub fn route_patterns() -> Vec<String> {
+    const TOP_LEVEL: &[&str] = &[
+        "/healthz",
+        "/metrics",
+        "/.well-known/did.json",
+        "/.well-known/ubl.json",
+        "/.well-known/ubl/schemas",
+        "/.well-known/ubl-clients/:client_id/did.json",
+        "/cid/:cid",
+    ];
+    const V1: &[&str] = &[
+        "/ingest",
+        "/ingest/bulk",
+        "/ingest/status/:id",
+        "/certify",
+        "/attest",
+        "/receipts",
+        "/receipts/import",
+        "/receipt/:cid",
+        "/receipt/:cid/cosign",
+        "/receipt/:cid/ack",
+        "/receipt/:cid/share",
+        "/receipt/:cid/share/:token",
+        "/share/:token",
+        "/audit",
+        "/integrity",
+        "/resolve",
+        "/execute",
+        "/execute/commit",
+        "/replay",
+        "/saga",
+        "/saga/:id",
+        "/saga/:id/close",
+        "/execute/rb",
+        "/execute/rb/estimate",
+        "/execute/rb/lint",
+        "/chips/standard",
+        "/chips/:cid",
+        "/vectors",
+        "/stats/receipts",
+        "/stats/pipelines",
+        "/stats/denies",
+        "/stats/usage",
+        "/transition/:cid",
+        "/redact/:cid",
+        "/admin/keyrings",
+        "/admin/ack-keys",
+        "/admin/tokens",
+        "/admin/retention",
+        "/admin/replay-cache",
+        "/admin/chip-allowlist",
+        "/admin/quota",
+        "/quota/usage",
+        "/admin/hold/:cid",
+        "/admin/cors",
+    ];
+    let mut patterns: Vec<String> = TOP_LEVEL.iter().map(|p| p.to_string()).collect();
+    for p in V1 {
+        patterns.push(format!("/v1{p}"));
+        patterns.push(format!("/a/:app/t/:tenant/v1{p}"));
+    }
+    patterns
+}
 
 /// Middleware: require valid Bearer token on non-public paths.
 async fn require_bearer_auth(state: AppState, mut req: Request, next: Next) -> Response {
@@ -596,9 +1582,13 @@ async fn require_bearer_auth(state: AppState, mut req: Request, next: Next) -> R
     if req.method() == axum::http::Method::OPTIONS {
         return next.run(req).await;
     }
-    // Skip auth for public paths
+    // Already identified via mTLS client certificate — no Bearer token needed
+    if req.extensions().get::<ClientInfo>().is_some() {
+        return next.run(req).await;
+    }
+    // Skip auth for public paths and configured exemptions
     let path = req.uri().path().to_string();
-    if PUBLIC_PATHS.iter().any(|p| path == *p) {
+    if state.gate_config.is_public(&path) {
         return next.run(req).await;
     }
     // Extract Bearer token
@@ -630,13 +1620,72 @@ async fn require_bearer_auth(state: AppState, mut req: Request, next: Next) -> R
     }
 }
 
+/// Map a route to the token scope it requires. None = no scope needed.
+/// Scoped paths (/a/:app/t/:tenant/v1/*) are normalized to their /v1 tail.
+fn required_scope(method: &str, path: &str) -> Option<&'static str> {
+    // Normalize /a/<app>/t/<tenant>/v1/... → /v1/...
+    let tail = match path.find("/v1/") {
+        Some(idx) => &path[idx..],
+        None => path,
+    };
+    if tail.starts_with("/cid/") {
+        return Some("receipts:read");
+    }
+    let route = tail.strip_prefix("/v1/")?;
+    // Importing writes into the chain, so it needs more than receipts:read
+    if route == "receipts/import" {
+        return Some("ingest").filter(|_| method != "OPTIONS");
+    }
+    // Chip inspection exposes tenant execution history; the standard
+    // library listing stays public crate data
+    if route.starts_with("chips/") && route != "chips/standard" {
+        return Some("receipts:read").filter(|_| method != "OPTIONS");
+    }
+    Some(match route.split('/').next().unwrap_or("") {
+        "admin" | "redact" => "admin",
+        "ingest" | "certify" => "ingest",
+        "execute" | "replay" | "saga" => "execute",
+        "receipts" | "receipt" | "transition" | "audit" | "resolve" | "integrity" => {
+            "receipts:read"
+        }
+        "stats" => "stats:read",
+        _ => return None,
+    })
+    .filter(|_| method != "OPTIONS")
+}
+
+/// Middleware: enforce fine-grained token scopes per route.
+/// Runs AFTER auth so ClientInfo is available; requests without a
+/// ClientInfo (auth disabled, public paths) pass through.
+async fn enforce_token_scopes(req: Request, next: Next) -> Response {
+    let path = req.uri().path().to_string();
+    let method = req.method().to_string();
+    if let (Some(required), Some(client)) = (
+        required_scope(&method, &path),
+        req.extensions().get::<ClientInfo>(),
+    ) {
+        if !client.scope_allowed(required) {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "insufficient_scope",
+                    "required_scope": required,
+                })),
+            )
+                .into_response();
+        }
+    }
+    next.run(req).await
+}
+
 /// Middleware: hierarchical rate limiting (app→tenant→client).
 /// Runs AFTER auth (so ClientInfo is available) and scope is extractable from path.
 async fn rate_limit_middleware(state: AppState, req: Request, next: Next) -> Response {
     let path = req.uri().path().to_string();
     let method = req.method().to_string();
-    // Skip rate limiting for public/read-only paths
-    if PUBLIC_PATHS.iter().any(|p| path == *p) {
+    // Skip rate limiting for public/read-only paths (exact matches only —
+    // prefix exemptions like share links stay rate limited)
+    if state.gate_config.public_paths.contains(&path) {
         return next.run(req).await;
     }
 
@@ -712,6 +1761,20 @@ async fn healthz() -> Json<serde_json::Value> {
 async fn metrics_endpoint(State(state): axum::extract::State<AppState>) -> impl IntoResponse {
     // Try to render prometheus metrics; if recorder not installed, return empty
     if let Some(handle) = &state.metrics_handle {
+        // Publish decoded-chip cache counters as gauges at scrape time
+        let chip_stats = ubl_runtime::chip_cache_stats();
+        gauge!("ubl_rb_chip_cache_hits").set(chip_stats.hits as f64);
+        gauge!("ubl_rb_chip_cache_misses").set(chip_stats.misses as f64);
+        gauge!("ubl_rb_chip_cache_entries").set(chip_stats.entries as f64);
+        gauge!("ubl_ledger_corrupted_reads").set(ubl_ledger::corrupted_read_count() as f64);
+        // Chain-integrity verifier results (background task / on-demand)
+        {
+            let integ = state.integrity.read().unwrap();
+            gauge!("ubl_integrity_checked").set(integ.checked as f64);
+            gauge!("ubl_integrity_body_cid_invalid").set(integ.body_cid_invalid as f64);
+            gauge!("ubl_integrity_signature_invalid").set(integ.signature_invalid as f64);
+            gauge!("ubl_integrity_missing_parents").set(integ.missing_parents as f64);
+        }
         let body = handle.render();
         (
             StatusCode::OK,
@@ -727,6 +1790,106 @@ async fn metrics_endpoint(State(state): axum::extract::State<AppState>) -> impl
     }
 }
 
+#[cfg(test)]
+mod gate_config_tests {
+    use super::*;
+
+    fn cfg() -> GateConfig {
+        GateConfig {
+            public_paths: PUBLIC_PATHS.iter().map(|p| p.to_string()).collect(),
+            public_prefixes: PUBLIC_PREFIXES.iter().map(|p| p.to_string()).collect(),
+            metrics_auth: false,
+            body_limits: BodyLimits::default(),
+        }
+    }
+
+    #[test]
+    fn defaults_keep_the_historic_public_set() {
+        let c = cfg();
+        assert!(c.is_public("/healthz"));
+        assert!(c.is_public("/metrics"));
+        assert!(c.is_public("/v1/share/some-token"));
+        assert!(c.is_public("/.well-known/ubl-clients/cli-1/did.json"));
+        assert!(!c.is_public("/v1/execute"));
+    }
+
+    #[test]
+    fn metrics_auth_locks_down_metrics() {
+        let c = GateConfig {
+            metrics_auth: true,
+            ..cfg()
+        };
+        assert!(!c.is_public("/metrics"));
+        assert!(c.is_public("/healthz"));
+    }
+
+    #[test]
+    fn extra_exemptions_open_routes() {
+        let mut c = cfg();
+        c.public_paths.push("/v1/integrity".into());
+        c.public_prefixes.push("/v1/receipt/".into());
+        assert!(c.is_public("/v1/integrity"));
+        assert!(c.is_public("/v1/receipt/b3:abc"));
+        assert!(!c.is_public("/v1/receipts"));
+    }
+
+    #[test]
+    fn default_config_validates_against_the_route_table() {
+        cfg().validate(&route_patterns()).unwrap();
+    }
+
+    #[test]
+    fn validation_rejects_unregistered_paths() {
+        let mut c = cfg();
+        c.public_paths.push("/v1/openapi.json".into());
+        let err = c.validate(&route_patterns()).unwrap_err();
+        assert!(err.contains("/v1/openapi.json"), "got: {err}");
+
+        let mut c = cfg();
+        c.public_prefixes.push("/v2/".into());
+        assert!(c.validate(&route_patterns()).is_err());
+
+        let mut c = cfg();
+        c.public_paths.push("no-slash".into());
+        assert!(c.validate(&route_patterns()).unwrap_err().contains("'/'"));
+    }
+
+    #[test]
+    fn body_limit_classes_cover_both_mounts() {
+        let l = BodyLimits::default();
+        // Bulk data → ingest limit
+        assert_eq!(l.for_path("/v1/ingest"), l.ingest);
+        assert_eq!(l.for_path("/v1/ingest/bulk"), l.ingest);
+        assert_eq!(l.for_path("/v1/receipts/import"), l.ingest);
+        assert_eq!(l.for_path("/a/ubl/t/acme/v1/ingest"), l.ingest);
+        // Manifest-carrying → execute limit
+        assert_eq!(l.for_path("/v1/execute"), l.execute);
+        assert_eq!(l.for_path("/v1/execute/commit"), l.execute);
+        assert_eq!(l.for_path("/v1/replay"), l.execute);
+        assert_eq!(l.for_path("/a/ubl/t/acme/v1/resolve"), l.execute);
+        // Everything else — certify included — gets the small limit
+        assert_eq!(l.for_path("/v1/certify"), l.admin);
+        assert_eq!(l.for_path("/v1/admin/tokens"), l.admin);
+        assert_eq!(l.for_path("/healthz"), l.admin);
+    }
+
+    #[test]
+    fn validation_rejects_zero_body_limits() {
+        let mut c = cfg();
+        c.body_limits.execute = 0;
+        let err = c.validate(&route_patterns()).unwrap_err();
+        assert!(err.contains("execute"), "got: {err}");
+    }
+
+    #[test]
+    fn param_segments_match_any_value() {
+        assert!(route_pattern_matches("/v1/receipt/:cid", "/v1/receipt/b3:abc"));
+        assert!(!route_pattern_matches("/v1/receipt/:cid", "/v1/receipt"));
+        assert!(route_pattern_covers_prefix("/v1/share/:token", "/v1/share/"));
+        assert!(!route_pattern_covers_prefix("/v1/share/:token", "/v1/shared/"));
+    }
+}
+
 #[cfg(test)]
 mod cors_tests {
     use super::*;
@@ -830,6 +1993,10 @@ mod cors_tests {
 
         let s = super::parse_scope_from_path("/a/x/t/y/v1/receipt/b3:abc");
         assert_eq!(s, Some(scope::Scope::new("x", "y")));
+
+        // Embedded gates see the mount prefix through OriginalUri
+        let s = super::parse_scope_from_path("/ubl/a/myapp/t/acme/v1/execute");
+        assert_eq!(s, Some(scope::Scope::new("myapp", "acme")));
     }
 }
 
@@ -922,8 +2089,175 @@ pub mod test {
     use std::net::SocketAddr;
     use tokio::net::TcpListener;
 
+    /// In-process gate harness for integration testers — inside this
+    /// repo and out. Configures an [`AppState`](super::AppState) through
+    /// named knobs (tokens, rate limits, quotas, a pinned clock) instead
+    /// of struct-update syntax, and hands back either a bare `Router`
+    /// for tower-level driving or a spawned [`TestGate`] whose `state`
+    /// field exposes the live stores (receipt chain, sagas, quotas) for
+    /// direct inspection.
+    ///
+    /// ```no_run
+    /// # async fn demo() {
+    /// let gate = ubl_gate::test::TestGateBuilder::new()
+    ///     .with_auth()
+    ///     .token("tok-1", "ci-client", "acme", &["ingest", "execute"])
+    ///     .fixed_clock(1_700_000_000)
+    ///     .spawn()
+    ///     .await;
+    /// // drive gate.base_url() over HTTP, then inspect gate.state
+    /// # }
+    /// ```
+    ///
+    /// Blob storage still rides the process-wide file ledger (`store/`
+    /// under the working directory); give each test run unique payloads
+    /// rather than expecting an empty ledger.
+    pub struct TestGateBuilder {
+        state: super::AppState,
+    }
+
+    impl TestGateBuilder {
+        /// A gate with auth disabled and every other default.
+        pub fn new() -> Self {
+            Self {
+                state: super::AppState {
+                    auth_disabled: true,
+                    ..super::AppState::default()
+                },
+            }
+        }
+
+        /// Enable bearer auth (disabled by default in the harness).
+        pub fn with_auth(mut self) -> Self {
+            self.state.auth_disabled = false;
+            self
+        }
+
+        /// Register a bearer token for `tenant` with the given scopes.
+        /// An empty scope list means unrestricted.
+        pub fn token(self, token: &str, client_id: &str, tenant: &str, scopes: &[&str]) -> Self {
+            self.state.token_store.register(
+                token,
+                super::ClientInfo {
+                    client_id: client_id.into(),
+                    tenant_id: tenant.into(),
+                    allowed_kids: vec![],
+                    scopes: scopes.iter().map(|s| s.to_string()).collect(),
+                    public_key_b64: None,
+                    did: None,
+                },
+            );
+            self
+        }
+
+        /// Replace the default rate limiter with `rpm`/`burst`.
+        pub fn rate_limit(mut self, rpm: u32, burst: u32) -> Self {
+            self.state.rate_limiter = super::RateLimiter::new(rpm, burst);
+            self
+        }
+
+        /// Set a tenant's quota policy.
+        pub fn quota(self, tenant: &str, policy: super::quota::QuotaPolicy) -> Self {
+            self.state.quota.set_policy(tenant, policy);
+            self
+        }
+
+        /// Pin the `issued_at` clock to a fixed unix timestamp. The
+        /// override is process-wide (see `ubl_runtime::clock`); clear it
+        /// with [`clear_clock`](Self::clear_clock) when a later test
+        /// needs real time again.
+        pub fn fixed_clock(self, secs: u64) -> Self {
+            ubl_runtime::clock::set_override(Some(ubl_runtime::clock::ClockSource::Fixed(secs)));
+            self
+        }
+
+        /// Restore env-driven clock behavior.
+        pub fn clear_clock(self) -> Self {
+            ubl_runtime::clock::set_override(None);
+            self
+        }
+
+        /// Detach receipt bodies above `bytes` into the ledger.
+        pub fn detach_body_bytes(mut self, bytes: usize) -> Self {
+            self.state.detach_body_bytes = bytes;
+            self
+        }
+
+        /// Escape hatch for fields without a named knob.
+        pub fn state_mut(&mut self) -> &mut super::AppState {
+            &mut self.state
+        }
+
+        /// The configured router plus a state handle, for tower-level
+        /// tests that never bind a socket.
+        pub fn router(self) -> (axum::Router, super::AppState) {
+            let state = self.state;
+            (super::app_with_state(state.clone()), state)
+        }
+
+        /// Bind a random local port and serve.
+        pub async fn spawn(self) -> TestGate {
+            let state = self.state;
+            let app = super::app_with_state(state.clone());
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let handle = tokio::spawn(async move {
+                axum::serve(listener, app).await.unwrap();
+            });
+            TestGate {
+                addr,
+                state,
+                handle,
+            }
+        }
+    }
+
+    impl Default for TestGateBuilder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// A running gate: address, live state handles, and teardown. The
+    /// server task is aborted on drop, so a test that returns early
+    /// doesn't leak its listener.
+    pub struct TestGate {
+        pub addr: SocketAddr,
+        /// The state the router runs on — every store is the live one.
+        pub state: super::AppState,
+        handle: tokio::task::JoinHandle<()>,
+    }
+
+    impl TestGate {
+        pub fn base_url(&self) -> String {
+            format!("http://{}", self.addr)
+        }
+
+        /// Look up a receipt exactly as handlers do: tenant-scoped key
+        /// first, then the legacy unscoped key.
+        pub fn receipt(&self, scope: &super::scope::Scope, cid: &str) -> Option<serde_json::Value> {
+            let chain = self.state.receipt_chain.read().unwrap();
+            chain
+                .get(&scope.scoped_cid(cid))
+                .or_else(|| chain.get(cid))
+                .cloned()
+        }
+
+        /// The current chain tip, if any execution has run.
+        pub fn tip(&self) -> Option<String> {
+            self.state.last_tip.read().unwrap().clone()
+        }
+    }
+
+    impl Drop for TestGate {
+        fn drop(&mut self) {
+            self.handle.abort();
+        }
+    }
+
     /// Spawn the server on a random port with auth disabled (for tests).
     /// Returns the address and a JoinHandle that keeps the server alive until dropped.
+    /// Thin wrapper kept for existing suites; prefer [`TestGateBuilder`].
     pub async fn spawn() -> (SocketAddr, tokio::task::JoinHandle<()>) {
         let state = super::AppState {
             auth_disabled: true, // tests run without auth by default
@@ -938,6 +2272,20 @@ pub mod test {
         (addr, handle)
     }
 
+    /// Spawn the server with a fully custom AppState (for tests that need
+    /// non-default thresholds or stores).
+    pub async fn spawn_with_state(
+        state: super::AppState,
+    ) -> (SocketAddr, tokio::task::JoinHandle<()>) {
+        let app = super::app_with_state(state);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (addr, handle)
+    }
+
     /// Spawn the server with auth ENABLED and the given token store.
     /// For testing auth flows.
     pub async fn spawn_with_auth(
@@ -957,3 +2305,86 @@ pub mod test {
         (addr, handle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rb_executor_runs_jobs_and_returns_results() {
+        let exec = RbExecutor::new(2, Duration::from_millis(200));
+        let out = exec.run(|| 21 * 2).await;
+        assert_eq!(out, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn rb_executor_sheds_when_saturated() {
+        let exec = RbExecutor::new(1, Duration::from_millis(50));
+        // Occupy the single slot with a slow blocking job
+        let busy = exec.clone();
+        let hold = tokio::spawn(async move {
+            busy.run(|| std::thread::sleep(Duration::from_millis(400)))
+                .await
+        });
+        // Give the holder time to take the permit
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Second job cannot get a slot within the queue timeout
+        let shed = exec.run(|| ()).await;
+        assert_eq!(shed, Err(()));
+        assert_eq!(exec.retry_after_secs(), 1, "sub-second timeout rounds up");
+        hold.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn required_scope_maps_routes() {
+        assert_eq!(required_scope("POST", "/v1/ingest"), Some("ingest"));
+        assert_eq!(required_scope("POST", "/v1/execute/rb"), Some("execute"));
+        assert_eq!(required_scope("POST", "/v1/saga"), Some("execute"));
+        assert_eq!(required_scope("GET", "/v1/chips/standard"), None);
+        assert_eq!(required_scope("GET", "/v1/chips/b3:abc"), Some("receipts:read"));
+        assert_eq!(required_scope("GET", "/v1/stats/denies"), Some("stats:read"));
+        assert_eq!(required_scope("GET", "/v1/receipt/b3:abc"), Some("receipts:read"));
+        assert_eq!(required_scope("POST", "/v1/admin/cors"), Some("admin"));
+        assert_eq!(required_scope("POST", "/v1/redact/b3:abc"), Some("admin"));
+        // Scoped paths normalize to their /v1 tail
+        assert_eq!(
+            required_scope("POST", "/a/shop/t/acme/v1/execute"),
+            Some("execute")
+        );
+        assert_eq!(required_scope("GET", "/cid/bafy123"), Some("receipts:read"));
+        assert_eq!(
+            required_scope("POST", "/v1/receipts/import"),
+            Some("ingest")
+        );
+        // Public and preflight traffic needs no scope
+        assert_eq!(required_scope("GET", "/healthz"), None);
+        assert_eq!(required_scope("OPTIONS", "/v1/execute"), None);
+    }
+
+    #[test]
+    fn scope_allowed_semantics() {
+        let read_only = ClientInfo {
+            client_id: "c".into(),
+            tenant_id: "t".into(),
+            allowed_kids: vec![],
+            scopes: vec!["receipts:read".into()],
+            public_key_b64: None,
+            did: None,
+        };
+        assert!(read_only.scope_allowed("receipts:read"));
+        assert!(!read_only.scope_allowed("execute"));
+
+        let admin = ClientInfo {
+            scopes: vec!["admin".into()],
+            ..read_only.clone()
+        };
+        assert!(admin.scope_allowed("execute"));
+
+        let legacy = ClientInfo {
+            scopes: vec![],
+            ..read_only
+        };
+        assert!(legacy.scope_allowed("admin"), "empty scopes = unrestricted");
+    }
+}
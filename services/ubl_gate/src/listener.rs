@@ -0,0 +1,194 @@
+//! Listener selection: TCP, Unix domain socket, or systemd socket
+//! activation.
+//!
+//! `UBL_LISTEN` picks the transport: a TCP address (`0.0.0.0:3000`, the
+//! default), or `unix:/run/ubl/gate.sock` for sidecar deployments that
+//! want filesystem permissions instead of network ACLs (`UBL_LISTEN_MODE`
+//! sets the socket mode, octal, default 660). When systemd hands the
+//! process a socket via `LISTEN_FDS`, that socket is adopted instead and
+//! `UBL_LISTEN` is ignored — the unit file owns the address.
+
+use anyhow::{Context, Result};
+use axum::Router;
+use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+
+/// First file descriptor passed by systemd socket activation.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+pub enum Listener {
+    Tcp(tokio::net::TcpListener),
+    Unix(tokio::net::UnixListener),
+}
+
+impl Listener {
+    /// Resolve the listener: an activated systemd socket when present,
+    /// otherwise whatever `UBL_LISTEN` names.
+    pub async fn from_env() -> Result<Self> {
+        if let Some(adopted) = from_systemd()? {
+            return Ok(adopted);
+        }
+        let spec = std::env::var("UBL_LISTEN").unwrap_or_else(|_| "0.0.0.0:3000".into());
+        Self::from_spec(&spec).await
+    }
+
+    /// Bind a listener for an explicit spec (`host:port` or `unix:<path>`).
+    pub async fn from_spec(spec: &str) -> Result<Self> {
+        if let Some(path) = spec.strip_prefix("unix:") {
+            // A stale socket from an unclean shutdown blocks the bind
+            let _ = std::fs::remove_file(path);
+            let listener = tokio::net::UnixListener::bind(path)
+                .with_context(|| format!("binding unix socket {path}"))?;
+            let mode = socket_mode()?;
+            std::fs::set_permissions(path, std::os::unix::fs::PermissionsExt::from_mode(mode))
+                .with_context(|| format!("setting mode {mode:o} on {path}"))?;
+            return Ok(Listener::Unix(listener));
+        }
+        let listener = tokio::net::TcpListener::bind(spec)
+            .await
+            .with_context(|| format!("binding {spec}"))?;
+        Ok(Listener::Tcp(listener))
+    }
+
+    /// Human-readable bound address for the startup log line.
+    pub fn describe(&self) -> String {
+        match self {
+            Listener::Tcp(l) => l
+                .local_addr()
+                .map(|a| a.to_string())
+                .unwrap_or_else(|_| "tcp:?".into()),
+            Listener::Unix(l) => match l.local_addr().ok().and_then(|a| {
+                a.as_pathname().map(|p| p.display().to_string())
+            }) {
+                Some(path) => format!("unix:{path}"),
+                None => "unix:?".into(),
+            },
+        }
+    }
+
+    /// Serve the app over this listener (plain HTTP; TLS stays TCP-only).
+    pub async fn serve(self, app: Router) -> Result<()> {
+        match self {
+            Listener::Tcp(l) => axum::serve(l, app).await?,
+            // axum::serve is TCP-only on this axum line, so unix sockets
+            // get the same manual hyper loop the TLS listener uses
+            Listener::Unix(l) => loop {
+                let (stream, _peer) = l.accept().await?;
+                let app = app.clone();
+                tokio::spawn(async move {
+                    let service = hyper_util::service::TowerToHyperService::new(app);
+                    let io = hyper_util::rt::TokioIo::new(stream);
+                    let _ = hyper_util::server::conn::auto::Builder::new(
+                        hyper_util::rt::TokioExecutor::new(),
+                    )
+                    .serve_connection_with_upgrades(io, service)
+                    .await;
+                });
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Socket mode for UDS listeners: `UBL_LISTEN_MODE` (octal), default 660.
+fn socket_mode() -> Result<u32> {
+    match std::env::var("UBL_LISTEN_MODE") {
+        Ok(s) => u32::from_str_radix(&s, 8)
+            .with_context(|| format!("UBL_LISTEN_MODE '{s}' is not octal")),
+        Err(_) => Ok(0o660),
+    }
+}
+
+/// Adopt the first socket systemd passed via `LISTEN_FDS`, if any.
+/// `LISTEN_PID` must name this process — an inherited env from a parent
+/// is not an invitation to steal its descriptors.
+fn from_systemd() -> Result<Option<Listener>> {
+    let Ok(fds) = std::env::var("LISTEN_FDS") else {
+        return Ok(None);
+    };
+    let count: i32 = fds.parse().context("LISTEN_FDS must be a number")?;
+    if count < 1 {
+        return Ok(None);
+    }
+    if let Ok(pid) = std::env::var("LISTEN_PID") {
+        if pid.parse::<u32>().ok() != Some(std::process::id()) {
+            return Ok(None);
+        }
+    }
+    // Safety: systemd owns fds from SD_LISTEN_FDS_START; we adopt exactly
+    // the first and nothing else in the process touches it
+    let owned = unsafe { OwnedFd::from_raw_fd(SD_LISTEN_FDS_START) };
+    let socket = socket2::Socket::from(owned);
+    socket
+        .set_nonblocking(true)
+        .context("activated socket nonblocking")?;
+    let addr = socket.local_addr().context("activated socket address")?;
+    if addr.is_unix() {
+        let std_listener = std::os::unix::net::UnixListener::from(OwnedFd::from(socket));
+        Ok(Some(Listener::Unix(tokio::net::UnixListener::from_std(
+            std_listener,
+        )?)))
+    } else {
+        let std_listener: std::net::TcpListener = socket.into();
+        Ok(Some(Listener::Tcp(tokio::net::TcpListener::from_std(
+            std_listener,
+        )?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn tcp_spec_binds_and_describes() {
+        let listener = Listener::from_spec("127.0.0.1:0").await.unwrap();
+        assert!(matches!(listener, Listener::Tcp(_)));
+        assert!(listener.describe().starts_with("127.0.0.1:"));
+    }
+
+    #[tokio::test]
+    async fn uds_client_gets_health_over_the_socket() {
+        let dir = std::env::temp_dir().join(format!("ubl-uds-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gate.sock");
+        let spec = format!("unix:{}", path.display());
+
+        let listener = Listener::from_spec(&spec).await.unwrap();
+        assert_eq!(listener.describe(), spec);
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o660, "socket keeps filesystem permissions");
+
+        let app = crate::app_with_state(crate::AppState::default());
+        tokio::spawn(async move {
+            listener.serve(app).await.unwrap();
+        });
+
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream
+            .write_all(b"GET /healthz HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut resp = Vec::new();
+        stream.read_to_end(&mut resp).await.unwrap();
+        let resp = String::from_utf8_lossy(&resp);
+        assert!(resp.starts_with("HTTP/1.1 200"), "got: {resp}");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn rebinding_replaces_a_stale_socket() {
+        let dir = std::env::temp_dir().join(format!("ubl-uds-stale-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gate.sock");
+        let spec = format!("unix:{}", path.display());
+        drop(Listener::from_spec(&spec).await.unwrap());
+        // The socket file is still on disk; a fresh bind must not fail
+        assert!(path.exists());
+        let again = Listener::from_spec(&spec).await.unwrap();
+        assert!(matches!(again, Listener::Unix(_)));
+        let _ = std::fs::remove_file(&path);
+    }
+}
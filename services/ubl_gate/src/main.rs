@@ -1,4 +1,3 @@
-use tokio::net::TcpListener;
 use tracing::{info, Level};
 
 #[tokio::main]
@@ -8,9 +7,38 @@ async fn main() -> anyhow::Result<()> {
         .with_target(false)
         .compact()
         .init();
-    let app = ubl_gate::app();
-    let listener = TcpListener::bind("0.0.0.0:3000").await?;
-    info!("listening on {}", listener.local_addr()?);
-    axum::serve(listener, app).await?;
+    // Verify journaled blobs before serving; corrupt ones are quarantined
+    let recovery = ubl_ledger::recover().await?;
+    if recovery.quarantined > 0 || recovery.missing > 0 {
+        info!(
+            "ledger recovery: {} scanned, {} quarantined, {} missing",
+            recovery.scanned, recovery.quarantined, recovery.missing
+        );
+    }
+    let state = ubl_gate::AppState::default();
+    // Periodic chain-integrity verification (results at /v1/integrity)
+    ubl_gate::integrity::spawn_verifier(state.clone());
+    // Archival tiering: old blobs move to the archive tier per tenant policy
+    ubl_gate::retention::spawn_sweeper();
+    let app = ubl_gate::app_with_state(state);
+    let listener = ubl_gate::listener::Listener::from_env().await?;
+    info!("listening on {}", listener.describe());
+    match (ubl_gate::tls::TlsOptions::from_env(), listener) {
+        (Some(opts), ubl_gate::listener::Listener::Tcp(tcp)) => {
+            info!(
+                "TLS enabled{}",
+                if opts.client_ca_path.is_some() {
+                    " (mTLS client verification)"
+                } else {
+                    ""
+                }
+            );
+            ubl_gate::tls::serve(tcp, app, opts).await?;
+        }
+        (Some(_), ubl_gate::listener::Listener::Unix(_)) => {
+            anyhow::bail!("TLS requires a TCP listener; unset UBL_TLS_* or use a tcp UBL_LISTEN")
+        }
+        (None, listener) => listener.serve(app).await?,
+    }
     Ok(())
 }
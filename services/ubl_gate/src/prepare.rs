@@ -0,0 +1,92 @@
+//! Two-phase execute: prepare tokens for preview-then-commit flows.
+//!
+//! `POST /v1/execute?phase=prepare` dry-runs the pipeline and parks the
+//! request under a single-use token; `POST /v1/execute/commit` redeems
+//! the token and performs the real execution. The runtime is
+//! deterministic, so the committed body CIDs match the preview (only the
+//! chain position can differ if other executions advance the tip in
+//! between). Stale prepares expire and are swept lazily.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+/// Default prepare lifetime; override with `UBL_PREPARE_TTL_SECS`.
+pub const DEFAULT_TTL_SECS: u64 = 300;
+
+/// How long a prepare stays redeemable.
+pub fn ttl_secs() -> u64 {
+    std::env::var("UBL_PREPARE_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TTL_SECS)
+}
+
+pub struct PrepareEntry {
+    /// The parked `ExecRequestFull`, replayed verbatim on commit.
+    pub req: serde_json::Value,
+    /// Tenant that prepared — commits from other tenants don't see it.
+    pub tenant: String,
+    pub created: Instant,
+}
+
+#[derive(Clone, Default)]
+pub struct PrepareStore {
+    entries: Arc<RwLock<HashMap<String, PrepareEntry>>>,
+}
+
+impl PrepareStore {
+    /// Park a request and mint its single-use token. Stale entries are
+    /// swept on the way in so abandoned prepares don't accumulate.
+    pub fn insert(&self, entry: PrepareEntry) -> String {
+        use rand::RngCore;
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = format!("prep_{}", hex::encode(bytes));
+        let ttl = ttl_secs();
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|_, e| e.created.elapsed().as_secs() < ttl);
+        entries.insert(token.clone(), entry);
+        token
+    }
+
+    /// Redeem a token, consuming it. Expired prepares redeem as `None`.
+    pub fn take(&self, token: &str) -> Option<PrepareEntry> {
+        let entry = self.entries.write().unwrap().remove(token)?;
+        if entry.created.elapsed().as_secs() >= ttl_secs() {
+            return None;
+        }
+        Some(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> PrepareEntry {
+        PrepareEntry {
+            req: serde_json::json!({"vars": {}}),
+            tenant: "acme".into(),
+            created: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn tokens_are_single_use() {
+        let store = PrepareStore::default();
+        let token = store.insert(entry());
+        assert!(store.take(&token).is_some());
+        assert!(store.take(&token).is_none());
+    }
+
+    #[test]
+    fn stale_prepares_do_not_redeem() {
+        let store = PrepareStore::default();
+        let token = store.insert(PrepareEntry {
+            created: Instant::now() - std::time::Duration::from_secs(ttl_secs() + 1),
+            ..entry()
+        });
+        assert!(store.take(&token).is_none());
+    }
+}
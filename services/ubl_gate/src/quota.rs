@@ -0,0 +1,251 @@
+//! Per-tenant execution quotas and budgets.
+//!
+//! Hard budget controls over a rolling window (daily or monthly):
+//! executions, RB-VM fuel and stored bytes. Usage is tracked per tenant
+//! in memory (like the other gate stores), enforced with structured 429
+//! denials plus a signed DENY receipt on the execute path, surfaced at
+//! `GET /v1/quota/usage` and exported as Prometheus gauges.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Budget window. Usage resets when the window key rolls over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    #[default]
+    Month,
+}
+
+impl Period {
+    /// Key identifying the current window ("2026-09-01" or "2026-09").
+    pub fn window_key(&self) -> String {
+        let now = chrono::Utc::now();
+        match self {
+            Period::Day => now.format("%Y-%m-%d").to_string(),
+            Period::Month => now.format("%Y-%m").to_string(),
+        }
+    }
+
+    /// Seconds until this window resets — goes out as Retry-After.
+    pub fn secs_to_reset(&self) -> u64 {
+        use chrono::{Datelike, TimeZone, Timelike};
+        let now = chrono::Utc::now();
+        match self {
+            Period::Day => 86_400 - u64::from(now.num_seconds_from_midnight()),
+            Period::Month => {
+                let (y, m) = if now.month() == 12 {
+                    (now.year() + 1, 1)
+                } else {
+                    (now.year(), now.month() + 1)
+                };
+                let next = chrono::Utc
+                    .with_ymd_and_hms(y, m, 1, 0, 0, 0)
+                    .single()
+                    .unwrap_or(now);
+                (next - now).num_seconds().max(0) as u64
+            }
+        }
+    }
+}
+
+/// Budget limits for one tenant. Unset = unmetered.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuotaPolicy {
+    #[serde(default)]
+    pub period: Period,
+    #[serde(default)]
+    pub max_executions: Option<u64>,
+    #[serde(default)]
+    pub max_fuel: Option<u64>,
+    #[serde(default)]
+    pub max_stored_bytes: Option<u64>,
+}
+
+/// Metered resources, named as they appear in denials and gauges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Executions,
+    Fuel,
+    StoredBytes,
+}
+
+impl Resource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resource::Executions => "executions",
+            Resource::Fuel => "fuel",
+            Resource::StoredBytes => "stored_bytes",
+        }
+    }
+}
+
+/// Usage counters inside the current window.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Usage {
+    pub window: String,
+    pub executions: u64,
+    pub fuel: u64,
+    pub stored_bytes: u64,
+}
+
+/// A budget hit: which resource, where the budget stands, and when the
+/// window resets.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaDenial {
+    pub resource: &'static str,
+    pub limit: u64,
+    pub used: u64,
+    pub retry_after_secs: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct QuotaStore {
+    policies: Arc<RwLock<HashMap<String, QuotaPolicy>>>,
+    usage: Arc<RwLock<HashMap<String, Usage>>>,
+}
+
+impl QuotaStore {
+    pub fn set_policy(&self, tenant: &str, policy: QuotaPolicy) {
+        self.policies
+            .write()
+            .unwrap()
+            .insert(tenant.to_string(), policy);
+    }
+
+    pub fn policy(&self, tenant: &str) -> Option<QuotaPolicy> {
+        self.policies.read().unwrap().get(tenant).cloned()
+    }
+
+    /// Current-window usage for a tenant (zeroed if the window rolled).
+    pub fn usage(&self, tenant: &str) -> Usage {
+        let window = self
+            .policy(tenant)
+            .unwrap_or_default()
+            .period
+            .window_key();
+        match self.usage.read().unwrap().get(tenant) {
+            Some(u) if u.window == window => u.clone(),
+            _ => Usage {
+                window,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Is any of the named budgets already exhausted? Unmetered tenants
+    /// always pass.
+    pub fn check(&self, tenant: &str, resources: &[Resource]) -> Result<(), QuotaDenial> {
+        let Some(policy) = self.policy(tenant) else {
+            return Ok(());
+        };
+        let usage = self.usage(tenant);
+        for r in resources {
+            let (limit, used) = match r {
+                Resource::Executions => (policy.max_executions, usage.executions),
+                Resource::Fuel => (policy.max_fuel, usage.fuel),
+                Resource::StoredBytes => (policy.max_stored_bytes, usage.stored_bytes),
+            };
+            if let Some(limit) = limit {
+                if used >= limit {
+                    return Err(QuotaDenial {
+                        resource: r.as_str(),
+                        limit,
+                        used,
+                        retry_after_secs: policy.period.secs_to_reset(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add to the tenant's counters (rolling the window if needed) and
+    /// refresh the usage gauges.
+    pub fn charge(&self, tenant: &str, executions: u64, fuel: u64, stored_bytes: u64) {
+        let window = self
+            .policy(tenant)
+            .unwrap_or_default()
+            .period
+            .window_key();
+        let mut usage = self.usage.write().unwrap();
+        let entry = usage.entry(tenant.to_string()).or_default();
+        if entry.window != window {
+            *entry = Usage {
+                window,
+                ..Default::default()
+            };
+        }
+        entry.executions += executions;
+        entry.fuel += fuel;
+        entry.stored_bytes += stored_bytes;
+        for (resource, value) in [
+            ("executions", entry.executions),
+            ("fuel", entry.fuel),
+            ("stored_bytes", entry.stored_bytes),
+        ] {
+            metrics::gauge!(
+                "ubl_quota_usage",
+                "tenant" => tenant.to_string(),
+                "resource" => resource,
+            )
+            .set(value as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capped(max_executions: u64) -> QuotaPolicy {
+        QuotaPolicy {
+            max_executions: Some(max_executions),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unmetered_tenants_always_pass() {
+        let store = QuotaStore::default();
+        store.charge("free", 1_000, 0, 0);
+        assert!(store.check("free", &[Resource::Executions]).is_ok());
+    }
+
+    #[test]
+    fn budget_blocks_at_the_limit() {
+        let store = QuotaStore::default();
+        store.set_policy("acme", capped(2));
+        assert!(store.check("acme", &[Resource::Executions]).is_ok());
+        store.charge("acme", 2, 0, 0);
+        let denial = store.check("acme", &[Resource::Executions]).unwrap_err();
+        assert_eq!(denial.resource, "executions");
+        assert_eq!(denial.used, 2);
+        assert_eq!(denial.limit, 2);
+        assert!(denial.retry_after_secs > 0);
+    }
+
+    #[test]
+    fn resources_are_checked_independently() {
+        let store = QuotaStore::default();
+        store.set_policy(
+            "acme2",
+            QuotaPolicy {
+                max_fuel: Some(10),
+                ..Default::default()
+            },
+        );
+        store.charge("acme2", 5, 10, 0);
+        // Executions unmetered, fuel exhausted
+        assert!(store.check("acme2", &[Resource::Executions]).is_ok());
+        assert!(store.check("acme2", &[Resource::Fuel]).is_err());
+    }
+
+    #[test]
+    fn window_keys_differ_per_period() {
+        assert!(Period::Day.window_key().len() > Period::Month.window_key().len());
+        assert!(Period::Day.secs_to_reset() <= 86_400);
+    }
+}
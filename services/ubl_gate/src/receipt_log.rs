@@ -0,0 +1,120 @@
+//! Machine-parsable log of every committed receipt.
+//!
+//! One structured tracing event (target `receipt_commit`) per receipt,
+//! plus an optional NDJSON file sink (`UBL_RECEIPT_LOG_PATH`) that can be
+//! shipped to a SIEM. This is operational telemetry, deliberately
+//! separate from the in-band `observability.logline` that travels inside
+//! receipts: the event schema is versioned and never affects CIDs.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
+
+/// Stable schema tag; bump only with additive, versioned changes.
+pub const SCHEMA: &str = "ubl/receipt-commit/v1";
+
+#[derive(Serialize)]
+struct ReceiptCommitEvent<'a> {
+    schema: &'static str,
+    ts: String,
+    tenant: &'a str,
+    cid: &'a str,
+    t: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decision: Option<&'a str>,
+    parents: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latency_ms: Option<u64>,
+}
+
+/// Render the NDJSON line for a committed receipt.
+fn render_event(tenant: &str, cid: &str, receipt: &Value, latency_ms: Option<u64>) -> String {
+    let event = ReceiptCommitEvent {
+        schema: SCHEMA,
+        ts: chrono::Utc::now().to_rfc3339(),
+        tenant,
+        cid,
+        t: receipt.get("t").and_then(|t| t.as_str()).unwrap_or("?"),
+        decision: receipt.pointer("/body/decision").and_then(|d| d.as_str()),
+        parents: receipt
+            .get("parents")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.as_str())
+            .collect(),
+        latency_ms,
+    };
+    serde_json::to_string(&event).unwrap_or_default()
+}
+
+/// File sink, opened once from `UBL_RECEIPT_LOG_PATH` (unset = disabled).
+fn sink() -> &'static Option<Mutex<std::fs::File>> {
+    static SINK: OnceLock<Option<Mutex<std::fs::File>>> = OnceLock::new();
+    SINK.get_or_init(|| {
+        std::env::var("UBL_RECEIPT_LOG_PATH")
+            .ok()
+            .and_then(|path| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .ok()
+            })
+            .map(Mutex::new)
+    })
+}
+
+/// Emit the structured event for one committed receipt: a tracing event
+/// always, an NDJSON line when the file sink is configured.
+pub fn log_commit(tenant: &str, cid: &str, receipt: &Value, latency_ms: Option<u64>) {
+    let line = render_event(tenant, cid, receipt, latency_ms);
+    tracing::info!(
+        target: "receipt_commit",
+        tenant,
+        cid,
+        t = receipt.get("t").and_then(|t| t.as_str()).unwrap_or("?"),
+        latency_ms,
+        "{line}"
+    );
+    if let Some(file) = sink() {
+        let mut file = file.lock().unwrap();
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn event_schema_is_stable() {
+        let receipt = json!({
+            "t": "ubl/wf",
+            "parents": ["b3:aa", "b3:bb"],
+            "body": {"decision": "ALLOW"},
+        });
+        let line = render_event("acme", "b3:cc", &receipt, Some(12));
+        let v: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(v["schema"], SCHEMA);
+        assert_eq!(v["tenant"], "acme");
+        assert_eq!(v["cid"], "b3:cc");
+        assert_eq!(v["t"], "ubl/wf");
+        assert_eq!(v["decision"], "ALLOW");
+        assert_eq!(v["parents"], json!(["b3:aa", "b3:bb"]));
+        assert_eq!(v["latency_ms"], 12);
+        assert!(v.get("ts").is_some());
+    }
+
+    #[test]
+    fn optional_fields_are_omitted_not_nulled() {
+        let receipt = json!({"t": "ubl/attestation", "parents": []});
+        let line = render_event("default", "b3:dd", &receipt, None);
+        let v: Value = serde_json::from_str(&line).unwrap();
+        assert!(v.get("decision").is_none());
+        assert!(v.get("latency_ms").is_none());
+        assert_eq!(v["parents"], json!([]));
+    }
+}
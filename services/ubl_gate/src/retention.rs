@@ -0,0 +1,33 @@
+//! Background archival tiering.
+//!
+//! Periodically sweeps every tenant's blobs against its retention policy
+//! (`ubl_ledger::tenant_retention`), moving blobs past the archive
+//! threshold to the archive tier. Reads stay transparent — the ledger
+//! getters fall through to the archive path — so clients never notice a
+//! migration. Policies are set per tenant via `POST /v1/admin/retention`.
+
+/// Spawn the periodic sweeper. Interval from `UBL_RETENTION_INTERVAL_SECS`
+/// (default 3600); set it low in staging to exercise migrations quickly.
+pub fn spawn_sweeper() {
+    let interval = std::env::var("UBL_RETENTION_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(3600);
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+            for (tenant, report) in ubl_ledger::archive_sweep_all().await {
+                if report.archived > 0 {
+                    tracing::info!(
+                        tenant,
+                        scanned = report.scanned,
+                        archived = report.archived,
+                        "retention sweep moved blobs to the archive tier"
+                    );
+                }
+                metrics::counter!("ubl_retention_archived_total", "tenant" => tenant)
+                    .increment(report.archived as u64);
+            }
+        }
+    });
+}
@@ -0,0 +1,116 @@
+//! Per-receipt share grants: capability URLs for auditors without tokens.
+//!
+//! A grant is minted by an authenticated caller for one receipt CID and
+//! redeemed unauthenticated via `GET /v1/share/:token`. Tokens are
+//! 256-bit random capabilities; possession is authorization. Grants
+//! expire (lazily swept on redeem) and can be revoked early.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Default grant lifetime when the minting request names none.
+pub const DEFAULT_TTL_SECS: u64 = 86_400; // 24h
+
+/// Longest lifetime a grant may be minted with.
+pub const MAX_TTL_SECS: u64 = 30 * 86_400;
+
+#[derive(Clone)]
+pub struct ShareGrant {
+    /// Storage key of the shared receipt ("app:tenant:cid").
+    pub scoped_cid: String,
+    pub cid: String,
+    pub tenant: String,
+    pub expires_at: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct ShareStore {
+    grants: Arc<RwLock<HashMap<String, ShareGrant>>>,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl ShareStore {
+    /// Mint a capability token for a grant. The token is the only handle:
+    /// it is returned once and stored hashed nowhere else.
+    pub fn mint(&self, grant: ShareGrant) -> String {
+        use rand::RngCore;
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = format!("shr_{}", hex::encode(bytes));
+        self.grants.write().unwrap().insert(token.clone(), grant);
+        token
+    }
+
+    /// Redeem a token. Expired grants are dropped on the way out.
+    pub fn redeem(&self, token: &str) -> Option<ShareGrant> {
+        let mut grants = self.grants.write().unwrap();
+        match grants.get(token) {
+            Some(g) if g.expires_at > now_secs() => Some(g.clone()),
+            Some(_) => {
+                grants.remove(token);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Revoke a token, but only when it belongs to the given CID so a
+    /// caller cannot revoke grants on receipts it never saw.
+    pub fn revoke(&self, cid: &str, token: &str) -> bool {
+        let mut grants = self.grants.write().unwrap();
+        match grants.get(token) {
+            Some(g) if g.cid == cid => {
+                grants.remove(token);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(cid: &str, expires_at: u64) -> ShareGrant {
+        ShareGrant {
+            scoped_cid: format!("default:default:{cid}"),
+            cid: cid.into(),
+            tenant: "default".into(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn mint_and_redeem() {
+        let store = ShareStore::default();
+        let token = store.mint(grant("b3:aa", now_secs() + 60));
+        assert!(token.starts_with("shr_"));
+        assert_eq!(store.redeem(&token).unwrap().cid, "b3:aa");
+        assert!(store.redeem("shr_unknown").is_none());
+    }
+
+    #[test]
+    fn expired_grants_do_not_redeem() {
+        let store = ShareStore::default();
+        let token = store.mint(grant("b3:aa", now_secs().saturating_sub(1)));
+        assert!(store.redeem(&token).is_none());
+        // and the entry is swept
+        assert!(store.grants.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn revoke_requires_matching_cid() {
+        let store = ShareStore::default();
+        let token = store.mint(grant("b3:aa", now_secs() + 60));
+        assert!(!store.revoke("b3:bb", &token), "wrong cid must not revoke");
+        assert!(store.revoke("b3:aa", &token));
+        assert!(store.redeem(&token).is_none());
+    }
+}
@@ -0,0 +1,64 @@
+//! Compatibility shim for the legacy `ubl_tdln_svc` execute contract.
+//!
+//! The tdln service exposed `/v1/execute` with a flat response — a single
+//! WF receipt plus `status`/`tip`/`decision` at the top level — and it
+//! panicked (`expect`) on execution failure, so its clients never saw a
+//! structured error. The gate serves the same shape behind
+//! `POST /v1/execute?format=tdln` so those clients can migrate gate-side
+//! without code changes; failures become a proper `status: "error"` JSON
+//! body instead of a dropped connection. New integrations should use the
+//! default (unqualified) response, which carries the full receipt set.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::{json, Value};
+
+/// Marker accepted by `?format=` on `/v1/execute`.
+pub const FORMAT: &str = "tdln";
+
+/// Successful execution in the tdln shape: flat fields, WF receipt only.
+pub fn execute_ok(tip_cid: &str, decision: &Value, ghost: bool, wf: &Value) -> Response {
+    (
+        StatusCode::OK,
+        Json(json!({
+            "status": "executed",
+            "tip": tip_cid,
+            "decision": decision,
+            "ghost": ghost,
+            "receipt": wf,
+        })),
+    )
+        .into_response()
+}
+
+/// Execution failure in the tdln shape. The legacy service panicked here;
+/// we return the detail (and the signed DENY receipt CID when one was
+/// minted) so callers can distinguish replays from real failures.
+pub fn execute_err(detail: &str, replay: bool, deny_receipt_cid: Option<&str>) -> Response {
+    let status = if replay {
+        StatusCode::CONFLICT
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    let mut body = json!({
+        "status": "error",
+        "error": detail,
+    });
+    if let Some(cid) = deny_receipt_cid {
+        body["deny_receipt_cid"] = json!(cid);
+    }
+    (status, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_status_mirrors_gate_semantics() {
+        let replay = execute_err("duplicate request (replay)", true, None);
+        assert_eq!(replay.status(), StatusCode::CONFLICT);
+        let hard = execute_err("policy bundle missing", false, Some("b3:aa"));
+        assert_eq!(hard.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,218 @@
+//! Optional TLS listener with mutual-TLS client authentication.
+//!
+//! Deployments that can't use bearer tokens enable this by pointing
+//! `UBL_TLS_CERT` / `UBL_TLS_KEY` at the server keypair. Setting
+//! `UBL_TLS_CLIENT_CA` additionally requires a client certificate signed by
+//! that CA; the certificate's SAN dNSName or sha256 fingerprint (hex) is
+//! mapped to a `ClientInfo` via the JSON file in `UBL_TLS_CLIENT_MAP`, and
+//! the resulting identity is injected exactly where bearer auth would put
+//! it — downstream middleware (kid scopes, token scopes, rate limits) sees
+//! no difference. Plain listeners keep using bearer tokens.
+
+use crate::ClientInfo;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::sync::Arc;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::{self, RootCertStore};
+use tokio_rustls::TlsAcceptor;
+
+/// TLS listener configuration, resolved from the environment.
+#[derive(Clone)]
+pub struct TlsOptions {
+    pub cert_path: String,
+    pub key_path: String,
+    /// When set, client certificates are required and verified against this CA.
+    pub client_ca_path: Option<String>,
+    /// Certificate identity (SAN dNSName or sha256 fingerprint hex) → client.
+    pub client_map: Arc<HashMap<String, ClientInfo>>,
+}
+
+impl TlsOptions {
+    /// Read `UBL_TLS_CERT` / `UBL_TLS_KEY` (both required for TLS mode),
+    /// `UBL_TLS_CLIENT_CA` and `UBL_TLS_CLIENT_MAP`. Returns None when the
+    /// gate should keep serving plain HTTP.
+    pub fn from_env() -> Option<Self> {
+        let cert_path = std::env::var("UBL_TLS_CERT").ok()?;
+        let key_path = std::env::var("UBL_TLS_KEY").ok()?;
+        let client_ca_path = std::env::var("UBL_TLS_CLIENT_CA").ok();
+        let client_map = std::env::var("UBL_TLS_CLIENT_MAP")
+            .ok()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Some(Self {
+            cert_path,
+            key_path,
+            client_ca_path,
+            client_map: Arc::new(client_map),
+        })
+    }
+}
+
+/// Build the rustls server config: server keypair plus, in mTLS mode, a
+/// WebPKI verifier over the configured client CA.
+fn build_server_config(opts: &TlsOptions) -> Result<rustls::ServerConfig> {
+    let certs: Vec<CertificateDer<'static>> =
+        rustls_pemfile::certs(&mut BufReader::new(std::fs::File::open(&opts.cert_path)?))
+            .collect::<std::io::Result<_>>()
+            .context("reading server certificate chain")?;
+    let key = rustls_pemfile::private_key(&mut BufReader::new(std::fs::File::open(
+        &opts.key_path,
+    )?))?
+    .context("no private key in UBL_TLS_KEY")?;
+
+    let builder = rustls::ServerConfig::builder();
+    let config = match &opts.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in
+                rustls_pemfile::certs(&mut BufReader::new(std::fs::File::open(ca_path)?))
+            {
+                roots.add(cert.context("reading client CA certificate")?)?;
+            }
+            let verifier = rustls::server::WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .context("building client certificate verifier")?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    }
+    .with_single_cert(certs, key)
+    .context("loading server keypair")?;
+    Ok(config)
+}
+
+/// Resolve a verified client certificate to a ClientInfo.
+/// Lookup order: sha256 fingerprint (hex), then each SAN dNSName.
+fn identify_client(
+    cert: &CertificateDer<'_>,
+    map: &HashMap<String, ClientInfo>,
+) -> Option<ClientInfo> {
+    use sha2::Digest;
+    let fingerprint = hex::encode(sha2::Sha256::digest(cert.as_ref()));
+    if let Some(ci) = map.get(&fingerprint) {
+        return Some(ci.clone());
+    }
+    if let Ok((_, parsed)) = x509_parser::parse_x509_certificate(cert.as_ref()) {
+        if let Ok(Some(san)) = parsed.subject_alternative_name() {
+            for name in &san.value.general_names {
+                if let x509_parser::extensions::GeneralName::DNSName(dns) = name {
+                    if let Some(ci) = map.get(*dns) {
+                        return Some(ci.clone());
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Serve the app over TLS. In mTLS mode each connection's verified client
+/// certificate is mapped to a ClientInfo and attached to every request on
+/// that connection, so bearer auth is bypassed for it.
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    app: axum::Router,
+    opts: TlsOptions,
+) -> Result<()> {
+    let acceptor = TlsAcceptor::from(Arc::new(build_server_config(&opts)?));
+    loop {
+        let (stream, _peer) = listener.accept().await?;
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        let client_map = opts.client_map.clone();
+        tokio::spawn(async move {
+            let tls_stream = match acceptor.accept(stream).await {
+                Ok(s) => s,
+                Err(_) => return, // handshake failure (bad cert etc.)
+            };
+            let client = tls_stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(|cert| identify_client(cert, &client_map));
+            let router = match client {
+                Some(ci) => app.layer(axum::Extension(ci)),
+                None => app,
+            };
+            let service = hyper_util::service::TowerToHyperService::new(router);
+            let io = hyper_util::rt::TokioIo::new(tls_stream);
+            let _ = hyper_util::server::conn::auto::Builder::new(
+                hyper_util::rt::TokioExecutor::new(),
+            )
+            .serve_connection_with_upgrades(io, service)
+            .await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cert_for(san: &str) -> (CertificateDer<'static>, rcgen::CertifiedKey) {
+        let ck = rcgen::generate_simple_self_signed(vec![san.to_string()]).unwrap();
+        (ck.cert.der().clone(), ck)
+    }
+
+    fn client(id: &str) -> ClientInfo {
+        ClientInfo {
+            client_id: id.into(),
+            tenant_id: "default".into(),
+            allowed_kids: vec![],
+            scopes: vec![],
+            public_key_b64: None,
+            did: None,
+        }
+    }
+
+    #[test]
+    fn identify_by_san_dns_name() {
+        let (cert, _ck) = cert_for("analytics.example.com");
+        let map = HashMap::from([("analytics.example.com".to_string(), client("analytics"))]);
+        let found = identify_client(&cert, &map).expect("SAN must resolve");
+        assert_eq!(found.client_id, "analytics");
+        assert!(identify_client(&cert, &HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn identify_by_fingerprint_wins_over_san() {
+        use sha2::Digest;
+        let (cert, _ck) = cert_for("shared.example.com");
+        let fingerprint = hex::encode(sha2::Sha256::digest(cert.as_ref()));
+        let map = HashMap::from([
+            (fingerprint, client("pinned")),
+            ("shared.example.com".to_string(), client("by-san")),
+        ]);
+        assert_eq!(identify_client(&cert, &map).unwrap().client_id, "pinned");
+    }
+
+    #[test]
+    fn server_config_builds_from_pem_files() {
+        let ck = rcgen::generate_simple_self_signed(vec!["gate.local".into()]).unwrap();
+        let dir = std::env::temp_dir().join(format!("ubl-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cert_path = dir.join("cert.pem");
+        let key_path = dir.join("key.pem");
+        std::fs::write(&cert_path, ck.cert.pem()).unwrap();
+        std::fs::write(&key_path, ck.key_pair.serialize_pem()).unwrap();
+
+        let opts = TlsOptions {
+            cert_path: cert_path.to_string_lossy().into_owned(),
+            key_path: key_path.to_string_lossy().into_owned(),
+            client_ca_path: None,
+            client_map: Arc::new(HashMap::new()),
+        };
+        assert!(build_server_config(&opts).is_ok());
+
+        // mTLS mode requires a readable CA bundle
+        let mtls = TlsOptions {
+            client_ca_path: Some(cert_path.to_string_lossy().into_owned()),
+            ..opts
+        };
+        assert!(build_server_config(&mtls).is_ok());
+    }
+}
@@ -0,0 +1,148 @@
+//! Chaos tests: fault injection (ubl_runtime::faults) against a live
+//! gate, proving the "never an opaque failure, always a structured error
+//! or receipt" contract when signing, canonicalization, or ledger writes
+//! fail.
+//!
+//! The fault config is process-global, so the scenarios run sequentially
+//! inside one test instead of racing across test threads.
+
+use base64::Engine;
+use serde_json::{json, Value};
+
+async fn setup() -> (String, reqwest::Client, tokio::task::JoinHandle<()>) {
+    let (addr, handle) = ubl_gate::test::spawn().await;
+    // Legacy full execute responses: the chain assertions read receipts
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-ubl-compat", "full".parse().unwrap());
+    let http = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+    (format!("http://{addr}"), http, handle)
+}
+
+fn exec_body(pipeline: &str, nonce: u128) -> Value {
+    json!({
+        "manifest": {
+            "pipeline": pipeline,
+            "in_grammar": {
+                "inputs": {"raw_b64": ""},
+                "mappings": [{"from": "raw_b64", "codec": "base64.decode", "to": "raw.bytes"}],
+                "output_from": "raw.bytes"
+            },
+            "out_grammar": {"inputs": {"content": ""}, "mappings": [], "output_from": "content"},
+            "policy": {"allow": true}
+        },
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(nonce.to_string())}
+    })
+}
+
+fn nonce() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
+
+#[tokio::test]
+async fn faults_yield_structured_errors_and_preserve_the_chain() {
+    let (base, http, _h) = setup().await;
+    ubl_runtime::faults::clear();
+
+    // ── Signing fails: structured error carrying the runtime code ──
+    ubl_runtime::faults::configure("sign=1.0");
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&exec_body("@chaos/sign/1.0.0", nonce()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422, "sign fault is a structured 422, not a 500");
+    let err: Value = resp.json().await.unwrap();
+    assert_eq!(err["code"], "unprocessable_entity");
+    assert!(err["message"].as_str().unwrap().contains("signing"));
+    ubl_runtime::faults::clear();
+
+    // ── Canonicalization fails: same contract ──────────────────────
+    ubl_runtime::faults::configure("canon=1.0");
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&exec_body("@chaos/canon/1.0.0", nonce()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+    let err: Value = resp.json().await.unwrap();
+    assert_eq!(err["code"], "unprocessable_entity");
+    assert!(err["message"].as_str().unwrap().contains("canon"));
+    ubl_runtime::faults::clear();
+
+    // ── Ledger writes fail: execution still completes with a full,
+    // chain-intact receipt set served from the in-memory registry ───
+    ubl_runtime::faults::configure("ledger=1.0");
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&exec_body("@chaos/ledger/1.0.0", nonce()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "ledger faults must not fail the run");
+    let run: Value = resp.json().await.unwrap();
+    assert_eq!(run["decision"], "ALLOW");
+    let wa = run["receipts"]["wa"]["body_cid"].as_str().unwrap();
+    let tr = run["receipts"]["transition"]["body_cid"].as_str().unwrap();
+    assert_eq!(run["receipts"]["wf"]["parents"][0], wa, "chain intact");
+    assert_eq!(run["receipts"]["wf"]["parents"][1], tr);
+    let tip = run["tip_cid"].as_str().unwrap();
+    let fetched = http
+        .get(format!("{base}/v1/receipt/{tip}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(fetched.status(), 200, "receipt survives the ledger outage");
+
+    // Ingest depends on the ledger, so it degrades to a structured
+    // internal error rather than an opaque failure
+    let resp = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"chaos": nonce().to_string()}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 500);
+    let err: Value = resp.json().await.unwrap();
+    assert_eq!(err["code"], "internal_error");
+    assert!(err["message"].as_str().unwrap().contains("ledger"));
+    ubl_runtime::faults::clear();
+
+    // ── Faults cleared: the gate recovers without restart ──────────
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&exec_body("@chaos/recovery/1.0.0", nonce()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "clearing the faults restores service");
+
+    // ── Partial rate: 0.5 fails every second signing batch ─────────
+    ubl_runtime::faults::configure("sign=0.5");
+    let mut ok = 0;
+    let mut failed = 0;
+    for i in 0..4u32 {
+        let resp = http
+            .post(format!("{base}/v1/execute"))
+            .json(&exec_body(&format!("@chaos/rate/{i}/1.0.0"), nonce()))
+            .send()
+            .await
+            .unwrap();
+        match resp.status().as_u16() {
+            200 => ok += 1,
+            422 => failed += 1,
+            other => panic!("unexpected status {other}"),
+        }
+    }
+    ubl_runtime::faults::clear();
+    assert_eq!(ok, 2, "a 0.5 rate lets every other run through");
+    assert_eq!(failed, 2, "a 0.5 rate fails every other run");
+}
+
@@ -1,11 +1,21 @@
+use base64::Engine;
 use reqwest::Client;
 use serde_json::{json, Value};
 use std::collections::BTreeMap;
 
+/// This suite predates the slim execute response: send `x-ubl-compat` by
+/// default so receipt assertions keep reading the full legacy shape.
+/// Tests covering the minimal default build a bare `Client::new()`.
+fn compat_client() -> Client {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-ubl-compat", "full".parse().unwrap());
+    Client::builder().default_headers(headers).build().unwrap()
+}
+
 async fn setup() -> (String, Client, tokio::task::JoinHandle<()>) {
     let (addr, handle) = ubl_gate::test::spawn().await;
     let base = format!("http://{addr}");
-    let http = Client::new();
+    let http = compat_client();
     (base, http, handle)
 }
 
@@ -89,6 +99,53 @@ async fn ingest_without_certify_has_no_receipt() {
     assert_eq!(rec.status(), 404, "no receipt without certify=true");
 }
 
+#[tokio::test]
+async fn ingest_transition_proves_normalization() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    // Default: no transition proof in the response
+    let resp = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"doc": format!("prov-{nonce}")}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert!(body.get("transition").is_none());
+
+    // Opt-in: a signed ubl/transition receipt, fetchable by its CID
+    let resp = http
+        .post(format!("{base}/v1/ingest?transition=true"))
+        .json(&json!({"payload": {"doc": format!("prov2-{nonce}")}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let tr_cid = body["transition"]["cid"].as_str().unwrap();
+    assert!(tr_cid.starts_with("b3:"));
+    let preimage = body["transition"]["preimage_raw_cid"].as_str().unwrap();
+    assert!(preimage.starts_with("b3:"));
+
+    let rc = http
+        .get(format!("{base}/v1/receipt/{tr_cid}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rc.status(), 200, "transition receipt must be stored");
+    let rc: Value = rc.json().await.unwrap();
+    assert_eq!(rc["t"], "ubl/transition");
+    assert_eq!(rc["body"]["op"], "rho.normalize@ai-nrf1/v1");
+    assert_eq!(rc["body"]["preimage_raw_cid"], preimage);
+    assert!(rc["body"]["rho_cid"].as_str().unwrap().starts_with("b3:"));
+    assert!(rc["proof"]["signature"].is_string(), "receipt is signed");
+}
+
 // ── CID retrieval: error paths ───────────────────────────────────
 
 #[tokio::test]
@@ -170,6 +227,79 @@ async fn certify_rejects_invalid_cid() {
     assert_eq!(resp.status(), 400);
 }
 
+#[tokio::test]
+async fn certify_emits_a_chained_attestation_receipt() {
+    let (base, _compat, _h) = setup().await;
+    // No compat header: certification takes the unified receipt shape
+    let http = Client::new();
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    // Move the chain tip so the attestation has a parent to hang off
+    let manifest = json!({
+        "pipeline": "cert-chain",
+        "in_grammar": {
+            "inputs": {"raw_b64": ""},
+            "mappings": [{"from": "raw_b64", "codec": "base64.decode", "to": "raw.bytes"}],
+            "output_from": "raw.bytes"
+        },
+        "out_grammar": {"inputs": {"content": ""}, "mappings": [], "output_from": "content"},
+        "policy": {"allow": true}
+    });
+    let b64 = base64::engine::general_purpose::STANDARD.encode(format!("cert-{nonce}"));
+    let run: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": manifest, "vars": {"raw_b64": b64}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = run["tip_cid"].as_str().unwrap().to_owned();
+
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"certify_me": format!("doc-{nonce}")}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_owned();
+
+    let cert: Value = http
+        .post(format!("{base}/v1/certify"))
+        .json(&json!({"cid": cid}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(cert["receipt"]["t"], "ubl/attestation");
+    assert_eq!(cert["receipt"]["body"]["action"], "certify");
+    assert_eq!(cert["receipt"]["body"]["cid"], cid.as_str());
+    assert_eq!(cert["receipt"]["parents"][0], tip.as_str());
+    assert!(cert["receipt"]["proof"]["signature"].is_string());
+
+    // Resolvable under the content CID and under its own body_cid
+    for handle in [cid.as_str(), cert["receipt"]["body_cid"].as_str().unwrap()] {
+        let rc: Value = http
+            .get(format!("{base}/v1/receipt/{handle}"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(rc["t"], "ubl/attestation", "lookup by {handle}");
+    }
+}
+
 // ── /v1/execute: runtime through the gate ────────────────────────
 
 #[tokio::test]
@@ -208,6 +338,68 @@ async fn execute_happy_path() {
     );
 }
 
+#[tokio::test]
+async fn execute_response_is_minimal_unless_sections_are_included() {
+    let (base, _compat, _h) = setup().await;
+    // Bare client: no x-ubl-compat header, so the slim default applies
+    let http = Client::new();
+    let manifest = json!({
+        "pipeline": "slim",
+        "in_grammar": {
+            "inputs": {"raw_b64": ""},
+            "mappings": [{"from": "raw_b64", "codec": "base64.decode", "to": "raw.bytes"}],
+            "output_from": "raw.bytes"
+        },
+        "out_grammar": {"inputs": {"content": ""}, "mappings": [], "output_from": "content"},
+        "policy": {"allow": true}
+    });
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let b64 = base64::engine::general_purpose::STANDARD.encode(format!("slim-{nonce}"));
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": manifest, "vars": {"raw_b64": b64}, "ghost": true}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["decision"], "ALLOW");
+    assert!(body["tip_cid"].as_str().unwrap().starts_with("b3:"));
+    assert!(body["url"].as_str().unwrap().contains("/v1/receipt/"));
+    assert!(body.get("receipts").is_none(), "receipts are opt-in now");
+    assert!(body.get("dimension_stack").is_none());
+
+    // Opting into all sections brings the heavy parts back
+    let b64 = base64::engine::general_purpose::STANDARD.encode(format!("slim2-{nonce}"));
+    let resp = http
+        .post(format!("{base}/v1/execute?include=receipts,trace,artifacts"))
+        .json(&json!({"manifest": manifest, "vars": {"raw_b64": b64}, "ghost": true}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["receipts"]["wf"]["t"], "ubl/wf");
+    assert!(body["trace"]["policy_trace"].is_array());
+    assert!(body["trace"]["phase_micros"]["sign"].is_u64());
+    assert_eq!(body["artifacts"]["dimension_stack"], json!(["parse", "policy", "render"]));
+
+    // Unknown sections are named, not silently ignored
+    let resp = http
+        .post(format!("{base}/v1/execute?include=everything"))
+        .json(&json!({"manifest": manifest, "vars": {"raw_b64": "eA=="}, "ghost": true}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    let err: Value = resp.json().await.unwrap();
+    assert_eq!(err["code"], "bad_request");
+    assert!(err["message"].as_str().unwrap().contains("everything"));
+}
+
 #[tokio::test]
 async fn execute_determinism_and_idempotency() {
     let (base, http, _h) = setup().await;
@@ -250,7 +442,7 @@ async fn execute_determinism_and_idempotency() {
         .unwrap();
     assert_eq!(r2.status(), 409, "replay must return 409 CONFLICT");
     let body2: Value = r2.json().await.unwrap();
-    assert!(body2["detail"]
+    assert!(body2["message"]
         .as_str()
         .unwrap()
         .contains("duplicate request"));
@@ -351,18 +543,22 @@ async fn did_document_has_required_fields() {
     let id = did["id"].as_str().unwrap();
     assert!(id.starts_with("did:key:z"), "DID must be did:key:z...");
 
-    // verificationMethod
+    // verificationMethod: the runtime key plus the gate's active signing
+    // key — federated peers verify fetched receipts against this document
     let vm = did["verificationMethod"].as_array().unwrap();
-    assert_eq!(vm.len(), 1);
-    assert_eq!(vm[0]["type"], "Ed25519VerificationKey2020");
-    assert!(vm[0]["publicKeyMultibase"]
-        .as_str()
-        .unwrap()
-        .starts_with("z"));
+    assert_eq!(vm.len(), 2);
+    for m in vm {
+        assert_eq!(m["type"], "Ed25519VerificationKey2020");
+        assert!(m["publicKeyMultibase"].as_str().unwrap().starts_with("z"));
+    }
+    assert!(
+        vm.iter().any(|m| m["id"] == "did:dev#k1"),
+        "active signing kid must resolve: {did}"
+    );
 
-    // assertionMethod references the verification method
+    // assertionMethod references the verification methods
     let am = did["assertionMethod"].as_array().unwrap();
-    assert_eq!(am.len(), 1);
+    assert_eq!(am.len(), 2);
     assert!(am[0].as_str().unwrap().contains("#ed25519"));
 }
 
@@ -544,7 +740,7 @@ async fn setup_auth_enabled() -> (String, Client, tokio::task::JoinHandle<()>) {
         axum::serve(listener, app).await.unwrap();
     });
     let base = format!("http://{addr}");
-    let http = Client::new();
+    let http = compat_client();
     (base, http, handle)
 }
 
@@ -626,6 +822,9 @@ async fn setup_auth_kid_scoped(
             client_id: "scoped-client".into(),
             tenant_id: "test-tenant".into(),
             allowed_kids,
+            scopes: vec![],
+            public_key_b64: None,
+            did: None,
         },
     );
     let app = ubl_gate::app_with_state(state);
@@ -634,7 +833,7 @@ async fn setup_auth_kid_scoped(
     let handle = tokio::spawn(async move {
         axum::serve(listener, app).await.unwrap();
     });
-    (format!("http://{addr}"), Client::new(), handle)
+    (format!("http://{addr}"), compat_client(), handle)
 }
 
 #[tokio::test]
@@ -696,8 +895,41 @@ async fn body_too_large_returns_413() {
         .send()
         .await
         .unwrap();
-    // tower-http returns 413 Payload Too Large
     assert_eq!(resp.status(), 413, "body > 1MiB must be rejected with 413");
+    // 413s carry the standard error shape, not a bare status
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["code"], "payload_too_large");
+    assert!(
+        body["message"].as_str().unwrap().contains("1048576"),
+        "message names the limit: {body}"
+    );
+}
+
+#[tokio::test]
+async fn body_limits_differ_by_route_class() {
+    let (base, http, _h) = setup().await;
+    // 300 KiB: over the admin-class limit (256 KiB), under ingest (1 MiB)
+    let body = format!(r#"{{"payload":{{"x":"{}"}}}}"#, "y".repeat(300 * 1024));
+    let resp = http
+        .post(format!("{base}/v1/certify"))
+        .header("content-type", "application/json")
+        .body(body.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 413, "certify takes the small admin limit");
+    let err: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(err["code"], "payload_too_large");
+
+    // The same bytes fit through the ingest class
+    let resp = http
+        .post(format!("{base}/v1/ingest"))
+        .header("content-type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_ne!(resp.status(), 413, "ingest keeps the 1 MiB limit");
 }
 
 #[tokio::test]
@@ -778,7 +1010,7 @@ async fn replay_returns_409_with_first_chain_intact() {
         .unwrap();
     assert_eq!(resp2.status(), 409, "replay must return 409 CONFLICT");
     let body2: Value = resp2.json().await.unwrap();
-    assert!(body2["detail"]
+    assert!(body2["message"]
         .as_str()
         .unwrap()
         .contains("duplicate request"));
@@ -834,7 +1066,7 @@ async fn setup_rate_limited(burst: u32) -> (String, Client, tokio::task::JoinHan
     let handle = tokio::spawn(async move {
         axum::serve(listener, app).await.unwrap();
     });
-    (format!("http://{addr}"), Client::new(), handle)
+    (format!("http://{addr}"), compat_client(), handle)
 }
 
 #[tokio::test]
@@ -926,6 +1158,9 @@ async fn setup_multi_tenant() -> (String, Client, tokio::task::JoinHandle<()>) {
             client_id: "client-a".into(),
             tenant_id: "tenant-alpha".into(),
             allowed_kids: vec![],
+            scopes: vec![],
+            public_key_b64: None,
+            did: None,
         },
     );
     state.token_store.register(
@@ -934,6 +1169,9 @@ async fn setup_multi_tenant() -> (String, Client, tokio::task::JoinHandle<()>) {
             client_id: "client-b".into(),
             tenant_id: "tenant-beta".into(),
             allowed_kids: vec![],
+            scopes: vec![],
+            public_key_b64: None,
+            did: None,
         },
     );
     let app = ubl_gate::app_with_state(state);
@@ -942,7 +1180,7 @@ async fn setup_multi_tenant() -> (String, Client, tokio::task::JoinHandle<()>) {
     let handle = tokio::spawn(async move {
         axum::serve(listener, app).await.unwrap();
     });
-    (format!("http://{addr}"), Client::new(), handle)
+    (format!("http://{addr}"), compat_client(), handle)
 }
 
 #[tokio::test]
@@ -1018,26 +1256,3665 @@ async fn tenant_ingest_returns_tenant_id() {
     );
 }
 
-// ── Healthz ──────────────────────────────────────────────────────
+// ── Per-tenant signing keyrings ──────────────────────────────────
+
+fn simple_manifest(pipeline: &str) -> Value {
+    json!({
+        "pipeline": pipeline,
+        "in_grammar": {
+            "inputs": {"raw_b64": ""},
+            "mappings": [{"from": "raw_b64", "codec": "base64.decode", "to": "raw.bytes"}],
+            "output_from": "raw.bytes"
+        },
+        "out_grammar": {
+            "inputs": {"content": ""},
+            "mappings": [],
+            "output_from": "content"
+        },
+        "policy": {"allow": true}
+    })
+}
 
 #[tokio::test]
-async fn healthz_returns_ok() {
+async fn replay_cache_serves_prior_wf_without_forking() {
     let (base, http, _h) = setup().await;
-    let resp = http.get(format!("{base}/healthz")).send().await.unwrap();
+
+    // Opt the default tenant into the deterministic replay cache
+    let resp = http
+        .post(format!("{base}/v1/admin/replay-cache"))
+        .json(&json!({"enabled": true}))
+        .send()
+        .await
+        .unwrap();
     assert_eq!(resp.status(), 200);
-    let body: Value = resp.json().await.unwrap();
-    assert_eq!(body, json!({"ok": true}));
-}
 
-// ── 404 for unknown routes ───────────────────────────────────────
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let payload = base64::engine::general_purpose::STANDARD.encode(format!("rc-{nonce}"));
+    let req = json!({
+        "manifest": simple_manifest("replay-cache"),
+        "vars": {"raw_b64": payload}
+    });
+
+    let first: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = first["cid"].as_str().unwrap().to_owned();
+    assert!(first.get("replayed").is_none(), "first run must execute");
+
+    // Identical pair → the prior WF receipt, flagged, not a 409 and not
+    // a new chain entry
+    let r2 = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r2.status(), 200);
+    let second: Value = r2.json().await.unwrap();
+    assert_eq!(second["replayed"], true);
+    assert_eq!(second["cid"].as_str().unwrap(), tip);
+    assert_eq!(second["decision"], first["decision"]);
+
+    // Opting out restores the plain idempotency conflict
+    http.post(format!("{base}/v1/admin/replay-cache"))
+        .json(&json!({"enabled": false}))
+        .send()
+        .await
+        .unwrap();
+    let r3 = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r3.status(), 409, "disabled tenant falls back to 409");
+}
 
 #[tokio::test]
-async fn unknown_route_returns_404() {
+async fn saga_groups_executions_under_a_merkle_root() {
     let (base, http, _h) = setup().await;
+
+    // Open a saga: a signed ubl/saga receipt whose body_cid is the id
+    let opened: Value = http
+        .post(format!("{base}/v1/saga"))
+        .json(&json!({"name": "order-flow"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let saga_id = opened["saga_id"].as_str().unwrap().to_owned();
+    assert!(saga_id.starts_with("b3:") && saga_id.len() == 67);
+    assert_eq!(opened["receipt"]["body"]["phase"], "open");
+    assert_eq!(opened["receipt"]["body"]["name"], "order-flow");
+
+    // Two executions referencing the saga get linked in order
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let mut tips = Vec::new();
+    for i in 0..2 {
+        let payload =
+            base64::engine::general_purpose::STANDARD.encode(format!("saga-{nonce}-{i}"));
+        let resp = http
+            .post(format!("{base}/v1/execute"))
+            .json(&json!({
+                "manifest": simple_manifest("saga-step"),
+                "vars": {"raw_b64": payload},
+                "saga_id": saga_id,
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let body: Value = resp.json().await.unwrap();
+        tips.push(body["tip_cid"].as_str().unwrap().to_owned());
+    }
+
+    // An unknown saga id is refused before anything runs
     let resp = http
-        .get(format!("{base}/v1/nonexistent"))
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({
+            "manifest": simple_manifest("saga-step"),
+            "vars": {"raw_b64": "aGk="},
+            "saga_id": format!("b3:{}", "0".repeat(64)),
+        }))
         .send()
         .await
         .unwrap();
     assert_eq!(resp.status(), 404);
+
+    // Close: the summary receipt carries the steps and their Merkle root
+    let resp = http
+        .post(format!("{base}/v1/saga/{saga_id}/close"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let closed: Value = resp.json().await.unwrap();
+    assert_eq!(closed["steps"], json!(tips));
+    let left = blake3::hash(tips[0].as_bytes());
+    let right = blake3::hash(tips[1].as_bytes());
+    let mut h = blake3::Hasher::new();
+    h.update(left.as_bytes());
+    h.update(right.as_bytes());
+    let expected_root = format!("b3:{}", hex::encode(h.finalize().as_bytes()));
+    assert_eq!(closed["steps_root"].as_str().unwrap(), expected_root);
+    assert_eq!(closed["receipt"]["body"]["phase"], "close");
+    assert_eq!(closed["receipt"]["parents"], json!([saga_id]));
+
+    // The saga reads back as a unit
+    let unit: Value = http
+        .get(format!("{base}/v1/saga/{saga_id}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(unit["steps"], json!(tips));
+    assert_eq!(unit["closed"], true);
+    assert_eq!(unit["open"]["body"]["phase"], "open");
+    assert_eq!(unit["close"]["body"]["steps_root"].as_str().unwrap(), expected_root);
+
+    // Closed means closed: no second close, no late steps
+    let resp = http
+        .post(format!("{base}/v1/saga/{saga_id}/close"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({
+            "manifest": simple_manifest("saga-step"),
+            "vars": {"raw_b64": "bGF0ZQ=="},
+            "saga_id": saga_id,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409);
+}
+
+#[tokio::test]
+async fn tenant_keyring_signs_scoped_receipts() {
+    let (base, http, _h) = setup().await;
+
+    // Provision a BYOK keyring for (myapp, acme)
+    let seed_b64 = base64::engine::general_purpose::STANDARD.encode([42u8; 32]);
+    let resp = http
+        .post(format!("{base}/a/myapp/t/acme/v1/admin/keyrings"))
+        .json(&json!({"seed_b64": seed_b64}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["kid"], "did:ubl:myapp:acme#k1");
+
+    // Execute in that scope: receipts must carry the tenant kid
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("aGVsbG8="))]);
+    let resp = http
+        .post(format!("{base}/a/myapp/t/acme/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("tenant-sign"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(
+        body["receipts"]["wf"]["proof"]["kid"], "did:ubl:myapp:acme#k1",
+        "WF receipt must be signed with the tenant kid: {body}"
+    );
+
+    // Legacy scope still uses the global dev keyring
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("d29ybGQ="))]);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("global-sign"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["receipts"]["wf"]["proof"]["kid"], "did:dev#k1");
+}
+
+#[tokio::test]
+async fn tenant_keyring_appears_in_did_json() {
+    let (base, http, _h) = setup().await;
+    let seed_b64 = base64::engine::general_purpose::STANDARD.encode([43u8; 32]);
+    http.post(format!("{base}/a/myapp/t/zeta/v1/admin/keyrings"))
+        .json(&json!({"seed_b64": seed_b64, "kid": "did:ubl:myapp:zeta#byok"}))
+        .send()
+        .await
+        .unwrap();
+
+    let doc: Value = http
+        .get(format!("{base}/.well-known/did.json"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let methods = doc["verificationMethod"].as_array().unwrap();
+    assert!(
+        methods.iter().any(|m| m["id"] == "did:ubl:myapp:zeta#byok"),
+        "tenant kid must appear in did.json: {doc}"
+    );
+}
+
+#[tokio::test]
+async fn admin_keyring_rejects_bad_seed() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .post(format!("{base}/v1/admin/keyrings"))
+        .json(&json!({"seed_b64": "dG9vc2hvcnQ="}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+// ── Detached receipt bodies ──────────────────────────────────────
+
+#[tokio::test]
+async fn oversized_bodies_are_detached_and_rehydrated() {
+    // Tiny threshold so every receipt body is detached
+    let state = ubl_gate::AppState {
+        auth_disabled: true,
+        detach_body_bytes: 32,
+        ..ubl_gate::AppState::default()
+    };
+    let (addr, _h) = ubl_gate::test::spawn_with_state(state).await;
+    let base = format!("http://{addr}");
+    let http = compat_client();
+
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("aGVsbG8="))]);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("detach"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let wf_cid = body["receipts"]["wf"]["body_cid"].as_str().unwrap().to_string();
+
+    // The listing is index-backed: flat entries, never an inline body
+    let listing: Value = http
+        .get(format!("{base}/v1/receipts"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let stored = &listing[&wf_cid];
+    assert!(stored.is_object(), "listing must know the receipt: {listing}");
+    assert!(stored.get("body").is_none(), "body must not be inline");
+
+    // GET /v1/receipt transparently rehydrates
+    let receipt: Value = http
+        .get(format!("{base}/v1/receipt/{wf_cid}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(receipt["body"].is_object(), "body must be rehydrated: {receipt}");
+    assert!(receipt.get("body_ref").is_none());
+    assert_eq!(receipt["body_cid"], wf_cid);
+
+    // Audit verifier rehydrates before integrity checks
+    let audit: Value = http
+        .get(format!("{base}/v1/audit"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(audit["integrity"]["invalid"], 0, "audit: {audit}");
+    assert!(audit["integrity"]["valid"].as_u64().unwrap() >= 1);
+}
+
+// ── Fuel estimation ──────────────────────────────────────────────
+
+/// TLV helper: one instruction (op, u16 length, payload).
+fn tlv_instr(op: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![op];
+    out.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+#[tokio::test]
+async fn estimate_rb_returns_fuel_and_suggested_limit() {
+    let (base, http, _h) = setup().await;
+    // ConstI64(1), ConstI64(2), AddI64, Drop → 4 fuel
+    let mut chip = tlv_instr(0x01, &1i64.to_be_bytes());
+    chip.extend(tlv_instr(0x01, &2i64.to_be_bytes()));
+    chip.extend(tlv_instr(0x05, &[]));
+    chip.extend(tlv_instr(0x11, &[]));
+    let chip_b64 = base64::engine::general_purpose::STANDARD.encode(&chip);
+
+    let resp = http
+        .post(format!("{base}/v1/execute/rb/estimate"))
+        .json(&json!({"chip_b64": chip_b64, "inputs": []}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["fuel_used"], 4);
+    assert_eq!(body["steps"], 4);
+    assert_eq!(body["steps_per_opcode"]["ConstI64"], 2);
+    assert!(
+        body["suggested_fuel_limit"].as_u64().unwrap() >= 4,
+        "suggested limit must include margin: {body}"
+    );
+}
+
+#[tokio::test]
+async fn estimate_rb_rejects_bad_base64() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .post(format!("{base}/v1/execute/rb/estimate"))
+        .json(&json!({"chip_b64": "not base64!!!", "inputs": []}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test]
+async fn execute_rb_error_classes_map_to_distinct_codes() {
+    let (base, http, _h) = setup().await;
+
+    // Deny is a decision, not a failure: 200 + DENY with the rule
+    // ConstI64(1), ConstI64(2), CmpI64(GT) → false, AssertTrue → deny
+    let mut deny_chip = tlv_instr(0x01, &1i64.to_be_bytes());
+    deny_chip.extend(tlv_instr(0x01, &2i64.to_be_bytes()));
+    deny_chip.extend(tlv_instr(0x08, &[4]));
+    deny_chip.extend(tlv_instr(0x09, &[]));
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&json!({
+            "chip_b64": base64::engine::general_purpose::STANDARD.encode(&deny_chip),
+            "inputs": [],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["decision"], "DENY");
+    assert_eq!(body["error_code"]["code"], "policy_deny");
+    assert_eq!(body["error_code"]["rule"], "assert_false");
+
+    // A starved fuel budget is 507: the request is fine, the limit isn't
+    let mut busy_chip = Vec::new();
+    for _ in 0..4 {
+        busy_chip.extend(tlv_instr(0x01, &1i64.to_be_bytes()));
+    }
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&json!({
+            "chip_b64": base64::engine::general_purpose::STANDARD.encode(&busy_chip),
+            "inputs": [],
+            "fuel": 2,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 507);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"], "fuel_exhausted");
+    assert_eq!(body["fuel_limit"], 2);
+    assert_eq!(body["error_code"]["code"], "fuel_exhausted");
+
+    // Bytes that don't decode as TLV are the client's bug: 400
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&json!({
+            "chip_b64": base64::engine::general_purpose::STANDARD.encode([0xFFu8, 0x00, 0x01]),
+            "inputs": [],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"], "malformed_chip");
+    assert_eq!(body["error_code"]["code"], "codec");
+
+    // Everything else (here: AssertTrue on an i64) stays 422
+    let mut mismatch_chip = tlv_instr(0x01, &1i64.to_be_bytes());
+    mismatch_chip.extend(tlv_instr(0x09, &[]));
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&json!({
+            "chip_b64": base64::engine::general_purpose::STANDARD.encode(&mismatch_chip),
+            "inputs": [],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error_code"]["code"], "engine");
+}
+
+#[tokio::test]
+async fn standard_chip_library_lists_and_lints_clean() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .get(format!("{base}/v1/chips/standard"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let chips = body["chips"].as_array().unwrap();
+    assert_eq!(chips.len(), 4);
+    let names: Vec<&str> = chips.iter().filter_map(|c| c["name"].as_str()).collect();
+    assert!(names.contains(&"age-gate"), "got: {names:?}");
+    assert!(names.contains(&"signature-check"), "got: {names:?}");
+
+    // Every listed chip carries a pinned CID and bytecode the lint
+    // endpoint accepts as-is
+    for chip in chips {
+        let cid = chip["bytecode_cid"].as_str().unwrap();
+        assert!(cid.starts_with("b3:"), "got: {cid}");
+        let lint: Value = http
+            .post(format!("{base}/v1/execute/rb/lint"))
+            .json(&json!({"chip_b64": chip["chip_b64"]}))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(lint["ok"], true, "{}: {lint}", chip["name"]);
+        // The inspection response disassembles the chip for humans
+        let disasm = lint["disasm"].as_str().unwrap();
+        assert!(!disasm.is_empty() && disasm.ends_with('\n'), "got: {disasm:?}");
+    }
+}
+
+#[tokio::test]
+async fn chip_inspection_reports_disasm_and_history() {
+    let (base, http, _h) = setup().await;
+
+    // Run a tiny chip twice; its bytecode CID is the inspection handle
+    let mut chip = tlv_instr(0x01, &7i64.to_be_bytes());
+    chip.extend(tlv_instr(0x11, &[]));
+    let cid = format!("b3:{}", hex::encode(blake3::hash(&chip).as_bytes()));
+    for _ in 0..2 {
+        let resp = http
+            .post(format!("{base}/v1/execute/rb"))
+            .json(&json!({
+                "chip_b64": base64::engine::general_purpose::STANDARD.encode(&chip),
+                "inputs": [],
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    let resp = http
+        .get(format!("{base}/v1/chips/{cid}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["cid"], json!(cid));
+    assert_eq!(body["disasm"], "ConstI64 7\nDrop\n");
+    assert_eq!(body["lint"]["ok"], true);
+    assert!(body["fuel"]["worst_case"].as_u64().unwrap() >= 2);
+    assert!(
+        body["fuel"]["suggested_limit"].as_u64().unwrap()
+            >= body["fuel"]["worst_case"].as_u64().unwrap()
+    );
+    assert_eq!(body["stats"]["runs"], 2);
+    assert_eq!(body["stats"]["denies"], 0);
+    assert!(body["stats"]["fuel_total"].as_u64().unwrap() >= 2);
+
+    // Standard-library chips resolve for every tenant, with their metadata
+    let listing: Value = http
+        .get(format!("{base}/v1/chips/standard"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let std_cid = listing["chips"][0]["bytecode_cid"].as_str().unwrap();
+    let body: Value = http
+        .get(format!("{base}/v1/chips/{std_cid}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["standard"]["name"], listing["chips"][0]["name"]);
+    assert_eq!(body["lint"]["ok"], true);
+
+    // Bytecode nobody in this tenant ran is invisible
+    let resp = http
+        .get(format!("{base}/v1/chips/b3:{}", "0".repeat(64)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn chip_allowlist_denies_unreviewed_bytecode() {
+    let (base, http, _h) = setup().await;
+
+    let mut chip = tlv_instr(0x01, &9i64.to_be_bytes());
+    chip.extend(tlv_instr(0x11, &[]));
+    let cid = format!("b3:{}", hex::encode(blake3::hash(&chip).as_bytes()));
+    let exec = json!({
+        "chip_b64": base64::engine::general_purpose::STANDARD.encode(&chip),
+        "inputs": [],
+    });
+
+    // An enabled empty allowlist denies every chip, with a signed DENY
+    // receipt naming the missing approval
+    let resp = http
+        .post(format!("{base}/v1/admin/chip-allowlist"))
+        .json(&json!({"enabled": true, "cids": []}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&exec)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+    let body: Value = resp.json().await.unwrap();
+    let deny = &body["deny_receipt"]["body"];
+    assert_eq!(deny["decision"], "DENY");
+    assert_eq!(deny["reason_code"]["code"], "chip_not_allowlisted");
+    assert_eq!(deny["reason_code"]["bytecode_cid"], json!(cid));
+
+    // Listing the CID is the approval
+    http.post(format!("{base}/v1/admin/chip-allowlist"))
+        .json(&json!({"enabled": true, "cids": [cid]}))
+        .send()
+        .await
+        .unwrap();
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&exec)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Clearing the policy restores run-anything
+    http.post(format!("{base}/v1/admin/chip-allowlist"))
+        .json(&json!({"enabled": false}))
+        .send()
+        .await
+        .unwrap();
+    let mut other = tlv_instr(0x01, &10i64.to_be_bytes());
+    other.extend(tlv_instr(0x11, &[]));
+    let resp = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&json!({
+            "chip_b64": base64::engine::general_purpose::STANDARD.encode(&other),
+            "inputs": [],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test]
+async fn interop_vectors_reproduce_from_runtime_primitives() {
+    let (base, _http, _h) = setup().await;
+
+    // Public interop data: no bearer token needed
+    let resp = Client::new()
+        .get(format!("{base}/v1/vectors"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["version"], "1");
+    assert_eq!(body["canon_profile"], "nrf1/v1");
+
+    // Every canonicalization vector recomputes from the served input
+    for case in body["canonicalization"].as_array().unwrap() {
+        let bytes = ubl_runtime::canon::canonical_bytes(&case["input"]).unwrap();
+        assert_eq!(
+            case["canonical"].as_str().unwrap().as_bytes(),
+            &bytes[..],
+            "case: {case}"
+        );
+        assert_eq!(case["cid"], json!(ubl_runtime::cid::cid_b3(&bytes)));
+    }
+    for case in body["cid"].as_array().unwrap() {
+        let bytes = hex::decode(case["bytes_hex"].as_str().unwrap()).unwrap();
+        assert_eq!(case["cid"], json!(ubl_runtime::cid::cid_b3(&bytes)));
+    }
+
+    // The JWS vector verifies under the published key
+    let jws = &body["jws"];
+    let seed: [u8; 32] = hex::decode(jws["seed_hex"].as_str().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let key = ed25519_dalek::SigningKey::from_bytes(&seed);
+    assert_eq!(
+        jws["public_key_hex"],
+        json!(hex::encode(key.verifying_key().to_bytes()))
+    );
+    let envelope = ubl_runtime::jws::JwsDetached {
+        protected: jws["protected"].as_str().unwrap().into(),
+        signature: jws["signature"].as_str().unwrap().into(),
+        kid: jws["kid"].as_str().unwrap().into(),
+    };
+    assert!(ubl_runtime::jws::verify_detached(
+        &envelope,
+        jws["payload"].as_str().unwrap().as_bytes(),
+        &key.verifying_key(),
+    ));
+
+    // The receipt vector's body_cid and proof recompute from its body
+    let receipt = &body["receipt"];
+    let body_bytes = ubl_runtime::canon::canonical_bytes(&receipt["body"]).unwrap();
+    assert_eq!(receipt["body_cid"], json!(ubl_runtime::cid::cid_b3(&body_bytes)));
+    let proof = ubl_runtime::jws::JwsDetached {
+        protected: receipt["proof"]["protected"].as_str().unwrap().into(),
+        signature: receipt["proof"]["signature"].as_str().unwrap().into(),
+        kid: receipt["proof"]["kid"].as_str().unwrap().into(),
+    };
+    assert!(ubl_runtime::jws::verify_detached(
+        &proof,
+        &body_bytes,
+        &key.verifying_key(),
+    ));
+}
+
+// ── Healthz ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn healthz_returns_ok() {
+    let (base, http, _h) = setup().await;
+    let resp = http.get(format!("{base}/healthz")).send().await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, json!({"ok": true}));
+}
+
+// ── 404 for unknown routes ───────────────────────────────────────
+
+#[tokio::test]
+async fn unknown_route_returns_404() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .get(format!("{base}/v1/nonexistent"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn replay_reproduces_wf_cid() {
+    let (base, http, _h) = setup().await;
+
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("cmVwbGF5"))]);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("replay"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let wf_cid = body["receipts"]["wf"]["body_cid"].as_str().unwrap().to_string();
+
+    let verdict: Value = http
+        .post(format!("{base}/v1/replay"))
+        .json(&json!({"cid": wf_cid}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(verdict["verdict"], "match", "replay: {verdict}");
+    assert_eq!(verdict["computed"]["wf_body_cid"], wf_cid);
+    assert_eq!(verdict["diverging"], json!([]));
+}
+
+#[tokio::test]
+async fn replay_unknown_cid_is_404() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .post(format!("{base}/v1/replay"))
+        .json(&json!({"cid": format!("b3:{}", "0".repeat(64))}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"], "replay_context_not_found");
+}
+
+#[tokio::test]
+async fn cors_origins_hot_reload_via_admin() {
+    let (base, http, _h) = setup().await;
+    let origin = "https://app.hotreload.example";
+
+    // Not in any allowlist yet: no CORS headers echoed back
+    let resp = http
+        .get(format!("{base}/healthz"))
+        .header("Origin", origin)
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+
+    // Onboard the origin at runtime — no restart
+    let added: Value = http
+        .post(format!("{base}/v1/admin/cors"))
+        .json(&json!({"origin": origin, "level": "global"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(added["added"], true);
+
+    let resp = http
+        .get(format!("{base}/healthz"))
+        .header("Origin", origin)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.headers()
+            .get("access-control-allow-origin")
+            .and_then(|v| v.to_str().ok()),
+        Some(origin)
+    );
+
+    // Listing reflects the addition
+    let listing: Value = http
+        .get(format!("{base}/v1/admin/cors"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(listing["global"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|o| o == origin));
+
+    // Offboard and verify the predicate sees the change immediately
+    let resp = http
+        .delete(format!("{base}/v1/admin/cors"))
+        .json(&json!({"origin": origin, "level": "global"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let resp = http
+        .get(format!("{base}/healthz"))
+        .header("Origin", origin)
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+}
+
+#[tokio::test]
+async fn cors_admin_rejects_unknown_level() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .post(format!("{base}/v1/admin/cors"))
+        .json(&json!({"origin": "https://x.example", "level": "bogus"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test]
+async fn content_digest_validated_when_present() {
+    let (base, http, _h) = setup().await;
+    let body = serde_json::to_vec(&json!({"chip_b64": "", "inputs": []})).unwrap();
+    use sha2::Digest;
+    let good = base64::engine::general_purpose::STANDARD.encode(sha2::Sha256::digest(&body));
+
+    // Matching digest passes through to the handler
+    let resp = http
+        .post(format!("{base}/v1/execute/rb/estimate"))
+        .header("content-type", "application/json")
+        .header("content-digest", format!("sha-256=:{good}:"))
+        .body(body.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_ne!(resp.status(), 400, "valid digest must not be rejected");
+
+    // Tampered body → 400 before the handler runs
+    let resp = http
+        .post(format!("{base}/v1/execute/rb/estimate"))
+        .header("content-type", "application/json")
+        .header("content-digest", format!("sha-256=:{good}:"))
+        .body(serde_json::to_vec(&json!({"chip_b64": "x", "inputs": []})).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    let err: Value = resp.json().await.unwrap();
+    assert_eq!(err["error"], "content_digest_mismatch");
+
+    // Header with only unknown algorithms is rejected, not skipped
+    let resp = http
+        .post(format!("{base}/v1/execute/rb/estimate"))
+        .header("content-type", "application/json")
+        .header("content-digest", "md5=:AAAA:")
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    let err: Value = resp.json().await.unwrap();
+    assert_eq!(err["error"], "unsupported_digest_algorithm");
+}
+
+#[tokio::test]
+async fn responses_signed_on_request() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .get(format!("{base}/v1/receipts"))
+        .header("x-ubl-sign-response", "1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let sig = resp
+        .headers()
+        .get("x-ubl-signature")
+        .and_then(|v| v.to_str().ok())
+        .expect("x-ubl-signature header")
+        .to_string();
+    let kid = resp
+        .headers()
+        .get("x-ubl-signature-kid")
+        .and_then(|v| v.to_str().ok())
+        .expect("kid header")
+        .to_string();
+    let body = resp.bytes().await.unwrap();
+
+    // Verify the detached JWS over the exact body bytes
+    let (protected, signature) = sig.split_once("..").expect("detached compact form");
+    let jws = ubl_runtime::jws::JwsDetached {
+        protected: protected.to_string(),
+        signature: signature.to_string(),
+        kid: kid.clone(),
+    };
+    let keys = ubl_runtime::KeyRing::dev();
+    assert_eq!(kid, keys.active_kid);
+    assert!(ubl_runtime::jws::verify_detached(
+        &jws,
+        &body,
+        &keys.active.verifying_key()
+    ));
+
+    // Unsigned by default
+    let resp = http.get(format!("{base}/v1/receipts")).send().await.unwrap();
+    assert!(resp.headers().get("x-ubl-signature").is_none());
+}
+
+#[tokio::test]
+async fn execute_failure_emits_signed_deny_receipt() {
+    let (base, http, _h) = setup().await;
+
+    // Duplicate request → idempotency error inside run_with_receipts
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("ZGVueQ=="))]);
+    let req = json!({"manifest": simple_manifest("deny-trace"), "vars": vars});
+    let first = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 200);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["code"], "conflict");
+
+    // The failure still leaves a signed, auditable DENY WF receipt
+    let deny = &body["deny_receipt"];
+    assert_eq!(deny["t"], "ubl/wf");
+    assert_eq!(deny["body"]["decision"], "DENY");
+    assert_eq!(deny["body"]["pipeline"], "deny-trace");
+    assert!(deny["proof"]["kid"].as_str().is_some());
+    let deny_cid = deny["body_cid"].as_str().unwrap();
+
+    // And it is retrievable from the receipt registry
+    let stored: Value = http
+        .get(format!("{base}/v1/receipt/{deny_cid}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(stored["body"]["decision"], "DENY");
+}
+
+// ── Redaction workflow (GDPR) ────────────────────────────────────
+
+#[tokio::test]
+async fn redact_leaves_tombstone_and_attestation() {
+    let (base, http, _h) = setup().await;
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"email": "user@example.com", "case": "redact"}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_string();
+
+    let body: Value = http
+        .post(format!("{base}/v1/redact/{cid}"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["redacted"], true);
+    assert_eq!(body["tombstone"]["original_cid"], cid.as_str());
+    assert_eq!(body["receipt"]["t"], "ubl/attestation");
+    assert_eq!(body["receipt"]["body"]["action"], "redact");
+
+    // The blob is gone: GET now answers 410 with the tombstone
+    let resp = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(resp.status(), 410, "redacted content must be Gone");
+    let tomb: Value = resp.json().await.unwrap();
+    assert_eq!(tomb["type"], "ubl/tombstone");
+    assert_eq!(tomb["original_cid"], cid.as_str());
+
+    // Redaction is idempotent
+    let again = http
+        .post(format!("{base}/v1/redact/{cid}"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(again.status(), 200);
+}
+
+#[tokio::test]
+async fn legal_hold_blocks_redaction_until_cleared() {
+    let (base, http, _h) = setup().await;
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"case": "legal-hold"}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_string();
+
+    let held = http
+        .post(format!("{base}/v1/admin/hold/{cid}"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(held.status(), 200);
+
+    let blocked = http
+        .post(format!("{base}/v1/redact/{cid}"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(blocked.status(), 403, "legal hold must block redaction");
+    // Blob still served while held
+    let raw = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(raw.status(), 200);
+
+    http.delete(format!("{base}/v1/admin/hold/{cid}"))
+        .send()
+        .await
+        .unwrap();
+    let resp = http
+        .post(format!("{base}/v1/redact/{cid}"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "cleared hold must allow redaction");
+}
+
+#[tokio::test]
+async fn redact_unknown_cid_is_404() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .post(format!(
+            "{base}/v1/redact/bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenera"
+        ))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+// ── Fine-grained token scopes (403) ──────────────────────────────
+
+async fn setup_with_scoped_token(
+    token: &str,
+    scopes: Vec<String>,
+) -> (String, Client, tokio::task::JoinHandle<()>) {
+    use tokio::net::TcpListener;
+
+    let state = ubl_gate::AppState {
+        auth_disabled: false,
+        ..Default::default()
+    };
+    state.token_store.register(
+        token,
+        ubl_gate::ClientInfo {
+            client_id: "scope-client".into(),
+            tenant_id: "default".into(),
+            allowed_kids: vec![],
+            scopes,
+            public_key_b64: None,
+            did: None,
+        },
+    );
+    let app = ubl_gate::app_with_state(state);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("http://{addr}"), compat_client(), handle)
+}
+
+#[tokio::test]
+async fn read_only_token_cannot_execute() {
+    let (base, http, _h) =
+        setup_with_scoped_token("analytics-token", vec!["receipts:read".into()]).await;
+
+    // Reads are allowed
+    let resp = http
+        .get(format!("{base}/v1/receipts"))
+        .bearer_auth("analytics-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "receipts:read grants reads");
+
+    // Executions are not
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .bearer_auth("analytics-token")
+        .json(&json!({"manifest": {}, "vars": {}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403, "read-only token must not execute");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"], "insufficient_scope");
+    assert_eq!(body["required_scope"], "execute");
+
+    // Nor admin routes
+    let resp = http
+        .post(format!("{base}/v1/admin/hold/b3:abc"))
+        .bearer_auth("analytics-token")
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403, "read-only token must not touch admin");
+}
+
+#[tokio::test]
+async fn admin_scope_implies_everything() {
+    let (base, http, _h) = setup_with_scoped_token("root-token", vec!["admin".into()]).await;
+    let resp = http
+        .get(format!("{base}/v1/receipts"))
+        .bearer_auth("root-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let resp = http
+        .post(format!("{base}/v1/ingest"))
+        .bearer_auth("root-token")
+        .json(&json!({"payload": {"scope": "admin"}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "admin scope implies ingest");
+}
+
+#[tokio::test]
+async fn legacy_token_without_scopes_is_unrestricted() {
+    let (base, http, _h) = setup_with_scoped_token("legacy-token", vec![]).await;
+    let resp = http
+        .post(format!("{base}/v1/ingest"))
+        .bearer_auth("legacy-token")
+        .json(&json!({"payload": {"scope": "legacy"}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "empty scopes stay unrestricted");
+}
+
+// ── /v1/integrity: chain verifier ────────────────────────────────
+
+fn integrity_manifest() -> Value {
+    json!({
+        "pipeline": "integrity-test",
+        "in_grammar": {
+            "inputs": {"raw_b64": ""},
+            "mappings": [{"from": "raw_b64", "codec": "base64.decode", "to": "raw.bytes"}],
+            "output_from": "raw.bytes"
+        },
+        "out_grammar": {
+            "inputs": {"content": ""},
+            "mappings": [],
+            "output_from": "content"
+        },
+        "policy": {"allow": true}
+    })
+}
+
+#[tokio::test]
+async fn integrity_clean_after_execute() {
+    let state = ubl_gate::AppState {
+        auth_disabled: true,
+        ..Default::default()
+    };
+    let (addr, _h) = ubl_gate::test::spawn_with_state(state.clone()).await;
+    let base = format!("http://{addr}");
+    let http = compat_client();
+
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("x".into(), json!("aGk="))]);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": integrity_manifest(), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let resp = http
+        .get(format!("{base}/v1/integrity"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let report: Value = resp.json().await.unwrap();
+    assert!(report["checked"].as_u64().unwrap() >= 2, "walks the chain from the tip");
+    assert_eq!(report["body_cid_invalid"], json!(0));
+    assert_eq!(report["signature_invalid"], json!(0));
+    assert_eq!(report["missing_parents"], json!(0));
+}
+
+#[tokio::test]
+async fn integrity_detects_tampered_receipt() {
+    let state = ubl_gate::AppState {
+        auth_disabled: true,
+        ..Default::default()
+    };
+    let (addr, _h) = ubl_gate::test::spawn_with_state(state.clone()).await;
+    let base = format!("http://{addr}");
+    let http = compat_client();
+
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("x".into(), json!("dGFtcGVy"))]);
+    http.post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": integrity_manifest(), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+
+    // Tamper with the tip receipt's body behind the gate's back
+    let tip = state.last_tip.read().unwrap().clone().unwrap();
+    {
+        let mut chain = state.receipt_chain.write().unwrap();
+        let receipt = chain.get_mut(&tip).unwrap();
+        receipt["body"]["tampered"] = json!(true);
+    }
+
+    let resp = http
+        .get(format!("{base}/v1/integrity"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409, "tampering must flip the report");
+    let report: Value = resp.json().await.unwrap();
+    assert!(report["body_cid_invalid"].as_u64().unwrap() >= 1);
+    let failure = &report["failures"][0];
+    assert_eq!(failure["kind"], json!("body_cid"));
+}
+
+#[tokio::test]
+async fn execute_rejects_pathological_vars_with_422() {
+    let (base, http, _h) = setup().await;
+    // 40 levels of nesting — past the default max_depth of 32
+    let mut deep = json!(1);
+    for _ in 0..40 {
+        deep = json!({ "n": deep });
+    }
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("x".into(), deep)]);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": integrity_manifest(), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+    let body: Value = resp.json().await.unwrap();
+    let msg = body["message"].as_str().unwrap_or_default();
+    assert!(msg.contains("max_depth"), "message must name the limit: {msg}");
+}
+
+// ── Content negotiation on /cid/:cid ─────────────────────────────
+
+#[tokio::test]
+async fn cid_accept_header_negotiation_with_etag() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"negotiated": true, "nonce": nonce}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_owned();
+
+    // application/x-nrf → canonical bytes, ETag is the CID
+    let raw = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Accept", "application/x-nrf")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(raw.status(), 200);
+    let etag = raw.headers()["etag"].to_str().unwrap().to_owned();
+    assert_eq!(etag, format!("\"{cid}\""));
+    assert_eq!(raw.headers()["content-type"], "application/x-nrf");
+    assert!(hex::encode(raw.bytes().await.unwrap()).starts_with("6e726631"));
+
+    // application/json → decoded view, no .json suffix needed
+    let j: Value = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(j["negotiated"], true);
+
+    // application/cbor → CBOR encoding of the decoded view
+    let c = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Accept", "application/cbor")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(c.status(), 200);
+    assert_eq!(c.headers()["content-type"], "application/cbor");
+    let cbor: Value = ciborium::from_reader(&*c.bytes().await.unwrap()).unwrap();
+    assert_eq!(cbor["negotiated"], true);
+
+    // If-None-Match with the CID ETag → 304, no body
+    let cached = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("If-None-Match", &etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(cached.status(), 304);
+
+    // Unknown Accept still serves canonical bytes
+    let fallback = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Accept", "text/html")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(fallback.headers()["content-type"], "application/x-nrf");
+}
+
+#[tokio::test]
+async fn cid_range_requests_serve_partial_content() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"ranged": true, "nonce": nonce}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_owned();
+    let total = r["bytes_len"].as_u64().unwrap();
+
+    // Full response advertises range support and Content-Length
+    let full = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(full.status(), 200);
+    assert_eq!(full.headers()["accept-ranges"], "bytes");
+    assert_eq!(
+        full.headers()["content-length"].to_str().unwrap(),
+        total.to_string()
+    );
+    let whole = full.bytes().await.unwrap();
+
+    // First four bytes are the NRF magic
+    let part = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Range", "bytes=0-3")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(part.status(), 206);
+    assert_eq!(
+        part.headers()["content-range"].to_str().unwrap(),
+        format!("bytes 0-3/{total}")
+    );
+    assert_eq!(&part.bytes().await.unwrap()[..], b"nrf1");
+
+    // Open-ended range streams the tail
+    let tail = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Range", "bytes=4-")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(tail.status(), 206);
+    assert_eq!(&tail.bytes().await.unwrap()[..], &whole[4..]);
+
+    // Suffix range serves the last N bytes
+    let suffix = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Range", "bytes=-2")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(suffix.status(), 206);
+    assert_eq!(&suffix.bytes().await.unwrap()[..], &whole[whole.len() - 2..]);
+
+    // Out-of-bounds start is unsatisfiable
+    let bad = http
+        .get(format!("{base}/cid/{cid}"))
+        .header("Range", format!("bytes={total}-"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad.status(), 416);
+    assert_eq!(
+        bad.headers()["content-range"].to_str().unwrap(),
+        format!("bytes */{total}")
+    );
+}
+
+// ── Index-backed receipts listing ────────────────────────────────
+
+#[tokio::test]
+async fn receipts_listing_served_from_tenant_index() {
+    let (base, http, _h) = setup().await;
+    let vars: BTreeMap<String, Value> =
+        BTreeMap::from([("input_data".into(), json!("aW5kZXhlZA=="))]);
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("index-list"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let wf_cid = body["receipts"]["wf"]["body_cid"].as_str().unwrap();
+    let wa_cid = body["receipts"]["wa"]["body_cid"].as_str().unwrap();
+
+    let listing: Value = http
+        .get(format!("{base}/v1/receipts"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    // Flat index entries: type, decision, pipeline, timestamp, parent
+    let wf = &listing[wf_cid];
+    assert_eq!(wf["t"], "ubl/wf");
+    assert_eq!(wf["decision"], "ALLOW");
+    assert_eq!(wf["pipeline"], "index-list");
+    assert!(wf["ts"].is_string(), "entry carries a timestamp: {wf}");
+    assert!(listing[wa_cid].is_object(), "WA must be indexed too");
+    assert_eq!(listing[wa_cid]["t"], "ubl/wa");
+}
+
+// ── Importing externally-signed receipts ─────────────────────────
+
+/// A partner gate's receipt plus the DID document that proves it.
+fn foreign_receipt() -> (Value, Value) {
+    let keys = ubl_runtime::KeyRing::from_seed([9u8; 32], "did:partner:acme#k1");
+    let body = json!({
+        "type": "ubl/wf",
+        "decision": "ALLOW",
+        "pipeline": "partner-pipeline",
+        "outputs_cid": "b3:0000",
+    });
+    let receipt =
+        ubl_runtime::build_receipt("ubl/wf", vec![], body, &keys.active, &keys.active_kid)
+            .unwrap();
+    let pubkey = keys.active.verifying_key().to_bytes();
+    let did_doc = json!({
+        "id": "did:partner:acme",
+        "verificationMethod": [
+            ubl_did::tenant_verification_method(&keys.active_kid, &pubkey)
+        ],
+    });
+    (serde_json::to_value(&receipt).unwrap(), did_doc)
+}
+
+#[tokio::test]
+async fn import_verifies_and_links_foreign_receipt() {
+    let (base, http, _h) = setup().await;
+    let (receipt, did_doc) = foreign_receipt();
+    let cid = receipt["body_cid"].as_str().unwrap().to_owned();
+
+    let resp = http
+        .post(format!("{base}/v1/receipts/import"))
+        .json(&json!({"receipt": receipt, "did_document": did_doc}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let out: Value = resp.json().await.unwrap();
+    assert_eq!(out["imported"], true);
+    assert_eq!(out["cid"], cid);
+    assert_eq!(out["issuer"], "did:partner:acme");
+    assert_eq!(out["attestation"]["t"], "ubl/attestation");
+    assert_eq!(out["attestation"]["parents"][0], cid);
+
+    // Stored flagged external, retrievable like any local receipt
+    let stored: Value = http
+        .get(format!("{base}/v1/receipt/{cid}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(stored["external"], true);
+    assert_eq!(stored["issuer"], "did:partner:acme");
+}
+
+#[tokio::test]
+async fn import_rejects_tampered_and_unprovable_receipts() {
+    let (base, http, _h) = setup().await;
+
+    // Tampered body no longer matches body_cid
+    let (mut receipt, did_doc) = foreign_receipt();
+    receipt["body"]["decision"] = json!("DENY");
+    let resp = http
+        .post(format!("{base}/v1/receipts/import"))
+        .json(&json!({"receipt": receipt, "did_document": did_doc}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+    let err: Value = resp.json().await.unwrap();
+    assert!(
+        err["message"].as_str().unwrap().contains("body_cid mismatch"),
+        "got: {err}"
+    );
+
+    // DID document without the signing kid proves nothing
+    let (receipt, _) = foreign_receipt();
+    let wrong_doc = json!({"id": "did:partner:other", "verificationMethod": []});
+    let resp = http
+        .post(format!("{base}/v1/receipts/import"))
+        .json(&json!({"receipt": receipt, "did_document": wrong_doc}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+
+    // Key swap: right kid, wrong key material
+    let (receipt, _) = foreign_receipt();
+    let other = ubl_runtime::KeyRing::from_seed([10u8; 32], "did:partner:acme#k1");
+    let swapped_doc = json!({
+        "id": "did:partner:acme",
+        "verificationMethod": [ubl_did::tenant_verification_method(
+            &other.active_kid,
+            &other.active.verifying_key().to_bytes(),
+        )],
+    });
+    let resp = http
+        .post(format!("{base}/v1/receipts/import"))
+        .json(&json!({"receipt": receipt, "did_document": swapped_doc}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+    let err: Value = resp.json().await.unwrap();
+    assert!(
+        err["message"]
+            .as_str()
+            .unwrap()
+            .contains("signature verification failed"),
+        "got: {err}"
+    );
+}
+
+// ── Cross-gate federation via did:web prev_tips ──────────────────
+
+/// did:web identifier for a gate spawned on a loopback port.
+fn gate_did(base: &str) -> String {
+    let host = base.trim_start_matches("http://").replace(':', "%3A");
+    format!("did:web:{host}")
+}
+
+#[tokio::test]
+async fn federated_prev_tip_verified_and_linked() {
+    // A partner organization's gate mints a tip; ours chains onto it
+    let (foreign_base, http, _hf) = setup().await;
+    let (local_base, _, _hl) = setup().await;
+
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("aGVsbG8="))]);
+    let minted: Value = http
+        .post(format!("{foreign_base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("fed-foreign"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let foreign_tip = minted["tip_cid"].as_str().unwrap().to_owned();
+
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("d29ybGQ="))]);
+    let resp = http
+        .post(format!("{local_base}/v1/execute"))
+        .json(&json!({
+            "manifest": simple_manifest("fed-local"),
+            "vars": vars,
+            "prev_tip": format!("{}#{foreign_tip}", gate_did(&foreign_base)),
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+
+    // WA chains onto the foreign tip and records who minted it
+    let wa = &body["receipts"]["wa"];
+    assert_eq!(wa["parents"][0], foreign_tip);
+    assert_eq!(wa["body"]["prev_tip"], foreign_tip);
+    assert_eq!(
+        wa["body"]["prev_tip_issuer"],
+        json!(*ubl_receipt::ISSUER_DID),
+        "WA must record the foreign issuer: {wa}"
+    );
+
+    // Provenance: resolving the WA cid surfaces the cross-gate linkage
+    let wa_cid = wa["body_cid"].as_str().unwrap();
+    let resolved: Value = http
+        .post(format!("{local_base}/v1/resolve"))
+        .json(&json!({"id": wa_cid}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(resolved["parents"][0], foreign_tip);
+    assert_eq!(resolved["prev_tip"], foreign_tip);
+    assert_eq!(resolved["prev_tip_issuer"], json!(*ubl_receipt::ISSUER_DID));
+}
+
+#[tokio::test]
+async fn federated_prev_tip_rejects_unverifiable_tips() {
+    let (foreign_base, http, _hf) = setup().await;
+    let (local_base, _, _hl) = setup().await;
+    let vars: BTreeMap<String, Value> = BTreeMap::from([("data".into(), json!("aGVsbG8="))]);
+    let exec = json!({"manifest": simple_manifest("fed-reject"), "vars": vars});
+
+    // A tip the foreign gate never minted cannot be fetched
+    let mut req = exec.clone();
+    req["prev_tip"] = json!(format!(
+        "{}#b3:{}",
+        gate_did(&foreign_base),
+        "0".repeat(64)
+    ));
+    let resp = http
+        .post(format!("{local_base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422, "unfetchable foreign tip must fail");
+
+    // Raw CIDs must name a receipt this gate already holds; an unknown
+    // one is a malformed chain, not a silent local fallback
+    let mut req = exec.clone();
+    req["prev_tip"] = json!("b3:abc123");
+    let resp = http
+        .post(format!("{local_base}/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422, "raw CID prev_tip must be rejected");
+    let err: Value = resp.json().await.unwrap();
+    assert!(
+        err["message"].as_str().unwrap().contains("did:web"),
+        "got: {err}"
+    );
+}
+
+// ── Response cache on immutable GETs ─────────────────────────────
+
+#[tokio::test]
+async fn immutable_gets_are_cached_with_cache_control() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"hot": true, "nonce": nonce}, "certify": true}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_owned();
+
+    // First GET warms the cache; both responses carry immutable caching headers
+    let first = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(first.status(), 200);
+    assert_eq!(
+        first.headers()["cache-control"],
+        "public, max-age=31536000, immutable"
+    );
+    assert_eq!(first.headers()["etag"], format!("\"{cid}\""));
+    let first_bytes = first.bytes().await.unwrap();
+
+    let second = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(second.status(), 200);
+    assert_eq!(
+        second.headers()["cache-control"],
+        "public, max-age=31536000, immutable"
+    );
+    assert_eq!(second.headers()["content-type"], "application/x-nrf");
+    assert_eq!(second.bytes().await.unwrap(), first_bytes);
+
+    // Receipts get the same treatment, including 304 on If-None-Match
+    let rec = http
+        .get(format!("{base}/v1/receipt/{cid}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rec.status(), 200);
+    assert_eq!(
+        rec.headers()["cache-control"],
+        "public, max-age=31536000, immutable"
+    );
+    let etag = rec.headers()["etag"].to_str().unwrap().to_owned();
+    assert_eq!(etag, format!("\"{cid}\""));
+    let rec_body = rec.bytes().await.unwrap();
+
+    let rec2 = http
+        .get(format!("{base}/v1/receipt/{cid}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rec2.status(), 200);
+    assert_eq!(rec2.bytes().await.unwrap(), rec_body);
+
+    let revalidated = http
+        .get(format!("{base}/v1/receipt/{cid}"))
+        .header("If-None-Match", &etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(revalidated.status(), 304);
+}
+
+#[tokio::test]
+async fn redaction_purges_cached_responses() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let r: Value = http
+        .post(format!("{base}/v1/ingest"))
+        .json(&json!({"payload": {"pii": "secret", "nonce": nonce}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = r["cid"].as_str().unwrap().to_owned();
+
+    // Warm the cache in every representation
+    for accept in ["application/x-nrf", "application/json", "application/cbor"] {
+        let warm = http
+            .get(format!("{base}/cid/{cid}"))
+            .header("Accept", accept)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(warm.status(), 200);
+    }
+
+    let redacted: Value = http
+        .post(format!("{base}/v1/redact/{cid}"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(redacted["redacted"], true);
+
+    // Cached copies must not outlive the blob: GET is Gone, not a stale hit
+    for accept in ["application/x-nrf", "application/json", "application/cbor"] {
+        let after = http
+            .get(format!("{base}/cid/{cid}"))
+            .header("Accept", accept)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(after.status(), 410, "{accept} must not serve a stale copy");
+    }
+}
+
+// ── Async ingest ─────────────────────────────────────────────────
+
+#[tokio::test]
+async fn respond_async_ingest_queues_and_resolves() {
+    let state = ubl_gate::AppState {
+        auth_disabled: true,
+        ingest_queue: ubl_gate::IngestQueue::new(1),
+        ..ubl_gate::AppState::default()
+    };
+    let (addr, _h) = ubl_gate::test::spawn_with_state(state.clone()).await;
+    let base = format!("http://{addr}");
+    let http = compat_client();
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    let accepted = http
+        .post(format!("{base}/v1/ingest"))
+        .header("Prefer", "respond-async")
+        .json(&json!({"payload": {"burst": true, "nonce": nonce}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(accepted.status(), 202);
+    let ticket: Value = accepted.json().await.unwrap();
+    let id = ticket["id"].as_str().unwrap().to_owned();
+    assert!(id.starts_with("ing_"), "ticket: {ticket}");
+    assert!(ticket["status_url"]
+        .as_str()
+        .unwrap()
+        .contains("/v1/ingest/status/"));
+
+    // Poll until the background job lands the blob
+    let mut status = Value::Null;
+    for _ in 0..100 {
+        status = http
+            .get(format!("{base}/v1/ingest/status/{id}"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if status["status"] == "done" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(status["status"], "done", "job: {status}");
+    let cid = status["cid"].as_str().unwrap();
+    let blob = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(blob.status(), 200);
+
+    // Unknown tracking ids are 404
+    let missing = http
+        .get(format!("{base}/v1/ingest/status/ing_{nonce:032x}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    // With the single queue slot held, the producer is shed with 429
+    let _slot = state.ingest_queue.try_enqueue().expect("slot free");
+    let shed = http
+        .post(format!("{base}/v1/ingest"))
+        .header("Prefer", "respond-async")
+        .json(&json!({"payload": {"burst": true, "nonce": nonce + 1}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(shed.status(), 429);
+    assert_eq!(shed.headers()["retry-after"], "1");
+    let body: Value = shed.json().await.unwrap();
+    assert_eq!(body["error"], "ingest_queue_full");
+}
+
+// ── Inclusion proofs ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn receipt_inclusion_proof_walks_to_chain_anchor() {
+    let (base, http, _h) = setup().await;
+    let mut tip = String::new();
+    for i in 0..3 {
+        let nonce = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let vars = json!({
+            "raw_b64": base64::engine::general_purpose::STANDARD.encode(format!("proof-{nonce}"))
+        });
+        let exec: Value = http
+            .post(format!("{base}/v1/execute"))
+            .json(&json!({"manifest": simple_manifest(&format!("proof-{i}")), "vars": vars}))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        tip = exec["tip_cid"].as_str().unwrap().to_owned();
+    }
+
+    // Plain GET stays proof-free
+    let plain: Value = http
+        .get(format!("{base}/v1/receipt/{tip}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(plain.get("inclusion_proof").is_none());
+
+    let with_proof: Value = http
+        .get(format!("{base}/v1/receipt/{tip}?include=proof"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let proof = &with_proof["inclusion_proof"];
+    assert_eq!(proof["complete"], true, "proof: {proof}");
+    let path = proof["path"].as_array().unwrap();
+    assert_eq!(proof["hops"], path.len() as u64);
+    // Three runs of wa+wf receipts each: the path spans all of them
+    assert!(path.len() >= 6, "path too short: {proof}");
+    assert_eq!(path[0]["cid"], tip);
+    // Each hop's first parent is the next hop's CID, down to the anchor
+    for pair in path.windows(2) {
+        assert_eq!(pair[0]["parents"][0], pair[1]["cid"]);
+    }
+    let last = path.last().unwrap();
+    assert!(last["parents"].as_array().unwrap().is_empty());
+    assert_eq!(proof["anchor"], last["cid"]);
+
+    // Unknown sections are rejected, not silently ignored
+    let bad = http
+        .get(format!("{base}/v1/receipt/{tip}?include=trace"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad.status(), 400);
+}
+
+// ── Stats aggregates ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn stats_endpoints_aggregate_receipts_denies_and_usage() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    // One ALLOW through a pipeline, one DENY from an empty chip allowlist
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode(format!("stats-{nonce}"))});
+    let exec = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("stats-allow"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(exec.status(), 200);
+    http.post(format!("{base}/v1/admin/chip-allowlist"))
+        .json(&json!({"enabled": true, "cids": []}))
+        .send()
+        .await
+        .unwrap();
+    let chip = tlv_instr(0x01, &9i64.to_be_bytes());
+    let denied = http
+        .post(format!("{base}/v1/execute/rb"))
+        .json(&json!({
+            "chip_b64": base64::engine::general_purpose::STANDARD.encode(&chip),
+            "inputs": [],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(denied.status(), 403);
+
+    let receipts: Value = http
+        .get(format!("{base}/v1/stats/receipts"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(receipts["total"].as_u64().unwrap() >= 2, "{receipts}");
+    let hours = receipts["per_hour"].as_array().unwrap();
+    assert!(!hours.is_empty());
+    assert!(hours[0]["hour"].as_str().unwrap().ends_with(":00:00Z"));
+
+    let pipelines: Value = http
+        .get(format!("{base}/v1/stats/pipelines"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let allow_line = pipelines["pipelines"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["pipeline"] == "stats-allow")
+        .expect("pipeline aggregate");
+    assert!(allow_line["decisions"]["ALLOW"].as_u64().unwrap() >= 1);
+
+    let denies: Value = http
+        .get(format!("{base}/v1/stats/denies"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(denies["total_denies"].as_u64().unwrap() >= 1);
+    assert!(
+        denies["top_rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["rule"] == "chip_not_allowlisted"),
+        "{denies}"
+    );
+
+    let usage: Value = http
+        .get(format!("{base}/v1/stats/usage"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(usage["tenant_id"], "default");
+    assert!(usage["usage"]["executions"].as_u64().unwrap() >= 1);
+
+    // Aggregates are cached: an immediate re-read serves the same body
+    let again: Value = http
+        .get(format!("{base}/v1/stats/receipts"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(again, receipts);
+}
+
+// ── JCS interchange ──────────────────────────────────────────────
+
+#[tokio::test]
+async fn receipts_emit_jcs_with_dual_cids() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode(format!("jcs-{nonce}"))});
+    let exec: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("jcs"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let cid = exec["tip_cid"].as_str().unwrap();
+
+    let receipt: Value = http
+        .get(format!("{base}/v1/receipt/{cid}?include=jcs"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let jcs = &receipt["jcs"];
+    // Both CIDs recompute from their respective canonical forms
+    assert_eq!(jcs["body_cid_b3"], receipt["body_cid"]);
+    let canonical = jcs["canonical"].as_str().unwrap();
+    assert_eq!(
+        jcs["body_cid_sha256"],
+        json!(ubl_runtime::jcs::cid_sha256(canonical.as_bytes()))
+    );
+    let reparsed: Value = serde_json::from_str(canonical).unwrap();
+    assert_eq!(reparsed, receipt["body"], "JCS is a faithful re-serialization");
+    assert_eq!(
+        canonical.as_bytes(),
+        &ubl_runtime::jcs::jcs_bytes(&receipt["body"]).unwrap()[..]
+    );
+
+    // Sections compose: proof and jcs ride the same response
+    let both: Value = http
+        .get(format!("{base}/v1/receipt/{cid}?include=proof,jcs"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(both["jcs"].is_object() && both["inclusion_proof"].is_object());
+    // Plain GETs stay untouched
+    let plain: Value = http
+        .get(format!("{base}/v1/receipt/{cid}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(plain.get("jcs").is_none());
+}
+
+// ── Embedded test harness ────────────────────────────────────────
+
+#[tokio::test]
+async fn test_gate_builder_drives_an_embedded_gate() {
+    let gate = ubl_gate::test::TestGateBuilder::new()
+        .with_auth()
+        .token(
+            "tok-builder",
+            "ci-client",
+            "default",
+            &["ingest", "execute", "receipts:read"],
+        )
+        .rate_limit(10_000, 1_000)
+        .spawn()
+        .await;
+    let base = gate.base_url();
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode("embedded")});
+
+    // Auth is on: anonymous producers bounce
+    let anon = Client::new()
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("builder"), "vars": vars}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(anon.status(), 401);
+
+    let exec: Value = Client::new()
+        .post(format!("{base}/v1/execute"))
+        .header("Authorization", "Bearer tok-builder")
+        .header("x-ubl-compat", "1")
+        .json(&json!({"manifest": simple_manifest("builder"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = exec["tip_cid"].as_str().unwrap().to_owned();
+
+    // Live handles: the chain is inspectable without another request
+    assert_eq!(gate.tip().as_deref(), Some(tip.as_str()));
+    let receipt = gate
+        .receipt(&ubl_gate::scope::Scope::legacy(), &tip)
+        .expect("tip receipt in the live chain");
+    assert_eq!(receipt["body_cid"], tip);
+    assert!(gate.state.receipt_chain.read().unwrap().len() >= 2);
+}
+
+// ── Signed issuance time ─────────────────────────────────────────
+
+#[tokio::test]
+async fn receipt_proofs_carry_signed_issued_at() {
+    let (base, http, _h) = setup().await;
+    let before = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode("timed")});
+    let exec: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("issued-at"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = exec["tip_cid"].as_str().unwrap();
+
+    let receipt: Value = http
+        .get(format!("{base}/v1/receipt/{tip}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let protected = receipt["proof"]["protected"].as_str().unwrap();
+    let header: Value = serde_json::from_slice(
+        &base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(protected)
+            .unwrap(),
+    )
+    .unwrap();
+    let after = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let issued_at = header["issued_at"].as_u64().expect("issued_at claim");
+    assert!(
+        (before..=after).contains(&issued_at),
+        "issued_at {issued_at} outside [{before}, {after}]"
+    );
+    // The claim sits under the signature: body_cid is untouched and the
+    // proof still verifies through the audit path
+    assert_eq!(receipt["body_cid"], tip);
+    let audit: Value = http
+        .get(format!("{base}/v1/audit"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(audit["integrity"]["invalid"], 0, "audit: {audit}");
+}
+
+// ── Threshold co-signing ─────────────────────────────────────────
+
+#[tokio::test]
+async fn cosign_accumulates_signers_until_threshold() {
+    let (base, http, _h) = setup().await;
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode("high-value")});
+    let exec: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("cosign"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = exec["tip_cid"].as_str().unwrap().to_owned();
+
+    // The primary signer cannot co-sign its own receipt
+    let dup = http
+        .post(format!("{base}/v1/receipt/{tip}/cosign"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(dup.status(), 409, "primary kid must not double-sign");
+
+    // A second signer arrives via a scoped keyring
+    let put = http
+        .post(format!("{base}/v1/admin/keyrings"))
+        .json(&json!({
+            "seed_b64": base64::engine::general_purpose::STANDARD.encode([42u8; 32]),
+            "kid": "did:ubl:default:default#cosign"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(put.status(), 200);
+
+    let signed: Value = http
+        .post(format!("{base}/v1/receipt/{tip}/cosign"))
+        .json(&json!({"threshold": 2}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(signed["signers"], 2);
+    assert_eq!(signed["threshold"], 2);
+    assert_eq!(signed["satisfied"], true);
+    assert_eq!(
+        signed["cosign"]["signatures"][0]["kid"],
+        "did:ubl:default:default#cosign"
+    );
+
+    // The stored receipt now carries the threshold proof
+    let rec: Value = http
+        .get(format!("{base}/v1/receipt/{tip}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(rec["cosign"]["threshold"], 2);
+    assert_eq!(rec["cosign"]["signatures"].as_array().unwrap().len(), 1);
+
+    // The same scoped kid cannot sign twice
+    let again = http
+        .post(format!("{base}/v1/receipt/{tip}/cosign"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(again.status(), 409);
+}
+
+// ── Attestation with ledger-anchored evidence ────────────────────
+
+#[tokio::test]
+async fn attest_links_evidence_cids_into_the_body() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let ingest = |payload: Value| {
+        let http = http.clone();
+        let base = base.clone();
+        async move {
+            let r: Value = http
+                .post(format!("{base}/v1/ingest"))
+                .json(&json!({"payload": payload}))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            r["cid"].as_str().unwrap().to_owned()
+        }
+    };
+    let target = ingest(json!({"artifact": "build", "nonce": nonce})).await;
+    let ev_a = ingest(json!({"log": "tests passed", "nonce": nonce})).await;
+    let ev_b = ingest(json!({"log": "review approved", "nonce": nonce})).await;
+
+    // Evidence that is not in the ledger is refused at creation time
+    let unstored = ubl_ai_nrf1::nrf::cid_from_nrf_bytes(b"never-stored-evidence").to_string();
+    let missing = http
+        .post(format!("{base}/v1/attest"))
+        .json(&json!({
+            "target_cid": target,
+            "claim": "audited",
+            "evidence": [unstored]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 422, "unstored evidence must be refused");
+
+    // Duplicated and unordered evidence canonicalizes in the body
+    let att: Value = http
+        .post(format!("{base}/v1/attest"))
+        .json(&json!({
+            "target_cid": target,
+            "claim": "audited",
+            "evidence": [ev_b, ev_a, ev_b]
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let att_cid = att["cid"].as_str().unwrap().to_owned();
+    assert_eq!(att["receipt"]["t"], "ubl/attestation");
+    let evidence = att["receipt"]["body"]["evidence"].as_array().unwrap();
+    assert_eq!(evidence.len(), 2, "evidence must deduplicate");
+    let mut sorted = evidence.clone();
+    sorted.sort_by_key(|v| v.as_str().unwrap().to_owned());
+    assert_eq!(*evidence, sorted, "evidence must be sorted");
+
+    // Provenance resolution surfaces the evidence trail
+    let resolved: Value = http
+        .post(format!("{base}/v1/resolve"))
+        .json(&json!({"id": att_cid}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(resolved["evidence"].as_array().unwrap().len(), 2);
+
+    // An unknown target is a 404, not a minted claim
+    let no_target = http
+        .post(format!("{base}/v1/attest"))
+        .json(&json!({
+            "target_cid": ubl_ai_nrf1::nrf::cid_from_nrf_bytes(b"never-stored-target").to_string(),
+            "claim": "audited"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(no_target.status(), 404);
+}
+
+// ── Share tokens: public verification ────────────────────────────
+
+#[tokio::test]
+async fn share_token_opens_one_receipt_without_auth() {
+    let (base, http, _h) = setup_auth_enabled().await;
+    let auth = ("authorization", "Bearer ubl-dev-token-001");
+
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode("shareable")});
+    let exec: Value = http
+        .post(format!("{base}/v1/execute"))
+        .header(auth.0, auth.1)
+        .json(&json!({"manifest": simple_manifest("share"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = exec["tip_cid"].as_str().unwrap().to_owned();
+
+    // Minting requires auth
+    let anon = http
+        .post(format!("{base}/v1/receipt/{tip}/share"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(anon.status(), 401, "minting a share requires a token");
+
+    let share: Value = http
+        .post(format!("{base}/v1/receipt/{tip}/share"))
+        .header(auth.0, auth.1)
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let token = share["token"].as_str().unwrap().to_owned();
+    assert_eq!(share["url"], format!("/v1/share/{token}"));
+
+    // The capability URL works with no Authorization header at all
+    let view: Value = http
+        .get(format!("{base}/v1/share/{token}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(view["cid"], tip.as_str());
+    assert_eq!(view["receipt"]["t"], "ubl/wf");
+    // Chain proof covers the ancestors (WA + transition), envelopes only
+    let chain = view["chain"].as_array().unwrap();
+    assert_eq!(chain.len(), 2);
+    for link in chain {
+        assert!(link["proof"]["signature"].is_string());
+        assert!(link.get("body").is_none(), "chain proof must not leak bodies");
+    }
+
+    // Other receipts stay closed: a guessed token is a 404
+    let miss = http
+        .get(format!("{base}/v1/share/shr_{}", "0".repeat(64)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(miss.status(), 404);
+
+    // Revocation kills the capability immediately
+    let revoked = http
+        .delete(format!("{base}/v1/receipt/{tip}/share/{token}"))
+        .header(auth.0, auth.1)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(revoked.status(), 200);
+    let after = http
+        .get(format!("{base}/v1/share/{token}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(after.status(), 404, "revoked share must stop resolving");
+}
+
+#[tokio::test]
+async fn share_rejects_unknown_receipts_and_bad_ttl() {
+    let (base, http, _h) = setup().await;
+    let missing = http
+        .post(format!(
+            "{base}/v1/receipt/b3:{}/share",
+            "0".repeat(64)
+        ))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode("ttl")});
+    let exec: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": simple_manifest("share-ttl"), "vars": vars}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tip = exec["tip_cid"].as_str().unwrap();
+    let bad_ttl = http
+        .post(format!("{base}/v1/receipt/{tip}/share"))
+        .json(&json!({"ttl_secs": 0}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad_ttl.status(), 400);
+}
+
+// ── tdln compatibility format on /v1/execute ─────────────────────
+
+#[tokio::test]
+async fn execute_format_tdln_serves_legacy_shape() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .to_string();
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode(&nonce)});
+    let body = json!({"manifest": simple_manifest("tdln-compat"), "vars": vars});
+
+    let resp = http
+        .post(format!("{base}/v1/execute?format=tdln"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let flat: Value = resp.json().await.unwrap();
+    assert_eq!(flat["status"], "executed");
+    assert!(flat["tip"].as_str().unwrap().starts_with("b3:"));
+    assert_eq!(flat["decision"], "ALLOW");
+    assert_eq!(flat["receipt"]["t"], "ubl/wf");
+    // Native-shape keys must NOT leak into the legacy contract
+    assert!(flat.get("receipts").is_none());
+    assert!(flat.get("tip_cid").is_none());
+
+    // Replaying the same inputs: the old service panicked; the shim
+    // reports a structured error with conflict semantics
+    let replay = http
+        .post(format!("{base}/v1/execute?format=tdln"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(replay.status(), 409);
+    let err: Value = replay.json().await.unwrap();
+    assert_eq!(err["status"], "error");
+    assert!(err["error"].as_str().unwrap().contains("duplicate request"));
+
+    // Unknown formats are rejected up front
+    let unknown = http
+        .post(format!("{base}/v1/execute?format=xml"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(unknown.status(), 400);
+}
+
+// ── Bulk NDJSON ingest ───────────────────────────────────────────
+
+#[tokio::test]
+async fn bulk_ingest_returns_per_line_results() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    // Two good payloads, one broken line, one blank, trailing line
+    // without a newline terminator
+    let ndjson = format!(
+        "{}\nnot json at all\n\n{}\n{}",
+        json!({"doc": "bulk-a", "nonce": nonce}),
+        json!({"doc": "bulk-b", "nonce": nonce}),
+        json!({"doc": "bulk-c", "nonce": nonce}),
+    );
+    let resp = http
+        .post(format!("{base}/v1/ingest/bulk"))
+        .header("content-type", "application/x-ndjson")
+        .body(ndjson)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers()["content-type"].to_str().unwrap(),
+        "application/x-ndjson"
+    );
+    let body = resp.text().await.unwrap();
+    let results: Vec<Value> = body
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(results.len(), 4, "blank line must not produce a result: {body}");
+    assert!(results[0]["cid"].as_str().unwrap().starts_with("baf"));
+    assert!(results[1]["error"].as_str().unwrap().contains("invalid JSON"));
+    assert_eq!(results[2]["line"], 3);
+    assert!(results[3]["cid"].is_string(), "unterminated last line: {body}");
+
+    // Stored payloads are immediately fetchable
+    let cid = results[0]["cid"].as_str().unwrap();
+    let got = http.get(format!("{base}/cid/{cid}")).send().await.unwrap();
+    assert_eq!(got.status(), 200);
+}
+
+// ── Two-phase execute (prepare/commit) ───────────────────────────
+
+#[tokio::test]
+async fn prepare_then_commit_executes_once() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .to_string();
+    let vars = json!({"raw_b64": base64::engine::general_purpose::STANDARD.encode(&nonce)});
+    let body = json!({"manifest": simple_manifest("two-phase"), "vars": vars});
+
+    // Phase 1: preview receipts without committing anything
+    let prep = http
+        .post(format!("{base}/v1/execute?phase=prepare"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(prep.status(), 200);
+    let prepared: Value = prep.json().await.unwrap();
+    assert_eq!(prepared["status"], "prepared");
+    let token = prepared["prepare_token"].as_str().unwrap().to_string();
+    assert!(token.starts_with("prep_"));
+    let preview_tip = prepared["preview"]["tip_cid"].as_str().unwrap().to_string();
+    assert_eq!(prepared["preview"]["decision"], "ALLOW");
+    assert_eq!(prepared["preview"]["receipts"]["wf"]["t"], "ubl/wf");
+
+    // The previewed tip is NOT in the chain yet
+    let probe = http
+        .get(format!("{base}/v1/receipt/{preview_tip}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(probe.status(), 404);
+
+    // Phase 2: commit signs and chains for real, matching the preview
+    let commit = http
+        .post(format!("{base}/v1/execute/commit"))
+        .json(&json!({"prepare_token": token}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(commit.status(), 200);
+    let executed: Value = commit.json().await.unwrap();
+    assert_eq!(executed["tip_cid"], preview_tip.as_str());
+    let stored = http
+        .get(format!("{base}/v1/receipt/{preview_tip}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stored.status(), 200);
+
+    // Tokens are single-use; a replayed commit finds nothing
+    let again = http
+        .post(format!("{base}/v1/execute/commit"))
+        .json(&json!({"prepare_token": token}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(again.status(), 404);
+
+    // Unknown phases are rejected up front
+    let bad = http
+        .post(format!("{base}/v1/execute?phase=later"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad.status(), 400);
+}
+
+// ── Tenant quotas and budgets ────────────────────────────────────
+
+#[tokio::test]
+async fn execution_quota_denies_with_receipt_and_usage() {
+    let (base, http, _h) = setup().await;
+    let quota = http
+        .post(format!("{base}/v1/admin/quota"))
+        .json(&json!({"period": "day", "max_executions": 1}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(quota.status(), 200);
+
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .to_string();
+    let body = json!({
+        "manifest": simple_manifest("quota-test"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(&nonce)},
+    });
+    let first = http
+        .post(format!("{base}/v1/execute"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 200);
+
+    // Usage reflects the charged run
+    let usage: Value = http
+        .get(format!("{base}/v1/quota/usage"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(usage["usage"]["executions"], 1);
+    assert_eq!(usage["limits"]["max_executions"], 1);
+
+    // Budget exhausted: structured 429 plus a signed DENY receipt
+    let body2 = json!({
+        "manifest": simple_manifest("quota-test"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(format!("{nonce}-2"))},
+    });
+    let second = http
+        .post(format!("{base}/v1/execute"))
+        .json(&body2)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(second.status(), 429);
+    assert!(second.headers().contains_key("retry-after"));
+    let denied: Value = second.json().await.unwrap();
+    assert_eq!(denied["code"], "quota_exceeded");
+    assert_eq!(denied["deny_receipt"]["body"]["decision"], "DENY");
+    assert_eq!(
+        denied["deny_receipt"]["body"]["reason_code"]["code"],
+        "quota_exceeded"
+    );
+}
+
+#[tokio::test]
+async fn bulk_ingest_enforces_stored_bytes_budget() {
+    let (base, http, _h) = setup().await;
+    let quota = http
+        .post(format!("{base}/v1/admin/quota"))
+        .json(&json!({"period": "day", "max_stored_bytes": 1}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(quota.status(), 200);
+
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    // First line fits under the untouched budget and charges it; the
+    // second is refused and stops the batch, so the third never runs
+    let ndjson = format!(
+        "{}\n{}\n{}\n",
+        json!({"doc": "budget-a", "nonce": nonce}),
+        json!({"doc": "budget-b", "nonce": nonce}),
+        json!({"doc": "budget-c", "nonce": nonce}),
+    );
+    let resp = http
+        .post(format!("{base}/v1/ingest/bulk"))
+        .header("content-type", "application/x-ndjson")
+        .body(ndjson)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = resp.text().await.unwrap();
+    let results: Vec<Value> = body
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(results.len(), 2, "batch must stop at the denial: {body}");
+    assert!(results[0]["cid"].is_string(), "{body}");
+    assert!(
+        results[1]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("quota_exceeded: stored_bytes"),
+        "{body}"
+    );
+
+    // Bulk-stored bytes show up in the same meter single ingest charges
+    let usage: Value = http
+        .get(format!("{base}/v1/quota/usage"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(usage["usage"]["stored_bytes"].as_u64().unwrap() >= 1);
+}
+
+// ── Local prev_tip validation ────────────────────────────────────
+
+#[tokio::test]
+async fn local_prev_tip_must_exist_in_registry() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .to_string();
+    let body = json!({
+        "manifest": simple_manifest("prev-tip-test"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(&nonce)},
+    });
+    let first = http
+        .post(format!("{base}/v1/execute"))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 200);
+    let executed: Value = first.json().await.unwrap();
+    let tip = executed["tip_cid"].as_str().unwrap().to_string();
+
+    // Chaining onto a receipt the gate holds is accepted and linked
+    let chained_body = json!({
+        "manifest": simple_manifest("prev-tip-test"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(format!("{nonce}-2"))},
+        "prev_tip": tip,
+    });
+    let second = http
+        .post(format!("{base}/v1/execute"))
+        .json(&chained_body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(second.status(), 200);
+    let chained: Value = second.json().await.unwrap();
+    assert_eq!(chained["receipts"]["wa"]["parents"][0], tip.as_str());
+
+    // A well-formed CID the registry has never seen is a malformed chain
+    let bogus_body = json!({
+        "manifest": simple_manifest("prev-tip-test"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(format!("{nonce}-3"))},
+        "prev_tip": format!("b3:{}", "f".repeat(64)),
+    });
+    let third = http
+        .post(format!("{base}/v1/execute"))
+        .json(&bogus_body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(third.status(), 422);
+    let err: Value = third.json().await.unwrap();
+    assert!(
+        err["message"]
+            .as_str()
+            .unwrap()
+            .contains("not found in the local registry"),
+        "got: {err}"
+    );
+}
+
+// ── Receipt body schemas ─────────────────────────────────────────
+
+#[tokio::test]
+async fn well_known_schemas_served_unauthenticated() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .get(format!("{base}/.well-known/ubl/schemas"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let docs: Value = resp.json().await.unwrap();
+    assert_eq!(docs["version"], "v1");
+    assert_eq!(docs["schemas"]["ubl/wf"]["properties"]["decision"]["enum"][0], "ALLOW");
+    assert_eq!(docs["schemas"]["ubl/wa"]["properties"]["type"]["const"], "ubl/wa");
+}
+
+// ── Embedding: gate mounted inside a host app ────────────────────
+
+#[tokio::test]
+async fn gate_mounts_under_a_prefix_in_a_host_app() {
+    let state = ubl_gate::AppState {
+        auth_disabled: true,
+        ..ubl_gate::AppState::default()
+    };
+    let host = axum::Router::new()
+        .route("/", axum::routing::get(|| async { "host app" }))
+        .merge(ubl_gate::router_with_prefix(state, "/ubl"));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let _h = tokio::spawn(async move {
+        axum::serve(listener, host).await.unwrap();
+    });
+    let http = compat_client();
+
+    // The host keeps its own routes
+    let root = http.get(format!("{base}/")).send().await.unwrap();
+    assert_eq!(root.status(), 200);
+    assert_eq!(root.text().await.unwrap(), "host app");
+
+    // Gate routes answer under the prefix
+    let health = http.get(format!("{base}/ubl/healthz")).send().await.unwrap();
+    assert_eq!(health.status(), 200);
+
+    // Scoped routes still resolve (app, tenant) behind the prefix
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let exec = http
+        .post(format!("{base}/ubl/a/demo/t/acme/v1/execute"))
+        .json(&json!({
+            "manifest": simple_manifest("@demo/embed/1.0.0"),
+            "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(nonce.to_string())}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(exec.status(), 200, "{}", exec.text().await.unwrap());
+
+    // Nothing leaks at the unprefixed paths
+    let bare = http.get(format!("{base}/healthz")).send().await.unwrap();
+    assert_eq!(bare.status(), 404);
+}
+
+// ── Replay conflicts point at the prior receipt ──────────────────
+
+#[tokio::test]
+async fn replay_conflict_points_at_the_original_receipt() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let req_body = json!({
+        "manifest": simple_manifest("@demo/replay-pointer/1.0.0"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(nonce.to_string())}
+    });
+
+    let first = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req_body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 200);
+    let first: Value = first.json().await.unwrap();
+    let tip = first["tip_cid"].as_str().unwrap().to_string();
+
+    let second = http
+        .post(format!("{base}/v1/execute"))
+        .json(&req_body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(second.status(), 409);
+    let conflict: Value = second.json().await.unwrap();
+    assert_eq!(conflict["code"], "conflict");
+    assert_eq!(conflict["existing"]["tip_cid"], tip.as_str(), "got: {conflict}");
+    assert_eq!(conflict["existing"]["wf_cid"], tip.as_str());
+    let url = conflict["existing"]["url"].as_str().unwrap();
+    assert!(url.ends_with(&format!("/v1/receipt/{tip}")), "got: {url}");
+
+    // The pointer resolves: fetching it returns the original WF receipt
+    let prior = http
+        .get(format!("{base}/v1/receipt/{tip}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(prior.status(), 200);
+    let prior: Value = prior.json().await.unwrap();
+    assert_eq!(prior["body_cid"], tip.as_str());
+}
+
+// ── Time-travel: registry snapshot at a past tip ─────────────────
+
+#[tokio::test]
+async fn as_of_tip_reconstructs_the_registry_at_that_tip() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let run = |n: u128| {
+        json!({
+            "manifest": simple_manifest("@demo/time-travel/1.0.0"),
+            "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(n.to_string())}
+        })
+    };
+
+    let first = http
+        .post(format!("{base}/v1/execute"))
+        .json(&run(nonce))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 200);
+    let first: Value = first.json().await.unwrap();
+    let old_tip = first["tip_cid"].as_str().unwrap().to_string();
+
+    let second = http
+        .post(format!("{base}/v1/execute"))
+        .json(&run(nonce + 1))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(second.status(), 200);
+    let second: Value = second.json().await.unwrap();
+    let new_wf = second["receipts"]["wf"]["body_cid"].as_str().unwrap();
+
+    // The snapshot at the old tip holds the first chain, not the second
+    let snap = http
+        .get(format!("{base}/v1/receipts?as_of_tip={old_tip}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(snap.status(), 200);
+    let snap: Value = snap.json().await.unwrap();
+    assert_eq!(snap["as_of_tip"], old_tip.as_str());
+    let receipts = snap["receipts"].as_object().unwrap();
+    assert!(receipts.contains_key(&old_tip), "old wf is in the snapshot");
+    let old_wa = first["receipts"]["wa"]["body_cid"].as_str().unwrap();
+    assert!(receipts.contains_key(old_wa), "parents are walked");
+    assert!(
+        !receipts.contains_key(new_wf),
+        "later receipts must not leak into the snapshot"
+    );
+
+    // Audit variant scopes the report to the same snapshot
+    let audit = http
+        .get(format!("{base}/v1/audit?as_of_tip={old_tip}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(audit.status(), 200);
+    let audit: Value = audit.json().await.unwrap();
+    assert_eq!(audit["as_of_tip"], old_tip.as_str());
+    assert_eq!(
+        audit["report"]["summary"]["total_receipts"].as_u64().unwrap(),
+        receipts.len() as u64
+    );
+
+    // Unknown tips are a 404, not an empty snapshot
+    let missing = http
+        .get(format!("{base}/v1/receipts?as_of_tip=b3:{}", "f".repeat(64)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+}
+
+// ── Signed gate identity document ────────────────────────────────
+
+#[tokio::test]
+async fn well_known_ubl_json_is_signed_and_self_describing() {
+    let (base, http, _h) = setup().await;
+    let resp = http
+        .get(format!("{base}/.well-known/ubl.json"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let doc: Value = resp.json().await.unwrap();
+
+    let body = &doc["body"];
+    assert_eq!(body["api_version"], "v1");
+    assert_eq!(body["canon"], "nrf1/v1");
+    assert!(body["issuer"].as_str().unwrap().starts_with("did:"));
+    assert!(body["receipt_types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t == "ubl/wf"));
+    assert_eq!(body["endpoints"]["execute"], "/v1/execute");
+
+    let keys = ubl_runtime::KeyRing::dev();
+    let kids = body["keys"].as_array().unwrap();
+    assert!(kids
+        .iter()
+        .any(|k| k["kid"] == keys.active_kid.as_str() && k["status"] == "active"));
+
+    // The proof covers the canonical body and verifies against the
+    // gate's published key — the same one did.json advertises
+    let bytes = ubl_runtime::canon::canonical_bytes(body).unwrap();
+    assert_eq!(doc["body_cid"], ubl_runtime::cid::cid_b3(&bytes).as_str());
+    let proof: ubl_runtime::jws::JwsDetached =
+        serde_json::from_value(doc["proof"].clone()).unwrap();
+    assert!(ubl_runtime::jws::verify_detached(
+        &proof,
+        &bytes,
+        &keys.active.verifying_key()
+    ));
+}
+
+// ── Client acknowledgements (counter-signing) ────────────────────
+
+#[tokio::test]
+async fn client_ack_mints_an_attestation_parented_to_the_target() {
+    let (base, http, _h) = setup().await;
+
+    // The client holds its own Ed25519 key; only the public half is
+    // registered with the gate
+    let client = ubl_runtime::KeyRing::from_seed([9u8; 32], "did:key:acme-consumer#k1");
+    let pubkey_b64 = base64::engine::general_purpose::STANDARD
+        .encode(client.active.verifying_key().to_bytes());
+    let resp = http
+        .post(format!("{base}/v1/admin/ack-keys"))
+        .json(&json!({"client_id": "acme-consumer", "public_key_b64": pubkey_b64}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Produce a WF receipt to acknowledge
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .to_string();
+    let run: Value = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({
+            "manifest": simple_manifest("@ack/target/1.0.0"),
+            "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(&nonce)}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let wf_cid = run["receipts"]["wf"]["body_cid"].as_str().unwrap().to_string();
+
+    // Counter-sign the body_cid and submit the acknowledgement
+    let jws = ubl_runtime::jws::sign_detached(wf_cid.as_bytes(), &client.active, &client.active_kid);
+    let resp = http
+        .post(format!("{base}/v1/receipt/{wf_cid}/ack"))
+        .json(&json!({"client_id": "acme-consumer", "jws": jws}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let ack: Value = resp.json().await.unwrap();
+    assert_eq!(ack["receipt"]["t"], "ubl/attestation");
+    assert_eq!(ack["receipt"]["parents"][0], wf_cid.as_str());
+    assert_eq!(ack["receipt"]["body"]["action"], "ack");
+    assert_eq!(ack["receipt"]["body"]["client_id"], "acme-consumer");
+
+    // The ack is a first-class receipt: fetchable from the registry
+    let ack_cid = ack["cid"].as_str().unwrap();
+    let fetched = http
+        .get(format!("{base}/v1/receipt/{ack_cid}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(fetched.status(), 200);
+
+    // A signature from an unregistered client is refused outright
+    let resp = http
+        .post(format!("{base}/v1/receipt/{wf_cid}/ack"))
+        .json(&json!({"client_id": "nobody", "jws": jws}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+
+    // A signature over the wrong payload fails verification
+    let bad = ubl_runtime::jws::sign_detached(b"b3:not-the-target", &client.active, &client.active_kid);
+    let resp = http
+        .post(format!("{base}/v1/receipt/{wf_cid}/ack"))
+        .json(&json!({"client_id": "acme-consumer", "jws": bad}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 422);
+}
+
+// ── Client key discovery via the token store ─────────────────────
+
+#[tokio::test]
+async fn registered_client_keys_drive_discovery_ack_and_import() {
+    let (base, http, _h) = setup_auth_enabled().await;
+    let admin = "Bearer ubl-dev-token-001";
+
+    // Mint a token carrying the client's public key and DID
+    let client = ubl_runtime::KeyRing::from_seed([11u8; 32], "did:web:widget.example#key-1");
+    let pubkey_b64 = base64::engine::general_purpose::STANDARD
+        .encode(client.active.verifying_key().to_bytes());
+    let resp = http
+        .post(format!("{base}/v1/admin/tokens"))
+        .header("authorization", admin)
+        .json(&json!({
+            "token": "widget-co-token-0001",
+            "client_id": "widget-co",
+            "did": "did:web:widget.example",
+            "public_key_b64": pubkey_b64,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let minted: Value = resp.json().await.unwrap();
+    assert_eq!(minted["did"], "did:web:widget.example");
+
+    // The discovery document is public — no bearer token needed
+    let resp = http
+        .get(format!("{base}/.well-known/ubl-clients/widget-co/did.json"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "client did.json must be public");
+    let doc: Value = resp.json().await.unwrap();
+    assert_eq!(doc["id"], "did:web:widget.example");
+    let multibase = doc["verificationMethod"][0]["publicKeyMultibase"]
+        .as_str()
+        .unwrap();
+    assert_eq!(
+        ubl_did::decode_public_key_multibase(multibase).unwrap(),
+        client.active.verifying_key().to_bytes()
+    );
+
+    // Acks resolve the key through the token store — no separate
+    // ack-key registration required
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .to_string();
+    let run: Value = http
+        .post(format!("{base}/v1/execute"))
+        .header("authorization", admin)
+        .json(&json!({
+            "manifest": simple_manifest("@discovery/ack/1.0.0"),
+            "vars": {"raw_b64": base64::engine::general_purpose::STANDARD.encode(&nonce)}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let wf_cid = run["receipts"]["wf"]["body_cid"].as_str().unwrap().to_string();
+    let jws = ubl_runtime::jws::sign_detached(wf_cid.as_bytes(), &client.active, &client.active_kid);
+    let resp = http
+        .post(format!("{base}/v1/receipt/{wf_cid}/ack"))
+        .header("authorization", admin)
+        .json(&json!({"client_id": "widget-co", "jws": jws}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200, "token-store key verifies the ack");
+
+    // Imports can omit the did_document when the issuer is registered
+    let foreign = ubl_runtime::build_receipt(
+        "ubl/wf",
+        vec![],
+        json!({"decision": "ALLOW", "pipeline": "@widget/export/1.0.0", "nonce": nonce}),
+        &client.active,
+        &client.active_kid,
+    )
+    .unwrap();
+    let resp = http
+        .post(format!("{base}/v1/receipts/import"))
+        .header("authorization", admin)
+        .json(&json!({"receipt": serde_json::to_value(&foreign).unwrap()}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.status(),
+        200,
+        "registered issuer resolves without a did_document"
+    );
+
+    // Unregistered issuers still have to bring their own document
+    let stranger = ubl_runtime::KeyRing::from_seed([12u8; 32], "did:web:stranger.example#key-1");
+    let foreign = ubl_runtime::build_receipt(
+        "ubl/wf",
+        vec![],
+        json!({"decision": "ALLOW", "pipeline": "@stranger/export/1.0.0"}),
+        &stranger.active,
+        &stranger.active_kid,
+    )
+    .unwrap();
+    let resp = http
+        .post(format!("{base}/v1/receipts/import"))
+        .header("authorization", admin)
+        .json(&json!({"receipt": serde_json::to_value(&foreign).unwrap()}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+// ── Execution context: ctx.* policy conditions ───────────────────
+
+#[tokio::test]
+async fn ctx_rules_pin_a_pipeline_to_its_tenant() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    let mut manifest = simple_manifest("ctx-pinned");
+    manifest["policy"] = json!({
+        "allow": true,
+        "rules": [{
+            "id": "ACME_ONLY",
+            "level": "tenant",
+            "condition": "ctx.tenant == \"acme\"",
+            "action": "DENY",
+            "reason": "pipeline reserved for tenant acme"
+        }]
+    });
+    // Fresh vars per call — identical inputs would trip replay detection
+    let vars_for = |tag: &str| {
+        json!({"raw_b64": base64::engine::general_purpose::STANDARD
+            .encode(format!("ctx-{tag}-{nonce}"))})
+    };
+
+    // The pinned tenant executes normally
+    let resp = http
+        .post(format!("{base}/a/demo/t/acme/v1/execute"))
+        .json(&json!({"manifest": manifest, "vars": vars_for("allowed")}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["decision"], "ALLOW");
+
+    // Any other tenant hits the ctx rule — DENY receipt, never a 500
+    let resp = http
+        .post(format!("{base}/v1/execute"))
+        .json(&json!({"manifest": manifest, "vars": vars_for("denied")}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["decision"], "DENY");
+    assert!(body["receipts"]["wf"]["body"]["reason"]
+        .as_str()
+        .unwrap()
+        .contains("reserved for tenant acme"));
+}
+
+#[tokio::test]
+async fn exec_context_stays_out_of_cids_unless_declared() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let req = json!({
+        "manifest": simple_manifest("ctx-free"),
+        "vars": {"raw_b64": base64::engine::general_purpose::STANDARD
+            .encode(format!("stable-{nonce}"))}
+    });
+
+    // First tenant executes; capture the input CID its receipts commit to
+    let a: Value = http
+        .post(format!("{base}/a/demo/t/acme/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(a["decision"], "ALLOW");
+    let inputs_cid = a["receipts"]["wa"]["body"]["inputs_raw_cid"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // The same vars through a different tenant must hash to the same
+    // inputs CID — the injected context stays out of the bound inputs —
+    // which replay detection observes as an exact duplicate.
+    let resp = http
+        .post(format!("{base}/a/demo/t/globex/v1/execute"))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409, "identical inputs replay across tenants");
+    let b: Value = resp.json().await.unwrap();
+    assert!(
+        b["message"].as_str().unwrap().contains(&inputs_cid),
+        "replay keyed on the same tenant-independent inputs CID"
+    );
+}
+
+// ── Audit export: CSV / NDJSON streaming ─────────────────────────
+
+#[tokio::test]
+async fn audit_export_streams_csv_and_ndjson_with_filters() {
+    let (base, http, _h) = setup().await;
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let pipeline = format!("audit-export-{nonce}");
+
+    // Two executions under distinct pipelines so filters have work to do
+    for (tag, p) in [("one", pipeline.as_str()), ("two", "audit-other")] {
+        let resp = http
+            .post(format!("{base}/v1/execute"))
+            .json(&json!({
+                "manifest": simple_manifest(p),
+                "vars": {"raw_b64": base64::engine::general_purpose::STANDARD
+                    .encode(format!("{tag}-{nonce}"))}
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    // CSV with column selection: header first, then one row per receipt
+    let resp = http
+        .get(format!(
+            "{base}/v1/audit?format=csv&columns=cid,pipeline,decision&pipeline={pipeline}"
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(resp
+        .headers()
+        .get("content-type")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .starts_with("text/csv"));
+    let text = resp.text().await.unwrap();
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("cid,pipeline,decision"));
+    let rows: Vec<&str> = lines.collect();
+    assert!(!rows.is_empty(), "filtered export must include the run");
+    for row in &rows {
+        assert!(row.contains(&pipeline), "filter leaked a foreign row: {row}");
+    }
+
+    // NDJSON: one JSON object per line, restricted to the selection
+    let resp = http
+        .get(format!(
+            "{base}/v1/audit?format=ndjson&columns=cid,decision&pipeline={pipeline}"
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    for line in resp.text().await.unwrap().lines() {
+        let row: Value = serde_json::from_str(line).unwrap();
+        assert!(row.get("cid").is_some());
+        assert!(row.get("pipeline").is_none(), "unselected column leaked");
+    }
+
+    // The same filters drive the receipt listing
+    let listing: Value = http
+        .get(format!("{base}/v1/receipts?pipeline={pipeline}&decision=ALLOW"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let entries = listing.as_object().unwrap();
+    assert!(!entries.is_empty());
+    for entry in entries.values() {
+        assert_eq!(entry["pipeline"], json!(pipeline));
+    }
+
+    // Bad selections are rejected up front
+    let resp = http
+        .get(format!("{base}/v1/audit?format=csv&columns=cid,bogus"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    let resp = http
+        .get(format!("{base}/v1/audit?format=yaml"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+// ── Config-driven public paths ───────────────────────────────────
+
+#[tokio::test]
+async fn route_pattern_table_matches_the_real_router() {
+    let (base, http, _h) = setup().await;
+    // PATCH is registered nowhere: an existing route answers 405
+    // (method not allowed), a stale table entry would answer 404.
+    for pattern in ubl_gate::route_patterns() {
+        let path: String = pattern
+            .split('/')
+            .map(|seg| seg.strip_prefix(':').unwrap_or(seg))
+            .collect::<Vec<_>>()
+            .join("/");
+        let resp = http
+            .patch(format!("{base}{path}"))
+            .header("content-type", "application/json")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.status(),
+            405,
+            "pattern '{pattern}' is not registered by the router"
+        );
+    }
+}
+
+#[tokio::test]
+async fn configured_exemptions_and_metrics_auth_are_enforced() {
+    use tokio::net::TcpListener;
+
+    let mut cfg = (*ubl_gate::AppState::default().gate_config).clone();
+    cfg.public_paths.push("/v1/integrity".into());
+    cfg.metrics_auth = true;
+    let state = ubl_gate::AppState {
+        auth_disabled: false,
+        gate_config: std::sync::Arc::new(cfg),
+        ..Default::default()
+    };
+    let app = ubl_gate::app_with_state(state);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let _h = tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    let base = format!("http://{addr}");
+    let http = compat_client();
+
+    // The configured exemption opens the route without a token
+    let resp = http
+        .get(format!("{base}/v1/integrity"))
+        .send()
+        .await
+        .unwrap();
+    assert_ne!(resp.status(), 401, "exempted route must skip auth");
+
+    // Locked-down metrics now demand a token
+    let resp = http.get(format!("{base}/metrics")).send().await.unwrap();
+    assert_eq!(resp.status(), 401);
+    let resp = http
+        .get(format!("{base}/metrics"))
+        .header("authorization", "Bearer ubl-dev-token-001")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Everything else still requires auth
+    let resp = http.get(format!("{base}/v1/receipts")).send().await.unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+// ── Transport compression ────────────────────────────────────────
+
+#[tokio::test]
+async fn responses_compress_only_when_the_client_asks() {
+    let (base, http, _h) = setup().await;
+
+    // No Accept-Encoding: canonical bytes on the wire
+    let resp = http
+        .get(format!("{base}/.well-known/ubl.json"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(
+        resp.headers().get("content-encoding").is_none(),
+        "unsolicited encoding"
+    );
+    let canonical: Value = resp.json().await.unwrap();
+
+    // Accept-Encoding: zstd — the wire form is compressed, and decoding
+    // yields the same document the canonical response carried
+    let resp = http
+        .get(format!("{base}/.well-known/ubl.json"))
+        .header("accept-encoding", "zstd")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-encoding").unwrap(),
+        "zstd",
+        "negotiated encoding must be announced"
+    );
+    let wire = resp.bytes().await.unwrap();
+    let decoded: Value = serde_json::from_slice(&zstd::stream::decode_all(wire.as_ref()).unwrap()).unwrap();
+    assert_eq!(decoded, canonical);
+
+    // gzip negotiates too
+    let resp = http
+        .get(format!("{base}/.well-known/ubl.json"))
+        .header("accept-encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers().get("content-encoding").unwrap(), "gzip");
 }
@@ -52,15 +52,18 @@ async fn race_card_end_to_end() {
     assert_eq!(j["hello"], "world");
     assert_eq!(j["n"], 42);
 
-    // 4) receipt (JWS: 3 dot-separated segments)
+    // 4) receipt: a unified attestation with a JWS proof (the standalone
+    //    JWS format lives behind the x-ubl-compat header now)
     let rec = http
         .get(format!("{base}/v1/receipt/{cid}"))
         .send()
         .await
         .unwrap();
     assert_eq!(rec.status(), 200);
-    let jws = rec.text().await.unwrap();
-    assert_eq!(jws.split('.').count(), 3, "receipt must be a JWS");
+    let rc: Value = rec.json().await.unwrap();
+    assert_eq!(rc["t"], "ubl/attestation");
+    assert_eq!(rc["body"]["cid"], cid.as_str());
+    assert!(rc["proof"]["signature"].is_string());
 
     // 5) DID document
     let did: Value = http